@@ -0,0 +1,101 @@
+//! Tracking of blocks that were rejected as invalid.
+
+use alloy_primitives::B256;
+use parking_lot::RwLock;
+use reth_primitives_traits::{Block, BlockTy, SealedBlock};
+use reth_storage_api::NodePrimitivesProvider;
+use std::collections::VecDeque;
+
+/// The maximum number of bad blocks that are kept around.
+pub const MAX_BAD_BLOCKS: usize = 10;
+
+/// A bounded store of the most recent blocks that were rejected as invalid.
+///
+/// Once [`MAX_BAD_BLOCKS`] are tracked, the oldest block is evicted for every new one.
+#[derive(Debug)]
+pub(crate) struct BadBlockStore<B: Block> {
+    blocks: RwLock<VecDeque<SealedBlock<B>>>,
+}
+
+impl<B: Block> BadBlockStore<B> {
+    /// Records a bad block, unless it is already tracked.
+    pub(crate) fn insert(&self, block: SealedBlock<B>) {
+        let mut blocks = self.blocks.write();
+        if blocks.iter().any(|bad| bad.hash() == block.hash()) {
+            return
+        }
+        if blocks.len() >= MAX_BAD_BLOCKS {
+            blocks.pop_front();
+        }
+        blocks.push_back(block);
+    }
+
+    /// Returns all tracked bad blocks, oldest first.
+    pub(crate) fn blocks(&self) -> Vec<SealedBlock<B>> {
+        self.blocks.read().iter().cloned().collect()
+    }
+
+    /// Returns the bad block with the given hash, if it is tracked.
+    pub(crate) fn get(&self, hash: B256) -> Option<SealedBlock<B>> {
+        self.blocks.read().iter().find(|bad| bad.hash() == hash).cloned()
+    }
+}
+
+impl<B: Block> Default for BadBlockStore<B> {
+    fn default() -> Self {
+        Self { blocks: RwLock::new(VecDeque::with_capacity(MAX_BAD_BLOCKS)) }
+    }
+}
+
+/// A type that provides access to the most recent blocks that were rejected as invalid.
+pub trait BadBlockReader: NodePrimitivesProvider + Send + Sync {
+    /// Returns the most recent bad blocks, oldest first.
+    fn bad_blocks(&self) -> Vec<SealedBlock<BlockTy<Self::Primitives>>>;
+
+    /// Returns the bad block with the given hash, if it is still tracked.
+    fn bad_block_by_hash(&self, hash: B256) -> Option<SealedBlock<BlockTy<Self::Primitives>>> {
+        self.bad_blocks().into_iter().find(|block| block.hash() == hash)
+    }
+}
+
+impl<T: BadBlockReader> BadBlockReader for &T {
+    fn bad_blocks(&self) -> Vec<SealedBlock<BlockTy<Self::Primitives>>> {
+        (*self).bad_blocks()
+    }
+
+    fn bad_block_by_hash(&self, hash: B256) -> Option<SealedBlock<BlockTy<Self::Primitives>>> {
+        (*self).bad_block_by_hash(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_ethereum_primitives::Block;
+
+    fn bad_block(number: u64) -> SealedBlock<Block> {
+        SealedBlock::seal_slow(Block {
+            header: Header { number, ..Default::default() },
+            body: Default::default(),
+        })
+    }
+
+    #[test]
+    fn evicts_oldest_bad_block() {
+        let store = BadBlockStore::default();
+        for number in 0..MAX_BAD_BLOCKS as u64 + 2 {
+            store.insert(bad_block(number));
+        }
+        // re-inserting a tracked block is a noop
+        store.insert(bad_block(MAX_BAD_BLOCKS as u64 + 1));
+
+        let blocks = store.blocks();
+        assert_eq!(blocks.len(), MAX_BAD_BLOCKS);
+        assert_eq!(blocks.first().unwrap().number, 2);
+        assert_eq!(blocks.last().unwrap().number, MAX_BAD_BLOCKS as u64 + 1);
+
+        assert!(store.get(bad_block(0).hash()).is_none());
+        assert_eq!(store.get(bad_block(2).hash()), Some(bad_block(2)));
+    }
+}
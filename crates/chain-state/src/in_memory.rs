@@ -1,8 +1,8 @@
 //! Types for tracking the canonical chain state in memory.
 
 use crate::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    ChainInfoTracker, MemoryOverlayStateProvider,
+    bad_blocks::BadBlockStore, CanonStateNotification, CanonStateNotificationSender,
+    CanonStateNotifications, ChainInfoTracker, MemoryOverlayStateProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber, BlockNumHash};
@@ -139,6 +139,8 @@ pub(crate) struct CanonicalInMemoryStateInner<N: NodePrimitives> {
     pub(crate) in_memory_state: InMemoryState<N>,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender<N>,
+    /// The most recent blocks that were rejected as invalid.
+    pub(crate) bad_blocks: BadBlockStore<N::Block>,
 }
 
 impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
@@ -192,6 +194,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 chain_info_tracker,
                 in_memory_state,
                 canon_state_notification_sender,
+                bad_blocks: Default::default(),
             }),
         }
     }
@@ -216,6 +219,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            bad_blocks: Default::default(),
         };
 
         Self { inner: Arc::new(inner) }
@@ -508,6 +512,23 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.canon_state_notification_sender.send(event).ok();
     }
 
+    /// Records a block that was rejected as invalid.
+    ///
+    /// Only the most recent [`MAX_BAD_BLOCKS`](crate::MAX_BAD_BLOCKS) bad blocks are kept.
+    pub fn insert_bad_block(&self, block: SealedBlock<N::Block>) {
+        self.inner.bad_blocks.insert(block)
+    }
+
+    /// Returns the most recent blocks that were rejected as invalid, oldest first.
+    pub fn bad_blocks(&self) -> Vec<SealedBlock<N::Block>> {
+        self.inner.bad_blocks.blocks()
+    }
+
+    /// Returns the bad block with the given hash, if it is still tracked.
+    pub fn bad_block_by_hash(&self, hash: B256) -> Option<SealedBlock<N::Block>> {
+        self.inner.bad_blocks.get(hash)
+    }
+
     /// Return state provider with reference to in-memory blocks that overlay database state.
     ///
     /// This merges the state of all blocks that are part of the chain that the requested block is
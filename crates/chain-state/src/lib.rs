@@ -13,6 +13,9 @@ pub use in_memory::*;
 
 mod noop;
 
+mod bad_blocks;
+pub use bad_blocks::{BadBlockReader, MAX_BAD_BLOCKS};
+
 mod chain_info;
 pub use chain_info::ChainInfoTracker;
 
@@ -1,10 +1,10 @@
 //! Noop impls for testing.
 
 use crate::{
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications,
+    BadBlockReader, CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications,
     ForkChoiceSubscriptions,
 };
-use reth_primitives_traits::NodePrimitives;
+use reth_primitives_traits::{NodePrimitives, SealedBlock};
 use reth_storage_api::noop::NoopProvider;
 use tokio::sync::{broadcast, watch};
 
@@ -27,3 +27,9 @@ impl<C: Send + Sync, N: NodePrimitives> ForkChoiceSubscriptions for NoopProvider
         ForkChoiceNotifications(rx)
     }
 }
+
+impl<C: Send + Sync, N: NodePrimitives> BadBlockReader for NoopProvider<C, N> {
+    fn bad_blocks(&self) -> Vec<SealedBlock<N::Block>> {
+        Vec::new()
+    }
+}
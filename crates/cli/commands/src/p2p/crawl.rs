@@ -0,0 +1,266 @@
+//! Command that crawls the discovery networks and creates an EIP-1459 DNS tree of the found nodes.
+
+use clap::{Parser, ValueEnum};
+use humantime::parse_duration;
+use reth_chainspec::{EnrForkIdEntry, EthChainSpec, ForkFilter, Hardforks, Head};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::get_secret_key;
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use reth_discv5::{
+    discv5::{self, Event, ListenConfig},
+    Config, Discv5, DEFAULT_DISCOVERY_V5_PORT,
+};
+use reth_dns_discovery::{tree::LinkEntry, EnrTree};
+use reth_network_peers::{Enr, NodeRecord};
+use secp256k1::{PublicKey, SecretKey, SECP256K1};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::select;
+use tokio_stream::StreamExt;
+use tracing::{debug, info};
+
+/// Crawl discv4 and discv5 for nodes of the chain and emit a signed EIP-1459 DNS tree of the
+/// nodes, as zone file or JSON records.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The chain to crawl.
+    ///
+    /// Nodes that don't advertise a fork id that is compatible with the chain are skipped.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::SUPPORTED_CHAINS[0],
+        value_parser = C::parser()
+    )]
+    pub(crate) chain: Arc<C::ChainSpec>,
+
+    /// The domain the tree is published at, e.g. `nodes.example.org`.
+    #[arg(long)]
+    domain: String,
+
+    /// How long to crawl before the tree is created.
+    #[arg(long, default_value = "5m", value_parser = parse_duration)]
+    duration: Duration,
+
+    /// Listen address for discv4.
+    #[arg(long, default_value = "0.0.0.0:30305")]
+    addr: SocketAddr,
+
+    /// Also crawl discv5.
+    #[arg(long)]
+    v5: bool,
+
+    /// The UDP port discv5 listens on, on the IP of `--addr`.
+    #[arg(long = "v5.port", default_value_t = DEFAULT_DISCOVERY_V5_PORT)]
+    v5_port: u16,
+
+    /// Comma separated enode URLs to start crawling from, defaults to the bootnodes of the chain.
+    #[arg(long, value_delimiter = ',')]
+    bootnodes: Option<Vec<NodeRecord>>,
+
+    /// Comma separated capabilities that nodes must advertise in their ENR, e.g. `snap`.
+    #[arg(long, value_delimiter = ',')]
+    capabilities: Vec<String>,
+
+    /// Links to other trees that are included in the tree, e.g.
+    /// `enrtree://<public key>@nodes.example.org`.
+    #[arg(long = "link", value_name = "ENRTREE")]
+    links: Vec<LinkEntry>,
+
+    /// The secret key that signs the tree. A new key is created if the file doesn't exist.
+    #[arg(long, value_name = "PATH")]
+    signing_key: PathBuf,
+
+    /// The sequence number of the tree, which must increase with every update of the tree.
+    ///
+    /// Defaults to the current unix timestamp.
+    #[arg(long)]
+    seq: Option<u64>,
+
+    /// The format of the records.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Zone)]
+    format: OutputFormat,
+
+    /// The TTL of the records in the zone file.
+    #[arg(long, default_value_t = 3600)]
+    ttl: u32,
+
+    /// Write the records to the file instead of stdout.
+    #[arg(long, short, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+/// The format of the records of the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// A DNS zone file with a TXT record per entry.
+    Zone,
+    /// A JSON object of the content of the TXT records by their name.
+    Json,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks>> Command<C> {
+    /// Execute the crawl command.
+    pub async fn execute(self) -> eyre::Result<()> {
+        let signing_key = get_secret_key(&self.signing_key)?;
+        let sk = reth_network::config::rng_secret_key();
+        let boot_nodes =
+            self.bootnodes.clone().unwrap_or_else(|| self.chain.bootnodes().unwrap_or_default());
+
+        // nodes on the same chain advertise a fork id that is valid at genesis, which accepts
+        // nodes that are ahead of it
+        let fork_filter = self
+            .chain
+            .fork_filter(Head { timestamp: self.chain.genesis().timestamp, ..Default::default() });
+
+        let local_enr = NodeRecord::from_secret_key(self.addr, &sk);
+        let config = Discv4Config::builder()
+            .add_boot_nodes(boot_nodes.clone())
+            .lookup_interval(Duration::from_secs(1))
+            .build();
+        let (_discv4, mut discv4_service) = Discv4::bind(self.addr, local_enr, sk, config).await?;
+        let mut discv4_updates = discv4_service.update_stream();
+        discv4_service.spawn();
+        info!(target: "reth::cli", addr=%self.addr, "Started discv4");
+
+        let mut discv5_updates = None;
+        if self.v5 {
+            let config = Config::builder(self.addr)
+                .discv5_config(
+                    discv5::ConfigBuilder::new(ListenConfig::from_ip(self.addr.ip(), self.v5_port))
+                        .build(),
+                )
+                .add_unsigned_boot_nodes(boot_nodes)
+                .lookup_interval(1)
+                .build();
+            let (_discv5, updates, _) = Discv5::start(&sk, config).await?;
+            discv5_updates = Some(updates);
+            info!(target: "reth::cli", port=%self.v5_port, "Started discv5");
+        }
+
+        let mut nodes = HashMap::new();
+        let deadline = tokio::time::sleep(self.duration);
+        tokio::pin!(deadline);
+
+        info!(target: "reth::cli", duration=?self.duration, "Crawling");
+        loop {
+            select! {
+                _ = &mut deadline => break,
+                update = discv4_updates.next() => {
+                    let Some(update) = update else { break };
+                    self.on_discv4_update(&fork_filter, &mut nodes, update);
+                }
+                event = async {
+                    if let Some(updates) = &mut discv5_updates {
+                        updates.recv().await
+                    } else {
+                        futures::future::pending().await
+                    }
+                } => {
+                    let Some(event) = event else { break };
+                    if let Event::Discovered(enr) | Event::SessionEstablished(enr, _) = event {
+                        // discv5 records are re-encoded with the key type of the tree
+                        if let Ok(enr) = enr.to_base64().parse() {
+                            self.on_enr(&fork_filter, &mut nodes, enr);
+                        }
+                    }
+                }
+            }
+        }
+        info!(target: "reth::cli", nodes=%nodes.len(), "Finished crawling");
+
+        let seq = self.seq.unwrap_or_else(|| {
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+        });
+        let mut tree = EnrTree::new(nodes.into_values().collect(), self.links.clone(), seq);
+        tree.sign(&signing_key)?;
+
+        let records = match self.format {
+            OutputFormat::Zone => tree.zone_file(&self.domain, self.ttl),
+            OutputFormat::Json => serde_json::to_string_pretty(&tree.records(&self.domain))?,
+        };
+        match &self.output {
+            Some(path) => reth_fs_util::write(path, records)?,
+            None => println!("{records}"),
+        }
+
+        let link = LinkEntry::<SecretKey> {
+            domain: self.domain.clone(),
+            pubkey: PublicKey::from_secret_key(SECP256K1, &signing_key),
+        };
+        info!(target: "reth::cli", %link, seq, "Created tree");
+
+        Ok(())
+    }
+
+    fn on_discv4_update(
+        &self,
+        fork_filter: &ForkFilter,
+        nodes: &mut HashMap<[u8; 32], Enr<SecretKey>>,
+        update: DiscoveryUpdate,
+    ) {
+        match update {
+            DiscoveryUpdate::Enr(enr) => self.on_enr(fork_filter, nodes, enr),
+            DiscoveryUpdate::Batch(updates) => {
+                for update in updates {
+                    self.on_discv4_update(fork_filter, nodes, update);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Keeps the latest record of the node if it is on the chain and advertises all required
+    /// capabilities.
+    fn on_enr(
+        &self,
+        fork_filter: &ForkFilter,
+        nodes: &mut HashMap<[u8; 32], Enr<SecretKey>>,
+        enr: Enr<SecretKey>,
+    ) {
+        let Some(Ok(EnrForkIdEntry { fork_id })) = enr.get_decodable::<EnrForkIdEntry>(b"eth")
+        else {
+            return
+        };
+        if fork_filter.validate(fork_id).is_err() ||
+            !self.capabilities.iter().all(|cap| enr.get_raw_rlp(cap.as_bytes()).is_some())
+        {
+            return
+        }
+
+        let node_id = enr.node_id().raw();
+        if nodes.get(&node_id).is_none_or(|known| known.seq() < enr.seq()) {
+            debug!(target: "reth::cli", node_id=?enr.node_id(), seq=%enr.seq(), "Crawled node");
+            nodes.insert(node_id, enr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_crawl_cmd() {
+        let cmd: Command<EthereumChainSpecParser> = Command::parse_from([
+            "reth",
+            "--domain",
+            "nodes.example.org",
+            "--signing-key",
+            "tree.key",
+            "--capabilities",
+            "snap",
+            "--format",
+            "json",
+        ]);
+        assert_eq!(cmd.format, OutputFormat::Json);
+        assert_eq!(cmd.capabilities, vec!["snap".to_string()]);
+    }
+}
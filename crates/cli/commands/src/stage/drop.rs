@@ -19,7 +19,7 @@ use reth_provider::{
     writer::UnifiedStorageWriter, DatabaseProviderFactory, StaticFileProviderFactory,
 };
 use reth_prune::PruneSegment;
-use reth_stages::StageId;
+use reth_stages::{stages::S3_STAGE_ID, StageId};
 use reth_static_file_types::StaticFileSegment;
 use std::sync::Arc;
 
@@ -88,6 +88,16 @@ impl<C: ChainSpecParser> Command<C> {
                 tx.clear::<tables::BlockWithdrawals>()?;
                 reset_stage_checkpoint(tx, StageId::Bodies)?;
 
+                // the address transactions index is built from the transactions
+                if tx
+                    .get::<tables::StageCheckpoints>(StageId::IndexAddressTransactions.to_string())?
+                    .is_some()
+                {
+                    tx.clear::<tables::AddressTransactions>()?;
+                    reset_prune_checkpoint(tx, PruneSegment::AddressTransactions)?;
+                    reset_stage_checkpoint(tx, StageId::IndexAddressTransactions)?;
+                }
+
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
             }
             StageEnum::Senders => {
@@ -108,6 +118,13 @@ impl<C: ChainSpecParser> Command<C> {
                 reset_prune_checkpoint(tx, PruneSegment::ContractLogs)?;
                 reset_stage_checkpoint(tx, StageId::Execution)?;
 
+                // the log index is built from the receipts
+                if tx.get::<tables::StageCheckpoints>(StageId::IndexLogs.to_string())?.is_some() {
+                    tx.clear::<tables::LogAddressIndex>()?;
+                    tx.clear::<tables::LogTopicIndex>()?;
+                    reset_stage_checkpoint(tx, StageId::IndexLogs)?;
+                }
+
                 let alloc = &self.env.chain.genesis().alloc;
                 insert_genesis_state(&provider_rw, alloc.iter())?;
             }
@@ -156,6 +173,29 @@ impl<C: ChainSpecParser> Command<C> {
                 reset_stage_checkpoint(tx, StageId::TransactionLookup)?;
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
             }
+            StageEnum::IndexLogs => {
+                tx.clear::<tables::LogAddressIndex>()?;
+                tx.clear::<tables::LogTopicIndex>()?;
+
+                // The log index is optional, so its checkpoint is removed instead of reset. This
+                // stops it from being maintained until the stage runs again.
+                tx.delete::<tables::StageCheckpoints>(StageId::IndexLogs.to_string(), None)?;
+            }
+            StageEnum::IndexAddressTransactions => {
+                tx.clear::<tables::AddressTransactions>()?;
+                reset_prune_checkpoint(tx, PruneSegment::AddressTransactions)?;
+
+                // Same as the log index, the checkpoint of the optional index is removed.
+                tx.delete::<tables::StageCheckpoints>(
+                    StageId::IndexAddressTransactions.to_string(),
+                    None,
+                )?;
+            }
+            StageEnum::S3 => {
+                // The headers and bodies appended by the stage are dropped together with the
+                // headers and bodies stages, so only its checkpoint is removed.
+                tx.delete::<tables::StageCheckpoints>(S3_STAGE_ID.to_string(), None)?;
+            }
         }
 
         tx.put::<tables::StageCheckpoints>(StageId::Finish.to_string(), Default::default())?;
@@ -39,8 +39,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexAddressTransactionsStage, IndexLogsStage, IndexStorageHistoryStage, MerkleStage,
+        S3Stage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -96,6 +96,10 @@ pub struct Command<C: ChainSpecParser> {
     #[arg(long)]
     checkpoints: bool,
 
+    /// Base URL of the static files downloaded by the `s3` stage.
+    #[arg(long, value_name = "URL")]
+    s3_url: Option<String>,
+
     #[command(flatten)]
     network: NetworkArgs,
 }
@@ -319,6 +323,24 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     )),
                     None,
                 ),
+                StageEnum::IndexLogs => {
+                    (Box::new(IndexLogsStage::new(config.stages.index_logs, etl_config)), None)
+                }
+                StageEnum::IndexAddressTransactions => (
+                    Box::new(IndexAddressTransactionsStage::new(
+                        config.stages.index_address_transactions,
+                        etl_config,
+                        prune_modes.address_transactions,
+                    )),
+                    None,
+                ),
+                StageEnum::S3 => {
+                    let url = self
+                        .s3_url
+                        .clone()
+                        .ok_or_else(|| eyre::eyre!("the s3 stage requires --s3-url"))?;
+                    (Box::new(S3Stage::new(url, data_dir.data_dir().join("s3"))), None)
+                }
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
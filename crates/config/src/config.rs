@@ -3,6 +3,7 @@ use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
 use std::{
+    collections::BTreeMap,
     path::{Path, PathBuf},
     time::Duration,
 };
@@ -29,6 +30,8 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the RPC server.
+    pub rpc: RpcConfig,
 }
 
 impl Config {
@@ -125,6 +128,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexAddressTransactionsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -426,6 +433,48 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexLogsConfig {
+    /// Whether to build and maintain the log address and topic index used by `eth_getLogs`.
+    ///
+    /// Disabling it stops extending an index that has already been built, but it is still used
+    /// for the blocks it covers until it's dropped with `reth stage drop index-logs`.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Address transactions index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexAddressTransactionsConfig {
+    /// Whether to build and maintain the index of the transactions sent by, sent to or creating
+    /// each address, used by `reth_getTransactionsByAddress`.
+    ///
+    /// Disabling it stops extending an index that has already been built, but it is still used
+    /// for the blocks it covers until it's dropped with
+    /// `reth stage drop index-address-transactions`.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -464,6 +513,7 @@ impl PruneConfig {
                     account_history,
                     storage_history,
                     bodies_history,
+                    address_transactions,
                     receipts_log_filter,
                 },
         } = other;
@@ -480,6 +530,8 @@ impl PruneConfig {
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
         self.segments.bodies_history = self.segments.bodies_history.or(bodies_history);
+        self.segments.address_transactions =
+            self.segments.address_transactions.or(address_transactions);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -487,6 +539,100 @@ impl PruneConfig {
     }
 }
 
+/// RPC server configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcConfig {
+    /// Rate limits of the HTTP and WS servers.
+    pub rate_limit: RpcRateLimitConfig,
+}
+
+/// Rate limits of the RPC server.
+///
+/// Each call is charged compute units, and calls to the methods of a rate limited group are
+/// rejected once the token buckets of the group run out of compute units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcRateLimitConfig {
+    /// Compute units charged for a call to a method that isn't listed in `compute_units`.
+    pub default_compute_units: u64,
+    /// Whether clients are identified by the `X-Forwarded-For` or `X-Real-IP` header.
+    ///
+    /// Only enable this if the server is behind a reverse proxy that sets these headers, since
+    /// clients could otherwise pick their own identity.
+    pub trust_forwarded_for: bool,
+    /// Compute units charged for a call, by method name or by method prefix ending with `*`, e.g.
+    /// `trace_*`. The exact method name takes precedence over the longest matching prefix.
+    pub compute_units: BTreeMap<String, u64>,
+    /// The rate limited method groups.
+    ///
+    /// A call only counts against the first group that matches its method.
+    pub groups: Vec<RpcRateLimitGroup>,
+}
+
+impl Default for RpcRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            default_compute_units: 1,
+            trust_forwarded_for: false,
+            compute_units: BTreeMap::new(),
+            groups: Vec::new(),
+        }
+    }
+}
+
+impl RpcRateLimitConfig {
+    /// Returns whether any method is rate limited.
+    pub fn is_enabled(&self) -> bool {
+        self.groups.iter().any(|group| group.global.is_some() || group.per_client.is_some())
+    }
+}
+
+/// A group of methods that is rate limited.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcRateLimitGroup {
+    /// Name of the group, included in rate limit errors and metrics.
+    pub name: String,
+    /// Methods of the group, by method name or by method prefix ending with `*`, e.g. `debug_*`.
+    pub methods: Vec<String>,
+    /// Limit shared by all clients.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub global: Option<TokenBucketConfig>,
+    /// Limit of each client.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub per_client: Option<TokenBucketConfig>,
+}
+
+impl RpcRateLimitGroup {
+    /// Returns whether the method belongs to the group.
+    pub fn matches(&self, method: &str) -> bool {
+        self.methods.iter().any(|pattern| method_matches(pattern, method))
+    }
+}
+
+/// Configuration of a token bucket that holds compute units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenBucketConfig {
+    /// Compute units added to the bucket per second.
+    pub refill_per_second: u64,
+    /// Maximum compute units held by the bucket, which is the largest burst that is allowed.
+    pub capacity: u64,
+}
+
+/// Returns whether the method matches the pattern, which is either a method name or a method
+/// prefix ending with `*`.
+pub fn method_matches(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
 /// Helper type to support older versions of Duration deserialization.
 #[cfg(feature = "serde")]
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
@@ -508,7 +654,7 @@ where
 
 #[cfg(all(test, feature = "serde"))]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{Config, TokenBucketConfig, EXTENSION};
     use crate::PruneConfig;
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
@@ -990,6 +1136,46 @@ receipts = 'full'
         assert!(err.contains("invalid value: string \"full\""), "{}", err);
     }
 
+    #[test]
+    fn test_rpc_rate_limit_config() {
+        let s = r#"
+[rpc.rate_limit]
+default_compute_units = 2
+
+[rpc.rate_limit.compute_units]
+eth_getLogs = 50
+"trace_*" = 100
+
+[[rpc.rate_limit.groups]]
+name = "tracing"
+methods = ["trace_*", "debug_*"]
+global = { refill_per_second = 1000, capacity = 2000 }
+per_client = { refill_per_second = 200, capacity = 400 }
+
+[[rpc.rate_limit.groups]]
+name = "logs"
+methods = ["eth_getLogs"]
+per_client = { refill_per_second = 100, capacity = 100 }
+"#;
+        let conf: Config = toml::from_str(s).unwrap();
+        let rate_limit = &conf.rpc.rate_limit;
+        assert!(rate_limit.is_enabled());
+        assert_eq!(rate_limit.default_compute_units, 2);
+        assert!(!rate_limit.trust_forwarded_for);
+        assert_eq!(rate_limit.compute_units.get("trace_*"), Some(&100));
+        assert_eq!(
+            rate_limit.groups[0].global,
+            Some(TokenBucketConfig { refill_per_second: 1000, capacity: 2000 })
+        );
+        assert!(rate_limit.groups[0].matches("debug_traceTransaction"));
+        assert!(!rate_limit.groups[1].matches("eth_getLogsByHash"));
+        assert_eq!(rate_limit.groups[1].global, None);
+
+        // roundtrip
+        let conf2: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf, conf2);
+    }
+
     #[test]
     fn test_prune_config_merge() {
         let mut config1 = PruneConfig {
@@ -1001,6 +1187,7 @@ receipts = 'full'
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
                 bodies_history: None,
+                address_transactions: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -1017,6 +1204,7 @@ receipts = 'full'
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
                 bodies_history: None,
+                address_transactions: Some(PruneMode::Distance(4000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -1035,6 +1223,7 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.address_transactions, Some(PruneMode::Distance(4000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
@@ -0,0 +1,123 @@
+use crate::trace::TracingHook;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, GethDefaultTracingOptions};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use std::path::PathBuf;
+
+/// Traces every opcode executed by the invalid block and saves the struct logs to a file.
+#[derive(Debug)]
+pub struct InvalidBlockOpcodeHook<P, E> {
+    /// The hook that replays the block and saves the traces.
+    hook: TracingHook<P, E>,
+}
+
+impl<P, E> InvalidBlockOpcodeHook<P, E> {
+    /// Creates a new opcode hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { hook: TracingHook::new(provider, evm_config, output_directory, healthy_node_client) }
+    }
+}
+
+impl<P, E, N> InvalidBlockOpcodeHook<P, E>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let config = GethDefaultTracingOptions::default().enable_memory().enable_return_data();
+
+        self.hook.on_invalid_block(
+            parent_header,
+            block,
+            "opcode",
+            GethDebugTracingOptions { config, ..Default::default() },
+            TracingInspectorConfig::from_geth_config(&config),
+            |inspector: &mut TracingInspector, res, _db| {
+                let gas_used = res.result.gas_used();
+                let return_value = res.result.output().cloned().unwrap_or_default();
+                Ok(inspector.geth_builder().geth_traces(gas_used, return_value, config).into())
+            },
+        )
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockOpcodeHook<P, E>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::opcode", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::tests::invalid_block;
+    use alloy_rpc_types_trace::geth::{GethTrace, TraceResult};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::BlockExecutionResult;
+
+    #[test]
+    fn saves_opcode_traces() {
+        let (provider, parent, block) = invalid_block(true);
+        let dir = tempfile::tempdir().unwrap();
+        let hook =
+            InvalidBlockOpcodeHook::new(provider, EthEvmConfig::mainnet(), dir.path().into(), None);
+
+        let output = BlockExecutionOutput {
+            result: BlockExecutionResult {
+                receipts: Vec::new(),
+                requests: Default::default(),
+                gas_used: 0,
+            },
+            state: Default::default(),
+        };
+        InvalidBlockHook::on_invalid_block(&hook, &parent, &block, &output, None);
+
+        let path = dir.path().join(format!("1_{}.opcode.re_executed.json", block.hash()));
+        let traces: Vec<TraceResult> =
+            serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        assert_eq!(traces.len(), 2);
+        assert!(matches!(traces[0], TraceResult::Error { .. }));
+        let TraceResult::Success { result: GethTrace::Default(frame), .. } = &traces[1] else {
+            panic!("expected the struct logs of the transfer")
+        };
+        assert!(!frame.failed);
+        assert_eq!(frame.gas, 21_000);
+    }
+}
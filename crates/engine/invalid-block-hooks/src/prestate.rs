@@ -0,0 +1,130 @@
+use crate::trace::TracingHook;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, PreStateConfig};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use std::path::PathBuf;
+
+/// Traces the invalid block with the prestate tracer in diff mode and saves the traces to a file.
+#[derive(Debug)]
+pub struct InvalidBlockPreStateHook<P, E> {
+    /// The hook that replays the block and saves the traces.
+    hook: TracingHook<P, E>,
+}
+
+impl<P, E> InvalidBlockPreStateHook<P, E> {
+    /// Creates a new prestate hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { hook: TracingHook::new(provider, evm_config, output_directory, healthy_node_client) }
+    }
+}
+
+impl<P, E, N> InvalidBlockPreStateHook<P, E>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let prestate_config = PreStateConfig { diff_mode: Some(true), ..Default::default() };
+
+        self.hook.on_invalid_block(
+            parent_header,
+            block,
+            "prestate",
+            GethDebugTracingOptions::prestate_tracer(prestate_config),
+            TracingInspectorConfig::from_geth_prestate_config(&prestate_config),
+            |inspector: &mut TracingInspector, res, db| {
+                Ok(inspector.geth_builder().geth_prestate_traces(res, &prestate_config, db)?.into())
+            },
+        )
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockPreStateHook<P, E>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::prestate", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::tests::invalid_block;
+    use alloy_primitives::{Address, U256};
+    use alloy_rpc_types_trace::geth::{GethTrace, PreStateFrame, TraceResult};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::BlockExecutionResult;
+
+    #[test]
+    fn saves_prestate_diff() {
+        let (provider, parent, block) = invalid_block(true);
+        let dir = tempfile::tempdir().unwrap();
+        let hook = InvalidBlockPreStateHook::new(
+            provider,
+            EthEvmConfig::mainnet(),
+            dir.path().into(),
+            None,
+        );
+
+        let output = BlockExecutionOutput {
+            result: BlockExecutionResult {
+                receipts: Vec::new(),
+                requests: Default::default(),
+                gas_used: 0,
+            },
+            state: Default::default(),
+        };
+        InvalidBlockHook::on_invalid_block(&hook, &parent, &block, &output, None);
+
+        let path = dir.path().join(format!("1_{}.prestate.re_executed.json", block.hash()));
+        let traces: Vec<TraceResult> =
+            serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        assert_eq!(traces.len(), 2);
+        assert!(matches!(traces[0], TraceResult::Error { .. }));
+        let TraceResult::Success {
+            result: GethTrace::PreStateTracer(PreStateFrame::Diff(diff)),
+            ..
+        } = &traces[1]
+        else {
+            panic!("expected the prestate diff of the transfer")
+        };
+        let recipient = &diff.post[&Address::repeat_byte(0xaa)];
+        assert_eq!(recipient.balance, Some(U256::from(1)));
+    }
+}
@@ -0,0 +1,306 @@
+//! Shared implementation of the tracing based invalid block hooks.
+
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, GethTrace, TraceResult};
+use pretty_assertions::Comparison;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm, Evm, HaltReasonFor};
+use reth_primitives_traits::{
+    BlockBody, NodePrimitives, RecoveredBlock, SealedHeader, SignedTransaction,
+};
+use reth_provider::{ChainSpecProvider, StateProviderBox, StateProviderFactory};
+use reth_revm::{
+    context::result::ResultAndState, database::StateProviderDatabase, db::CacheDB, DatabaseCommit,
+};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::Serialize;
+use std::{fmt::Debug, fs::File, io::Write, path::PathBuf};
+
+/// The database the block transactions are re-executed on.
+pub(crate) type TraceDb = CacheDB<StateProviderDatabase<StateProviderBox>>;
+
+/// Re-executes the invalid block with a [`TracingInspector`] and saves the traces to a file.
+///
+/// The prestate and opcode hooks only differ in the tracer they use, the replay of the block and
+/// the comparison against the healthy node are shared.
+#[derive(Debug)]
+pub(crate) struct TracingHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The directory to write the traces to. Additionally, diff files will be written to this
+    /// directory in case of a mismatch against the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, E> TracingHook<P, E> {
+    /// Creates a new tracing hook.
+    pub(crate) const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, E, N> TracingHook<P, E>
+where
+    P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>,
+    E: ConfigureEvm<Primitives = N>,
+    N: NodePrimitives,
+{
+    /// Traces all transactions of the block and writes the traces to
+    /// `{number}_{hash}.{kind}.re_executed.json`.
+    ///
+    /// If a healthy node client is configured, the block is also traced on the healthy node with
+    /// the given options. Its traces are written to `{number}_{hash}.{kind}.healthy.json`, and a
+    /// `{number}_{hash}.{kind}.diff` file is written if they don't match.
+    ///
+    /// See [`Self::trace_block`] for the `inspector_config` and `f` arguments.
+    pub(crate) fn on_invalid_block<F>(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        kind: &str,
+        opts: GethDebugTracingOptions,
+        inspector_config: TracingInspectorConfig,
+        f: F,
+    ) -> eyre::Result<()>
+    where
+        F: FnMut(
+            &mut TracingInspector,
+            &ResultAndState<HaltReasonFor<E>>,
+            &TraceDb,
+        ) -> eyre::Result<GethTrace>,
+    {
+        let traces = self.trace_block(parent_header, block, inspector_config, f)?;
+
+        let number = block.number();
+        let block_hash = block.hash();
+        let re_executed_path =
+            self.save_file(format!("{number}_{block_hash}.{kind}.re_executed.json"), &traces)?;
+
+        let Some(healthy_node_client) = &self.healthy_node_client else { return Ok(()) };
+
+        let healthy_node_traces = futures::executor::block_on(async move {
+            DebugApiClient::<()>::debug_trace_block_by_number(
+                healthy_node_client,
+                number.into(),
+                Some(opts),
+            )
+            .await
+        })?;
+
+        let healthy_path = self.save_file(
+            format!("{number}_{block_hash}.{kind}.healthy.json"),
+            &healthy_node_traces,
+        )?;
+
+        if traces != healthy_node_traces {
+            let diff_path = self.save_diff(
+                format!("{number}_{block_hash}.{kind}.diff"),
+                traces.as_slice(),
+                healthy_node_traces.as_slice(),
+            )?;
+            warn!(
+                target: "engine::invalid_block_hooks",
+                kind,
+                diff_path = %diff_path.display(),
+                re_executed_path = %re_executed_path.display(),
+                healthy_path = %healthy_path.display(),
+                "Traces mismatch against healthy node"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Re-executes all transactions of the block on top of the parent state, tracing each of them
+    /// with a fresh [`TracingInspector`] created from the given config.
+    ///
+    /// The closure turns the inspector and the execution result of a transaction into a
+    /// [`GethTrace`]. It's called before the state changes of the transaction are committed, so
+    /// the database still holds the state the transaction was executed on.
+    ///
+    /// Transactions of an invalid block may fail to execute, their errors are recorded as
+    /// [`TraceResult::Error`] and the remaining transactions are traced on the unchanged state.
+    fn trace_block<F>(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        inspector_config: TracingInspectorConfig,
+        mut f: F,
+    ) -> eyre::Result<Vec<TraceResult>>
+    where
+        F: FnMut(
+            &mut TracingInspector,
+            &ResultAndState<HaltReasonFor<E>>,
+            &TraceDb,
+        ) -> eyre::Result<GethTrace>,
+    {
+        let mut db = CacheDB::new(StateProviderDatabase::new(
+            self.provider.state_by_block_hash(parent_header.hash())?,
+        ));
+        let evm_env = self.evm_config.evm_env(block.header());
+
+        // apply relevant system calls
+        SystemCaller::new(self.provider.chain_spec()).apply_pre_execution_changes(
+            block.header(),
+            &mut self.evm_config.evm_with_env(&mut db, evm_env.clone()),
+        )?;
+
+        let mut results = Vec::with_capacity(block.body().transactions().len());
+        for tx in block.transactions_recovered() {
+            let tx_hash = *tx.tx_hash();
+            let gas_limit = tx.gas_limit();
+
+            let mut inspector = TracingInspector::new(inspector_config);
+            let res = match self
+                .evm_config
+                .evm_with_env_and_inspector(&mut db, evm_env.clone(), &mut inspector)
+                .transact(self.evm_config.tx_env(tx))
+            {
+                Ok(res) => res,
+                Err(err) => {
+                    results.push(TraceResult::Error {
+                        error: err.to_string(),
+                        tx_hash: Some(tx_hash),
+                    });
+                    continue
+                }
+            };
+
+            inspector.set_transaction_gas_limit(gas_limit);
+            let result = f(&mut inspector, &res, &db)?;
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+
+            // need to apply the state changes of this transaction before executing the next one
+            db.commit(res.state);
+        }
+
+        Ok(results)
+    }
+
+    /// Saves the diff of two values into a file with the given name in the output directory.
+    fn save_diff<T: PartialEq + Debug + ?Sized>(
+        &self,
+        filename: String,
+        original: &T,
+        new: &T,
+    ) -> eyre::Result<PathBuf> {
+        let path = self.output_directory.join(filename);
+        let diff = Comparison::new(original, new);
+        File::create(&path)?.write_all(diff.to_string().as_bytes())?;
+
+        Ok(path)
+    }
+
+    fn save_file<T: Serialize + ?Sized>(
+        &self,
+        filename: String,
+        value: &T,
+    ) -> eyre::Result<PathBuf> {
+        let path = self.output_directory.join(filename);
+        File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody, Header, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use alloy_rpc_types_trace::geth::GethDefaultTracingOptions;
+    use reth_ethereum_primitives::{Block, Transaction};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{Block as _, SignerRecoverable};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_testing_utils::generators::{self, generate_key, sign_tx_with_key_pair};
+
+    /// Returns a provider with a funded account and a block on top of its genesis with a transfer
+    /// of the funded account.
+    ///
+    /// If `with_failing_tx` is set, the transfer is preceded by a transfer of an account without
+    /// balance, which fails to execute.
+    pub(crate) fn invalid_block(
+        with_failing_tx: bool,
+    ) -> (MockEthProvider, SealedHeader, RecoveredBlock<Block>) {
+        let provider = MockEthProvider::default();
+        let mut rng = generators::rng();
+        let transfer = |key_pair| {
+            let tx = TxLegacy {
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::repeat_byte(0xaa)),
+                value: U256::from(1),
+                ..Default::default()
+            };
+            sign_tx_with_key_pair(key_pair, Transaction::Legacy(tx))
+        };
+
+        let mut transactions = Vec::new();
+        if with_failing_tx {
+            transactions.push(transfer(generate_key(&mut rng)));
+        }
+        let funded = transfer(generate_key(&mut rng));
+        provider
+            .add_account(funded.recover_signer().unwrap(), ExtendedAccount::new(0, U256::from(1)));
+        transactions.push(funded);
+
+        let parent = SealedHeader::seal_slow(provider.chain_spec().genesis_header().clone());
+        let header = Header {
+            parent_hash: parent.hash(),
+            number: 1,
+            gas_limit: 30_000_000,
+            ..Default::default()
+        };
+        let block = Block::new(header, BlockBody { transactions, ..Default::default() })
+            .seal_slow()
+            .try_recover()
+            .unwrap();
+
+        (provider, parent, block)
+    }
+
+    #[test]
+    fn records_failed_transactions() {
+        let (provider, parent, block) = invalid_block(true);
+        let dir = tempfile::tempdir().unwrap();
+        let hook = TracingHook::new(provider, EthEvmConfig::mainnet(), dir.path().into(), None);
+
+        let traces = hook
+            .trace_block(
+                &parent,
+                &block,
+                TracingInspectorConfig::default_geth(),
+                |inspector: &mut TracingInspector, res, _db| {
+                    let gas_used = res.result.gas_used();
+                    let config = GethDefaultTracingOptions::default();
+                    Ok(inspector
+                        .geth_builder()
+                        .geth_traces(gas_used, Default::default(), config)
+                        .into())
+                },
+            )
+            .unwrap();
+
+        // the failed transaction doesn't stop the replay of the block
+        let tx_hashes = block.transactions_recovered().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+        let results = traces
+            .iter()
+            .map(|trace| match trace {
+                TraceResult::Success { tx_hash, .. } => (true, *tx_hash),
+                TraceResult::Error { tx_hash, .. } => (false, *tx_hash),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![(false, Some(tx_hashes[0])), (true, Some(tx_hashes[1]))]);
+    }
+}
@@ -385,3 +385,46 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::tests::invalid_block;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::BlockExecutionResult;
+
+    #[test]
+    fn saves_witness_and_bundle_state_mismatch() {
+        let (provider, parent, block) = invalid_block(false);
+        let dir = tempfile::tempdir().unwrap();
+        let hook = InvalidBlockWitnessHook::new(
+            provider,
+            EthEvmConfig::mainnet(),
+            dir.path().into(),
+            None,
+        );
+
+        // the original execution didn't change any state, unlike the re-execution
+        let output = BlockExecutionOutput {
+            result: BlockExecutionResult {
+                receipts: Vec::new(),
+                requests: Default::default(),
+                gas_used: 0,
+            },
+            state: Default::default(),
+        };
+        InvalidBlockHook::on_invalid_block(&hook, &parent, &block, &output, None);
+
+        let prefix = format!("1_{}", block.hash());
+        let witness: ExecutionWitness = serde_json::from_slice(
+            &std::fs::read(dir.path().join(format!("{prefix}.witness.re_executed.json"))).unwrap(),
+        )
+        .unwrap();
+        assert!(!witness.keys.is_empty());
+        for file in
+            ["bundle_state.original.json", "bundle_state.re_executed.json", "bundle_state.diff"]
+        {
+            assert!(dir.path().join(format!("{prefix}.{file}")).exists(), "{file} is missing");
+        }
+    }
+}
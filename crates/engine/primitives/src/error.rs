@@ -45,3 +45,27 @@ impl BeaconForkChoiceUpdateError {
         Self::Internal(Box::new(e))
     }
 }
+
+/// Represents error cases when rewinding the canonical chain.
+#[derive(Debug, thiserror::Error)]
+pub enum BeaconSetHeadError {
+    /// Thrown when the requested block is not part of the canonical chain.
+    #[error("block {0} is not part of the canonical chain")]
+    NotCanonical(u64),
+    /// Thrown when the chain can't be rewound while backfill sync is running.
+    #[error("backfill sync is in progress")]
+    BackfillInProgress,
+    /// Thrown when the engine task is unavailable/stopped.
+    #[error("beacon consensus engine task stopped")]
+    EngineUnavailable,
+    /// An internal error occurred while rewinding the chain.
+    #[error(transparent)]
+    Internal(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl BeaconSetHeadError {
+    /// Create a new internal error.
+    pub fn internal<E: core::error::Error + Send + Sync + 'static>(e: E) -> Self {
+        Self::Internal(Box::new(e))
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
+    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, BeaconSetHeadError,
+    ExecutionPayload, ForkchoiceStatus,
 };
+use alloc::boxed::Box;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::{
     ForkChoiceUpdateResult, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId,
     PayloadStatus, PayloadStatusEnum,
@@ -160,6 +164,15 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         /// The sender for returning forkchoice updated result.
         tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>,
     },
+    /// Message to rewind the canonical chain to the given block.
+    ///
+    /// This is not part of the Engine API, but is used by `debug_setHead`.
+    SetHead {
+        /// The number of the block that should become the new canonical head.
+        number: BlockNumber,
+        /// The sender for returning the new canonical head.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>,
+    },
 }
 
 impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
@@ -183,6 +196,9 @@ impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
                     payload_attrs.is_some()
                 )
             }
+            Self::SetHead { number, .. } => {
+                write!(f, "SetHead(number: {number})")
+            }
         }
     }
 }
@@ -236,6 +252,16 @@ where
             .await?)
     }
 
+    /// Sends a message to the beacon consensus engine to rewind the canonical chain to the given
+    /// block and waits for a response.
+    ///
+    /// Returns the new canonical head.
+    pub async fn set_head(&self, number: BlockNumber) -> Result<BlockNumHash, BeaconSetHeadError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::SetHead { number, tx });
+        rx.await.map_err(|_| BeaconSetHeadError::EngineUnavailable)?
+    }
+
     /// Sends a forkchoice update message to the beacon consensus engine and returns the receiver to
     /// wait for a response.
     fn send_fork_choice_updated(
@@ -254,3 +280,27 @@ where
         rx
     }
 }
+
+/// Rewinds the canonical chain of the consensus engine, see [`BeaconEngineMessage::SetHead`].
+///
+/// This is implemented by [`BeaconConsensusEngineHandle`] and allows sending the request without
+/// knowing the payload types of the engine.
+pub trait EngineSetHead: fmt::Debug + Send + Sync + 'static {
+    /// Rewinds the canonical chain to the given block and returns the new canonical head.
+    fn set_head(
+        &self,
+        number: BlockNumber,
+    ) -> Pin<Box<dyn Future<Output = Result<BlockNumHash, BeaconSetHeadError>> + Send + '_>>;
+}
+
+impl<Payload> EngineSetHead for BeaconConsensusEngineHandle<Payload>
+where
+    Payload: PayloadTypes,
+{
+    fn set_head(
+        &self,
+        number: BlockNumber,
+    ) -> Pin<Box<dyn Future<Output = Result<BlockNumHash, BeaconSetHeadError>> + Send + '_>> {
+        Box::pin(Self::set_head(self, number))
+    }
+}
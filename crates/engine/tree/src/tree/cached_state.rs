@@ -1,5 +1,5 @@
 //! Implements a state provider that has a shared cache in front of it.
-use alloy_primitives::{Address, StorageKey, StorageValue, B256};
+use alloy_primitives::{Address, StorageKey, StorageValue, B256, U256};
 use metrics::Gauge;
 use mini_moka::sync::CacheBuilder;
 use reth_errors::ProviderResult;
@@ -162,6 +162,25 @@ impl<S: StateProvider> StateProvider for CachedStateProvider<S> {
             }
         }
     }
+
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        self.state_provider.hashed_account_range(hashed_state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        self.state_provider.hashed_storage_range(hashed_address, hashed_storage, start, limit)
+    }
 }
 
 impl<S: BytecodeReader> BytecodeReader for CachedStateProvider<S> {
@@ -256,6 +275,14 @@ impl<S: StorageRootProvider> StorageRootProvider for CachedStateProvider<S> {
     ) -> ProviderResult<StorageMultiProof> {
         self.state_provider.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn hashed_storage_root(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        self.state_provider.hashed_storage_root(hashed_address, hashed_storage)
+    }
 }
 
 impl<S: BlockHashReader> BlockHashReader for CachedStateProvider<S> {
@@ -19,8 +19,8 @@ use reth_chain_state::{
 };
 use reth_consensus::{Consensus, FullConsensus};
 use reth_engine_primitives::{
-    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, ExecutionPayload,
-    ForkchoiceStateTracker, OnForkChoiceUpdated,
+    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, BeaconSetHeadError,
+    ExecutionPayload, ForkchoiceStateTracker, OnForkChoiceUpdated,
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_evm::ConfigureEvm;
@@ -1120,6 +1120,12 @@ where
                                 // handle the event if any
                                 self.on_maybe_tree_event(maybe_event)?;
                             }
+                            BeaconEngineMessage::SetHead { number, tx } => {
+                                let output = self.on_set_head(number);
+                                if let Err(err) = tx.send(output) {
+                                    error!(target: "engine::tree", "Failed to send event: {err:?}");
+                                }
+                            }
                         }
                     }
                 }
@@ -1254,6 +1260,105 @@ where
         self.try_connect_buffered_blocks(self.state.tree_state.current_canonical_head)
     }
 
+    /// Rewinds the canonical chain to the block with the given number.
+    ///
+    /// This behaves like `reth stage unwind`, but while the node is running: all blocks above the
+    /// new head are dropped from the in-memory state and, if they were already persisted, unwound
+    /// on disk by the persistence service. Afterwards, the last valid forkchoice state is
+    /// re-applied on top of the new head.
+    fn on_set_head(&mut self, number: u64) -> Result<BlockNumHash, BeaconSetHeadError> {
+        if !self.backfill_sync_state.is_idle() {
+            // the pipeline requires exclusive access to the database
+            return Err(BeaconSetHeadError::BackfillInProgress)
+        }
+
+        let current_head = self.state.tree_state.current_canonical_head;
+        if number > current_head.number {
+            return Err(BeaconSetHeadError::NotCanonical(number))
+        }
+        if number == current_head.number {
+            return Ok(current_head)
+        }
+
+        debug!(target: "engine::tree", ?number, ?current_head, "Rewinding canonical chain");
+
+        // wait for any in-flight persistence task, so that we know which blocks are on disk
+        while let Some((rx, _, _)) = self.persistence_state.rx.take() {
+            if let Some(persisted) = rx.blocking_recv().map_err(BeaconSetHeadError::internal)? {
+                self.persistence_state.finish(persisted.hash, persisted.number);
+                self.on_new_persisted_block().map_err(BeaconSetHeadError::internal)?;
+            }
+        }
+
+        let new_head_hash = match self.canonical_in_memory_state.hash_by_number(number) {
+            Some(hash) => Some(hash),
+            None => self.provider.block_hash(number).map_err(BeaconSetHeadError::internal)?,
+        };
+        let new_head = new_head_hash
+            .map(|hash| self.sealed_header_by_hash(hash))
+            .transpose()
+            .map_err(BeaconSetHeadError::internal)?
+            .flatten()
+            .ok_or(BeaconSetHeadError::NotCanonical(number))?;
+
+        if number < self.persistence_state.last_persisted_block.number {
+            // the new head is already persisted, so the blocks above it need to be unwound on disk
+            let (tx, rx) = oneshot::channel();
+            let _ = self.persistence.remove_blocks_above(number, tx);
+            rx.blocking_recv().map_err(BeaconSetHeadError::internal)?;
+            self.persistence_state.finish(new_head.hash(), number);
+        }
+
+        // drop all blocks above the new head and rebuild the in-memory canonical chain
+        self.state.tree_state.rewind_to(new_head.num_hash());
+        let mut canonical = Vec::new();
+        let mut current_hash = new_head.hash();
+        while let Some(block) =
+            self.state.tree_state.executed_block_by_hash(current_hash).filter(|block| {
+                block.recovered_block().number() >
+                    self.persistence_state.last_persisted_block.number
+            })
+        {
+            current_hash = block.recovered_block().parent_hash();
+            canonical.push(block.clone());
+        }
+        canonical.reverse();
+
+        self.canonical_in_memory_state.clear_state();
+        self.canonical_in_memory_state.update_chain(NewCanonicalChain::Commit { new: canonical });
+        self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+
+        self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+        self.metrics.tree.canonical_chain_height.set(number as f64);
+
+        // re-apply the last forkchoice state on top of the new head, replacing safe and finalized
+        // blocks that are no longer part of the canonical chain
+        if let Some(last_state) = self.state.forkchoice_state_tracker.last_valid_state() {
+            let canonical_or_head = |hash: B256| -> ProviderResult<B256> {
+                let is_canonical = hash.is_zero() ||
+                    self.find_canonical_header(hash)?
+                        .is_some_and(|header| header.number() <= number);
+                Ok(if is_canonical { hash } else { new_head.hash() })
+            };
+            let state = ForkchoiceState {
+                head_block_hash: new_head.hash(),
+                safe_block_hash: canonical_or_head(last_state.safe_block_hash)
+                    .map_err(BeaconSetHeadError::internal)?,
+                finalized_block_hash: canonical_or_head(last_state.finalized_block_hash)
+                    .map_err(BeaconSetHeadError::internal)?,
+            };
+
+            let output = self
+                .on_forkchoice_updated(state, None, EngineApiMessageVersion::default())
+                .map_err(BeaconSetHeadError::internal)?;
+            let status = output.outcome.forkchoice_status();
+            self.state.forkchoice_state_tracker.set_latest(state, status);
+            self.emit_event(BeaconConsensusEngineEvent::ForkchoiceUpdated(state, status));
+        }
+
+        Ok(new_head.num_hash())
+    }
+
     /// Attempts to make the given target canonical.
     ///
     /// This will update the tracked canonical in memory state and do the necessary housekeeping.
@@ -2281,8 +2386,9 @@ where
         );
         let latest_valid_hash = self.latest_valid_hash_for_invalid_payload(block.parent_hash())?;
 
-        // keep track of the invalid header
+        // keep track of the invalid header and the bad block itself, so it can be inspected via RPC
         self.state.invalid_headers.insert(block.block_with_parent());
+        self.canonical_in_memory_state.insert_bad_block(block.clone());
         self.emit_event(EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::InvalidBlock(
             Box::new(block),
         )));
@@ -231,6 +231,23 @@ impl<N: NodePrimitives> TreeState<N> {
         debug!(target: "engine::tree", ?upper_bound, ?last_persisted_hash, "Removed canonical blocks from the tree");
     }
 
+    /// Removes all blocks above the given new canonical head, regardless of the fork they belong
+    /// to, and points to the new head.
+    ///
+    /// This is used when the chain is rewound to an older block.
+    pub(crate) fn rewind_to(&mut self, new_head: BlockNumHash) {
+        let removed = self
+            .blocks_by_number
+            .range(new_head.number + 1..)
+            .flat_map(|(_, blocks)| blocks.iter().map(|block| block.recovered_block().hash()))
+            .collect::<Vec<_>>();
+        for hash in removed {
+            self.remove_by_hash(hash);
+        }
+        self.persisted_trie_updates.retain(|_, (number, _)| *number <= new_head.number);
+        self.current_canonical_head = new_head;
+    }
+
     /// Prunes old persisted trie updates based on the current block number
     /// and chain type (OP Stack or regular)
     pub(crate) fn prune_persisted_trie_updates(&mut self) {
@@ -496,6 +513,29 @@ mod tests {
         assert_eq!(tree_state.blocks_by_number[&5].len(), 2);
     }
 
+    #[test]
+    fn test_tree_state_rewind_to() {
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..6).collect();
+        for block in &blocks {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[4].recovered_block().num_hash());
+
+        let fork_block_4 = test_block_builder
+            .get_executed_block_with_number(4, blocks[2].recovered_block().hash());
+        tree_state.insert_executed(fork_block_4.clone());
+
+        tree_state.rewind_to(blocks[2].recovered_block().num_hash());
+
+        assert_eq!(tree_state.canonical_head(), &blocks[2].recovered_block().num_hash());
+        assert_eq!(tree_state.blocks_by_hash.len(), 3);
+        assert_eq!(tree_state.blocks_by_number.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(!tree_state.parent_to_child.contains_key(&blocks[2].recovered_block().hash()));
+        assert!(tree_state.executed_block_by_hash(fork_block_4.recovered_block().hash()).is_none());
+    }
+
     #[tokio::test]
     async fn test_tree_state_remove_before() {
         let start_num_hash = BlockNumHash::default();
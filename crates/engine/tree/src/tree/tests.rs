@@ -456,6 +456,95 @@ async fn test_engine_request_during_backfill() {
     assert!(resp.payload_status.is_syncing());
 }
 
+#[tokio::test]
+async fn test_engine_tree_set_head() {
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..10).collect();
+    let mut test_harness = TestHarness::new(MAINNET.clone()).with_blocks(blocks.clone());
+
+    test_harness.fcu_to(blocks[9].recovered_block().hash(), ForkchoiceStatus::Valid).await;
+
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::SetHead { number: 5, tx }.into(),
+        ))
+        .unwrap();
+    let new_head = rx.await.unwrap().unwrap();
+    assert_eq!(new_head, blocks[5].recovered_block().num_hash());
+
+    // the last forkchoice state is re-applied on top of the new head
+    test_harness.check_fcu(new_head.hash, ForkchoiceStatus::Valid).await;
+
+    assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &new_head);
+    assert_eq!(
+        test_harness.tree.canonical_in_memory_state.get_canonical_head().num_hash(),
+        new_head
+    );
+    assert!(test_harness.tree.canonical_in_memory_state.state_by_number(5).is_some());
+    assert!(test_harness.tree.canonical_in_memory_state.state_by_number(6).is_none());
+    assert!(test_harness
+        .tree
+        .state
+        .tree_state
+        .executed_block_by_hash(blocks[6].recovered_block().hash())
+        .is_none());
+
+    // the head can't be moved forward
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::SetHead { number: 9, tx }.into(),
+        ))
+        .unwrap();
+    assert_matches!(rx.await.unwrap(), Err(BeaconSetHeadError::NotCanonical(9)));
+}
+
+#[test]
+fn test_engine_tree_set_head_below_persisted() {
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..10).collect();
+    let mut test_harness = TestHarness::new(MAINNET.clone()).with_blocks(blocks.clone());
+    test_harness.tree.persistence_state.last_persisted_block =
+        blocks[3].recovered_block().num_hash();
+
+    // answer the unwind like the persistence service
+    let new_head = blocks[2].recovered_block().num_hash();
+    let action_rx = std::mem::replace(&mut test_harness.action_rx, channel().1);
+    let persistence = std::thread::spawn(move || {
+        let PersistenceAction::RemoveBlocksAbove(number, tx) = action_rx.recv().unwrap() else {
+            panic!("expected the blocks to be removed on disk")
+        };
+        tx.send(Some(new_head)).unwrap();
+        number
+    });
+
+    let (tx, rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::SetHead { number: 2, tx }.into(),
+        ))
+        .unwrap();
+    assert_eq!(rx.blocking_recv().unwrap().unwrap(), new_head);
+    assert_eq!(persistence.join().unwrap(), 2);
+
+    assert_eq!(test_harness.tree.persistence_state.last_persisted_block, new_head);
+    assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &new_head);
+    assert_eq!(
+        test_harness.tree.canonical_in_memory_state.get_canonical_head().num_hash(),
+        new_head
+    );
+    // all remaining blocks are on disk, so none are kept in memory
+    assert!(test_harness.tree.canonical_in_memory_state.state_by_number(3).is_none());
+    assert!(test_harness
+        .tree
+        .state
+        .tree_state
+        .executed_block_by_hash(blocks[3].recovered_block().hash())
+        .is_none());
+}
+
 #[test]
 fn test_disconnected_payload() {
     let s = include_str!("../../test-data/holesky/2.rlp");
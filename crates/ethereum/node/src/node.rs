@@ -12,6 +12,7 @@ use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
+use reth_ethereum_payload_builder::EthereumMinerConfig;
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
     eth::spec::EthExecutorSpec, ConfigureEvm, EvmFactory, EvmFactoryFor, NextBlockEnvAttributes,
@@ -37,9 +38,9 @@ use reth_node_builder::{
 use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
 use reth_rpc::{
     eth::core::{EthApiFor, EthRpcConverterFor},
-    ValidationApi,
+    MinerApi, ValidationApi,
 };
-use reth_rpc_api::servers::BlockSubmissionValidationApiServer;
+use reth_rpc_api::servers::{BlockSubmissionValidationApiServer, MinerApiServer};
 use reth_rpc_builder::{config::RethRpcServerConfig, middleware::RethRpcMiddleware};
 use reth_rpc_eth_api::{
     helpers::pending_block::BuildPendingEnv, RpcConvert, RpcTypes, SignableTxRequest,
@@ -48,19 +49,31 @@ use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolPooledTx, PoolTransaction,
-    TransactionPool, TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, ConfiguredEthTransactionPool, ConfiguredOrdering, PoolPooledTx,
+    PoolTransaction, TransactionOrderingKind, TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use revm::context::TxEnv;
 use std::{default::Default, marker::PhantomData, sync::Arc, time::SystemTime};
 
 /// Type configuration for a regular Ethereum node.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumNode;
+pub struct EthereumNode {
+    /// Payload builder settings that can be changed at runtime.
+    ///
+    /// Shared between the payload builder and the `miner_` api, which updates the extra data and
+    /// the gas limit of the built blocks.
+    pub miner_config: EthereumMinerConfig,
+}
 
 impl EthereumNode {
+    /// Configures the payload builder settings that are shared with the `miner_` api.
+    pub fn with_miner_config(mut self, miner_config: EthereumMinerConfig) -> Self {
+        self.miner_config = miner_config;
+        self
+    }
+
     /// Returns a [`ComponentsBuilder`] configured for a regular Ethereum node.
     pub fn components<Node>() -> ComponentsBuilder<
         Node,
@@ -178,6 +191,11 @@ pub struct EthereumAddOns<
     RpcMiddleware = Identity,
 > {
     inner: RpcAddOns<N, EthB, EV, EB, RpcMiddleware>,
+    /// Payload builder settings updated by the `miner_` api.
+    ///
+    /// If not set, the `miner_` api can't change the extra data and the gas limit of the built
+    /// blocks.
+    miner_config: Option<EthereumMinerConfig>,
 }
 
 impl<N> Default for EthereumAddOns<N, EthereumEthApiBuilder, EthereumEngineValidatorBuilder>
@@ -193,6 +211,7 @@ where
                 BasicEngineApiBuilder::default(),
                 Default::default(),
             ),
+            miner_config: None,
         }
     }
 }
@@ -202,6 +221,12 @@ where
     N: FullNodeComponents,
     EthB: EthApiBuilder<N>,
 {
+    /// Configures the payload builder settings that are updated by the `miner_` api.
+    pub fn with_miner_config(mut self, miner_config: EthereumMinerConfig) -> Self {
+        self.miner_config = Some(miner_config);
+        self
+    }
+
     /// Replace the engine API builder.
     pub fn with_engine_api<T>(
         self,
@@ -210,8 +235,8 @@ where
     where
         T: Send,
     {
-        let Self { inner } = self;
-        EthereumAddOns { inner: inner.with_engine_api(engine_api_builder) }
+        let Self { inner, miner_config } = self;
+        EthereumAddOns { inner: inner.with_engine_api(engine_api_builder), miner_config }
     }
 
     /// Replace the engine validator builder.
@@ -222,8 +247,11 @@ where
     where
         T: Send,
     {
-        let Self { inner } = self;
-        EthereumAddOns { inner: inner.with_engine_validator(engine_validator_builder) }
+        let Self { inner, miner_config } = self;
+        EthereumAddOns {
+            inner: inner.with_engine_validator(engine_validator_builder),
+            miner_config,
+        }
     }
 
     /// Sets rpc middleware
@@ -231,8 +259,8 @@ where
     where
         T: Send,
     {
-        let Self { inner } = self;
-        EthereumAddOns { inner: inner.with_rpc_middleware(rpc_middleware) }
+        let Self { inner, miner_config } = self;
+        EthereumAddOns { inner: inner.with_rpc_middleware(rpc_middleware), miner_config }
     }
 }
 
@@ -269,6 +297,10 @@ where
             Arc::new(EthereumEngineValidator::new(ctx.config.chain.clone())),
         );
 
+        let miner_api = self
+            .miner_config
+            .map(|config| MinerApi::new(ctx.node.pool().clone()).with_builder_config(config));
+
         self.inner
             .launch_add_ons_with(ctx, move |container| {
                 container.modules.merge_if_module_configured(
@@ -276,6 +308,12 @@ where
                     validation_api.into_rpc(),
                 )?;
 
+                if let Some(miner_api) = miner_api {
+                    container
+                        .modules
+                        .replace_if_module_configured(RethRpcModule::Miner, miner_api.into_rpc())?;
+                }
+
                 Ok(())
             })
             .await
@@ -345,11 +383,13 @@ where
         EthereumAddOns<NodeAdapter<N>, EthereumEthApiBuilder, EthereumEngineValidatorBuilder>;
 
     fn components_builder(&self) -> Self::ComponentsBuilder {
-        Self::components()
+        Self::components().payload(BasicPayloadServiceBuilder::new(
+            EthereumPayloadBuilder::default().with_miner_config(self.miner_config.clone()),
+        ))
     }
 
     fn add_ons(&self) -> Self::AddOns {
-        EthereumAddOns::default()
+        EthereumAddOns::default().with_miner_config(self.miner_config.clone())
     }
 }
 
@@ -396,7 +436,17 @@ where
 #[derive(Debug, Default, Clone, Copy)]
 #[non_exhaustive]
 pub struct EthereumPoolBuilder {
-    // TODO add options for txpool args
+    /// How to order the transactions of the pool, overrides `--txpool.ordering`.
+    pub ordering: Option<TransactionOrderingKind>,
+}
+
+impl EthereumPoolBuilder {
+    /// Sets the ordering of the transactions of the pool, which takes precedence over the node's
+    /// config.
+    pub const fn with_ordering(mut self, ordering: TransactionOrderingKind) -> Self {
+        self.ordering = Some(ordering);
+        self
+    }
 }
 
 impl<Types, Node> PoolBuilder<Node> for EthereumPoolBuilder
@@ -407,7 +457,7 @@ where
     >,
     Node: FullNodeTypes<Types = Types>,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
+    type Pool = ConfiguredEthTransactionPool<Node::Provider, DiskFileBlobStore>;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let pool_config = ctx.pool_config();
@@ -439,7 +489,6 @@ where
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
             .set_tx_fee_cap(ctx.config().rpc.rpc_tx_fee_cap)
             .with_max_tx_gas_limit(ctx.config().txpool.max_tx_gas_limit)
-            .with_minimum_priority_fee(ctx.config().txpool.minimum_priority_fee)
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
             .build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
 
@@ -454,11 +503,16 @@ where
             });
         }
 
+        let ordering = self.ordering.unwrap_or(ctx.config().txpool.ordering);
         let transaction_pool = TxPoolBuilder::new(ctx)
             .with_validator(validator)
-            .build_and_spawn_maintenance_task(blob_store, pool_config)?;
+            .build_with_ordering_and_spawn_maintenance_task(
+                ConfiguredOrdering::new(ordering),
+                blob_store,
+                pool_config,
+            )?;
 
-        info!(target: "reth::cli", "Transaction pool initialized");
+        info!(target: "reth::cli", %ordering, "Transaction pool initialized");
         debug!(target: "reth::cli", "Spawned txpool maintenance task");
 
         Ok(transaction_pool)
@@ -4,7 +4,7 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_ethereum_payload_builder::{EthereumBuilderConfig, EthereumMinerConfig};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
@@ -16,7 +16,18 @@ use reth_transaction_pool::{PoolTransaction, TransactionPool};
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Builder settings that can be changed at runtime through the `miner_` api.
+    pub miner_config: EthereumMinerConfig,
+}
+
+impl EthereumPayloadBuilder {
+    /// Configures the shareable builder settings that can be changed through the `miner_` api.
+    pub fn with_miner_config(mut self, miner_config: EthereumMinerConfig) -> Self {
+        self.miner_config = miner_config;
+        self
+    }
+}
 
 impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
 where
@@ -52,7 +63,9 @@ where
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
+            EthereumBuilderConfig::new()
+                .with_gas_limit(gas_limit)
+                .with_miner_config(self.miner_config),
         ))
     }
 }
@@ -1,13 +1,19 @@
 use crate::utils::eth_payload_attributes;
+use alloy_eips::Encodable2718;
 use alloy_genesis::Genesis;
+use alloy_primitives::{Address, TxKind, U256};
+use alloy_rpc_types_eth::TransactionRequest;
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::{
     node::NodeTestContext, setup, transaction::TransactionTestContext, wallet::Wallet,
 };
+use reth_ethereum_primitives::TransactionSigned;
 use reth_node_builder::{NodeBuilder, NodeHandle};
 use reth_node_core::{args::RpcServerArgs, node_config::NodeConfig};
 use reth_node_ethereum::EthereumNode;
+use reth_primitives_traits::Recovered;
 use reth_tasks::TaskManager;
+use reth_transaction_pool::{BundleTransaction, PoolBundle, TransactionPool};
 use std::sync::Arc;
 
 #[tokio::test]
@@ -127,3 +133,65 @@ async fn test_failed_run_eth_node_with_no_auth_engine_api_over_ipc_opts() -> eyr
 
     Ok(())
 }
+
+#[tokio::test]
+async fn bundle_keeps_descendants_of_conflicting_mempool_transaction() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, wallet) = setup::<EthereumNode>(
+        1,
+        Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+                .cancun_activated()
+                .build(),
+        ),
+        false,
+        eth_payload_attributes,
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+
+    let transfer = |nonce| {
+        TransactionTestContext::sign_tx(
+            wallet.inner.clone(),
+            TransactionRequest {
+                nonce: Some(nonce),
+                value: Some(U256::from(100)),
+                to: Some(TxKind::Call(Address::random())),
+                gas: Some(21000),
+                max_fee_per_gas: Some(20e9 as u128),
+                max_priority_fee_per_gas: Some(20e9 as u128),
+                chain_id: Some(1),
+                ..Default::default()
+            },
+        )
+    };
+
+    // the mempool holds the first two nonces of the sender
+    node.rpc.inject_tx(transfer(0).await.encoded_2718().into()).await?;
+    let descendant = node.rpc.inject_tx(transfer(1).await.encoded_2718().into()).await?;
+
+    // the bundle uses the first nonce for another transaction
+    let bundle_tx = TransactionSigned::from(transfer(0).await);
+    let bundle_tx_hash = *bundle_tx.tx_hash();
+    node.inner.pool.bundle_pool().unwrap().add_bundle(PoolBundle {
+        transactions: vec![BundleTransaction {
+            transaction: Recovered::new_unchecked(bundle_tx, wallet.inner.address()),
+            can_revert: false,
+        }],
+        block_number: 1,
+        max_block_number: 1,
+        signer: None,
+        replacement_uuid: None,
+    })?;
+
+    // only the conflicting mempool transaction is skipped, its descendant follows the bundle
+    let payload = node.advance_block().await?;
+    let hashes =
+        payload.block().body().transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![bundle_tx_hash, descendant]);
+
+    Ok(())
+}
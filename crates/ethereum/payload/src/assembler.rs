@@ -0,0 +1,43 @@
+//! Block assembler that applies the settings of the `miner_` api to built blocks.
+
+use alloy_primitives::Bytes;
+use reth_ethereum_primitives::Block;
+use reth_evm::execute::{
+    BlockAssembler, BlockAssemblerInput, BlockExecutionError, BlockExecutorFactory,
+};
+
+/// Wraps the block assembler of the evm config and replaces the extra data of the assembled blocks
+/// with the extra data set through the `miner_` api, if any.
+#[derive(Debug)]
+pub(crate) struct MinerBlockAssembler<'a, A> {
+    /// The block assembler of the evm config.
+    inner: &'a A,
+    /// The extra data to include in the assembled blocks.
+    extra_data: Option<Bytes>,
+}
+
+impl<'a, A> MinerBlockAssembler<'a, A> {
+    /// Creates a new [`MinerBlockAssembler`].
+    pub(crate) const fn new(inner: &'a A, extra_data: Option<Bytes>) -> Self {
+        Self { inner, extra_data }
+    }
+}
+
+impl<F, A> BlockAssembler<F> for MinerBlockAssembler<'_, A>
+where
+    F: BlockExecutorFactory,
+    A: BlockAssembler<F, Block = Block>,
+{
+    type Block = Block;
+
+    fn assemble_block(
+        &self,
+        input: BlockAssemblerInput<'_, '_, F>,
+    ) -> Result<Block, BlockExecutionError> {
+        let mut block = self.inner.assemble_block(input)?;
+        if let Some(extra_data) = &self.extra_data {
+            block.header.extra_data = extra_data.clone();
+        }
+        Ok(block)
+    }
+}
@@ -0,0 +1,144 @@
+//! Execution of the bundles of the [`BundlePool`](reth_transaction_pool::BundlePool) at the top
+//! of a block.
+
+use alloy_consensus::{Transaction, Typed2718};
+use alloy_primitives::U256;
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_evm::{ConfigureEvm, Evm, EvmEnvFor};
+use reth_revm::db::{CacheDB, State};
+use reth_transaction_pool::{PoolBundle, PoolTransaction, ValidPoolTransaction};
+use revm::DatabaseRef;
+use std::sync::Arc;
+use tracing::trace;
+
+/// The outcome of a bundle that was executed against a checkpoint of the state of a block.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BundleSimulation {
+    /// The gas used by the transactions of the bundle.
+    pub(crate) gas_used: u64,
+    /// The fees the transactions of the bundle pay to the block builder.
+    pub(crate) fees: U256,
+}
+
+/// Executes the bundle against a checkpoint of the given state, which is discarded afterwards.
+///
+/// Returns `None` if the bundle can't be included, because one of its transactions is invalid,
+/// reverts without being allowed to, or contains blobs. Since none of its transactions change the
+/// state, such a bundle is rolled back as a whole.
+pub(crate) fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    state: &State<DB>,
+    evm_env: EvmEnvFor<EvmConfig>,
+    bundle: &PoolBundle<TransactionSigned>,
+) -> Option<BundleSimulation>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: DatabaseRef,
+{
+    let base_fee = evm_env.block_env.basefee;
+    let mut checkpoint = CacheDB::new(state);
+    let mut evm = evm_config.evm_with_env(&mut checkpoint, evm_env);
+
+    let mut simulation = BundleSimulation { gas_used: 0, fees: U256::ZERO };
+    for tx in &bundle.transactions {
+        if tx.transaction.is_eip4844() {
+            trace!(target: "payload_builder", bundle=?bundle.hash(), "skipping bundle with blob transaction");
+            return None
+        }
+
+        let result = match evm.transact_commit(evm_config.tx_env(&tx.transaction)) {
+            Ok(result) => result,
+            Err(err) => {
+                trace!(target: "payload_builder", %err, bundle=?bundle.hash(), tx=?tx.transaction.hash(), "skipping bundle with invalid transaction");
+                return None
+            }
+        };
+        if !result.is_success() && !tx.can_revert {
+            trace!(target: "payload_builder", bundle=?bundle.hash(), tx=?tx.transaction.hash(), "skipping bundle with reverted transaction");
+            return None
+        }
+
+        let miner_fee = tx
+            .transaction
+            .effective_tip_per_gas(base_fee)
+            .expect("fee is always valid; execution succeeded");
+        simulation.gas_used += result.gas_used();
+        simulation.fees += U256::from(miner_fee) * U256::from(result.gas_used());
+    }
+
+    Some(simulation)
+}
+
+/// Returns the sum of the gas limits of the transactions of the bundle.
+pub(crate) fn bundle_gas_limit(bundle: &PoolBundle<TransactionSigned>) -> u64 {
+    bundle
+        .transactions
+        .iter()
+        .fold(0, |gas_limit, tx| gas_limit.saturating_add(tx.transaction.gas_limit()))
+}
+
+/// The mempool transactions that would fill the block if it had no bundles, used to check that a
+/// bundle pays more than the mempool transactions it displaces.
+///
+/// Bundles go to the top of the block, so each one pushes the mempool transactions with the lowest
+/// priority out of the end of the block.
+#[derive(Debug, Default)]
+pub(crate) struct DisplacedTransactions {
+    /// The gas limit and fees of the transactions that fill the block, by descending priority.
+    transactions: Vec<(u64, U256)>,
+    /// The sum of the gas limits of the transactions.
+    gas: u64,
+    /// The gas of the block that is left to the transactions.
+    available_gas: u64,
+}
+
+impl DisplacedTransactions {
+    /// Collects the best mempool transactions that fit into a block with the given gas limit.
+    pub(crate) fn new<T>(
+        best_txs: impl Iterator<Item = Arc<ValidPoolTransaction<T>>>,
+        base_fee: u64,
+        block_gas_limit: u64,
+    ) -> Self
+    where
+        T: PoolTransaction,
+    {
+        let mut displaced = Self { available_gas: block_gas_limit, ..Default::default() };
+        for tx in best_txs {
+            if displaced.gas + tx.gas_limit() > block_gas_limit {
+                break
+            }
+            let miner_fee = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            displaced.gas += tx.gas_limit();
+            displaced
+                .transactions
+                .push((tx.gas_limit(), U256::from(miner_fee) * U256::from(tx.gas_limit())));
+        }
+        displaced
+    }
+
+    /// Returns the fees of the transactions that no longer fit into the block if the given gas is
+    /// used at its top.
+    pub(crate) fn fees(&self, gas_used: u64) -> U256 {
+        let available_gas = self.available_gas.saturating_sub(gas_used);
+        let mut gas = self.gas;
+        let mut fees = U256::ZERO;
+        for (gas_limit, fee) in self.transactions.iter().rev() {
+            if gas <= available_gas {
+                break
+            }
+            gas -= gas_limit;
+            fees += fee;
+        }
+        fees
+    }
+
+    /// Removes the transactions that no longer fit into the block once the given gas is used at its
+    /// top.
+    pub(crate) fn displace(&mut self, gas_used: u64) {
+        self.available_gas = self.available_gas.saturating_sub(gas_used);
+        while self.gas > self.available_gas {
+            let Some((gas_limit, _)) = self.transactions.pop() else { break };
+            self.gas -= gas_limit;
+        }
+    }
+}
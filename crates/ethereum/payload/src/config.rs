@@ -1,5 +1,9 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+use alloy_primitives::Bytes;
+use parking_lot::RwLock;
+use reth_payload_primitives::MinerConfig;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
+use std::sync::Arc;
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -9,6 +13,8 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Settings that can be changed while the node is running, through the `miner_` api.
+    pub miner_config: Option<EthereumMinerConfig>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +26,11 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            miner_config: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,14 +45,93 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Configures the shareable settings that can be changed while the node is running.
+    pub fn with_miner_config(mut self, miner_config: EthereumMinerConfig) -> Self {
+        self.miner_config = Some(miner_config);
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
     /// Returns the gas limit for the next block based
     /// on parent and desired gas limits.
+    ///
+    /// A gas limit set through the `miner_` api takes precedence over the configured one.
     pub fn gas_limit(&self, parent_gas_limit: u64) -> u64 {
-        calculate_block_gas_limit(parent_gas_limit, self.desired_gas_limit)
+        let desired_gas_limit = self
+            .miner_config
+            .as_ref()
+            .and_then(EthereumMinerConfig::gas_limit)
+            .unwrap_or(self.desired_gas_limit);
+        calculate_block_gas_limit(parent_gas_limit, desired_gas_limit)
+    }
+
+    /// Returns the extra data set through the `miner_` api, if any.
+    ///
+    /// If set, it replaces the extra data of the block assembler in built blocks.
+    pub fn extra_data(&self) -> Option<Bytes> {
+        self.miner_config.as_ref().and_then(EthereumMinerConfig::extra_data)
+    }
+}
+
+/// Contains the settings of the Ethereum payload builder that can be changed while the node is
+/// running.
+///
+/// This type is shareable and can be used to update the settings through the `miner_` api, all
+/// clones refer to the same settings.
+#[derive(Debug, Clone, Default)]
+pub struct EthereumMinerConfig {
+    inner: Arc<RwLock<EthereumMinerConfigInner>>,
+}
+
+impl EthereumMinerConfig {
+    /// Returns the extra data to include in built blocks, if set.
+    pub fn extra_data(&self) -> Option<Bytes> {
+        self.inner.read().extra_data.clone()
+    }
+
+    /// Sets the extra data to include in built blocks.
+    pub fn set_extra_data(&self, extra_data: Bytes) {
+        self.inner.write().extra_data = Some(extra_data);
+    }
+
+    /// Returns the gas limit to target, if set.
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.inner.read().gas_limit
     }
+
+    /// Sets the gas limit to target.
+    pub fn set_gas_limit(&self, gas_limit: u64) {
+        self.inner.write().gas_limit = Some(gas_limit);
+    }
+}
+
+impl MinerConfig for EthereumMinerConfig {
+    fn set_extra_data(&self, extra_data: Bytes) {
+        Self::set_extra_data(self, extra_data)
+    }
+
+    fn set_gas_limit(&self, gas_limit: u64) {
+        Self::set_gas_limit(self, gas_limit)
+    }
+}
+
+/// Two configs are equal if they share the same settings.
+impl PartialEq for EthereumMinerConfig {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for EthereumMinerConfig {}
+
+#[derive(Debug, Default)]
+struct EthereumMinerConfigInner {
+    /// Replaces the extra data of the block assembler.
+    extra_data: Option<Bytes>,
+    /// Replaces the desired gas limit of the [`EthereumBuilderConfig`].
+    gas_limit: Option<u64>,
 }
 
 /// Calculate the gas limit for the next block based on parent and desired gas limits.
@@ -19,7 +19,7 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
-    execute::{BlockBuilder, BlockBuilderOutcome},
+    execute::{BasicBlockBuilder, BlockBuilder, BlockBuilderOutcome},
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
 use reth_evm_ethereum::EthEvmConfig;
@@ -35,9 +35,15 @@ use reth_transaction_pool::{
     ValidPoolTransaction,
 };
 use revm::context_interface::Block as _;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tracing::{debug, trace, warn};
 
+mod assembler;
+use assembler::MinerBlockAssembler;
+
+mod bundle;
+use bundle::DisplacedTransactions;
+
 mod config;
 pub use config::*;
 
@@ -152,23 +158,34 @@ where
 
     let state_provider = client.state_by_block_hash(parent_header.hash())?;
     let state = StateProviderDatabase::new(&state_provider);
+    // the state is read through a checkpoint when executing bundles, so it's used by reference
     let mut db =
-        State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
-
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-            },
-        )
+        State::builder().with_database_ref(cached_reads.as_db(state)).with_bundle_update().build();
+
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: attributes.suggested_fee_recipient(),
+        prev_randao: attributes.prev_randao(),
+        gas_limit: builder_config.gas_limit(parent_header.gas_limit),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+    };
+
+    let evm_env = evm_config
+        .next_evm_env(&parent_header, &next_block_attributes)
         .map_err(PayloadBuilderError::other)?;
+    let evm = evm_config.evm_with_env(&mut db, evm_env.clone());
+    let ctx = evm_config.context_for_next_block(&parent_header, next_block_attributes);
+    let mut builder: BasicBlockBuilder<'_, _, _, _, EthPrimitives> = BasicBlockBuilder {
+        executor: evm_config.create_executor(evm, ctx.clone()),
+        ctx,
+        assembler: MinerBlockAssembler::new(
+            evm_config.block_assembler(),
+            builder_config.extra_data(),
+        ),
+        parent: &parent_header,
+        transactions: Vec::new(),
+    };
 
     let chain_spec = client.chain_spec();
 
@@ -177,10 +194,11 @@ where
     let block_gas_limit: u64 = builder.evm_mut().block().gas_limit;
     let base_fee = builder.evm_mut().block().basefee;
 
-    let mut best_txs = best_txs(BestTransactionsAttributes::new(
+    let best_txs_attributes = BestTransactionsAttributes::new(
         base_fee,
         builder.evm_mut().block().blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    );
+    let mut best_txs = best_txs(best_txs_attributes);
     let mut total_fees = U256::ZERO;
 
     builder.apply_pre_execution_changes().map_err(|err| {
@@ -198,6 +216,61 @@ where
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
+    // bundles go to the top of the block, each one is executed against a checkpoint first and only
+    // included as a whole, if it pays more than the mempool transactions it pushes out of the block
+    let bundles = pool
+        .bundle_pool()
+        .map(|bundle_pool| bundle_pool.bundles_at(parent_header.number + 1))
+        .unwrap_or_default();
+    let mut displaced = if bundles.is_empty() {
+        DisplacedTransactions::default()
+    } else {
+        DisplacedTransactions::new(
+            pool.best_transactions_with_attributes(best_txs_attributes),
+            base_fee,
+            block_gas_limit,
+        )
+    };
+    // the nonces used by the included bundles, mempool transactions with these nonces are skipped
+    // but their descendants can still follow the bundle transactions
+    let mut bundle_nonces = HashSet::new();
+    for bundle in bundles {
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        // the gas limits are checked up front, because the block executor rejects transactions
+        // with a gas limit above the remaining block gas
+        if bundle::bundle_gas_limit(&bundle) > block_gas_limit.saturating_sub(cumulative_gas_used) {
+            trace!(target: "payload_builder", bundle=?bundle.hash(), "skipping bundle that exceeds the remaining block gas");
+            continue
+        }
+
+        let Some(simulation) =
+            bundle::simulate_bundle(&evm_config, builder.evm().db(), evm_env.clone(), &bundle)
+        else {
+            continue
+        };
+        let displaced_fees = displaced.fees(simulation.gas_used);
+        if simulation.fees <= displaced_fees {
+            trace!(target: "payload_builder", bundle=?bundle.hash(), fees=%simulation.fees, %displaced_fees, "skipping bundle that pays less than the transactions it displaces");
+            continue
+        }
+
+        // the bundle is executed on the state of its checkpoint, so it behaves the same
+        trace!(target: "payload_builder", bundle=?bundle.hash(), "including bundle");
+        for tx in &bundle.transactions {
+            let gas_used = builder
+                .execute_transaction(tx.transaction.clone())
+                .map_err(PayloadBuilderError::evm)?;
+            cumulative_gas_used += gas_used;
+            bundle_nonces.insert((tx.transaction.signer(), tx.transaction.nonce()));
+        }
+        total_fees += simulation.fees;
+        displaced.displace(simulation.gas_used);
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -216,6 +289,13 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
+        // the nonce was already used by a bundle, this only skips the transaction itself, so the
+        // next nonce of the sender is still yielded
+        if bundle_nonces.contains(&(*pool_tx.sender_ref(), pool_tx.nonce())) {
+            trace!(target: "payload_builder", tx=?pool_tx.hash(), "skipping transaction that conflicts with an included bundle");
+            continue
+        }
+
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
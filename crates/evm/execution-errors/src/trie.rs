@@ -209,3 +209,46 @@ impl From<TrieWitnessError> for ProviderError {
         Self::TrieWitnessError(error.to_string())
     }
 }
+
+/// Errors when verifying a range proof.
+#[derive(Error, PartialEq, Eq, Clone, Debug)]
+pub enum RangeProofError {
+    /// The number of keys and values differ.
+    #[error("range proof has {keys} keys but {values} values")]
+    LengthMismatch {
+        /// The number of keys.
+        keys: usize,
+        /// The number of values.
+        values: usize,
+    },
+    /// The keys are not strictly increasing.
+    #[error("range proof keys are not strictly increasing at {_0}")]
+    UnorderedKeys(B256),
+    /// A key is lower than the origin of the range.
+    #[error("range proof key {key} is lower than origin {origin}")]
+    KeyBeforeOrigin {
+        /// The offending key.
+        key: B256,
+        /// The origin of the range.
+        origin: B256,
+    },
+    /// A node on the boundary paths is missing from the proof.
+    #[error("range proof is missing node {hash} at {path:?}")]
+    MissingNode {
+        /// The path of the missing node.
+        path: Nibbles,
+        /// The hash of the missing node.
+        hash: B256,
+    },
+    /// The range does not hash to the expected root.
+    #[error("range proof root mismatch: got {got}, expected {expected}")]
+    RootMismatch {
+        /// The computed root.
+        got: B256,
+        /// The expected root.
+        expected: B256,
+    },
+    /// RLP decoding error.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+}
@@ -8,9 +8,17 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod target;
+
+pub use target::{BanEntry, BanTarget, IpRange, ParseBanTargetError};
+
 type PeerId = alloy_primitives::B512;
 
-use std::{collections::HashMap, net::IpAddr, time::Instant};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 /// Determines whether or not the IP is globally routable.
 /// Should be replaced with [`IpAddr::is_global`](std::net::IpAddr::is_global) once it is stable.
@@ -33,6 +41,8 @@ pub struct BanList {
     banned_ips: HashMap<IpAddr, Option<Instant>>,
     /// A set of [`PeerId`] whose packets get dropped instantly.
     banned_peers: HashMap<PeerId, Option<Instant>>,
+    /// A set of IP ranges whose packets get dropped instantly.
+    banned_ranges: BTreeMap<IpRange, Option<Instant>>,
 }
 
 impl BanList {
@@ -52,7 +62,7 @@ impl BanList {
         banned_peers: HashMap<PeerId, Option<Instant>>,
         banned_ips: HashMap<IpAddr, Option<Instant>>,
     ) -> Self {
-        Self { banned_ips, banned_peers }
+        Self { banned_ips, banned_peers, banned_ranges: BTreeMap::new() }
     }
 
     /// Removes all peers that are no longer banned.
@@ -85,10 +95,26 @@ impl BanList {
         evicted
     }
 
+    /// Removes all ip ranges that are no longer banned.
+    pub fn evict_ranges(&mut self, now: Instant) -> Vec<IpRange> {
+        let mut evicted = Vec::new();
+        self.banned_ranges.retain(|range, until| {
+            if let Some(until) = until {
+                if now > *until {
+                    evicted.push(*range);
+                    return false
+                }
+            }
+            true
+        });
+        evicted
+    }
+
     /// Removes all entries that should no longer be banned.
     ///
-    /// Returns the evicted entries.
+    /// Returns the evicted ip addresses and peers, evicted ip ranges are not returned.
     pub fn evict(&mut self, now: Instant) -> (Vec<IpAddr>, Vec<PeerId>) {
+        self.evict_ranges(now);
         let ips = self.evict_ips(now);
         let peers = self.evict_peers(now);
         (ips, peers)
@@ -100,10 +126,12 @@ impl BanList {
         self.is_banned_peer(peer_id) || self.is_banned_ip(ip)
     }
 
-    /// checks the ban list to see if it contains the given ip
+    /// checks the ban list to see if it contains the given ip, either directly or in a banned
+    /// range
     #[inline]
     pub fn is_banned_ip(&self, ip: &IpAddr) -> bool {
-        self.banned_ips.contains_key(ip)
+        self.banned_ips.contains_key(ip) ||
+            self.banned_ranges.keys().any(|range| range.contains(ip))
     }
 
     /// checks the ban list to see if it contains the given ip
@@ -117,6 +145,11 @@ impl BanList {
         self.banned_ips.remove(ip);
     }
 
+    /// Unbans the ip range
+    pub fn unban_range(&mut self, range: &IpRange) {
+        self.banned_ranges.remove(range);
+    }
+
     /// Unbans the ip address
     pub fn unban_peer(&mut self, peer_id: &PeerId) {
         self.banned_peers.remove(peer_id);
@@ -159,6 +192,80 @@ impl BanList {
             self.banned_ips.insert(ip, until);
         }
     }
+
+    /// Bans the ip range indefinitely or until the given timeout.
+    ///
+    /// Unlike single IPs, ranges of non-global IPs can be banned.
+    pub fn ban_range_with(&mut self, range: IpRange, until: Option<Instant>) {
+        self.banned_ranges.insert(range, until);
+    }
+
+    /// Bans the target indefinitely or until the given timeout.
+    ///
+    /// Returns false if the target can't be banned, see [`Self::ban_ip_with`].
+    pub fn ban(&mut self, target: BanTarget, until: Option<Instant>) -> bool {
+        match target {
+            BanTarget::Peer(peer_id) => self.ban_peer_with(peer_id, until),
+            BanTarget::Ip(ip) => {
+                if !is_global(&ip) {
+                    return false
+                }
+                self.ban_ip_with(ip, until)
+            }
+            BanTarget::Range(range) => self.ban_range_with(range, until),
+        }
+        true
+    }
+
+    /// Unbans the target.
+    ///
+    /// Returns true if the target was banned.
+    pub fn unban(&mut self, target: &BanTarget) -> bool {
+        match target {
+            BanTarget::Peer(peer_id) => self.banned_peers.remove(peer_id).is_some(),
+            BanTarget::Ip(ip) => self.banned_ips.remove(ip).is_some(),
+            BanTarget::Range(range) => self.banned_ranges.remove(range).is_some(),
+        }
+    }
+
+    /// Returns all bans with their expiry as unix timestamp.
+    pub fn entries(&self) -> Vec<BanEntry> {
+        let now = Instant::now();
+        let unix_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let expires_at = |until: &Option<Instant>| {
+            until.map(|until| (unix_now + until.saturating_duration_since(now)).as_secs())
+        };
+
+        let peers = self.banned_peers.iter().map(|(peer_id, until)| BanEntry {
+            target: BanTarget::Peer(*peer_id),
+            expires_at: expires_at(until),
+        });
+        let ips = self.banned_ips.iter().map(|(ip, until)| BanEntry {
+            target: BanTarget::Ip(*ip),
+            expires_at: expires_at(until),
+        });
+        let ranges = self.banned_ranges.iter().map(|(range, until)| BanEntry {
+            target: BanTarget::Range(*range),
+            expires_at: expires_at(until),
+        });
+        peers.chain(ips).chain(ranges).collect()
+    }
+
+    /// Bans the targets of the entries until they expire, e.g. after loading them from disk.
+    ///
+    /// Entries that already expired are skipped.
+    pub fn extend_entries(&mut self, entries: impl IntoIterator<Item = BanEntry>) {
+        let now = Instant::now();
+        let unix_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        for BanEntry { target, expires_at } in entries {
+            let until = match expires_at.map(Duration::from_secs) {
+                Some(expires_at) if expires_at <= unix_now => continue,
+                Some(expires_at) => Some(now + (expires_at - unix_now)),
+                None => None,
+            };
+            self.ban(target, until);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -185,6 +292,46 @@ mod tests {
         assert!(!banlist.is_banned_ip(&ip));
     }
 
+    #[test]
+    fn can_ban_unban_range() {
+        let range: IpRange = "10.0.0.0/8".parse().unwrap();
+        let mut banlist = BanList::default();
+        assert!(banlist.ban(BanTarget::Range(range), None));
+        assert!(banlist.is_banned_ip(&IpAddr::from([10, 1, 2, 3])));
+        assert!(!banlist.is_banned_ip(&IpAddr::from([11, 1, 2, 3])));
+        assert!(banlist.unban(&BanTarget::Range(range)));
+        assert!(!banlist.is_banned_ip(&IpAddr::from([10, 1, 2, 3])));
+        assert!(!banlist.unban(&BanTarget::Range(range)));
+    }
+
+    #[test]
+    fn entries_roundtrip() {
+        let now = Instant::now();
+        let peer = PeerId::random();
+        let mut banlist = BanList::default();
+        banlist.ban(BanTarget::Peer(peer), Some(now + Duration::from_secs(60)));
+        banlist.ban(BanTarget::Ip(IpAddr::from([1, 1, 1, 1])), None);
+        banlist.ban(BanTarget::Range("1.2.0.0/16".parse().unwrap()), None);
+
+        let mut entries = banlist.entries();
+        assert_eq!(entries.len(), 3);
+        // expired entries are not restored
+        entries.push(BanEntry {
+            target: BanTarget::Ip(IpAddr::from([2, 2, 2, 2])),
+            expires_at: Some(1),
+        });
+
+        let mut restored = BanList::default();
+        restored.extend_entries(entries);
+        assert!(restored.is_banned_peer(&peer));
+        assert!(restored.is_banned_ip(&IpAddr::from([1, 2, 3, 4])));
+        assert!(!restored.is_banned_ip(&IpAddr::from([2, 2, 2, 2])));
+
+        // the peer is unbanned after the restored expiry
+        restored.evict(now + Duration::from_secs(62));
+        assert!(!restored.is_banned_peer(&peer));
+    }
+
     #[test]
     fn cannot_ban_non_global() {
         let mut ip = IpAddr::from([0, 0, 0, 0]);
@@ -0,0 +1,185 @@
+//! Targets of bans.
+
+use crate::PeerId;
+use std::{
+    fmt,
+    net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+/// A range of IP addresses in CIDR notation, e.g. `10.0.0.0/8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr))]
+pub struct IpRange {
+    /// The first address of the range.
+    addr: IpAddr,
+    /// The number of leading bits that all addresses of the range share.
+    prefix_len: u8,
+}
+
+impl IpRange {
+    /// Creates the range of all addresses that share the first `prefix_len` bits with the given
+    /// address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, ParseBanTargetError> {
+        let addr = match addr {
+            IpAddr::V4(addr) if prefix_len <= 32 => {
+                let mask = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or_default();
+                IpAddr::V4(Ipv4Addr::from(u32::from(addr) & mask))
+            }
+            IpAddr::V6(addr) if prefix_len <= 128 => {
+                let mask = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or_default();
+                IpAddr::V6(Ipv6Addr::from(u128::from(addr) & mask))
+            }
+            _ => return Err(ParseBanTargetError::InvalidPrefixLength(prefix_len)),
+        };
+        Ok(Self { addr, prefix_len })
+    }
+
+    /// Returns the first address of the range.
+    pub const fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the prefix that all addresses of the range share.
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns true if the address is in the range.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        Self::new(*ip, self.prefix_len).is_ok_and(|range| range.addr == self.addr)
+    }
+}
+
+impl fmt::Display for IpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = ParseBanTargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix_len) = s.split_once('/').ok_or(ParseBanTargetError::MissingPrefix)?;
+        let prefix_len =
+            prefix_len.parse().map_err(|_| ParseBanTargetError::Invalid(s.to_string()))?;
+        Self::new(addr.parse()?, prefix_len)
+    }
+}
+
+/// What to ban: a peer, an IP address or a range of IP addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr))]
+pub enum BanTarget {
+    /// A peer by its id.
+    Peer(PeerId),
+    /// A single IP address.
+    Ip(IpAddr),
+    /// A range of IP addresses.
+    Range(IpRange),
+}
+
+impl fmt::Display for BanTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Peer(peer_id) => write!(f, "{peer_id:x}"),
+            Self::Ip(ip) => ip.fmt(f),
+            Self::Range(range) => range.fmt(f),
+        }
+    }
+}
+
+impl FromStr for BanTarget {
+    type Err = ParseBanTargetError;
+
+    /// Parses an IP address, a CIDR range, a hex encoded peer id or an enode URL.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('/') && !s.starts_with("enode://") {
+            return s.parse().map(Self::Range)
+        }
+        if let Ok(ip) = s.parse() {
+            return Ok(Self::Ip(ip))
+        }
+        let id = s
+            .strip_prefix("enode://")
+            .map_or(s, |enode| enode.split_once('@').map_or(enode, |(id, _)| id));
+        id.parse().map(Self::Peer).map_err(|_| ParseBanTargetError::Invalid(s.to_string()))
+    }
+}
+
+/// Error when parsing a [`BanTarget`] or [`IpRange`].
+#[derive(Debug, thiserror::Error)]
+pub enum ParseBanTargetError {
+    /// Failed to parse the IP address.
+    #[error(transparent)]
+    AddrParseError(#[from] AddrParseError),
+    /// The range has no prefix length.
+    #[error("missing prefix length")]
+    MissingPrefix,
+    /// The prefix length exceeds the length of the address.
+    #[error("invalid prefix length {0}")]
+    InvalidPrefixLength(u8),
+    /// Neither a peer id, an IP address nor a range.
+    #[error("invalid ban target: {0}")]
+    Invalid(String),
+}
+
+/// A ban and when it expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BanEntry {
+    /// What is banned.
+    pub target: BanTarget,
+    /// The unix timestamp in seconds when the ban expires, `None` if it never expires.
+    pub expires_at: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ranges() {
+        let range: IpRange = "10.1.2.3/16".parse().unwrap();
+        assert_eq!(range.to_string(), "10.1.0.0/16");
+        assert!(range.contains(&IpAddr::from([10, 1, 255, 1])));
+        assert!(!range.contains(&IpAddr::from([10, 2, 0, 1])));
+        assert!(!range.contains(&"::1".parse().unwrap()));
+
+        let all: IpRange = "0.0.0.0/0".parse().unwrap();
+        assert!(all.contains(&IpAddr::from([1, 2, 3, 4])));
+
+        let range: IpRange = "2001:db8::/32".parse().unwrap();
+        assert!(range.contains(&"2001:db8:1::1".parse().unwrap()));
+        assert!(!range.contains(&"2001:db9::1".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<IpRange>().is_err());
+        assert!("10.0.0.0".parse::<IpRange>().is_err());
+    }
+
+    #[test]
+    fn parse_targets() {
+        let peer_id = PeerId::random();
+        let enode = format!("enode://{peer_id:x}@10.0.0.1:30303");
+        assert_eq!(enode.parse::<BanTarget>().unwrap(), BanTarget::Peer(peer_id));
+        assert_eq!(format!("{peer_id:x}").parse::<BanTarget>().unwrap(), BanTarget::Peer(peer_id));
+        assert_eq!(
+            "1.2.3.4".parse::<BanTarget>().unwrap(),
+            BanTarget::Ip(IpAddr::from([1, 2, 3, 4]))
+        );
+        let target = "1.2.3.0/24".parse::<BanTarget>().unwrap();
+        assert_eq!(target.to_string().parse::<BanTarget>().unwrap(), target);
+        assert!("peer".parse::<BanTarget>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_entry() {
+        let entry = BanEntry { target: "1.2.3.0/24".parse().unwrap(), expires_at: Some(10) };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"target":"1.2.3.0/24","expiresAt":10}"#);
+        assert_eq!(serde_json::from_str::<BanEntry>(&json).unwrap(), entry);
+    }
+}
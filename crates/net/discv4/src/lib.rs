@@ -42,6 +42,7 @@ use itertools::Itertools;
 use parking_lot::Mutex;
 use proto::{EnrRequest, EnrResponse};
 use reth_ethereum_forks::ForkId;
+use reth_net_banlist::BanTarget;
 use reth_network_peers::{pk2id, PeerId};
 use secp256k1::SecretKey;
 use std::{
@@ -370,6 +371,20 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Adds the peer, ip or ip range to the ban list indefinitely or until the given timestamp.
+    ///
+    /// This will prevent any future inclusion in the table
+    pub fn ban_target(&self, target: BanTarget, until: Option<Instant>) {
+        let cmd = Discv4Command::BanTarget(target, until);
+        self.send_to_service(cmd);
+    }
+
+    /// Removes the peer, ip or ip range from the ban list.
+    pub fn unban_target(&self, target: BanTarget) {
+        let cmd = Discv4Command::UnbanTarget(target);
+        self.send_to_service(cmd);
+    }
+
     /// Sets the tcp port
     ///
     /// This will update our [`NodeRecord`]'s tcp port.
@@ -378,6 +393,23 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Sets the udp port
+    ///
+    /// This will update our [`NodeRecord`]'s udp port, e.g. after the port was mapped to a
+    /// different external port.
+    pub fn set_udp_port(&self, port: u16) {
+        let cmd = Discv4Command::SetUdpPort(port);
+        self.send_to_service(cmd);
+    }
+
+    /// Sets the external ip
+    ///
+    /// This will update our [`NodeRecord`]'s ip address.
+    pub fn set_external_ip(&self, ip: IpAddr) {
+        let cmd = Discv4Command::SetExternalIp(ip);
+        self.send_to_service(cmd);
+    }
+
     /// Sets the pair in the EIP-868 [`Enr`] of the node.
     ///
     /// If the key already exists, this will update it.
@@ -865,6 +897,21 @@ impl Discv4Service {
         self.config.ban_list.ban_peer_until(node_id, until);
     }
 
+    /// Adds the peer, ip or ip range to the ban list indefinitely or until the given timestamp.
+    ///
+    /// Single non-global ips are not banned, see [`BanList::ban`](reth_net_banlist::BanList::ban).
+    pub fn ban_target(&mut self, target: BanTarget, until: Option<Instant>) {
+        if let BanTarget::Peer(node_id) = target {
+            self.remove_node(node_id);
+        }
+        self.config.ban_list.ban(target, until);
+    }
+
+    /// Removes the peer, ip or ip range from the ban list.
+    pub fn unban_target(&mut self, target: &BanTarget) {
+        self.config.ban_list.unban(target);
+    }
+
     /// Removes a `node_id` from the routing table.
     ///
     /// This allows applications, for whatever reason, to remove nodes from the local routing
@@ -1340,6 +1387,7 @@ impl Discv4Service {
             if resp.echo_hash == msg.request_hash {
                 let key = kad_key(id);
                 let fork_id = msg.eth_fork_id();
+                self.notify(DiscoveryUpdate::Enr(msg.enr));
                 let (record, old_fork_id) = match self.kbuckets.entry(&key) {
                     kbucket::Entry::Present(mut entry, _) => {
                         let id = entry.value_mut().update_with_fork_id(fork_id);
@@ -1750,6 +1798,8 @@ impl Discv4Service {
                     Discv4Command::BanIp(ip) => {
                         self.ban_ip(ip);
                     }
+                    Discv4Command::BanTarget(target, until) => self.ban_target(target, until),
+                    Discv4Command::UnbanTarget(target) => self.unban_target(&target),
                     Discv4Command::SetEIP868RLPPair { key, rlp } => {
                         debug!(target: "discv4", key=%String::from_utf8_lossy(&key), "Update EIP-868 extension pair");
 
@@ -1763,7 +1813,19 @@ impl Discv4Service {
                         } else {
                             let _ = self.local_eip_868_enr.set_tcp6(port, &self.secret_key);
                         }
+                        *self.shared_node_record.lock() = self.local_node_record;
+                    }
+                    Discv4Command::SetUdpPort(port) => {
+                        debug!(target: "discv4", %port, "Update udp port");
+                        self.local_node_record.udp_port = port;
+                        if self.local_node_record.address.is_ipv4() {
+                            let _ = self.local_eip_868_enr.set_udp4(port, &self.secret_key);
+                        } else {
+                            let _ = self.local_eip_868_enr.set_udp6(port, &self.secret_key);
+                        }
+                        *self.shared_node_record.lock() = self.local_node_record;
                     }
+                    Discv4Command::SetExternalIp(ip) => self.set_external_ip_addr(ip),
 
                     Discv4Command::Terminated => {
                         // terminate the service
@@ -2045,10 +2107,14 @@ impl Default for ReceiveCache {
 enum Discv4Command {
     Add(NodeRecord),
     SetTcpPort(u16),
+    SetUdpPort(u16),
+    SetExternalIp(IpAddr),
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
     BanPeer(PeerId),
     BanIp(IpAddr),
+    BanTarget(BanTarget, Option<Instant>),
+    UnbanTarget(BanTarget),
     Remove(PeerId),
     Lookup { node_id: Option<PeerId>, tx: Option<NodeRecordSender> },
     SetLookupInterval(Duration),
@@ -2399,6 +2465,8 @@ pub enum DiscoveryUpdate {
     DiscoveredAtCapacity(NodeRecord),
     /// Received a [`ForkId`] via EIP-868 for the given [`NodeRecord`].
     EnrForkId(NodeRecord, ForkId),
+    /// Received the ENR of a node via EIP-868.
+    Enr(Enr<SecretKey>),
     /// Node that was removed from the table
     Removed(PeerId),
     /// A series of updates
@@ -2812,6 +2880,29 @@ mod tests {
         let _ = discv4.lookup_self().await;
     }
 
+    #[tokio::test]
+    async fn test_set_external_addr() {
+        reth_tracing::init_test_tracing();
+
+        let config = Discv4Config::builder().enable_eip868(true).build();
+        let (discv4, mut service) = create_discv4_with_config(config).await;
+
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        discv4.set_external_ip(ip);
+        discv4.set_tcp_port(40404);
+        discv4.set_udp_port(40405);
+        poll_fn(|cx| {
+            let _ = service.poll(cx);
+            Poll::Ready(())
+        })
+        .await;
+
+        let record = discv4.node_record();
+        assert_eq!((record.address, record.tcp_port, record.udp_port), (ip, 40404, 40405));
+        assert_eq!(service.local_eip_868_enr.udp4(), Some(40405));
+        assert_eq!(service.local_eip_868_enr.tcp4(), Some(40404));
+    }
+
     #[tokio::test]
     async fn test_requests_timeout() {
         reth_tracing::init_test_tracing();
@@ -13,7 +13,10 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-pub use crate::resolver::{DnsResolver, MapResolver, Resolver};
+pub use crate::{
+    publisher::EnrTree,
+    resolver::{DnsResolver, MapResolver, Resolver},
+};
 use crate::{
     query::{QueryOutcome, QueryPool, ResolveEntryResult, ResolveRootResult},
     sync::{ResolveKind, SyncAction},
@@ -51,6 +54,7 @@ use tracing::{debug, trace};
 
 mod config;
 mod error;
+pub mod publisher;
 mod query;
 pub mod resolver;
 mod sync;
@@ -0,0 +1,228 @@
+//! Creates [EIP-1459](https://eips.ethereum.org/EIPS/eip-1459) trees that can be published via DNS.
+//!
+//! The node records and links are the leaves of two merkle trees, see [`crate::tree`]. Every entry
+//! is published as a TXT record at the subdomain named after the abbreviated hash of its content,
+//! the signed root entry is published at the domain itself.
+
+use crate::tree::{BranchEntry, DnsEntry, LinkEntry, NodeEntry, TreeRootEntry};
+use alloy_primitives::keccak256;
+use data_encoding::BASE32_NOPAD;
+use enr::{Enr, Error as EnrError};
+use secp256k1::SecretKey;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// The number of bytes of the keccak256 hash of an entry that make up its subdomain.
+const HASH_ABBREV_SIZE: usize = 16;
+
+/// The maximum number of children of a branch entry, so that the branch fits into a TXT record of
+/// 370 bytes: every child is a base32 encoded abbreviated hash followed by a comma.
+const MAX_CHILDREN: usize = 370 / ((HASH_ABBREV_SIZE * 8).div_ceil(5) + 1);
+
+/// The maximum length of a single character string of a TXT record.
+const MAX_TXT_STRING_LEN: usize = 255;
+
+/// A tree of node records and links to other trees.
+///
+/// The root of the tree must be signed with [`EnrTree::sign`] before it is published.
+#[derive(Debug, Clone)]
+pub struct EnrTree {
+    /// The root of the tree.
+    root: TreeRootEntry,
+    /// All entries of the tree by their subdomain.
+    entries: BTreeMap<String, DnsEntry<SecretKey>>,
+}
+
+// === impl EnrTree ===
+
+impl EnrTree {
+    /// Creates the tree of the given node records and links with the given sequence number.
+    ///
+    /// The records are sorted by node id and the links by their text representation, so the same
+    /// input always results in the same tree.
+    pub fn new(
+        mut nodes: Vec<Enr<SecretKey>>,
+        mut links: Vec<LinkEntry<SecretKey>>,
+        sequence_number: u64,
+    ) -> Self {
+        nodes.sort_by_key(|enr| enr.node_id().raw());
+        nodes.dedup_by_key(|enr| enr.node_id().raw());
+        links.sort_by_cached_key(|link| link.to_string());
+        links.dedup();
+
+        let mut entries = BTreeMap::new();
+        let enr_root =
+            build(&mut entries, nodes.into_iter().map(|enr| DnsEntry::Node(NodeEntry { enr })));
+        let enr_root = insert(&mut entries, enr_root);
+        let link_root = build(&mut entries, links.into_iter().map(DnsEntry::Link));
+        let link_root = insert(&mut entries, link_root);
+
+        let root =
+            TreeRootEntry { enr_root, link_root, sequence_number, signature: Default::default() };
+        Self { root, entries }
+    }
+
+    /// Signs the root of the tree with the given key.
+    ///
+    /// Clients that sync the tree verify the root against the public key of the
+    /// [`LinkEntry`] that points to the tree.
+    pub fn sign(&mut self, key: &SecretKey) -> Result<(), EnrError> {
+        self.root.sign(key)
+    }
+
+    /// Returns the root of the tree.
+    pub const fn root(&self) -> &TreeRootEntry {
+        &self.root
+    }
+
+    /// Returns the node records of the tree.
+    pub fn nodes(&self) -> impl Iterator<Item = &Enr<SecretKey>> + '_ {
+        self.entries.values().filter_map(|entry| match entry {
+            DnsEntry::Node(node) => Some(&node.enr),
+            _ => None,
+        })
+    }
+
+    /// Returns the content of all TXT records of the tree when published at the given domain, by
+    /// their fully qualified name.
+    pub fn records(&self, domain: &str) -> BTreeMap<String, String> {
+        let mut records = self
+            .entries
+            .iter()
+            .map(|(hash, entry)| (format!("{hash}.{domain}"), entry.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        records.insert(domain.to_string(), self.root.to_string());
+        records
+    }
+
+    /// Returns the TXT records of the tree when published at the given domain in zone file
+    /// format, with the root record first.
+    ///
+    /// Records that exceed the maximum length of a character string are split into multiple
+    /// strings, as specified by RFC 1035.
+    pub fn zone_file(&self, domain: &str, ttl: u32) -> String {
+        let mut zone = String::new();
+        let root = (domain.to_string(), self.root.to_string());
+        let entries = self
+            .entries
+            .iter()
+            .map(|(hash, entry)| (format!("{hash}.{domain}"), entry.to_string()));
+        for (name, content) in std::iter::once(root).chain(entries) {
+            let strings = content
+                .as_bytes()
+                .chunks(MAX_TXT_STRING_LEN)
+                .map(|chunk| format!("\"{}\"", String::from_utf8_lossy(chunk)))
+                .collect::<Vec<_>>();
+            let _ = writeln!(zone, "{name}. {ttl} IN TXT {}", strings.join(" "));
+        }
+        zone
+    }
+}
+
+/// Builds the subtree of the given entries and returns its root, which is _not_ inserted.
+///
+/// Entries are grouped into branches of at most [`MAX_CHILDREN`] children until a single root
+/// remains.
+fn build(
+    entries: &mut BTreeMap<String, DnsEntry<SecretKey>>,
+    children: impl IntoIterator<Item = DnsEntry<SecretKey>>,
+) -> DnsEntry<SecretKey> {
+    let mut children = children.into_iter().collect::<Vec<_>>();
+    if children.len() == 1 {
+        return children.remove(0)
+    }
+    if children.len() <= MAX_CHILDREN {
+        let children = children.into_iter().map(|entry| insert(entries, entry)).collect();
+        return DnsEntry::Branch(BranchEntry { children })
+    }
+
+    let mut subtrees = Vec::with_capacity(children.len().div_ceil(MAX_CHILDREN));
+    while !children.is_empty() {
+        let rest = children.split_off(children.len().min(MAX_CHILDREN));
+        subtrees.push(build(entries, std::mem::replace(&mut children, rest)));
+    }
+    build(entries, subtrees)
+}
+
+/// Inserts the entry and returns its subdomain, the base32 encoded abbreviated hash of the entry.
+fn insert(
+    entries: &mut BTreeMap<String, DnsEntry<SecretKey>>,
+    entry: DnsEntry<SecretKey>,
+) -> String {
+    let hash = keccak256(entry.to_string().as_bytes());
+    let subdomain = BASE32_NOPAD.encode(&hash[..HASH_ABBREV_SIZE]);
+    entries.insert(subdomain.clone(), entry);
+    subdomain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DnsDiscoveryConfig, DnsDiscoveryEvent, DnsDiscoveryService, MapResolver};
+    use enr::EnrKey;
+    use secp256k1::rand::thread_rng;
+    use std::{collections::HashSet, future::poll_fn, net::Ipv4Addr, num::NonZeroUsize, sync::Arc};
+
+    fn rng_enr() -> Enr<SecretKey> {
+        let secret_key = SecretKey::new(&mut thread_rng());
+        Enr::builder().ip4(Ipv4Addr::LOCALHOST).udp4(30303).tcp4(30303).build(&secret_key).unwrap()
+    }
+
+    #[test]
+    fn build_tree() {
+        let nodes = (0..100).map(|_| rng_enr()).collect::<Vec<_>>();
+        let mut tree = EnrTree::new(nodes.clone(), vec![], 1);
+        tree.sign(&SecretKey::new(&mut thread_rng())).unwrap();
+        assert_eq!(tree.nodes().count(), nodes.len());
+
+        // the same nodes in a different order result in the same tree
+        let mut reversed = nodes;
+        reversed.reverse();
+        let other = EnrTree::new(reversed, vec![], 1);
+        assert_eq!(other.root().enr_root, tree.root().enr_root);
+
+        for (name, content) in tree.records("nodes.example.org") {
+            assert!(content.len() <= 370 || content.starts_with("enr:"), "{name}: {content}");
+            if name != "nodes.example.org" {
+                let entry: DnsEntry<SecretKey> = content.parse().unwrap();
+                assert_eq!(entry.to_string(), content);
+            }
+        }
+
+        let zone = tree.zone_file("nodes.example.org", 3600);
+        assert!(zone.starts_with("nodes.example.org. 3600 IN TXT \"enrtree-root:v1 e="));
+        assert_eq!(zone.lines().count(), tree.records("nodes.example.org").len());
+    }
+
+    #[tokio::test]
+    async fn sync_published_tree() {
+        reth_tracing::init_test_tracing();
+
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let nodes = (0..30).map(|_| rng_enr()).collect::<Vec<_>>();
+        let other =
+            LinkEntry { domain: "other.example.org".to_string(), pubkey: secret_key.public() };
+        let mut tree = EnrTree::new(nodes.clone(), vec![other], 7);
+        tree.sign(&secret_key).unwrap();
+
+        let link =
+            LinkEntry { domain: "nodes.example.org".to_string(), pubkey: secret_key.public() };
+        let resolver = MapResolver::default();
+        for (name, content) in tree.records(&link.domain) {
+            resolver.insert(name, content);
+        }
+
+        let config = DnsDiscoveryConfig {
+            max_requests_per_sec: NonZeroUsize::new(1_000).unwrap(),
+            ..Default::default()
+        };
+        let mut service = DnsDiscoveryService::new(Arc::new(resolver), config);
+        service.sync_tree_with_link(link);
+
+        let mut discovered = HashSet::new();
+        while discovered.len() < nodes.len() {
+            let DnsDiscoveryEvent::Enr(enr) = poll_fn(|cx| service.poll(cx)).await;
+            discovered.insert(enr.node_id());
+        }
+        assert!(nodes.iter().all(|enr| discovered.contains(&enr.node_id())));
+    }
+}
@@ -100,6 +100,11 @@ impl Capability {
         Self::eth(EthVersion::Eth68)
     }
 
+    /// Returns the `snap/1` capability.
+    pub const fn snap_1() -> Self {
+        Self::new_static("snap", 1)
+    }
+
     /// Whether this is eth v66 protocol.
     #[inline]
     pub fn is_eth_v66(&self) -> bool {
@@ -123,6 +128,12 @@ impl Capability {
     pub fn is_eth(&self) -> bool {
         self.is_eth_v66() || self.is_eth_v67() || self.is_eth_v68()
     }
+
+    /// Whether this is snap v1.
+    #[inline]
+    pub fn is_snap_v1(&self) -> bool {
+        self.name == "snap" && self.version == 1
+    }
 }
 
 impl fmt::Display for Capability {
@@ -6,9 +6,11 @@
 //! Current version: snap/1
 
 use alloc::vec::Vec;
-use alloy_primitives::{Bytes, B256};
+use alloy_consensus::constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY};
+use alloy_primitives::{Bytes, B256, U256};
 use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
 use reth_codecs_derive::add_arbitrary_tests;
+use reth_primitives_traits::Account;
 
 /// Message IDs for the snap sync protocol
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -61,6 +63,74 @@ pub struct AccountData {
     pub body: Bytes,
 }
 
+/// Account body in the slim format used by the snap protocol.
+///
+/// Identical to the consensus account encoding, except that the storage root and code hash are
+/// encoded as empty byte strings if they are equal to the empty trie root and empty code hash.
+// https://github.com/ethereum/devp2p/blob/master/caps/snap.md#data-format
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct SlimAccount {
+    /// Account nonce
+    pub nonce: u64,
+    /// Account balance
+    pub balance: U256,
+    /// Storage root, empty if the storage trie is empty
+    pub storage_root: Bytes,
+    /// Code hash, empty if the account has no code
+    pub code_hash: Bytes,
+}
+
+impl SlimAccount {
+    /// Creates the slim representation of the given account with the given storage root.
+    pub fn new(account: Account, storage_root: B256) -> Self {
+        let code_hash = account.get_bytecode_hash();
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root: if storage_root == EMPTY_ROOT_HASH {
+                Bytes::new()
+            } else {
+                Bytes::copy_from_slice(storage_root.as_slice())
+            },
+            code_hash: if code_hash == KECCAK_EMPTY {
+                Bytes::new()
+            } else {
+                Bytes::copy_from_slice(code_hash.as_slice())
+            },
+        }
+    }
+
+    /// Returns the storage root of the account.
+    pub fn storage_root(&self) -> B256 {
+        if self.storage_root.is_empty() {
+            EMPTY_ROOT_HASH
+        } else {
+            B256::left_padding_from(&self.storage_root)
+        }
+    }
+
+    /// Returns the code hash of the account.
+    pub fn code_hash(&self) -> B256 {
+        if self.code_hash.is_empty() {
+            KECCAK_EMPTY
+        } else {
+            B256::left_padding_from(&self.code_hash)
+        }
+    }
+
+    /// Converts the slim account into an [`Account`], dropping the storage root.
+    pub fn into_account(self) -> Account {
+        let code_hash = self.code_hash();
+        Account {
+            nonce: self.nonce,
+            balance: self.balance,
+            bytecode_hash: (code_hash != KECCAK_EMPTY).then_some(code_hash),
+        }
+    }
+}
+
 /// Response containing a number of consecutive accounts and the Merkle proofs for the entire range.
 // http://github.com/ethereum/devp2p/blob/master/caps/snap.md#accountrange-0x01
 #[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
@@ -225,6 +295,45 @@ impl SnapProtocolMessage {
         }
     }
 
+    /// Returns the request ID of this message.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Sets the request ID of this message.
+    pub const fn set_request_id(&mut self, request_id: u64) {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id = request_id,
+            Self::AccountRange(msg) => msg.request_id = request_id,
+            Self::GetStorageRanges(msg) => msg.request_id = request_id,
+            Self::StorageRanges(msg) => msg.request_id = request_id,
+            Self::GetByteCodes(msg) => msg.request_id = request_id,
+            Self::ByteCodes(msg) => msg.request_id = request_id,
+            Self::GetTrieNodes(msg) => msg.request_id = request_id,
+            Self::TrieNodes(msg) => msg.request_id = request_id,
+        }
+    }
+
+    /// Returns `true` if this message is a request.
+    pub const fn is_request(&self) -> bool {
+        matches!(
+            self,
+            Self::GetAccountRange(_) |
+                Self::GetStorageRanges(_) |
+                Self::GetByteCodes(_) |
+                Self::GetTrieNodes(_)
+        )
+    }
+
     /// Encode the message to bytes
     pub fn encode(&self) -> Bytes {
         let mut buf = Vec::new();
@@ -406,6 +515,23 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_slim_account_roundtrip() {
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        let slim = SlimAccount::new(account, EMPTY_ROOT_HASH);
+        assert!(slim.storage_root.is_empty());
+        assert!(slim.code_hash.is_empty());
+        assert_eq!(slim.storage_root(), EMPTY_ROOT_HASH);
+        assert_eq!(slim.clone().into_account(), account);
+
+        let account =
+            Account { nonce: 3, balance: U256::from(4), bytecode_hash: Some(b256_from_u64(5)) };
+        let slim = SlimAccount::new(account, b256_from_u64(6));
+        assert_eq!(slim.storage_root(), b256_from_u64(6));
+        assert_eq!(slim.code_hash(), b256_from_u64(5));
+        assert_eq!(slim.into_account(), account);
+    }
+
     #[test]
     fn test_unknown_message_id() {
         // Create some random data
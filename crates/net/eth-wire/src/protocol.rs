@@ -45,6 +45,13 @@ impl Protocol {
         Self::eth(EthVersion::Eth68)
     }
 
+    /// Returns the `snap/1` protocol.
+    ///
+    /// See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+    pub const fn snap_1() -> Self {
+        Self::new(Capability::snap_1(), 8)
+    }
+
     /// Consumes the type and returns a tuple of the [Capability] and number of messages.
     #[inline]
     pub(crate) fn split(self) -> (Capability, u8) {
@@ -1,4 +1,4 @@
-//! Helpers for resolving the external IP.
+//! Helpers for resolving the external IP and mapping ports on the gateway of the local network.
 //!
 //! ## Feature Flags
 //!
@@ -12,8 +12,15 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod mapping;
+pub mod natpmp;
 pub mod net_if;
+pub mod upnp;
 
+pub use mapping::{
+    ExternalAddress, MappedPort, PortMapping, PortMappingConfig, PortMappingError,
+    PortMappingHandle, PortMappingProtocol, DEFAULT_PORT_MAPPING_LEASE,
+};
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
 
 use std::{
@@ -44,8 +51,11 @@ pub enum NatResolver {
     /// Resolve with any available resolver.
     #[default]
     Any,
-    /// Resolve external IP via `UPnP`.
+    /// Resolve external IP via `UPnP` and map ports on the gateway, see [`PortMappingHandle`].
     Upnp,
+    /// Resolve external IP via NAT-PMP or PCP and map ports on the gateway, see
+    /// [`PortMappingHandle`].
+    NatPmp,
     /// Resolve external IP via a network request.
     PublicIp,
     /// Use the given [`IpAddr`]
@@ -76,6 +86,7 @@ impl fmt::Display for NatResolver {
         match self {
             Self::Any => f.write_str("any"),
             Self::Upnp => f.write_str("upnp"),
+            Self::NatPmp => f.write_str("natpmp"),
             Self::PublicIp => f.write_str("publicip"),
             Self::ExternalIp(ip) => write!(f, "extip:{ip}"),
             Self::NetIf => f.write_str("netif"),
@@ -102,6 +113,7 @@ impl FromStr for NatResolver {
         let r = match s {
             "any" => Self::Any,
             "upnp" => Self::Upnp,
+            "natpmp" | "pcp" => Self::NatPmp,
             "none" => Self::None,
             "publicip" | "public-ip" => Self::PublicIp,
             "netif" => Self::NetIf,
@@ -202,7 +214,11 @@ pub async fn external_ip() -> Option<IpAddr> {
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Upnp | NatResolver::NatPmp => match resolve_gateway_ip(resolver).await {
+            Some(ip) => Some(ip),
+            None => resolve_external_ip().await,
+        },
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
@@ -216,6 +232,24 @@ pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     }
 }
 
+/// Asks the gateway of the local network for its external IP.
+async fn resolve_gateway_ip(resolver: NatResolver) -> Option<IpAddr> {
+    let ip = match resolver {
+        NatResolver::Upnp => match upnp::search_gateway(Default::default()).await {
+            Ok(gateway) => gateway.external_ip().await,
+            Err(err) => Err(err),
+        },
+        _ => match natpmp::Gateway::default_gateway() {
+            Ok(gateway) => gateway.external_ip().await,
+            Err(err) => Err(err),
+        },
+    };
+    ip.inspect_err(|err| {
+        debug!(target: "net::nat", %err, ?resolver, "Failed to resolve external IP from gateway");
+    })
+    .ok()
+}
+
 async fn resolve_external_ip() -> Option<IpAddr> {
     let futures = EXTERNAL_IP_APIS.iter().copied().map(resolve_external_ip_url_res).map(Box::pin);
     futures_util::future::select_ok(futures)
@@ -271,6 +305,8 @@ mod tests {
     fn test_from_str() {
         assert_eq!(NatResolver::Any, "any".parse().unwrap());
         assert_eq!(NatResolver::None, "none".parse().unwrap());
+        assert_eq!(NatResolver::NatPmp, "natpmp".parse().unwrap());
+        assert_eq!(NatResolver::NatPmp.to_string(), "natpmp");
 
         let ip = NatResolver::ExternalIp(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         let s = "extip:0.0.0.0";
@@ -0,0 +1,404 @@
+//! Port mappings on the gateway of the local network.
+
+use crate::{natpmp, upnp, NatResolver};
+use std::{
+    future::poll_fn,
+    net::IpAddr,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use tracing::{debug, trace};
+
+/// The default lease duration of port mappings.
+pub const DEFAULT_PORT_MAPPING_LEASE: Duration = Duration::from_secs(60 * 60);
+
+/// How long to wait before retrying to map ports after a failure.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Errors of port mapping requests.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// No gateway responded.
+    #[error("no gateway found")]
+    NoGateway,
+    /// I/O error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// HTTP request to the gateway failed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The gateway sent an unexpected response.
+    #[error("invalid gateway response: {0}")]
+    InvalidResponse(String),
+    /// The `UPnP` gateway returned an error.
+    #[error("UPnP error {code}: {description}")]
+    Upnp {
+        /// The error code.
+        code: u16,
+        /// The error description.
+        description: String,
+    },
+    /// The NAT-PMP gateway returned a result code other than success.
+    #[error("NAT-PMP error with result code {0}")]
+    NatPmp(u16),
+}
+
+/// The transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortMappingProtocol {
+    /// TCP, used by `RLPx`.
+    Tcp,
+    /// UDP, used by discovery.
+    Udp,
+}
+
+impl PortMappingProtocol {
+    /// Returns the protocol name used by `UPnP`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tcp => "TCP",
+            Self::Udp => "UDP",
+        }
+    }
+}
+
+/// A local port that should be reachable from outside the local network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortMapping {
+    /// The protocol of the port.
+    pub protocol: PortMappingProtocol,
+    /// The local port.
+    pub local_port: u16,
+}
+
+impl PortMapping {
+    /// Creates a TCP port mapping.
+    pub const fn tcp(local_port: u16) -> Self {
+        Self { protocol: PortMappingProtocol::Tcp, local_port }
+    }
+
+    /// Creates a UDP port mapping.
+    pub const fn udp(local_port: u16) -> Self {
+        Self { protocol: PortMappingProtocol::Udp, local_port }
+    }
+}
+
+/// A local port that is mapped to an external port of the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MappedPort {
+    /// The protocol of the port.
+    pub protocol: PortMappingProtocol,
+    /// The local port.
+    pub local_port: u16,
+    /// The external port of the gateway.
+    pub external_port: u16,
+}
+
+/// The external address of the local node, as reported by the gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalAddress {
+    /// The external IP of the gateway.
+    pub ip: IpAddr,
+    /// The mapped ports.
+    pub ports: Vec<MappedPort>,
+}
+
+impl ExternalAddress {
+    /// Returns the external port that the local port is mapped to.
+    pub fn external_port(&self, protocol: PortMappingProtocol, local_port: u16) -> Option<u16> {
+        self.ports
+            .iter()
+            .find(|port| port.protocol == protocol && port.local_port == local_port)
+            .map(|port| port.external_port)
+    }
+}
+
+/// Configures the port mappings of a [`PortMappingHandle`].
+#[derive(Debug, Clone)]
+pub struct PortMappingConfig {
+    /// The ports to map.
+    pub mappings: Vec<PortMapping>,
+    /// The requested lease duration of the mappings, which are renewed after half of it.
+    pub lease: Duration,
+    /// The description of the mappings that is shown by `UPnP` gateways.
+    pub description: String,
+    /// How to discover a `UPnP` gateway.
+    pub upnp_search: upnp::SearchOptions,
+    /// The NAT-PMP gateway, defaults to the gateway of the default route.
+    pub natpmp_gateway: Option<natpmp::Gateway>,
+}
+
+impl PortMappingConfig {
+    /// Creates a config that maps the given ports.
+    pub fn new(mappings: impl IntoIterator<Item = PortMapping>) -> Self {
+        let mut deduped = Vec::new();
+        for mapping in mappings {
+            if !deduped.contains(&mapping) {
+                deduped.push(mapping);
+            }
+        }
+        Self {
+            mappings: deduped,
+            lease: DEFAULT_PORT_MAPPING_LEASE,
+            description: "reth".to_string(),
+            upnp_search: Default::default(),
+            natpmp_gateway: None,
+        }
+    }
+}
+
+/// A gateway that supports port mappings.
+#[derive(Debug)]
+enum Gateway {
+    Upnp(upnp::Gateway),
+    NatPmp(natpmp::Gateway),
+}
+
+impl Gateway {
+    async fn discover(
+        resolver: NatResolver,
+        config: &PortMappingConfig,
+    ) -> Result<Self, PortMappingError> {
+        match resolver {
+            NatResolver::Upnp => Ok(Self::Upnp(upnp::search_gateway(config.upnp_search).await?)),
+            _ => {
+                let gateway = match config.natpmp_gateway {
+                    Some(gateway) => gateway,
+                    None => natpmp::Gateway::default_gateway()?,
+                };
+                Ok(Self::NatPmp(gateway))
+            }
+        }
+    }
+
+    async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => gateway.external_ip().await,
+            Self::NatPmp(gateway) => gateway.external_ip().await,
+        }
+    }
+
+    /// Maps the port and returns the external port and the granted lifetime of the mapping.
+    async fn add_port_mapping(
+        &self,
+        mapping: PortMapping,
+        config: &PortMappingConfig,
+    ) -> Result<(u16, Duration), PortMappingError> {
+        let PortMapping { protocol, local_port } = mapping;
+        match self {
+            Self::Upnp(gateway) => {
+                gateway
+                    .add_port_mapping(protocol, local_port, config.lease, &config.description)
+                    .await?;
+                Ok((local_port, config.lease))
+            }
+            Self::NatPmp(gateway) => {
+                gateway.add_port_mapping(protocol, local_port, config.lease).await
+            }
+        }
+    }
+
+    async fn remove_port_mapping(&self, mapping: MappedPort) -> Result<(), PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => {
+                gateway.remove_port_mapping(mapping.protocol, mapping.external_port).await
+            }
+            Self::NatPmp(gateway) => {
+                gateway.remove_port_mapping(mapping.protocol, mapping.local_port).await
+            }
+        }
+    }
+}
+
+/// Handle to a task that maps local ports on the gateway of the local network and keeps them
+/// mapped.
+///
+/// The task renews the mappings after half of their lease duration and reports the external
+/// address whenever it changes. The mappings are removed when the handle is shut down or dropped.
+#[derive(Debug)]
+pub struct PortMappingHandle {
+    /// Receives the external address after it changed.
+    updates: mpsc::UnboundedReceiver<ExternalAddress>,
+    /// Stops the task when sent or dropped.
+    shutdown: Option<oneshot::Sender<()>>,
+    /// The spawned task.
+    task: JoinHandle<()>,
+}
+
+impl PortMappingHandle {
+    /// Spawns a task that maps the configured ports with the resolver, if it supports port
+    /// mappings, which are [`NatResolver::Upnp`] and [`NatResolver::NatPmp`].
+    ///
+    /// This must be called from within a tokio runtime.
+    pub fn spawn(resolver: NatResolver, config: PortMappingConfig) -> Option<Self> {
+        if !matches!(resolver, NatResolver::Upnp | NatResolver::NatPmp) ||
+            config.mappings.is_empty()
+        {
+            return None
+        }
+        let (updates_tx, updates) = mpsc::unbounded_channel();
+        let (shutdown, shutdown_rx) = oneshot::channel();
+        let task = tokio::spawn(run_port_mapping(resolver, config, updates_tx, shutdown_rx));
+        Some(Self { updates, shutdown: Some(shutdown), task })
+    }
+
+    /// Polls for the next change of the external address.
+    pub fn poll_update(&mut self, cx: &mut Context<'_>) -> Poll<Option<ExternalAddress>> {
+        self.updates.poll_recv(cx)
+    }
+
+    /// Waits for the next change of the external address.
+    pub async fn next_update(&mut self) -> Option<ExternalAddress> {
+        poll_fn(|cx| self.poll_update(cx)).await
+    }
+
+    /// Removes the mappings and waits for the task to finish.
+    pub async fn shutdown(mut self) {
+        self.shutdown.take();
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for PortMappingHandle {
+    fn drop(&mut self) {
+        // dropping the sender stops the task, which removes the mappings in the background
+        self.shutdown.take();
+    }
+}
+
+/// Maps the ports until shutdown, then removes the mappings.
+async fn run_port_mapping(
+    resolver: NatResolver,
+    config: PortMappingConfig,
+    updates: mpsc::UnboundedSender<ExternalAddress>,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let mut gateway = None;
+    let mut mapped = Vec::new();
+    let mut current = None;
+    loop {
+        let map = map_ports(resolver, &config, &mut gateway, &mut mapped);
+        let renew_in = tokio::select! {
+            res = map => match res {
+                Ok((address, lifetime)) => {
+                    if current.as_ref() != Some(&address) {
+                        debug!(target: "net::nat", ?address, "Mapped ports on gateway");
+                        current = Some(address.clone());
+                        let _ = updates.send(address);
+                    }
+                    lifetime / 2
+                }
+                Err(err) => {
+                    debug!(target: "net::nat", %err, ?resolver, "Failed to map ports on gateway");
+                    gateway = None;
+                    RETRY_INTERVAL.min(config.lease / 2)
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+        trace!(target: "net::nat", ?renew_in, "Scheduled renewal of port mappings");
+        tokio::select! {
+            _ = tokio::time::sleep(renew_in) => {}
+            _ = &mut shutdown => break,
+        }
+    }
+
+    if let Some(gateway) = gateway {
+        for mapping in mapped {
+            if let Err(err) = gateway.remove_port_mapping(mapping).await {
+                debug!(target: "net::nat", %err, ?mapping, "Failed to remove port mapping");
+            }
+        }
+    }
+}
+
+/// Maps all configured ports and returns the external address and the shortest lifetime of the
+/// mappings.
+///
+/// Successful mappings are recorded in `mapped`, so they are removed on shutdown even if mapping
+/// other ports fails.
+async fn map_ports(
+    resolver: NatResolver,
+    config: &PortMappingConfig,
+    gateway: &mut Option<Gateway>,
+    mapped: &mut Vec<MappedPort>,
+) -> Result<(ExternalAddress, Duration), PortMappingError> {
+    let gateway = match gateway {
+        Some(gateway) => gateway,
+        None => gateway.insert(Gateway::discover(resolver, config).await?),
+    };
+    let ip = gateway.external_ip().await?;
+    let mut ports = Vec::with_capacity(config.mappings.len());
+    let mut lifetime = config.lease;
+    for mapping in &config.mappings {
+        let (external_port, granted) = gateway.add_port_mapping(*mapping, config).await?;
+        let port = MappedPort {
+            protocol: mapping.protocol,
+            local_port: mapping.local_port,
+            external_port,
+        };
+        mapped.retain(|mapped| {
+            mapped.protocol != port.protocol || mapped.local_port != port.local_port
+        });
+        mapped.push(port);
+        ports.push(port);
+        // permanent mappings are still renewed, in case the gateway restarted
+        if !granted.is_zero() {
+            lifetime = lifetime.min(granted);
+        }
+    }
+    Ok((ExternalAddress { ip, ports }, lifetime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{natpmp::tests::FakeNatPmp, upnp::tests::FakeIgd};
+
+    #[tokio::test]
+    async fn maps_and_removes_ports_with_upnp() {
+        let igd = FakeIgd::spawn(false).await;
+        let mut config = PortMappingConfig::new([
+            PortMapping::tcp(30303),
+            PortMapping::udp(30303),
+            PortMapping::udp(30303),
+        ]);
+        config.upnp_search = igd.search_options();
+
+        let mut handle = PortMappingHandle::spawn(NatResolver::Upnp, config).unwrap();
+        let address = handle.next_update().await.unwrap();
+        assert_eq!(address.ip, IpAddr::V4(FakeIgd::EXTERNAL_IP));
+        assert_eq!(address.external_port(PortMappingProtocol::Udp, 30303), Some(30303));
+        assert_eq!(address.ports.len(), 2);
+        assert_eq!(igd.mappings.lock().unwrap().len(), 2);
+
+        handle.shutdown().await;
+        assert!(igd.mappings.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn maps_and_removes_ports_with_natpmp() {
+        let server = FakeNatPmp::spawn().await;
+        let mut config = PortMappingConfig::new([PortMapping::tcp(30303), PortMapping::udp(30304)]);
+        config.natpmp_gateway = Some(natpmp::Gateway::new(server.addr));
+
+        let mut handle = PortMappingHandle::spawn(NatResolver::NatPmp, config).unwrap();
+        let address = handle.next_update().await.unwrap();
+        assert_eq!(address.ip, IpAddr::V4(FakeNatPmp::EXTERNAL_IP));
+        assert_eq!(address.external_port(PortMappingProtocol::Tcp, 30303), Some(30304));
+        assert_eq!(address.external_port(PortMappingProtocol::Udp, 30304), Some(30305));
+
+        handle.shutdown().await;
+        assert!(server.mappings.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn only_spawns_for_gateway_resolvers() {
+        let config = PortMappingConfig::new([PortMapping::tcp(30303)]);
+        assert!(PortMappingHandle::spawn(NatResolver::PublicIp, config).is_none());
+    }
+}
@@ -0,0 +1,262 @@
+//! Minimal NAT-PMP client, see [RFC 6886](https://datatracker.ietf.org/doc/html/rfc6886).
+//!
+//! PCP servers ([RFC 6887](https://datatracker.ietf.org/doc/html/rfc6887)) are required to answer
+//! NAT-PMP requests, so this also works with gateways that implement PCP.
+
+use crate::{PortMappingError, PortMappingProtocol};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// The port that NAT-PMP servers listen on.
+pub const NATPMP_PORT: u16 = 5351;
+
+/// The NAT-PMP version.
+const VERSION: u8 = 0;
+
+/// Opcode of external address requests.
+const OP_EXTERNAL_ADDRESS: u8 = 0;
+
+/// Offset of response opcodes to their request opcodes.
+const OP_RESPONSE: u8 = 128;
+
+/// The initial timeout of requests, which is doubled with every retransmission.
+const INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// The number of times a request is sent before giving up.
+///
+/// The RFC allows up to 9 attempts, which takes more than a minute. Gateways without NAT-PMP
+/// support never answer, so this gives up after less than 4 seconds.
+const MAX_ATTEMPTS: u32 = 4;
+
+/// A NAT-PMP gateway.
+#[derive(Debug, Clone, Copy)]
+pub struct Gateway {
+    /// The address of the NAT-PMP server of the gateway.
+    addr: SocketAddr,
+}
+
+impl Gateway {
+    /// Creates a new gateway with the address of its NAT-PMP server.
+    pub const fn new(addr: SocketAddr) -> Self {
+        Self { addr }
+    }
+
+    /// Returns the gateway of the default route of this host.
+    pub fn default_gateway() -> Result<Self, PortMappingError> {
+        let ip = default_gateway_ip().ok_or(PortMappingError::NoGateway)?;
+        Ok(Self::new(SocketAddr::new(ip.into(), NATPMP_PORT)))
+    }
+
+    /// Returns the address of the NAT-PMP server of the gateway.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the external IP address of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        let response = self.request(&[VERSION, OP_EXTERNAL_ADDRESS], 12).await?;
+        Ok(Ipv4Addr::new(response[8], response[9], response[10], response[11]).into())
+    }
+
+    /// Maps an external port of the gateway to the local port.
+    ///
+    /// The gateway is asked for the same external port, but may map a different one. Returns the
+    /// external port and the lifetime of the mapping granted by the gateway.
+    pub async fn add_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        port: u16,
+        lease: Duration,
+    ) -> Result<(u16, Duration), PortMappingError> {
+        let lifetime = lease.as_secs().clamp(1, u32::MAX as u64) as u32;
+        let response = self.request_mapping(protocol, port, port, lifetime).await?;
+        let external_port = u16::from_be_bytes([response[10], response[11]]);
+        let lifetime = u32::from_be_bytes([response[12], response[13], response[14], response[15]]);
+        Ok((external_port, Duration::from_secs(lifetime as u64)))
+    }
+
+    /// Removes the mapping of the local port.
+    pub async fn remove_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        port: u16,
+    ) -> Result<(), PortMappingError> {
+        // a mapping is removed by requesting it with zero lifetime and external port
+        self.request_mapping(protocol, port, 0, 0).await.map(drop)
+    }
+
+    async fn request_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        port: u16,
+        external_port: u16,
+        lifetime: u32,
+    ) -> Result<[u8; 16], PortMappingError> {
+        let opcode = match protocol {
+            PortMappingProtocol::Udp => 1,
+            PortMappingProtocol::Tcp => 2,
+        };
+        let mut request = [0u8; 12];
+        request[0] = VERSION;
+        request[1] = opcode;
+        request[4..6].copy_from_slice(&port.to_be_bytes());
+        request[6..8].copy_from_slice(&external_port.to_be_bytes());
+        request[8..12].copy_from_slice(&lifetime.to_be_bytes());
+        let response = self.request(&request, 16).await?;
+        if u16::from_be_bytes([response[8], response[9]]) != port {
+            return Err(PortMappingError::InvalidResponse("unexpected internal port".into()))
+        }
+        Ok(response.try_into().expect("response has expected length"))
+    }
+
+    /// Sends the request to the gateway and returns the successful response of the expected
+    /// length, retransmitting the request until the gateway responds.
+    async fn request(&self, request: &[u8], len: usize) -> Result<Vec<u8>, PortMappingError> {
+        let bind_addr: SocketAddr = if self.addr.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(bind_addr).await?;
+        socket.connect(self.addr).await?;
+
+        let mut timeout = INITIAL_TIMEOUT;
+        let mut buf = [0u8; 64];
+        for _ in 0..MAX_ATTEMPTS {
+            socket.send(request).await?;
+            let deadline = tokio::time::Instant::now() + timeout;
+            while let Ok(received) = tokio::time::timeout_at(deadline, socket.recv(&mut buf)).await
+            {
+                let response = &buf[..received?];
+                // ignore responses to other requests
+                if response.len() < 4 || response[1] != request[1] + OP_RESPONSE {
+                    continue
+                }
+                let result = u16::from_be_bytes([response[2], response[3]]);
+                if result != 0 {
+                    return Err(PortMappingError::NatPmp(result))
+                }
+                if response.len() < len {
+                    return Err(PortMappingError::InvalidResponse("response too short".into()))
+                }
+                return Ok(response[..len].to_vec())
+            }
+            timeout *= 2;
+        }
+        Err(PortMappingError::NoGateway)
+    }
+}
+
+/// Returns the gateway of the default IPv4 route from `/proc/net/route`.
+fn default_gateway_ip() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    parse_default_gateway(&routes)
+}
+
+/// Parses the gateway of the default route from the content of `/proc/net/route`.
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace().skip(1);
+        let (destination, gateway) = (fields.next()?, fields.next()?);
+        if destination != "00000000" {
+            return None
+        }
+        // addresses are in host byte order
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        Some(Ipv4Addr::from(gateway.to_le_bytes())).filter(|ip| !ip.is_unspecified())
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    /// Port mappings of the [`FakeNatPmp`] by opcode and internal port, with the external port.
+    pub(crate) type FakeMappings = Arc<Mutex<HashMap<(u8, u16), u16>>>;
+
+    /// A fake NAT-PMP server that maps every internal port to the next external port.
+    pub(crate) struct FakeNatPmp {
+        /// The address of the server.
+        pub(crate) addr: SocketAddr,
+        /// The current port mappings.
+        pub(crate) mappings: FakeMappings,
+    }
+
+    impl FakeNatPmp {
+        /// The external IP of the gateway.
+        pub(crate) const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(198, 51, 100, 9);
+
+        /// Spawns the server on localhost. The server drops the first request to test
+        /// retransmissions.
+        pub(crate) async fn spawn() -> Self {
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let addr = socket.local_addr().unwrap();
+            let mappings = FakeMappings::default();
+            let state = mappings.clone();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 64];
+                let mut dropped_first = false;
+                while let Ok((len, from)) = socket.recv_from(&mut buf).await {
+                    if !std::mem::replace(&mut dropped_first, true) {
+                        continue
+                    }
+                    let request = &buf[..len];
+                    let mut response = vec![VERSION, request[1] + OP_RESPONSE, 0, 0, 0, 0, 0, 1];
+                    if request[1] == OP_EXTERNAL_ADDRESS {
+                        response.extend_from_slice(&Self::EXTERNAL_IP.octets());
+                    } else {
+                        let internal = u16::from_be_bytes([request[4], request[5]]);
+                        let lifetime = &request[8..12];
+                        let external = if lifetime == [0; 4] {
+                            state.lock().unwrap().remove(&(request[1], internal));
+                            0
+                        } else {
+                            state.lock().unwrap().insert((request[1], internal), internal + 1);
+                            internal + 1
+                        };
+                        response.extend_from_slice(&internal.to_be_bytes());
+                        response.extend_from_slice(&external.to_be_bytes());
+                        response.extend_from_slice(lifetime);
+                    }
+                    let _ = socket.send_to(&response, from).await;
+                }
+            });
+            Self { addr, mappings }
+        }
+    }
+
+    #[tokio::test]
+    async fn map_ports_on_fake_natpmp() {
+        let server = FakeNatPmp::spawn().await;
+        let gateway = Gateway::new(server.addr);
+        assert_eq!(gateway.external_ip().await.unwrap(), IpAddr::V4(FakeNatPmp::EXTERNAL_IP));
+
+        let lease = Duration::from_secs(600);
+        let mapped = gateway.add_port_mapping(PortMappingProtocol::Tcp, 30303, lease).await;
+        assert_eq!(mapped.unwrap(), (30304, lease));
+        gateway.add_port_mapping(PortMappingProtocol::Udp, 30303, lease).await.unwrap();
+        assert_eq!(server.mappings.lock().unwrap().len(), 2);
+
+        gateway.remove_port_mapping(PortMappingProtocol::Tcp, 30303).await.unwrap();
+        assert_eq!(
+            server.mappings.lock().unwrap().keys().copied().collect::<Vec<_>>(),
+            vec![(1, 30303)]
+        );
+    }
+
+    #[test]
+    fn parse_default_gateway_from_routes() {
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                      eth0\t0010A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\n\
+                      eth0\t00000000\t0100A8C0\t0003\t0\t0\t0\t00000000\n";
+        assert_eq!(parse_default_gateway(routes), Some(Ipv4Addr::new(192, 168, 0, 1)));
+        assert_eq!(parse_default_gateway("Iface\tDestination\tGateway\n"), None);
+    }
+}
@@ -0,0 +1,489 @@
+//! Minimal client for `UPnP` Internet Gateway Devices (IGD).
+//!
+//! Gateways are discovered via SSDP, port mappings are requested with SOAP calls to the
+//! `WANIPConnection` or `WANPPPConnection` service of the gateway.
+
+use crate::{PortMappingError, PortMappingProtocol};
+use reqwest::Url;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+use tracing::trace;
+
+/// The multicast address that SSDP search requests are sent to.
+pub const SSDP_MULTICAST_ADDR: SocketAddr =
+    SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 250), 1900));
+
+/// The device type of internet gateways.
+const IGD_DEVICE_TYPE: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+
+/// Service types of gateways that manage port mappings, in order of preference.
+const WAN_SERVICE_TYPES: &[&str] = &[
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// Error code of gateways that only support permanent port mappings.
+const ONLY_PERMANENT_LEASES_SUPPORTED: u16 = 725;
+
+/// Timeout of HTTP requests to the gateway.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Options for discovering a gateway.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchOptions {
+    /// The address that the search request is sent to.
+    pub broadcast_addr: SocketAddr,
+    /// The local address to send the search request from.
+    pub bind_addr: SocketAddr,
+    /// How long to wait for a gateway to respond.
+    pub timeout: Duration,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            broadcast_addr: SSDP_MULTICAST_ADDR,
+            bind_addr: SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)),
+            timeout: Duration::from_secs(3),
+        }
+    }
+}
+
+/// An internet gateway that supports port mappings.
+#[derive(Debug, Clone)]
+pub struct Gateway {
+    /// The URL of the control endpoint of the WAN connection service.
+    control_url: Url,
+    /// The type of the WAN connection service.
+    service_type: String,
+    /// The local address that the gateway is reached from.
+    local_ip: IpAddr,
+    /// The HTTP client.
+    client: reqwest::Client,
+}
+
+impl Gateway {
+    /// Returns the local address that the gateway is reached from, which is the address that
+    /// ports are mapped to.
+    pub const fn local_ip(&self) -> IpAddr {
+        self.local_ip
+    }
+
+    /// Returns the external IP address of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        let response = self.soap_request("GetExternalIPAddress", "").await?;
+        let ip = xml_element(&response, "NewExternalIPAddress")
+            .ok_or_else(|| PortMappingError::InvalidResponse("missing external IP".into()))?;
+        ip.trim().parse().map_err(|_| PortMappingError::InvalidResponse(format!("invalid IP {ip}")))
+    }
+
+    /// Maps the external port of the gateway to the same port of the local address.
+    ///
+    /// If the gateway only supports permanent mappings, the port is mapped without a lease
+    /// duration.
+    pub async fn add_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        port: u16,
+        lease: Duration,
+        description: &str,
+    ) -> Result<(), PortMappingError> {
+        match self.request_port_mapping(protocol, port, lease.as_secs(), description).await {
+            Err(PortMappingError::Upnp { code: ONLY_PERMANENT_LEASES_SUPPORTED, .. }) => {
+                self.request_port_mapping(protocol, port, 0, description).await
+            }
+            res => res,
+        }
+    }
+
+    /// Removes the mapping of the external port of the gateway.
+    pub async fn remove_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        port: u16,
+    ) -> Result<(), PortMappingError> {
+        let args = format!(
+            "<NewRemoteHost></NewRemoteHost><NewExternalPort>{port}</NewExternalPort>\
+             <NewProtocol>{}</NewProtocol>",
+            protocol.as_str()
+        );
+        self.soap_request("DeletePortMapping", &args).await.map(drop)
+    }
+
+    async fn request_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        port: u16,
+        lease_secs: u64,
+        description: &str,
+    ) -> Result<(), PortMappingError> {
+        let args = format!(
+            "<NewRemoteHost></NewRemoteHost><NewExternalPort>{port}</NewExternalPort>\
+             <NewProtocol>{}</NewProtocol><NewInternalPort>{port}</NewInternalPort>\
+             <NewInternalClient>{}</NewInternalClient><NewEnabled>1</NewEnabled>\
+             <NewPortMappingDescription>{description}</NewPortMappingDescription>\
+             <NewLeaseDuration>{lease_secs}</NewLeaseDuration>",
+            protocol.as_str(),
+            self.local_ip,
+        );
+        self.soap_request("AddPortMapping", &args).await.map(drop)
+    }
+
+    /// Calls the action of the WAN connection service and returns the response body.
+    async fn soap_request(&self, action: &str, args: &str) -> Result<String, PortMappingError> {
+        let service_type = &self.service_type;
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{service_type}\">{args}</u:{action}></s:Body>\
+             </s:Envelope>"
+        );
+        let response = self
+            .client
+            .post(self.control_url.clone())
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{service_type}#{action}\""))
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            let code = xml_element(&text, "errorCode").and_then(|code| code.trim().parse().ok());
+            return Err(PortMappingError::Upnp {
+                code: code.unwrap_or_default(),
+                description: xml_element(&text, "errorDescription")
+                    .unwrap_or(status.as_str())
+                    .to_string(),
+            });
+        }
+        Ok(text)
+    }
+}
+
+/// Discovers a gateway that supports port mappings.
+///
+/// This sends an SSDP search request and uses the first gateway that responds with a supported
+/// WAN connection service.
+pub async fn search_gateway(options: SearchOptions) -> Result<Gateway, PortMappingError> {
+    let socket = UdpSocket::bind(options.bind_addr).await?;
+    let request = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {SSDP_MULTICAST_ADDR}\r\nST: {IGD_DEVICE_TYPE}\r\n\
+         MAN: \"ssdp:discover\"\r\nMX: 2\r\n\r\n"
+    );
+    socket.send_to(request.as_bytes(), options.broadcast_addr).await?;
+
+    let client = reqwest::Client::builder().timeout(HTTP_TIMEOUT).build()?;
+    let search = async {
+        let mut buf = [0u8; 2048];
+        loop {
+            let (len, from) = socket.recv_from(&mut buf).await?;
+            let Some(location) = ssdp_location(&String::from_utf8_lossy(&buf[..len])) else {
+                continue
+            };
+            match gateway_from_location(&client, &location).await {
+                Ok(gateway) => return Ok(gateway),
+                Err(err) => {
+                    trace!(target: "net::nat", %from, %location, %err, "Ignoring UPnP device")
+                }
+            }
+        }
+    };
+    tokio::time::timeout(options.timeout, search).await.map_err(|_| PortMappingError::NoGateway)?
+}
+
+/// Fetches the device description at the location and returns the gateway of its WAN connection
+/// service.
+async fn gateway_from_location(
+    client: &reqwest::Client,
+    location: &str,
+) -> Result<Gateway, PortMappingError> {
+    let location =
+        Url::parse(location).map_err(|err| PortMappingError::InvalidResponse(err.to_string()))?;
+    let description = client.get(location.clone()).send().await?.error_for_status()?.text().await?;
+
+    let (service_type, control_url) = WAN_SERVICE_TYPES
+        .iter()
+        .find_map(|service_type| {
+            xml_elements(&description, "service")
+                .find(|service| {
+                    xml_element(service, "serviceType").is_some_and(|ty| ty.trim() == *service_type)
+                })
+                .and_then(|service| xml_element(service, "controlURL"))
+                .map(|control_url| (*service_type, control_url.trim()))
+        })
+        .ok_or_else(|| PortMappingError::InvalidResponse("no WAN connection service".into()))?;
+    let control_url = location
+        .join(control_url)
+        .map_err(|err| PortMappingError::InvalidResponse(err.to_string()))?;
+
+    // the local address of the route to the gateway
+    let gateway_addr = control_url
+        .socket_addrs(|| Some(80))
+        .ok()
+        .and_then(|addrs| addrs.into_iter().next())
+        .ok_or_else(|| PortMappingError::InvalidResponse(format!("invalid URL {control_url}")))?;
+    let socket = UdpSocket::bind(SocketAddr::new(
+        if gateway_addr.is_ipv4() { Ipv4Addr::UNSPECIFIED.into() } else { IpAddr::V6(0.into()) },
+        0,
+    ))
+    .await?;
+    socket.connect(gateway_addr).await?;
+    let local_ip = socket.local_addr()?.ip();
+
+    Ok(Gateway {
+        control_url,
+        service_type: service_type.to_string(),
+        local_ip,
+        client: client.clone(),
+    })
+}
+
+/// Returns the value of the `LOCATION` header of an SSDP response.
+fn ssdp_location(response: &str) -> Option<String> {
+    response.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("location").then(|| value.trim().to_string())
+    })
+}
+
+/// Returns the content of the first element with the given name, ignoring namespace prefixes.
+fn xml_element<'a>(xml: &'a str, name: &'a str) -> Option<&'a str> {
+    xml_elements(xml, name).next()
+}
+
+/// Returns the contents of all elements with the given name, ignoring namespace prefixes.
+fn xml_elements<'a>(xml: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let mut rest = xml;
+    std::iter::from_fn(move || loop {
+        let start = rest.find('<')?;
+        rest = &rest[start + 1..];
+        let end = rest.find('>')?;
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        let tag_name = tag.split_whitespace().next().unwrap_or_default();
+        let local_name = tag_name.rsplit(':').next().unwrap_or_default();
+        if tag.starts_with('/') || tag.ends_with('/') || local_name != name {
+            continue
+        }
+        let closing = format!("</{tag_name}>");
+        let content_end = rest.find(&closing)?;
+        let content = &rest[..content_end];
+        rest = &rest[content_end + closing.len()..];
+        return Some(content)
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Port mappings of the [`FakeIgd`] by protocol and external port, with their lease duration.
+    pub(crate) type FakeMappings = Arc<Mutex<HashMap<(String, u16), u64>>>;
+
+    /// A fake internet gateway that answers SSDP searches and manages port mappings.
+    pub(crate) struct FakeIgd {
+        /// The address that SSDP searches are answered on.
+        pub(crate) ssdp_addr: SocketAddr,
+        /// The current port mappings.
+        pub(crate) mappings: FakeMappings,
+    }
+
+    impl FakeIgd {
+        /// The external IP of the gateway.
+        pub(crate) const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(203, 0, 113, 7);
+
+        /// Spawns the gateway on localhost. If `permanent_only` is set, it rejects mappings with
+        /// a lease duration like some routers do.
+        pub(crate) async fn spawn(permanent_only: bool) -> Self {
+            let ssdp = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let ssdp_addr = ssdp.local_addr().unwrap();
+            let http = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let http_addr = http.local_addr().unwrap();
+            let mappings = FakeMappings::default();
+
+            tokio::spawn(async move {
+                let mut buf = [0u8; 1024];
+                while let Ok((_, from)) = ssdp.recv_from(&mut buf).await {
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nST: {IGD_DEVICE_TYPE}\r\n\
+                         LOCATION: http://{http_addr}/desc.xml\r\n\r\n"
+                    );
+                    let _ = ssdp.send_to(response.as_bytes(), from).await;
+                }
+            });
+
+            let state = mappings.clone();
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = http.accept().await {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    // read until the end of the body, which is small enough for a single read
+                    // after the headers
+                    loop {
+                        let n = stream.read(&mut buf).await.unwrap();
+                        request.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&request);
+                        if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                            let len = headers
+                                .lines()
+                                .find_map(|line| {
+                                    let (name, value) = line.split_once(':')?;
+                                    name.eq_ignore_ascii_case("content-length")
+                                        .then(|| value.trim().parse::<usize>().ok())?
+                                })
+                                .unwrap_or_default();
+                            if body.len() >= len {
+                                break
+                            }
+                        }
+                        if n == 0 {
+                            break
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&request).to_string();
+                    let (status, body) = Self::handle(&request, &state, permanent_only);
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\
+                         Connection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                }
+            });
+
+            Self { ssdp_addr, mappings }
+        }
+
+        /// Returns the search options that discover this gateway.
+        pub(crate) fn search_options(&self) -> SearchOptions {
+            SearchOptions {
+                broadcast_addr: self.ssdp_addr,
+                bind_addr: "127.0.0.1:0".parse().unwrap(),
+                timeout: Duration::from_secs(2),
+            }
+        }
+
+        fn handle(
+            request: &str,
+            mappings: &FakeMappings,
+            permanent_only: bool,
+        ) -> (&'static str, String) {
+            if request.starts_with("GET /desc.xml") {
+                let desc = "<?xml version=\"1.0\"?><root><device><deviceList><device>\
+                    <serviceList><service>\
+                    <serviceType>urn:schemas-upnp-org:service:WANCommonInterfaceConfig:1\
+                    </serviceType><controlURL>/common</controlURL></service><service>\
+                    <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+                    <controlURL>/ctl/IPConn</controlURL></service></serviceList>\
+                    </device></deviceList></device></root>";
+                return ("200 OK", desc.to_string())
+            }
+
+            let arg = |name| xml_element(request, name).unwrap_or_default().to_string();
+            let ok = |action: &str, args: String| {
+                (
+                    "200 OK",
+                    format!(
+                        "<s:Envelope><s:Body><u:{action}Response>{args}</u:{action}Response>\
+                         </s:Body></s:Envelope>"
+                    ),
+                )
+            };
+            let error = |code: u16| {
+                (
+                    "500 Internal Server Error",
+                    format!(
+                        "<s:Envelope><s:Body><s:Fault><detail><UPnPError>\
+                         <errorCode>{code}</errorCode><errorDescription>error</errorDescription>\
+                         </UPnPError></detail></s:Fault></s:Body></s:Envelope>"
+                    ),
+                )
+            };
+
+            if request.contains("#GetExternalIPAddress") {
+                let ip = Self::EXTERNAL_IP;
+                ok(
+                    "GetExternalIPAddress",
+                    format!("<NewExternalIPAddress>{ip}</NewExternalIPAddress>"),
+                )
+            } else if request.contains("#AddPortMapping") {
+                let lease = arg("NewLeaseDuration").parse().unwrap();
+                if permanent_only && lease != 0 {
+                    return error(ONLY_PERMANENT_LEASES_SUPPORTED)
+                }
+                let port = arg("NewExternalPort").parse().unwrap();
+                mappings.lock().unwrap().insert((arg("NewProtocol"), port), lease);
+                ok("AddPortMapping", String::new())
+            } else if request.contains("#DeletePortMapping") {
+                let port = arg("NewExternalPort").parse().unwrap();
+                match mappings.lock().unwrap().remove(&(arg("NewProtocol"), port)) {
+                    Some(_) => ok("DeletePortMapping", String::new()),
+                    None => error(714),
+                }
+            } else {
+                error(401)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn map_ports_on_fake_igd() {
+        let igd = FakeIgd::spawn(false).await;
+        let gateway = search_gateway(igd.search_options()).await.unwrap();
+        assert_eq!(gateway.local_ip(), IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(gateway.external_ip().await.unwrap(), IpAddr::V4(FakeIgd::EXTERNAL_IP));
+
+        let lease = Duration::from_secs(600);
+        gateway.add_port_mapping(PortMappingProtocol::Tcp, 30303, lease, "reth").await.unwrap();
+        gateway.add_port_mapping(PortMappingProtocol::Udp, 30303, lease, "reth").await.unwrap();
+        assert_eq!(igd.mappings.lock().unwrap().get(&("TCP".to_string(), 30303)), Some(&600));
+
+        gateway.remove_port_mapping(PortMappingProtocol::Tcp, 30303).await.unwrap();
+        assert_eq!(
+            igd.mappings.lock().unwrap().keys().cloned().collect::<Vec<_>>(),
+            vec![("UDP".to_string(), 30303)]
+        );
+        assert!(matches!(
+            gateway.remove_port_mapping(PortMappingProtocol::Tcp, 30303).await,
+            Err(PortMappingError::Upnp { code: 714, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_permanent_mapping() {
+        let igd = FakeIgd::spawn(true).await;
+        let gateway = search_gateway(igd.search_options()).await.unwrap();
+        gateway
+            .add_port_mapping(PortMappingProtocol::Tcp, 30303, Duration::from_secs(600), "reth")
+            .await
+            .unwrap();
+        assert_eq!(igd.mappings.lock().unwrap().get(&("TCP".to_string(), 30303)), Some(&0));
+    }
+
+    #[test]
+    fn parse_xml_elements() {
+        let xml = "<s:Body><u:Res><NewExternalIPAddress> 1.2.3.4 </NewExternalIPAddress>\
+                   <empty/></u:Res></s:Body>";
+        assert_eq!(xml_element(xml, "NewExternalIPAddress"), Some(" 1.2.3.4 "));
+        assert_eq!(xml_element(xml, "Res").map(|res| res.ends_with("<empty/>")), Some(true));
+        assert_eq!(xml_element(xml, "missing"), None);
+        assert_eq!(
+            ssdp_location("HTTP/1.1 200 OK\r\nLocation: http://10.0.0.1:5000/desc.xml\r\n\r\n"),
+            Some("http://10.0.0.1:5000/desc.xml".to_string())
+        );
+    }
+}
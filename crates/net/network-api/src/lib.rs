@@ -24,10 +24,11 @@ pub mod test_utils;
 use test_utils::PeersHandleProvider;
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
+pub use reth_net_banlist::{BanEntry, BanTarget};
 pub use reth_network_p2p::{BlockClient, HeadersClient};
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{PeerKind, PeerScore, Reputation, ReputationChangeKind};
 
-pub use downloaders::BlockDownloaderProvider;
+pub use downloaders::{BlockDownloaderProvider, SnapDownloaderProvider};
 pub use error::NetworkError;
 pub use events::{
     DiscoveredEvent, DiscoveryEvent, NetworkEvent, NetworkEventListenerProvider, PeerRequest,
@@ -40,7 +41,12 @@ use reth_eth_wire_types::{
 };
 use reth_network_p2p::sync::NetworkSyncUpdater;
 use reth_network_peers::NodeRecord;
-use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
+use std::{
+    future::Future,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// The `PeerId` type.
 pub type PeerId = alloy_primitives::B512;
@@ -49,7 +55,8 @@ pub type PeerId = alloy_primitives::B512;
 pub trait FullNetwork:
     BlockDownloaderProvider<
         Client: BlockClient<Block = <Self::Primitives as NetworkPrimitives>::Block>,
-    > + NetworkSyncUpdater
+    > + SnapDownloaderProvider
+    + NetworkSyncUpdater
     + NetworkInfo
     + NetworkEventListenerProvider
     + Peers
@@ -63,7 +70,8 @@ pub trait FullNetwork:
 impl<T> FullNetwork for T where
     T: BlockDownloaderProvider<
             Client: BlockClient<Block = <Self::Primitives as NetworkPrimitives>::Block>,
-        > + NetworkSyncUpdater
+        > + SnapDownloaderProvider
+        + NetworkSyncUpdater
         + NetworkInfo
         + NetworkEventListenerProvider
         + Peers
@@ -215,6 +223,30 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Get the score of the responses of a peer in its current session.
+    fn score_by_id(
+        &self,
+        peer_id: PeerId,
+    ) -> impl Future<Output = Result<Option<PeerScore>, NetworkError>> + Send;
+
+    /// Bans the peer, IP address or IP range indefinitely or for the given duration and
+    /// disconnects the peers that match the ban.
+    ///
+    /// Returns false if the target can't be banned, e.g. a non-global IP address.
+    fn ban(
+        &self,
+        target: BanTarget,
+        duration: Option<Duration>,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send;
+
+    /// Lifts the ban of the peer, IP address or IP range.
+    ///
+    /// Returns true if the target was banned.
+    fn unban(&self, target: BanTarget) -> impl Future<Output = Result<bool, NetworkError>> + Send;
+
+    /// Returns all bans.
+    fn bans(&self) -> impl Future<Output = Result<Vec<BanEntry>, NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
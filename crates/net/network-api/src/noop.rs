@@ -4,21 +4,26 @@
 //! generic over it.
 
 use core::{fmt, marker::PhantomData};
-use std::net::{IpAddr, SocketAddr};
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use crate::{
     events::{NetworkPeersEvents, PeerEventStream},
     test_utils::{PeersHandle, PeersHandleProvider},
-    BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
-    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    BanEntry, BanTarget, BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
+    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerRequest,
+    PeerScore, Peers, PeersInfo, SnapDownloaderProvider,
 };
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
 use reth_eth_wire_types::{
     DisconnectReason, EthNetworkPrimitives, NetworkPrimitives, ProtocolVersion,
 };
-use reth_network_p2p::{sync::NetworkSyncUpdater, NoopFullBlockClient};
+use reth_network_p2p::{
+    snap::client::NoopSnapClient, sync::NetworkSyncUpdater, NoopFullBlockClient,
+};
 use reth_network_peers::NodeRecord;
 use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 use reth_tokio_util::{EventSender, EventStream};
@@ -159,6 +164,26 @@ where
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn score_by_id(&self, _peer_id: PeerId) -> Result<Option<PeerScore>, NetworkError> {
+        Ok(None)
+    }
+
+    async fn ban(
+        &self,
+        _target: BanTarget,
+        _duration: Option<Duration>,
+    ) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn unban(&self, _target: BanTarget) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn bans(&self) -> Result<Vec<BanEntry>, NetworkError> {
+        Ok(vec![])
+    }
 }
 
 impl<Net> BlockDownloaderProvider for NoopNetwork<Net>
@@ -172,6 +197,17 @@ where
     }
 }
 
+impl<Net> SnapDownloaderProvider for NoopNetwork<Net>
+where
+    Net: NetworkPrimitives,
+{
+    type SnapClient = NoopSnapClient;
+
+    fn snap_client(&self) -> Option<Self::SnapClient> {
+        None
+    }
+}
+
 impl<Net> NetworkSyncUpdater for NoopNetwork<Net>
 where
     Net: fmt::Debug + Send + Sync + 'static,
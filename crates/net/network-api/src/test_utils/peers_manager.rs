@@ -1,9 +1,10 @@
 //! Interaction with `reth_network::PeersManager`, for integration testing. Otherwise
 //! `reth_network::NetworkManager` manages `reth_network::PeersManager`.
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 use derive_more::Constructor;
+use reth_net_banlist::{BanEntry, BanTarget};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{Peer, ReputationChangeKind};
 use tokio::sync::{mpsc, oneshot};
@@ -61,6 +62,35 @@ impl PeersHandle {
 
         rx.await.unwrap_or_default()
     }
+
+    /// Bans the peer, IP address or IP range indefinitely or for the given duration.
+    ///
+    /// Returns false if the target can't be banned, see
+    /// [`BanList::ban`](reth_net_banlist::BanList::ban).
+    pub async fn ban(&self, target: BanTarget, duration: Option<Duration>) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::Ban(target, duration, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Lifts the ban of the peer, IP address or IP range.
+    ///
+    /// Returns true if the target was banned.
+    pub async fn unban(&self, target: BanTarget) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::Unban(target, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Returns all bans.
+    pub async fn bans(&self) -> Vec<BanEntry> {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetBans(tx));
+
+        rx.await.unwrap_or_default()
+    }
 }
 
 /// Commands the `PeersManager` listens for.
@@ -78,4 +108,12 @@ pub enum PeerCommand {
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
     GetPeers(oneshot::Sender<Vec<NodeRecord>>),
+    /// Ban a peer, IP address or IP range indefinitely or for the given duration.
+    ///
+    /// Connected peers that match the ban are disconnected.
+    Ban(BanTarget, Option<Duration>, oneshot::Sender<bool>),
+    /// Lift the ban of a peer, IP address or IP range.
+    Unban(BanTarget, oneshot::Sender<bool>),
+    /// Get all bans
+    GetBans(oneshot::Sender<Vec<BanEntry>>),
 }
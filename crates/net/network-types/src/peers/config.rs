@@ -3,11 +3,11 @@
 use std::{
     collections::HashSet,
     io::{self, ErrorKind},
-    path::Path,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
-use reth_net_banlist::BanList;
+use reth_net_banlist::{BanEntry, BanList};
 use reth_network_peers::{NodeRecord, TrustedPeer};
 use tracing::info;
 
@@ -153,6 +153,10 @@ pub struct PeersConfig {
     /// Restrictions on `PeerIds` and Ips.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ban_list: BanList,
+    /// File the bans are written to whenever they are changed on request, e.g. via the admin
+    /// API.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persistent_bans_file: Option<PathBuf>,
     /// Restrictions on connections.
     pub connection_info: ConnectionsConfig,
     /// How to weigh reputation changes.
@@ -175,6 +179,7 @@ impl Default for PeersConfig {
             connection_info: Default::default(),
             reputation_weights: Default::default(),
             ban_list: Default::default(),
+            persistent_bans_file: None,
             // Ban peers for 12h
             ban_duration: Duration::from_secs(60 * 60 * 12),
             backoff_durations: Default::default(),
@@ -301,6 +306,33 @@ impl PeersConfig {
         Ok(self.with_basic_nodes(nodes))
     }
 
+    /// Read from file bans that were persisted, see [`BanList::entries`]. Ignored if None.
+    ///
+    /// The bans are added to the configured ban list, expired bans are skipped.
+    pub fn with_ban_list_from_file(
+        mut self,
+        optional_file: Option<impl AsRef<Path>>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else { return Ok(self) };
+        let reader = match std::fs::File::open(file_path.as_ref()) {
+            Ok(file) => io::BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
+            Err(e) => Err(e)?,
+        };
+        info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved bans");
+        let entries: Vec<BanEntry> = serde_json::from_reader(reader)?;
+        self.ban_list.extend_entries(entries);
+        Ok(self)
+    }
+
+    /// Configure the file the bans are written to whenever they are changed on request, so they
+    /// can be restored with [`Self::with_ban_list_from_file`] even if the node doesn't shut down
+    /// gracefully.
+    pub fn with_persistent_bans_file(mut self, persistent_bans_file: Option<PathBuf>) -> Self {
+        self.persistent_bans_file = persistent_bans_file;
+        self
+    }
+
     /// Returns settings for testing
     #[cfg(any(test, feature = "test-utils"))]
     pub fn test() -> Self {
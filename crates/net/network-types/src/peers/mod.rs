@@ -2,10 +2,12 @@ pub mod addr;
 pub mod config;
 pub mod kind;
 pub mod reputation;
+pub mod score;
 pub mod state;
 
 pub use config::{ConnectionsConfig, PeersConfig};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
+pub use score::PeerScore;
 
 use alloy_eip2124::ForkId;
 use tracing::debug;
@@ -17,6 +19,7 @@ use crate::{
 
 /// Tracks info about a single peer.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Peer {
     /// Where to reach the peer.
     pub addr: PeerAddr,
@@ -35,6 +38,8 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// The score of the responses of the peer in the current session.
+    pub score: PeerScore,
 }
 
 // === impl Peer ===
@@ -66,6 +71,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            score: PeerScore::default(),
         }
     }
 
@@ -0,0 +1,130 @@
+//! Peer scoring based on the quality of the responses of a session.
+
+use std::time::Duration;
+
+/// The weight of a new sample in the moving averages of the latency and throughput.
+const SMOOTHING_FACTOR: f64 = 0.2;
+
+/// The minimum latency of a response, caps the throughput of very fast responses.
+const MIN_LATENCY: Duration = Duration::from_millis(1);
+
+/// Tracks the quality of the responses of a peer during a session.
+///
+/// The score of a peer is the throughput of _valid_ data it is expected to deliver: the moving
+/// average of the throughput of its responses, where invalid responses count as zero bytes,
+/// weighted by the share of valid responses. The throughput of a response depends on both the
+/// latency and the size of the response.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerScore {
+    /// The score of the peer, see [`PeerScore::value`].
+    value: f64,
+    /// Moving average of the latency of the responses in milliseconds.
+    latency_ms: f64,
+    /// Moving average of the throughput of the responses in bytes per second.
+    throughput: f64,
+    /// Number of valid responses.
+    valid_responses: u64,
+    /// Number of invalid responses, e.g. errors, timeouts or likely bad responses.
+    invalid_responses: u64,
+}
+
+// === impl PeerScore ===
+
+impl PeerScore {
+    /// Records a valid response of the given size in bytes that took `latency`.
+    pub fn on_response(&mut self, latency: Duration, bytes: usize) {
+        self.valid_responses += 1;
+        self.record(latency, bytes);
+    }
+
+    /// Records an invalid response that took `latency`.
+    pub fn on_invalid_response(&mut self, latency: Duration) {
+        self.invalid_responses += 1;
+        self.record(latency, 0);
+    }
+
+    /// Returns the score of the peer, the expected throughput of valid data in bytes per second.
+    ///
+    /// This is `0` until the first response was recorded, see also [`PeerScore::has_samples`].
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the moving average of the latency of the responses.
+    pub fn latency(&self) -> Duration {
+        Duration::from_secs_f64(self.latency_ms / 1000.0)
+    }
+
+    /// Returns the moving average of the throughput of the responses in bytes per second.
+    pub const fn throughput(&self) -> f64 {
+        self.throughput
+    }
+
+    /// Returns the share of valid responses, which is `0.5` without any responses.
+    pub fn validity(&self) -> f64 {
+        (self.valid_responses + 1) as f64 / (self.num_responses() + 2) as f64
+    }
+
+    /// Returns the number of recorded responses.
+    pub const fn num_responses(&self) -> u64 {
+        self.valid_responses + self.invalid_responses
+    }
+
+    /// Returns true if at least one response was recorded.
+    pub const fn has_samples(&self) -> bool {
+        self.num_responses() > 0
+    }
+
+    fn record(&mut self, latency: Duration, bytes: usize) {
+        let latency = latency.max(MIN_LATENCY);
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        let throughput = bytes as f64 / latency.as_secs_f64();
+
+        if self.num_responses() == 1 {
+            self.latency_ms = latency_ms;
+            self.throughput = throughput;
+        } else {
+            self.latency_ms += SMOOTHING_FACTOR * (latency_ms - self.latency_ms);
+            self.throughput += SMOOTHING_FACTOR * (throughput - self.throughput);
+        }
+        self.value = self.validity() * self.throughput;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faster_peer_scores_higher() {
+        let mut fast = PeerScore::default();
+        let mut slow = PeerScore::default();
+        assert!(!fast.has_samples());
+
+        for _ in 0..10 {
+            fast.on_response(Duration::from_millis(50), 1024);
+            slow.on_response(Duration::from_millis(500), 1024);
+        }
+        assert!(fast.value() > slow.value());
+        assert_eq!(fast.latency(), Duration::from_millis(50));
+        assert_eq!(fast.num_responses(), 10);
+    }
+
+    #[test]
+    fn invalid_responses_lower_score() {
+        let mut valid = PeerScore::default();
+        let mut invalid = PeerScore::default();
+
+        for _ in 0..10 {
+            valid.on_response(Duration::from_millis(100), 1024);
+            invalid.on_response(Duration::from_millis(100), 1024);
+        }
+        let score = invalid.value();
+        invalid.on_invalid_response(Duration::from_millis(100));
+        assert!(invalid.value() < score);
+        assert!(invalid.validity() < valid.validity());
+        assert!(invalid.value() < valid.value());
+    }
+}
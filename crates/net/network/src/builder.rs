@@ -4,6 +4,7 @@ use std::fmt::Debug;
 
 use crate::{
     eth_requests::EthRequestHandler,
+    snap::{SnapFetchClient, SnapPeers, SnapProtocolHandler, SnapRequestHandler},
     transactions::{
         config::{StrictEthAnnouncementFilter, TransactionPropagationKind},
         policy::NetworkPolicies,
@@ -20,6 +21,10 @@ use tokio::sync::mpsc;
 /// 256 requests with malicious 10MB body requests is 2.6GB which can be absorbed by the node.
 pub(crate) const ETH_REQUEST_CHANNEL_CAPACITY: usize = 256;
 
+/// We set the max channel capacity of the `SnapRequestHandler` to 256, same as for the
+/// `EthRequestHandler`. Snap responses are capped at the same soft response limit.
+pub(crate) const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
 /// A builder that can configure all components of the network.
 #[expect(missing_debug_implementations)]
 pub struct NetworkBuilder<Tx, Eth, N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -72,6 +77,28 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         NetworkBuilder { network, request_handler, transactions }
     }
 
+    /// Installs the `snap` sub-protocol on the network.
+    ///
+    /// State can then be requested from connected `snap` peers with the [`SnapFetchClient`]
+    /// returned by the network handle. If a client is given, `snap` requests are served from it by
+    /// the returned [`SnapRequestHandler`], which must be spawned. Otherwise requests are answered
+    /// with empty responses.
+    pub fn snap<Client>(&mut self, client: Option<Client>) -> Option<SnapRequestHandler<Client>> {
+        let handle = self.network.handle().clone();
+        let peers = handle.peers_handle().clone();
+        let snap_peers = SnapPeers::default();
+        handle.set_snap_client(SnapFetchClient::new(snap_peers.clone(), peers.clone()));
+
+        let (tx, request_handler) = client
+            .map(|client| {
+                let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+                (tx, SnapRequestHandler::new(client, rx))
+            })
+            .unzip();
+        self.network.add_rlpx_sub_protocol(SnapProtocolHandler::new(tx, snap_peers, peers));
+        request_handler
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
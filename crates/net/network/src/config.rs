@@ -4,7 +4,7 @@ use crate::{
     error::NetworkError,
     import::{BlockImport, ProofOfStakeBlockImport},
     transactions::TransactionsManagerConfig,
-    NetworkHandle, NetworkManager,
+    NetworkHandle, NetworkManager, TrafficRecorderConfig,
 };
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOVERY_ADDRESS};
@@ -90,6 +90,8 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// This can be overridden to support custom handshake logic via the
     /// [`NetworkConfigBuilder`].
     pub handshake: Arc<dyn EthRlpxHandshake>,
+    /// How to record the messages of the active sessions, disabled if `None`.
+    pub traffic_recorder: Option<TrafficRecorderConfig>,
 }
 
 // === impl NetworkConfig ===
@@ -217,6 +219,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// The Ethereum P2P handshake, see also:
     /// <https://github.com/ethereum/devp2p/blob/master/rlpx.md#initial-handshake>.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// How to record the messages of the active sessions.
+    traffic_recorder: Option<TrafficRecorderConfig>,
 }
 
 impl NetworkConfigBuilder<EthNetworkPrimitives> {
@@ -257,6 +261,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             transactions_manager_config: Default::default(),
             nat: None,
             handshake: Arc::new(EthHandshake::default()),
+            traffic_recorder: None,
         }
     }
 
@@ -565,6 +570,13 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Records the `eth` messages of all sessions to rotating files, see
+    /// [`TrafficRecorder`](crate::TrafficRecorder).
+    pub fn record_traffic(mut self, config: TrafficRecorderConfig) -> Self {
+        self.traffic_recorder = Some(config);
+        self
+    }
+
     /// Consumes the type and creates the actual [`NetworkConfig`]
     /// for the given client type that can interact with the chain.
     ///
@@ -597,6 +609,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             transactions_manager_config,
             nat,
             handshake,
+            traffic_recorder,
         } = self;
 
         let head = head.unwrap_or_else(|| Head {
@@ -665,6 +678,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             transactions_manager_config,
             nat,
             handshake,
+            traffic_recorder,
         }
     }
 }
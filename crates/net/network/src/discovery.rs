@@ -12,6 +12,11 @@ use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use reth_net_banlist::BanTarget;
+use reth_net_nat::{
+    ExternalAddress, NatResolver, PortMapping, PortMappingConfig, PortMappingHandle,
+    PortMappingProtocol,
+};
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerAddr;
@@ -22,10 +27,11 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::Instant,
 };
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::{wrappers::ReceiverStream, Stream};
-use tracing::trace;
+use tracing::{debug, trace};
 
 /// Default max capacity for cache of discovered peers.
 ///
@@ -54,6 +60,10 @@ pub struct Discovery {
     discv5: Option<Discv5>,
     /// All KAD table updates from the discv5 service.
     discv5_updates: Option<ReceiverStream<discv5::Event>>,
+    /// The local UDP port of the discv5 service.
+    discv5_udp_port: Option<u16>,
+    /// Handle to the task that maps the local ports on the gateway.
+    port_mapping: Option<PortMappingHandle>,
     /// Handler to interact with the DNS discovery service
     _dns_discovery: Option<DnsDiscoveryHandle>,
     /// Updates from the DNS discovery service.
@@ -98,6 +108,7 @@ impl Discovery {
             Ok((Some(discv4), Some(discv4_updates), Some(discv4_service)))
         };
 
+        let discv5_udp_port = discv5_config.as_ref().map(|config| config.discovery_socket().port());
         let discv5_future = async {
             let Some(config) = discv5_config else { return Ok::<_, NetworkError>((None, None)) };
             let (discv5, discv5_updates, _local_enr_discv5) = Discv5::start(&sk, config).await?;
@@ -129,6 +140,8 @@ impl Discovery {
             _discv4_service,
             discv5,
             discv5_updates,
+            discv5_udp_port,
+            port_mapping: None,
             discovered_nodes: LruMap::new(DEFAULT_MAX_CAPACITY_DISCOVERED_PEERS_CACHE),
             queued_events: Default::default(),
             _dns_disc_service,
@@ -157,6 +170,48 @@ impl Discovery {
         // todo: update discv5 enr
     }
 
+    /// Maps the `RLPx` and discovery ports on the gateway of the local network, if the resolver
+    /// supports port mappings.
+    ///
+    /// The external address of the mappings is advertised in the local ENRs.
+    pub(crate) fn map_ports(&mut self, resolver: NatResolver) {
+        let mut mappings = vec![PortMapping::tcp(self.local_enr.tcp_port)];
+        if let Some(discv4) = &self.discv4 {
+            mappings.push(PortMapping::udp(discv4.node_record().udp_port));
+        }
+        mappings.extend(self.discv5_udp_port.map(PortMapping::udp));
+        self.port_mapping = PortMappingHandle::spawn(resolver, PortMappingConfig::new(mappings));
+    }
+
+    /// Advertises the external address of the port mappings.
+    fn on_port_mapping_update(&self, address: ExternalAddress) {
+        debug!(target: "net::discovery", ?address, "Updating external address from port mappings");
+        let ExternalAddress { ip, .. } = address;
+        let tcp_port = address
+            .external_port(PortMappingProtocol::Tcp, self.local_enr.tcp_port)
+            .unwrap_or(self.local_enr.tcp_port);
+        if let Some(discv4) = &self.discv4 {
+            discv4.set_external_ip(ip);
+            discv4.set_tcp_port(tcp_port);
+            if let Some(udp_port) =
+                address.external_port(PortMappingProtocol::Udp, self.local_enr.udp_port)
+            {
+                discv4.set_udp_port(udp_port);
+            }
+        }
+        if let Some(discv5) = &self.discv5 {
+            let udp_port = self
+                .discv5_udp_port
+                .and_then(|port| address.external_port(PortMappingProtocol::Udp, port));
+            discv5.with_discv5(|discv5| {
+                discv5.update_local_enr_socket((ip, tcp_port).into(), true);
+                if let Some(udp_port) = udp_port {
+                    discv5.update_local_enr_socket((ip, udp_port).into(), false);
+                }
+            });
+        }
+    }
+
     /// Bans the [`IpAddr`] in the discovery service.
     pub(crate) fn ban_ip(&self, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
@@ -177,6 +232,21 @@ impl Discovery {
         }
     }
 
+    /// Bans the peer, [`IpAddr`] or ip range in the discv4 service, which supports timed and range
+    /// bans.
+    pub(crate) fn ban_target(&self, target: BanTarget, until: Option<Instant>) {
+        if let Some(discv4) = &self.discv4 {
+            discv4.ban_target(target, until)
+        }
+    }
+
+    /// Lifts the ban of the peer, [`IpAddr`] or ip range in the discv4 service.
+    pub(crate) fn unban_target(&self, target: BanTarget) {
+        if let Some(discv4) = &self.discv4 {
+            discv4.unban_target(target)
+        }
+    }
+
     /// Returns a shared reference to the discv4.
     pub fn discv4(&self) -> Option<Discv4> {
         self.discv4.clone()
@@ -240,6 +310,7 @@ impl Discovery {
             DiscoveryUpdate::Removed(peer_id) => {
                 self.discovered_nodes.remove(&peer_id);
             }
+            DiscoveryUpdate::Enr(_) => {}
             DiscoveryUpdate::Batch(updates) => {
                 for update in updates {
                     self.on_discv4_update(update);
@@ -256,6 +327,13 @@ impl Discovery {
                 return Poll::Ready(event)
             }
 
+            // advertise the external address of the port mappings
+            while let Some(Poll::Ready(Some(address))) =
+                self.port_mapping.as_mut().map(|mapping| mapping.poll_update(cx))
+            {
+                self.on_port_mapping_update(address);
+            }
+
             // drain the discv4 update stream
             while let Some(Poll::Ready(Some(update))) =
                 self.discv4_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
@@ -326,6 +404,8 @@ impl Discovery {
             discv4_updates: Default::default(),
             discv5: None,
             discv5_updates: None,
+            discv5_udp_port: None,
+            port_mapping: None,
             queued_events: Default::default(),
             _discv4_service: Default::default(),
             _dns_discovery: None,
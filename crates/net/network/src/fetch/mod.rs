@@ -15,7 +15,7 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerScore, ReputationChangeKind};
 use std::{
     collections::{HashMap, VecDeque},
     ops::RangeInclusive,
@@ -24,6 +24,7 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::Instant,
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -92,6 +93,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 timeout,
                 last_response_likely_bad: false,
                 range_info,
+                request_started_at: None,
+                score: PeerScore::default(),
             },
         );
     }
@@ -133,9 +136,14 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         }
     }
 
+    /// Returns the score of the peer's responses in the current session.
+    pub(crate) fn peer_score(&self, peer_id: &PeerId) -> Option<PeerScore> {
+        self.peers.get(peer_id).map(|peer| peer.score)
+    }
+
     /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data.
+    /// prioritizing those with the highest score or lowest timeout/latency and those that recently
+    /// responded with adequate data.
     fn next_best_peer(&self) -> Option<PeerId> {
         let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
 
@@ -148,8 +156,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 continue
             }
 
-            // replace best peer if this peer has better rtt
-            if maybe_better.1.timeout() < best_peer.1.timeout() &&
+            // replace best peer if this peer has a better score or rtt
+            if maybe_better.1.is_better_than(best_peer.1) &&
                 !maybe_better.1.last_response_likely_bad
             {
                 best_peer = maybe_better;
@@ -222,6 +230,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         // update the peer's state
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             peer.state = req.peer_state();
+            peer.request_started_at = Some(Instant::now());
         }
 
         match req {
@@ -270,6 +279,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
         let is_likely_bad_response =
             resp.as_ref().is_some_and(|r| res.is_likely_bad_headers_response(&r.request));
+        let response_size = res
+            .as_ref()
+            .map_or(0, |headers| headers.iter().map(alloy_rlp::Encodable::length).sum::<usize>());
 
         if let Some(resp) = resp {
             // delegate the response
@@ -279,6 +291,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.on_response(is_error || is_likely_bad_response, response_size);
 
             // If the peer is still ready to accept new requests, we try to send a followup
             // request immediately.
@@ -300,6 +313,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         res: RequestResult<Vec<N::BlockBody>>,
     ) -> Option<BlockResponseOutcome> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
+        let response_size = res
+            .as_ref()
+            .map_or(0, |bodies| bodies.iter().map(alloy_rlp::Encodable::length).sum::<usize>());
 
         if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
@@ -307,6 +323,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.on_response(is_likely_bad_response, response_size);
 
             if peer.state.on_request_finished() && !is_likely_bad_response {
                 return self.followup_request(peer_id)
@@ -353,12 +370,38 @@ struct Peer {
     /// Tracks the range info for the peer.
     #[allow(dead_code)]
     range_info: Option<BlockRangeInfo>,
+    /// When the inflight request to the peer was sent.
+    request_started_at: Option<Instant>,
+    /// The score of the peer's responses in this session.
+    score: PeerScore,
 }
 
 impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns true if the peer is expected to serve requests better than the other peer.
+    ///
+    /// Peers are compared by score if both already responded in this session, otherwise by
+    /// timeout.
+    fn is_better_than(&self, other: &Self) -> bool {
+        if self.score.has_samples() && other.score.has_samples() {
+            return self.score.value() > other.score.value()
+        }
+        self.timeout() < other.timeout()
+    }
+
+    /// Records the response to the inflight request in the peer's score.
+    fn on_response(&mut self, is_bad_response: bool, size: usize) {
+        let Some(started_at) = self.request_started_at.take() else { return };
+        let latency = started_at.elapsed();
+        if is_bad_response {
+            self.score.on_invalid_response(latency);
+        } else {
+            self.score.on_response(latency, size);
+        }
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -479,7 +522,7 @@ mod tests {
     use crate::{peers::PeersManager, PeersConfig};
     use alloy_consensus::Header;
     use alloy_primitives::B512;
-    use std::future::poll_fn;
+    use std::{future::poll_fn, time::Duration};
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_poll_fetcher() {
@@ -553,6 +596,50 @@ mod tests {
         assert_eq!(fetcher.next_best_peer(), Some(peer2));
     }
 
+    #[tokio::test]
+    async fn test_peer_prioritization_by_score() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+
+        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(10)), None);
+        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(300)), None);
+
+        // without responses, the peer with the lowest timeout is preferred
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+
+        // peer2 serves more data per second than peer1
+        for _ in 0..5 {
+            fetcher
+                .peers
+                .get_mut(&peer1)
+                .unwrap()
+                .score
+                .on_response(Duration::from_millis(500), 1024);
+            fetcher
+                .peers
+                .get_mut(&peer2)
+                .unwrap()
+                .score
+                .on_response(Duration::from_millis(50), 1024);
+        }
+        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+
+        // peer2 starts sending invalid responses
+        for _ in 0..20 {
+            fetcher
+                .peers
+                .get_mut(&peer2)
+                .unwrap()
+                .score
+                .on_invalid_response(Duration::from_millis(50));
+        }
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.peer_score(&peer2).unwrap().num_responses(), 25);
+    }
+
     #[tokio::test]
     async fn test_on_block_headers_response() {
         let manager = PeersManager::new(PeersConfig::default());
@@ -129,6 +129,7 @@ pub mod import;
 pub mod message;
 pub mod peers;
 pub mod protocol;
+pub mod snap;
 pub mod transactions;
 
 mod budget;
@@ -154,9 +155,10 @@ pub use reth_network_api::{
 pub use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState};
 pub use reth_network_types::{PeersConfig, SessionsConfig};
 pub use session::{
-    ActiveSessionHandle, ActiveSessionMessage, Direction, EthRlpxConnection, PeerInfo,
-    PendingSessionEvent, PendingSessionHandle, PendingSessionHandshakeError, SessionCommand,
-    SessionEvent, SessionId, SessionManager,
+    read_recording, ActiveSessionHandle, ActiveSessionMessage, Direction, EthRlpxConnection,
+    MessageDirection, PeerInfo, PendingSessionEvent, PendingSessionHandle,
+    PendingSessionHandshakeError, RecordedMessage, RecordingError, SessionCommand, SessionEvent,
+    SessionId, SessionManager, TrafficRecorder, TrafficRecorderConfig,
 };
 
 pub use builder::NetworkBuilder;
@@ -29,7 +29,7 @@ use crate::{
     peers::PeersManager,
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
-    session::SessionManager,
+    session::{SessionManager, TrafficRecorder},
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
@@ -250,6 +250,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             transactions_manager_config: _,
             nat,
             handshake,
+            traffic_recorder,
         } = config;
 
         let peers_manager = PeersManager::new(peers_config);
@@ -279,7 +280,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
         }
 
-        let discovery = Discovery::new(
+        let mut discovery = Discovery::new(
             listener_addr,
             discovery_v4_addr,
             secret_key,
@@ -288,6 +289,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             dns_discovery_config,
         )
         .await?;
+        if let Some(nat) = nat {
+            discovery.map_ports(nat);
+        }
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
@@ -295,6 +299,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
+        let recorder = traffic_recorder.map(TrafficRecorder::spawn).transpose()?;
         let sessions = SessionManager::new(
             secret_key,
             sessions_config,
@@ -304,6 +309,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             fork_filter,
             extra_protocols,
             handshake,
+            recorder,
         );
 
         let state = NetworkState::new(
@@ -433,6 +439,17 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         Ok(())
     }
 
+    /// Collect the bans from the [`NetworkManager`] and write them to the given
+    /// `persistent_bans_file`.
+    ///
+    /// The bans are restored on startup with `PeersConfig::with_ban_list_from_file`.
+    pub fn write_bans_to_file(&self, persistent_bans_file: &Path) -> Result<(), FsPathError> {
+        let bans = self.swarm.state().peers().bans();
+        persistent_bans_file.parent().map(fs::create_dir_all).transpose()?;
+        reth_fs_util::write_json_file(persistent_bans_file, &bans)?;
+        Ok(())
+    }
+
     /// Returns a new [`FetchClient`] that can be cloned and shared.
     ///
     /// The [`FetchClient`] is the entrypoint for sending requests to the network.
@@ -1,5 +1,5 @@
 use crate::{
-    config::NetworkMode, message::PeerMessage, protocol::RlpxSubProtocol,
+    config::NetworkMode, message::PeerMessage, protocol::RlpxSubProtocol, snap::SnapFetchClient,
     swarm::NetworkConnectionState, transactions::TransactionsHandle, FetchClient,
 };
 use alloy_primitives::B256;
@@ -16,21 +16,22 @@ use reth_ethereum_forks::Head;
 use reth_network_api::{
     events::{NetworkPeersEvents, PeerEvent, PeerEventStream},
     test_utils::{PeersHandle, PeersHandleProvider},
-    BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
+    BanEntry, BanTarget, BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, SnapDownloaderProvider,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{PeerAddr, PeerKind, PeerScore, Reputation, ReputationChangeKind};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use std::{
     net::SocketAddr,
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
-        Arc,
+        Arc, OnceLock,
     },
+    time::Duration,
 };
 use tokio::sync::{
     mpsc::{self, UnboundedSender},
@@ -83,6 +84,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
             discv5,
             event_sender,
             nat,
+            snap_client: OnceLock::new(),
         };
         Self { inner: Arc::new(inner) }
     }
@@ -92,6 +94,13 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         &self.inner.local_peer_id
     }
 
+    /// Sets the client for requesting state from `snap` peers.
+    ///
+    /// This is done once the `snap` sub-protocol is installed.
+    pub(crate) fn set_snap_client(&self, client: SnapFetchClient) {
+        let _ = self.inner.snap_client.set(client);
+    }
+
     fn manager(&self) -> &UnboundedSender<NetworkHandleMessage<N>> {
         &self.inner.to_manager_tx
     }
@@ -356,6 +365,27 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    async fn score_by_id(&self, peer_id: PeerId) -> Result<Option<PeerScore>, NetworkError> {
+        Ok(self.peers_handle().peer_by_id(peer_id).await.map(|peer| peer.score))
+    }
+
+    /// Sends the ban to the [`PeersManager`](crate::PeersManager) via the [`PeersHandle`].
+    async fn ban(
+        &self,
+        target: BanTarget,
+        duration: Option<Duration>,
+    ) -> Result<bool, NetworkError> {
+        Ok(self.peers_handle().ban(target, duration).await)
+    }
+
+    async fn unban(&self, target: BanTarget) -> Result<bool, NetworkError> {
+        Ok(self.peers_handle().unban(target).await)
+    }
+
+    async fn bans(&self) -> Result<Vec<BanEntry>, NetworkError> {
+        Ok(self.peers_handle().bans().await)
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -432,6 +462,14 @@ impl<N: NetworkPrimitives> BlockDownloaderProvider for NetworkHandle<N> {
     }
 }
 
+impl<N: NetworkPrimitives> SnapDownloaderProvider for NetworkHandle<N> {
+    type SnapClient = SnapFetchClient;
+
+    fn snap_client(&self) -> Option<Self::SnapClient> {
+        self.inner.snap_client.get().cloned()
+    }
+}
+
 #[derive(Debug)]
 struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Number of active peer sessions the node's currently handling.
@@ -464,6 +502,8 @@ struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
     /// The NAT resolver
     nat: Option<NatResolver>,
+    /// The client for requesting state from `snap` peers, if the protocol is installed.
+    snap_client: OnceLock<SnapFetchClient>,
 }
 
 /// Provides access to modify the network's additional protocol handlers.
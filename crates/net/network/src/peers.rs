@@ -10,7 +10,7 @@ use futures::StreamExt;
 
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
-use reth_net_banlist::BanList;
+use reth_net_banlist::{BanEntry, BanList, BanTarget};
 use reth_network_api::test_utils::{PeerCommand, PeersHandle};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
@@ -19,7 +19,7 @@ use reth_network_types::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeerScore, PeersConfig,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
@@ -27,6 +27,7 @@ use std::{
     fmt::Display,
     io::{self},
     net::{IpAddr, SocketAddr},
+    path::PathBuf,
     task::{Context, Poll},
     time::Duration,
 };
@@ -38,6 +39,10 @@ use tokio::{
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{trace, warn};
 
+/// The minimum number of responses of an inbound peer in the current session before it can be
+/// evicted in favor of a new inbound peer.
+const MIN_EVICTION_RESPONSES: u64 = 5;
+
 /// Maintains the state of _all_ the peers known to the network.
 ///
 /// This is supposed to be owned by the network itself, but can be reached via the [`PeersHandle`].
@@ -70,6 +75,8 @@ pub struct PeersManager {
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
     ban_list: BanList,
+    /// File the bans are written to when they are changed on request.
+    persistent_bans_file: Option<PathBuf>,
     /// Tracks currently backed off peers.
     backed_off_peers: HashMap<PeerId, std::time::Instant>,
     /// Interval at which to check for peers to unban and release from the backoff map.
@@ -100,6 +107,7 @@ impl PeersManager {
             connection_info,
             reputation_weights,
             ban_list,
+            persistent_bans_file,
             ban_duration,
             backoff_durations,
             trusted_nodes,
@@ -153,6 +161,7 @@ impl PeersManager {
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info: ConnectionInfo::new(connection_info),
             ban_list,
+            persistent_bans_file,
             backed_off_peers: Default::default(),
             ban_duration,
             backoff_durations,
@@ -202,6 +211,13 @@ impl PeersManager {
         })
     }
 
+    /// Updates the score of the peer's responses in the current session.
+    pub(crate) fn on_peer_score(&mut self, peer_id: &PeerId, score: PeerScore) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.score = score;
+        }
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -249,6 +265,15 @@ impl PeersManager {
 
         // check if we even have slots for a new incoming connection
         if !self.connection_info.has_in_capacity() {
+            // the new peer can take the slot of a poorly performing inbound peer once the session
+            // is established
+            if self.connection_info.has_in_pending_capacity() && self.worst_inbound_peer().is_some()
+            {
+                self.throttle_incoming_ip(addr);
+                self.connection_info.inc_pending_in();
+                return Ok(())
+            }
+
             if self.trusted_peer_ids.is_empty() {
                 // if we don't have any incoming slots and no trusted peers, we don't accept any new
                 // connections
@@ -354,6 +379,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.score = PeerScore::default();
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -371,8 +397,19 @@ impl PeersManager {
         // increment new incoming connection
         self.connection_info.inc_in();
 
-        // disconnect the peer if we don't have capacity for more inbound connections
+        // if we don't have capacity for more inbound connections, either make room by evicting the
+        // worst performing inbound peer or disconnect the new peer
         if !is_trusted && !has_in_capacity {
+            let peer_id = match self.worst_inbound_peer() {
+                Some(evicted) => {
+                    trace!(target: "net::peers", ?evicted, ?peer_id, "evicting inbound peer");
+                    if let Some(peer) = self.peers.get_mut(&evicted) {
+                        peer.state.disconnect();
+                    }
+                    evicted
+                }
+                None => peer_id,
+            };
             self.queued_actions.push_back(PeerAction::Disconnect {
                 peer_id,
                 reason: Some(DisconnectReason::TooManyPeers),
@@ -380,6 +417,38 @@ impl PeersManager {
         }
     }
 
+    /// Returns the connected inbound peer that should be evicted in favor of a new inbound peer.
+    ///
+    /// Only untrusted, non-static peers that already responded [`MIN_EVICTION_RESPONSES`] times in
+    /// the current session are considered. The peer with the lowest score is evicted if its score
+    /// is below half of the average score of these peers, otherwise no peer is evicted.
+    fn worst_inbound_peer(&self) -> Option<PeerId> {
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        let mut worst: Option<(PeerId, f64)> = None;
+        for (peer_id, peer) in &self.peers {
+            if !peer.state.is_incoming() ||
+                peer.is_trusted() ||
+                peer.is_static() ||
+                peer.score.num_responses() < MIN_EVICTION_RESPONSES
+            {
+                continue
+            }
+            let score = peer.score.value();
+            sum += score;
+            count += 1;
+            if worst.is_none_or(|(_, worst_score)| score < worst_score) {
+                worst = Some((*peer_id, score));
+            }
+        }
+        if count < 2 {
+            return None
+        }
+
+        let (peer_id, score) = worst?;
+        (score < sum / count as f64 / 2.0).then_some(peer_id)
+    }
+
     /// Bans the peer temporarily with the configured ban timeout
     fn ban_peer(&mut self, peer_id: PeerId) {
         let mut ban_duration = self.ban_duration;
@@ -422,6 +491,79 @@ impl PeersManager {
         self.queued_actions.push_back(PeerAction::UnBanPeer { peer_id });
     }
 
+    /// Bans the peer, IP address or IP range on request, e.g. via the admin API, and disconnects
+    /// the connected peers that match the ban.
+    ///
+    /// Returns false if the target can't be banned.
+    pub(crate) fn ban_target(&mut self, target: BanTarget, duration: Option<Duration>) -> bool {
+        let until = duration.map(|duration| std::time::Instant::now() + duration);
+        if !self.ban_list.ban(target, until) {
+            return false
+        }
+        trace!(target: "net::peers", %target, ?duration, "banned");
+
+        for (peer_id, peer) in &mut self.peers {
+            let is_banned = match target {
+                BanTarget::Peer(banned) => banned == *peer_id,
+                BanTarget::Ip(_) | BanTarget::Range(_) => {
+                    self.ban_list.is_banned_ip(&peer.addr.tcp().ip())
+                }
+            };
+            if is_banned && peer.state.is_connected() {
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+        if let BanTarget::Peer(peer_id) = target {
+            self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
+        }
+        self.queued_actions.push_back(PeerAction::DiscoveryBanTarget { target, until });
+        self.persist_bans();
+        true
+    }
+
+    /// Lifts the ban of the peer, IP address or IP range.
+    ///
+    /// Returns true if the target was banned.
+    pub(crate) fn unban_target(&mut self, target: BanTarget) -> bool {
+        if !self.ban_list.unban(&target) {
+            return false
+        }
+        trace!(target: "net::peers", %target, "unbanned");
+
+        if let BanTarget::Peer(peer_id) = target {
+            if let Some(peer) = self.peers.get_mut(&peer_id) {
+                peer.unban();
+            }
+            self.queued_actions.push_back(PeerAction::UnBanPeer { peer_id });
+        }
+        self.queued_actions.push_back(PeerAction::DiscoveryUnbanTarget { target });
+        self.persist_bans();
+        true
+    }
+
+    /// Returns all bans, see [`BanList::entries`].
+    pub(crate) fn bans(&self) -> Vec<BanEntry> {
+        self.ban_list.entries()
+    }
+
+    /// Writes the bans to the persistent bans file, if any, so bans changed on request are kept
+    /// even if the node doesn't shut down gracefully.
+    fn persist_bans(&self) {
+        let Some(file) = &self.persistent_bans_file else { return };
+        let result = file
+            .parent()
+            .map(reth_fs_util::create_dir_all)
+            .transpose()
+            .and_then(|_| reth_fs_util::write_json_file(file, &self.bans()));
+        if let Err(err) = result {
+            warn!(target: "net::peers", %err, ?file, "Failed to write bans to file");
+        }
+    }
+
     /// Tick function to update reputation of all connected peers.
     /// Peers are rewarded with reputation increases for the time they are connected since the last
     /// tick. This is to prevent peers from being disconnected eventually due to slashed
@@ -552,6 +694,7 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.score = PeerScore::default();
         }
     }
 
@@ -1002,6 +1145,15 @@ impl PeersManager {
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::Ban(target, duration, tx) => {
+                        let _ = tx.send(self.ban_target(target, duration));
+                    }
+                    PeerCommand::Unban(target, tx) => {
+                        let _ = tx.send(self.unban_target(target));
+                    }
+                    PeerCommand::GetBans(tx) => {
+                        let _ = tx.send(self.bans());
+                    }
                 }
             }
 
@@ -1171,6 +1323,18 @@ pub enum PeerAction {
         /// The IP address.
         ip_addr: IpAddr,
     },
+    /// Ban the peer, IP or IP range in discovery on request.
+    DiscoveryBanTarget {
+        /// The banned peer, IP or IP range.
+        target: BanTarget,
+        /// When the ban expires, if ever.
+        until: Option<std::time::Instant>,
+    },
+    /// Lift the ban of the peer, IP or IP range in discovery on request.
+    DiscoveryUnbanTarget {
+        /// The unbanned peer, IP or IP range.
+        target: BanTarget,
+    },
     /// Ban the peer temporarily
     BanPeer {
         /// The peer ID.
@@ -1209,11 +1373,11 @@ mod tests {
         errors::{EthHandshakeError, EthStreamError, P2PHandshakeError, P2PStreamError},
         DisconnectReason,
     };
-    use reth_net_banlist::BanList;
+    use reth_net_banlist::{BanList, BanTarget};
     use reth_network_api::Direction;
     use reth_network_peers::{PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, PeerScore, ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1899,6 +2063,48 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_ban_target_range() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+
+        assert!(matches!(event!(peers), PeerAction::PeerAdded(_)));
+        assert!(matches!(event!(peers), PeerAction::Connect { .. }));
+
+        let range = BanTarget::Range("127.0.1.0/24".parse().unwrap());
+        assert!(peers.ban_target(range, Some(Duration::from_secs(60))));
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, reason } => {
+                assert_eq!(peer_id, peer);
+                assert_eq!(reason, Some(DisconnectReason::DisconnectRequested));
+            }
+            _ => unreachable!(),
+        }
+        // the range is banned in discovery as well
+        match event!(peers) {
+            PeerAction::DiscoveryBanTarget { target, until } => {
+                assert_eq!(target, range);
+                assert!(until.is_some());
+            }
+            _ => unreachable!(),
+        }
+
+        // peers in the range are not added
+        let other = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        peers.add_peer(PeerId::random(), PeerAddr::from_tcp(other), None);
+        assert_eq!(peers.peers.len(), 1);
+
+        // single non-global IPs can't be banned
+        assert!(!peers.ban_target(BanTarget::Ip(socket_addr.ip()), None));
+
+        assert_eq!(peers.bans().len(), 1);
+        assert!(peers.unban_target(range));
+        assert!(!peers.unban_target(range));
+        assert!(peers.bans().is_empty());
+    }
+
     #[tokio::test]
     async fn accept_incoming_trusted_unknown_peer_address() {
         let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 99)), 8008);
@@ -2472,6 +2678,50 @@ mod tests {
         assert!(peers.peers.contains_key(&basic_peer));
     }
 
+    #[tokio::test]
+    async fn evict_worst_inbound_peer_at_capacity() {
+        let mut peers = PeersManager::new(PeersConfig::test().with_max_inbound(2));
+
+        // saturate the inbound slots
+        let mut connected = Vec::new();
+        for i in 0..peers.connection_info.config.max_inbound {
+            let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, i as u8)), 8008);
+            assert!(peers.on_incoming_pending_session(addr.ip()).is_ok());
+            let peer_id = PeerId::random();
+            peers.on_incoming_session_established(peer_id, addr);
+            assert!(matches!(event!(peers), PeerAction::PeerAdded(id) if id == peer_id));
+            connected.push(peer_id);
+        }
+
+        let (good, bad) = (connected[0], connected[1]);
+        let mut good_score = PeerScore::default();
+        let mut bad_score = PeerScore::default();
+        for _ in 0..5 {
+            good_score.on_response(Duration::from_millis(100), 1024);
+            bad_score.on_invalid_response(Duration::from_millis(100));
+        }
+        peers.on_peer_score(&good, good_score);
+        peers.on_peer_score(&bad, bad_score);
+
+        // the new peer takes the slot of the worst scoring peer
+        let new_peer = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 99)), 8008);
+        assert!(peers.on_incoming_pending_session(addr.ip()).is_ok());
+        peers.on_incoming_session_established(new_peer, addr);
+
+        assert!(matches!(event!(peers), PeerAction::PeerAdded(id) if id == new_peer));
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, reason } => {
+                assert_eq!(peer_id, bad);
+                assert_eq!(reason, Some(DisconnectReason::TooManyPeers));
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(peers.peers[&bad].state, PeerConnectionState::DisconnectingIn);
+        assert_eq!(peers.peers[&new_peer].state, PeerConnectionState::In);
+        assert_eq!(peers.peers.get(&new_peer).map(|peer| peer.score), Some(PeerScore::default()));
+    }
+
     #[tokio::test]
     async fn test_incoming_at_capacity() {
         let mut config = PeersConfig::test();
@@ -16,16 +16,18 @@ use crate::{
     session::{
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
-        BlockRangeInfo, EthVersion, SessionId,
+        BlockRangeInfo, EthVersion, MessageDirection, SessionId, TrafficRecorder,
     },
 };
 use alloy_primitives::Sealable;
+use alloy_rlp::Encodable;
 use futures::{stream::Fuse, SinkExt, StreamExt};
 use metrics::Gauge;
 use reth_eth_wire::{
     errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, MessageError, RequestPair},
-    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, NetworkPrimitives, NewBlockPayload,
+    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, EthMessageID, NetworkPrimitives,
+    NewBlockPayload,
 };
 use reth_eth_wire_types::RawCapabilityMessage;
 use reth_metrics::common::mpsc::MeteredPollSender;
@@ -128,6 +130,8 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     /// Optional interval for sending periodic range updates to the remote peer (eth69+)
     /// Recommended frequency is ~2 minutes per spec
     pub(crate) range_update_interval: Option<Interval>,
+    /// Records the messages exchanged with the remote peer, if enabled.
+    pub(crate) recorder: Option<TrafficRecorder>,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
@@ -149,6 +153,13 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
         self.queued_outgoing.shrink_to_fit();
     }
 
+    /// Records the message if traffic recording is enabled, see [`TrafficRecorder`].
+    fn record(&self, direction: MessageDirection, id: EthMessageID, message: &impl Encodable) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(self.remote_peer_id, direction, self.conn.version(), id, message);
+        }
+    }
+
     /// Returns how many responses we've currently queued up.
     fn queued_response_count(&self) -> usize {
         self.queued_outgoing.messages.iter().filter(|m| m.is_response()).count()
@@ -622,8 +633,14 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                 if let Some(msg) = this.queued_outgoing.pop_front() {
                     progress = true;
                     let res = match msg {
-                        OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
-                        OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
+                        OutgoingMessage::Eth(msg) => {
+                            this.record(MessageDirection::Outgoing, msg.message_id(), &msg);
+                            this.conn.start_send_unpin(msg)
+                        }
+                        OutgoingMessage::Broadcast(msg) => {
+                            this.record(MessageDirection::Outgoing, msg.message_id(), &msg);
+                            this.conn.start_send_broadcast(msg)
+                        }
                         OutgoingMessage::Raw(msg) => this.conn.start_send_raw(msg),
                     };
                     if let Err(err) = res {
@@ -699,6 +716,7 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         match res {
                             Ok(msg) => {
                                 trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                this.record(MessageDirection::Incoming, msg.message_id(), &msg);
                                 // decode and handle message
                                 match this.on_incoming_message(msg) {
                                     OnIncomingMessageOutcome::Ok => {
@@ -1046,6 +1064,7 @@ mod tests {
                             alloy_primitives::B256::ZERO,
                         ),
                         range_update_interval: None,
+                        recorder: None,
                     }
                 }
                 ev => {
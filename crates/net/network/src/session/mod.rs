@@ -4,6 +4,7 @@ mod active;
 mod conn;
 mod counter;
 mod handle;
+mod recorder;
 mod types;
 pub use types::BlockRangeInfo;
 
@@ -54,6 +55,10 @@ pub use handle::{
     ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
     SessionCommand,
 };
+pub use recorder::{
+    read_recording, MessageDirection, RecordedMessage, RecordingError, TrafficRecorder,
+    TrafficRecorderConfig,
+};
 pub use reth_network_api::{Direction, PeerInfo};
 
 /// Internal identifier for active sessions.
@@ -117,6 +122,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     metrics: SessionManagerMetrics,
     /// The [`EthRlpxHandshake`] is used to perform the initial handshake with the peer.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// Records the messages of the active sessions, if enabled.
+    recorder: Option<TrafficRecorder>,
     /// Shared local range information that gets propagated to active sessions.
     /// This represents the range of blocks that this node can serve to other peers.
     local_range_info: BlockRangeInfo,
@@ -136,6 +143,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
         handshake: Arc<dyn EthRlpxHandshake>,
+        recorder: Option<TrafficRecorder>,
     ) -> Self {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
@@ -170,6 +178,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             disconnections_counter: Default::default(),
             metrics: Default::default(),
             handshake,
+            recorder,
             local_range_info,
         }
     }
@@ -571,6 +580,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     range_info: None,
                     local_range_info: self.local_range_info.clone(),
                     range_update_interval,
+                    recorder: self.recorder.clone(),
                 };
 
                 self.spawn(session);
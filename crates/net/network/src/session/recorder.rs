@@ -0,0 +1,480 @@
+//! Records the `eth` messages of active sessions to rotating files.
+//!
+//! Every message is written as a single line:
+//!
+//! ```text
+//! <unix timestamp in µs> <peer id> <in|out> eth/<version> <message id> <RLP encoded message>
+//! ```
+//!
+//! The RLP encoded message is the hex encoded message id followed by the message, as it is sent
+//! over the wire after decompression. A recording can be read with [`read_recording`] and
+//! replayed against a network with [`Replay`](crate::test_utils::Replay).
+
+use alloy_primitives::{hex, Bytes};
+use alloy_rlp::{Decodable, Encodable};
+use reth_eth_wire::{
+    message::{MessageError, ProtocolMessage},
+    EthMessage, EthMessageID, EthVersion, NetworkPrimitives,
+};
+use reth_network_peers::PeerId;
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::mpsc::{self, SyncSender, TrySendError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+/// The name of the file the recorder writes to.
+///
+/// Rotated files get a numeric suffix, the higher the suffix the older the file, e.g.
+/// `traffic.log.1` is the most recently rotated file.
+pub const TRAFFIC_FILE_NAME: &str = "traffic.log";
+
+/// The default size in bytes after which the current file is rotated.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 200 * 1024 * 1024;
+
+/// The default number of rotated files to keep.
+pub const DEFAULT_MAX_FILES: usize = 5;
+
+/// How many messages can be queued for the writer before messages are dropped.
+const RECORDER_CHANNEL_CAPACITY: usize = 10_000;
+
+/// Configures a [`TrafficRecorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrafficRecorderConfig {
+    /// The directory the recording is written to.
+    pub dir: PathBuf,
+    /// The size in bytes after which the current file is rotated.
+    pub max_file_size: u64,
+    /// The number of rotated files to keep, older files are removed.
+    pub max_files: usize,
+}
+
+impl TrafficRecorderConfig {
+    /// Creates a config that records to the given directory with the default limits.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), max_file_size: DEFAULT_MAX_FILE_SIZE, max_files: DEFAULT_MAX_FILES }
+    }
+
+    /// Sets the size in bytes after which the current file is rotated.
+    pub const fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Sets the number of rotated files to keep.
+    pub const fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+}
+
+/// Records the `eth` messages that are exchanged with peers.
+///
+/// The messages are written to the files by a dedicated thread, so recording doesn't block the
+/// sessions. If the writer can't keep up, messages are dropped. The thread exits once all clones
+/// of the recorder are dropped.
+#[derive(Debug, Clone)]
+pub struct TrafficRecorder {
+    to_writer: SyncSender<RecordedMessage>,
+}
+
+// === impl TrafficRecorder ===
+
+impl TrafficRecorder {
+    /// Creates the directory of the recording and spawns the thread that writes the messages.
+    pub fn spawn(config: TrafficRecorderConfig) -> io::Result<Self> {
+        let mut writer = RotatingWriter::open(config)?;
+        let (to_writer, messages) =
+            mpsc::sync_channel::<RecordedMessage>(RECORDER_CHANNEL_CAPACITY);
+
+        std::thread::Builder::new().name("traffic-recorder".to_string()).spawn(move || {
+            while let Ok(message) = messages.recv() {
+                // write everything that's queued before flushing
+                let res = std::iter::once(message)
+                    .chain(messages.try_iter())
+                    .try_for_each(|message| writer.write(&message))
+                    .and_then(|_| writer.flush());
+                if let Err(err) = res {
+                    warn!(target: "net::session", %err, "Failed to record messages");
+                }
+            }
+        })?;
+
+        Ok(Self { to_writer })
+    }
+
+    /// Records a message that was exchanged with the peer.
+    pub(crate) fn record(
+        &self,
+        peer_id: PeerId,
+        direction: MessageDirection,
+        version: EthVersion,
+        id: EthMessageID,
+        message: &impl Encodable,
+    ) {
+        let mut encoded = Vec::with_capacity(id.length() + message.length());
+        id.encode(&mut encoded);
+        message.encode(&mut encoded);
+
+        let message = RecordedMessage {
+            timestamp: SystemTime::now(),
+            peer_id,
+            direction,
+            version,
+            message: encoded.into(),
+        };
+        match self.to_writer.try_send(message) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => {}
+            Err(TrySendError::Full(message)) => {
+                debug!(
+                    target: "net::session",
+                    peer_id=?message.peer_id,
+                    "Dropped recorded message, recorder is too slow"
+                );
+            }
+        }
+    }
+}
+
+/// The direction of a recorded message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageDirection {
+    /// The message was received from the peer.
+    Incoming,
+    /// The message was sent to the peer.
+    Outgoing,
+}
+
+impl MessageDirection {
+    /// Returns `true` if the message was received from the peer.
+    pub const fn is_incoming(&self) -> bool {
+        matches!(self, Self::Incoming)
+    }
+}
+
+/// A message that was exchanged with a peer, see [`TrafficRecorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedMessage {
+    /// When the message was received or sent.
+    pub timestamp: SystemTime,
+    /// The peer the message was exchanged with.
+    pub peer_id: PeerId,
+    /// Whether the message was received from or sent to the peer.
+    pub direction: MessageDirection,
+    /// The `eth` version of the session.
+    pub version: EthVersion,
+    /// The RLP encoded message id followed by the RLP encoded message.
+    pub message: Bytes,
+}
+
+// === impl RecordedMessage ===
+
+impl RecordedMessage {
+    /// Returns the id of the message.
+    pub fn message_id(&self) -> Option<EthMessageID> {
+        EthMessageID::decode(&mut &self.message[..]).ok()
+    }
+
+    /// Decodes the message according to the `eth` version of the session.
+    pub fn decode<N: NetworkPrimitives>(&self) -> Result<EthMessage<N>, MessageError> {
+        ProtocolMessage::decode_message(self.version, &mut &self.message[..]).map(|msg| msg.message)
+    }
+}
+
+impl fmt::Display for RecordedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timestamp = self.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let direction = if self.direction.is_incoming() { "in" } else { "out" };
+        write!(
+            f,
+            "{} {} {direction} eth/{} {} {}",
+            timestamp.as_micros(),
+            hex::encode_prefixed(self.peer_id),
+            u8::from(self.version),
+            self.message_id().map_or_else(|| "-".to_string(), |id| format!("{id:?}")),
+            hex::encode_prefixed(&self.message),
+        )
+    }
+}
+
+impl FromStr for RecordedMessage {
+    type Err = RecordingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| RecordingError::InvalidRecord(format!("{reason}: {s}"));
+
+        let mut fields = s.split_whitespace();
+        let mut next =
+            |name: &str| fields.next().ok_or_else(|| invalid(&format!("missing {name}")));
+
+        let timestamp = next("timestamp")?.parse().map_err(|_| invalid("invalid timestamp"))?;
+        let peer_id = next("peer id")?.parse().map_err(|_| invalid("invalid peer id"))?;
+        let direction = match next("direction")? {
+            "in" => MessageDirection::Incoming,
+            "out" => MessageDirection::Outgoing,
+            _ => return Err(invalid("invalid direction")),
+        };
+        let version = next("version")?
+            .strip_prefix("eth/")
+            .and_then(|version| version.parse().ok())
+            .ok_or_else(|| invalid("invalid version"))?;
+        // the message id is informational, it's part of the encoded message
+        let _id = next("message id")?;
+        let message = next("message")?.parse().map_err(|_| invalid("invalid message"))?;
+
+        Ok(Self {
+            timestamp: UNIX_EPOCH + Duration::from_micros(timestamp),
+            peer_id,
+            direction,
+            version,
+            message,
+        })
+    }
+}
+
+/// Errors when reading a recording.
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingError {
+    /// Failed to read the recording.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// A line of the recording is not a valid record.
+    #[error("invalid record, {0}")]
+    InvalidRecord(String),
+}
+
+/// Reads the messages of a recording, see [`TrafficRecorder`].
+///
+/// The path is either a single file of a recording or the directory of a recording, in which case
+/// the rotated files are read from the oldest to the most recent file.
+pub fn read_recording(path: impl AsRef<Path>) -> Result<Vec<RecordedMessage>, RecordingError> {
+    let path = path.as_ref();
+    if !path.is_dir() {
+        return read_recording_file(path)
+    }
+
+    let mut rotated = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let name = entry?.file_name();
+        let suffix = name.to_str().and_then(|name| name.strip_prefix(TRAFFIC_FILE_NAME));
+        if let Some(Ok(num)) = suffix.and_then(|s| s.strip_prefix('.')).map(usize::from_str) {
+            rotated.push(num);
+        }
+    }
+    rotated.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut messages = Vec::new();
+    for num in rotated {
+        messages.extend(read_recording_file(&rotated_file(path, num))?);
+    }
+    let current = path.join(TRAFFIC_FILE_NAME);
+    if current.exists() {
+        messages.extend(read_recording_file(&current)?);
+    }
+    Ok(messages)
+}
+
+/// Reads the messages of a single file of a recording.
+fn read_recording_file(path: &Path) -> Result<Vec<RecordedMessage>, RecordingError> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut messages = Vec::new();
+    for (num, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let message = line.parse().map_err(|err| match err {
+            RecordingError::InvalidRecord(reason) => {
+                RecordingError::InvalidRecord(format!("{}:{}: {reason}", path.display(), num + 1))
+            }
+            err => err,
+        })?;
+        messages.push(message);
+    }
+    Ok(messages)
+}
+
+/// Returns the path of the rotated file with the given number.
+fn rotated_file(dir: &Path, num: usize) -> PathBuf {
+    dir.join(format!("{TRAFFIC_FILE_NAME}.{num}"))
+}
+
+/// Writes the recorded messages to the current file and rotates the files once the current file
+/// exceeds the configured size.
+#[derive(Debug)]
+struct RotatingWriter {
+    config: TrafficRecorderConfig,
+    file: BufWriter<File>,
+    size: u64,
+}
+
+impl RotatingWriter {
+    /// Opens the current file of the recording, appending to an existing file.
+    fn open(config: TrafficRecorderConfig) -> io::Result<Self> {
+        fs::create_dir_all(&config.dir)?;
+        let file =
+            File::options().create(true).append(true).open(config.dir.join(TRAFFIC_FILE_NAME))?;
+        let size = file.metadata()?.len();
+        Ok(Self { config, file: BufWriter::new(file), size })
+    }
+
+    /// Writes the message to the current file, rotating the files first if necessary.
+    fn write(&mut self, message: &RecordedMessage) -> io::Result<()> {
+        let line = format!("{message}\n");
+        if self.size > 0 && self.size + line.len() as u64 > self.config.max_file_size {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Flushes the buffered messages to the current file.
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+
+    /// Moves the current file to `traffic.log.1`, shifts the rotated files by one and removes the
+    /// files exceeding the configured number of files.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let dir = &self.config.dir;
+
+        let oldest = rotated_file(dir, self.config.max_files);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for num in (1..self.config.max_files).rev() {
+            let file = rotated_file(dir, num);
+            if file.exists() {
+                fs::rename(file, rotated_file(dir, num + 1))?;
+            }
+        }
+        let current = dir.join(TRAFFIC_FILE_NAME);
+        if self.config.max_files > 0 {
+            fs::rename(&current, rotated_file(dir, 1))?;
+        }
+
+        self.file = BufWriter::new(File::create(current)?);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_eth_wire::{
+        message::RequestPair, BlockHashNumber, EthNetworkPrimitives, GetBlockHeaders,
+        HeadersDirection, NewBlockHashes,
+    };
+    use std::time::Instant;
+
+    fn record(
+        writer: &mut RotatingWriter,
+        direction: MessageDirection,
+        message: &EthMessage<EthNetworkPrimitives>,
+    ) -> RecordedMessage {
+        let mut encoded = Vec::new();
+        message.message_id().encode(&mut encoded);
+        message.encode(&mut encoded);
+        let message = RecordedMessage {
+            timestamp: UNIX_EPOCH + Duration::from_micros(1_700_000_000_000_000),
+            peer_id: PeerId::random(),
+            direction,
+            version: EthVersion::Eth68,
+            message: encoded.into(),
+        };
+        writer.write(&message).unwrap();
+        message
+    }
+
+    #[test]
+    fn record_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = RotatingWriter::open(TrafficRecorderConfig::new(dir.path())).unwrap();
+
+        let request = EthMessage::GetBlockHeaders(RequestPair {
+            request_id: 7,
+            message: GetBlockHeaders {
+                start_block: 100u64.into(),
+                limit: 10,
+                skip: 0,
+                direction: HeadersDirection::Rising,
+            },
+        });
+        let hashes = EthMessage::NewBlockHashes(NewBlockHashes(vec![BlockHashNumber {
+            hash: B256::random(),
+            number: 100,
+        }]));
+        let expected = vec![
+            record(&mut writer, MessageDirection::Incoming, &request),
+            record(&mut writer, MessageDirection::Outgoing, &hashes),
+        ];
+        writer.flush().unwrap();
+
+        let recording = read_recording(dir.path()).unwrap();
+        assert_eq!(recording, expected);
+        assert_eq!(recording[0].message_id(), Some(EthMessageID::GetBlockHeaders));
+        assert_eq!(recording[0].decode::<EthNetworkPrimitives>().unwrap(), request);
+        assert_eq!(recording[1].decode::<EthNetworkPrimitives>().unwrap(), hashes);
+
+        let line = recording[0].to_string();
+        assert!(line.starts_with("1700000000000000 0x"), "{line}");
+        assert!(line.contains(" in eth/68 GetBlockHeaders 0x03"), "{line}");
+    }
+
+    #[test]
+    fn rotate_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TrafficRecorderConfig::new(dir.path()).with_max_file_size(1).with_max_files(2);
+        let mut writer = RotatingWriter::open(config).unwrap();
+
+        let messages = (0..4u64)
+            .map(|number| {
+                let hashes = NewBlockHashes(vec![BlockHashNumber { hash: B256::ZERO, number }]);
+                record(&mut writer, MessageDirection::Incoming, &EthMessage::NewBlockHashes(hashes))
+            })
+            .collect::<Vec<_>>();
+        writer.flush().unwrap();
+
+        // every message is in its own file and the oldest message was removed
+        assert!(!rotated_file(dir.path(), 3).exists());
+        assert_eq!(read_recording(dir.path()).unwrap(), messages[1..]);
+        assert_eq!(read_recording(rotated_file(dir.path(), 2)).unwrap(), messages[1..2]);
+    }
+
+    #[test]
+    fn record_from_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = TrafficRecorder::spawn(TrafficRecorderConfig::new(dir.path())).unwrap();
+        let peer_id = PeerId::random();
+        let hashes = EthMessage::<EthNetworkPrimitives>::NewBlockHashes(NewBlockHashes(vec![]));
+        recorder.record(
+            peer_id,
+            MessageDirection::Incoming,
+            EthVersion::Eth68,
+            hashes.message_id(),
+            &hashes,
+        );
+        drop(recorder);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let recording = read_recording(dir.path()).unwrap();
+            if let [message] = recording.as_slice() {
+                assert_eq!(message.peer_id, peer_id);
+                assert_eq!(message.decode::<EthNetworkPrimitives>().unwrap(), hashes);
+                break
+            }
+            assert!(Instant::now() < deadline, "message was not recorded");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! A client for requesting state from `snap` peers.
+
+use futures::Future;
+use parking_lot::Mutex;
+use reth_eth_wire::snap::{
+    GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage,
+    SnapProtocolMessage,
+};
+use reth_network_api::{test_utils::PeersHandle, ReputationChangeKind};
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError, RequestResult},
+    priority::Priority,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Sleep,
+};
+
+/// The timeout for a single `snap` request.
+pub const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A request that is sent to a peer over its `snap` connection.
+#[derive(Debug)]
+pub(crate) struct SnapRequest {
+    /// The request message.
+    pub(crate) request: SnapProtocolMessage,
+    /// The channel for the peer's response.
+    pub(crate) response: oneshot::Sender<RequestResult<SnapResponse>>,
+}
+
+/// A connected peer that supports `snap`.
+#[derive(Debug)]
+pub(crate) struct SnapPeer {
+    /// Identifies the connection that registered this peer.
+    pub(crate) connection_id: u64,
+    /// Sender half of the connection's request channel.
+    pub(crate) to_connection: mpsc::UnboundedSender<SnapRequest>,
+    /// Number of requests currently in flight to this peer.
+    pub(crate) inflight: Arc<AtomicUsize>,
+}
+
+/// All connected `snap` peers, shared between the [`SnapFetchClient`] and the connections.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SnapPeers {
+    peers: Arc<Mutex<HashMap<PeerId, SnapPeer>>>,
+}
+
+impl SnapPeers {
+    /// Registers a new connection with the peer.
+    pub(crate) fn insert(&self, peer_id: PeerId, peer: SnapPeer) {
+        self.peers.lock().insert(peer_id, peer);
+    }
+
+    /// Removes the peer if it is still registered by the given connection.
+    pub(crate) fn remove(&self, peer_id: &PeerId, connection_id: u64) {
+        let mut peers = self.peers.lock();
+        if peers.get(peer_id).is_some_and(|peer| peer.connection_id == connection_id) {
+            peers.remove(peer_id);
+        }
+    }
+
+    /// Returns the number of connected `snap` peers.
+    pub(crate) fn len(&self) -> usize {
+        self.peers.lock().len()
+    }
+
+    /// Sends the request to the least busy peer.
+    fn send(
+        &self,
+        request: SnapProtocolMessage,
+    ) -> Result<(PeerId, SnapRequestTicket), RequestError> {
+        let peers = self.peers.lock();
+        let (peer_id, peer) = peers
+            .iter()
+            .min_by_key(|(_, peer)| peer.inflight.load(Ordering::Relaxed))
+            .ok_or(RequestError::UnsupportedCapability)?;
+
+        let (tx, rx) = oneshot::channel();
+        peer.to_connection
+            .send(SnapRequest { request, response: tx })
+            .map_err(|_| RequestError::ConnectionDropped)?;
+        peer.inflight.fetch_add(1, Ordering::Relaxed);
+
+        Ok((*peer_id, SnapRequestTicket { rx, inflight: peer.inflight.clone() }))
+    }
+}
+
+/// Tracks a request that was handed to a connection.
+#[derive(Debug)]
+struct SnapRequestTicket {
+    rx: oneshot::Receiver<RequestResult<SnapResponse>>,
+    inflight: Arc<AtomicUsize>,
+}
+
+impl Drop for SnapRequestTicket {
+    fn drop(&mut self) {
+        self.inflight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Front-end API for requesting state from connected `snap` peers.
+///
+/// Every request is sent to the connected peer with the fewest requests in flight.
+#[derive(Debug, Clone)]
+pub struct SnapFetchClient {
+    /// All connected `snap` peers.
+    peers: SnapPeers,
+    /// Used for reporting peers.
+    peers_handle: PeersHandle,
+    /// The timeout for a single request.
+    timeout: Duration,
+}
+
+impl SnapFetchClient {
+    /// Creates a new client for the given peers.
+    pub(crate) const fn new(peers: SnapPeers, peers_handle: PeersHandle) -> Self {
+        Self { peers, peers_handle, timeout: SNAP_REQUEST_TIMEOUT }
+    }
+
+    /// Sets the timeout for a single request.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn request(&self, request: SnapProtocolMessage) -> SnapResponseFuture {
+        let state = match self.peers.send(request) {
+            Ok((peer_id, ticket)) => ResponseState::Pending {
+                peer_id,
+                ticket,
+                timeout: Box::pin(tokio::time::sleep(self.timeout)),
+            },
+            Err(err) => ResponseState::Failed(Some(err)),
+        };
+        SnapResponseFuture { state, peers_handle: self.peers_handle.clone() }
+    }
+}
+
+impl DownloadClient for SnapFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.peers.len()
+    }
+}
+
+impl SnapClient for SnapFetchClient {
+    type Output = SnapResponseFuture;
+
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetAccountRange(request))
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetStorageRanges(request))
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetByteCodes(request))
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetTrieNodes(request))
+    }
+}
+
+/// A future that resolves to the response of a `snap` peer.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct SnapResponseFuture {
+    state: ResponseState,
+    peers_handle: PeersHandle,
+}
+
+#[derive(Debug)]
+enum ResponseState {
+    Pending { peer_id: PeerId, ticket: SnapRequestTicket, timeout: Pin<Box<Sleep>> },
+    Failed(Option<RequestError>),
+}
+
+impl Future for SnapResponseFuture {
+    type Output = PeerRequestResult<SnapResponse>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.state {
+            ResponseState::Pending { peer_id, ticket, timeout } => {
+                if let Poll::Ready(res) = Pin::new(&mut ticket.rx).poll(cx) {
+                    let res = res.map_err(|_| RequestError::ConnectionDropped).and_then(|res| res);
+                    return Poll::Ready(res.map(|response| WithPeerId::new(*peer_id, response)))
+                }
+                ready!(timeout.as_mut().poll(cx));
+                this.peers_handle.reputation_change(*peer_id, ReputationChangeKind::Timeout);
+                Poll::Ready(Err(RequestError::Timeout))
+            }
+            ResponseState::Failed(err) => {
+                Poll::Ready(Err(err.take().expect("polled after completion")))
+            }
+        }
+    }
+}
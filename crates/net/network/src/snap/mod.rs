@@ -0,0 +1,19 @@
+//! Support for the `snap` protocol.
+//!
+//! The `snap` protocol runs as an additional `RLPx` sub-protocol next to `eth`. Requests received
+//! by the [`SnapProtocolHandler`] connections are delegated to the [`SnapRequestHandler`] which
+//! serves them from the database. State can be requested from connected `snap` peers with the
+//! [`SnapFetchClient`].
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+mod client;
+mod protocol;
+mod requests;
+
+pub(crate) use client::SnapPeers;
+pub use client::{SnapFetchClient, SnapResponseFuture, SNAP_REQUEST_TIMEOUT};
+pub use protocol::{SnapConnection, SnapProtocolHandler};
+pub use requests::{
+    IncomingSnapRequest, SnapRequestHandler, MAX_CODE_LOOKUPS, MAX_TRIE_NODE_LOOKUPS,
+};
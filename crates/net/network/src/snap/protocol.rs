@@ -0,0 +1,347 @@
+//! The `snap` protocol as an additional `RLPx` sub-protocol.
+
+use super::{
+    client::{SnapPeer, SnapPeers, SnapRequest},
+    requests::IncomingSnapRequest,
+};
+use crate::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use alloy_primitives::bytes::BytesMut;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities,
+    multiplex::ProtocolConnection,
+    protocol::Protocol,
+    snap::{
+        AccountRangeMessage, ByteCodesMessage, SnapProtocolMessage, StorageRangesMessage,
+        TrieNodesMessage,
+    },
+};
+use reth_network_api::{test_utils::PeersHandle, Direction, ReputationChangeKind};
+use reth_network_p2p::{
+    error::{RequestError, RequestResult},
+    snap::client::SnapResponse,
+};
+use reth_network_peers::PeerId;
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    oneshot,
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, trace};
+
+/// The protocol handler that installs the `snap/1` protocol on every connection.
+///
+/// Incoming requests are delegated to the
+/// [`SnapRequestHandler`](super::requests::SnapRequestHandler), if the node serves `snap`.
+/// Every connection is also registered with the [`SnapFetchClient`](super::SnapFetchClient) so
+/// that state can be requested from the peer.
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Sender half of the channel to the snap request handler, if requests are served.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// All connected `snap` peers.
+    snap_peers: SnapPeers,
+    /// Used to identify connections.
+    next_connection_id: Arc<AtomicU64>,
+    /// Used for reporting peers.
+    peers: PeersHandle,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new handler that forwards requests to the given channel, if any.
+    ///
+    /// If no channel is given, every request is answered with an empty response.
+    pub(crate) fn new(
+        to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+        snap_peers: SnapPeers,
+        peers: PeersHandle,
+    ) -> Self {
+        Self { to_request_handler, snap_peers, next_connection_id: Default::default(), peers }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = Self;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+}
+
+impl ConnectionHandler for SnapProtocolHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let connection_id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.snap_peers.insert(
+            peer_id,
+            SnapPeer { connection_id, to_connection: tx, inflight: Default::default() },
+        );
+
+        SnapConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            peers: self.peers,
+            pending_responses: FuturesUnordered::new(),
+            connection_id,
+            snap_peers: self.snap_peers,
+            outgoing_requests: UnboundedReceiverStream::new(rx),
+            inflight_requests: HashMap::default(),
+            next_request_id: 0,
+            terminated: false,
+        }
+    }
+}
+
+/// A `snap` connection with a single peer.
+///
+/// Yields the encoded messages that should be sent to the peer.
+#[derive(Debug)]
+#[must_use = "Connection does nothing unless polled."]
+pub struct SnapConnection {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// Incoming messages from the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the channel to the snap request handler, if requests are served.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// Used for reporting peers.
+    peers: PeersHandle,
+    /// Responses that are being served by the request handler.
+    pending_responses: FuturesUnordered<PendingSnapResponse>,
+    /// Identifies this connection in the shared [`SnapPeers`].
+    connection_id: u64,
+    /// All connected `snap` peers.
+    snap_peers: SnapPeers,
+    /// Requests that should be sent to the peer.
+    outgoing_requests: UnboundedReceiverStream<SnapRequest>,
+    /// Requests sent to the peer that are awaiting a response, by request id.
+    inflight_requests: HashMap<u64, oneshot::Sender<RequestResult<SnapResponse>>>,
+    /// The id of the next request sent to the peer.
+    next_request_id: u64,
+    /// Flag indicating whether this stream has previously been terminated.
+    terminated: bool,
+}
+
+impl SnapConnection {
+    /// Report bad message from current peer.
+    fn report_bad_message(&self) {
+        self.peers.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    /// Delegates the request to the request handler and tracks the pending response.
+    ///
+    /// If the request handler can't serve the request, the peer gets the empty response.
+    fn delegate_request<T: Send + 'static>(
+        &self,
+        to_request: impl FnOnce(oneshot::Sender<RequestResult<T>>) -> IncomingSnapRequest,
+        into_message: fn(T) -> SnapProtocolMessage,
+        empty_response: SnapProtocolMessage,
+    ) {
+        let Some(to_request_handler) = &self.to_request_handler else {
+            self.pending_responses.push(Box::pin(futures::future::ready(Some(empty_response))));
+            return
+        };
+        let (tx, rx) = oneshot::channel();
+        if let Err(err) = to_request_handler.try_send(to_request(tx)) {
+            match err {
+                TrySendError::Full(_) => {
+                    debug!(target: "net::snap", peer_id=%self.peer_id, "SnapRequestHandler channel is full!");
+                }
+                TrySendError::Closed(_) => {}
+            }
+            self.pending_responses.push(Box::pin(futures::future::ready(Some(empty_response))));
+            return
+        }
+        self.pending_responses.push(Box::pin(async move {
+            Some(rx.await.ok().and_then(Result::ok).map_or(empty_response, into_message))
+        }));
+    }
+
+    /// Assigns a request id to the request and returns the message that should be sent to the
+    /// peer.
+    fn on_outgoing_request(&mut self, request: SnapRequest) -> SnapProtocolMessage {
+        let SnapRequest { mut request, response } = request;
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+        request.set_request_id(request_id);
+        self.inflight_requests.insert(request_id, response);
+        request
+    }
+
+    /// Resolves the inflight request the response belongs to.
+    fn on_response(&mut self, request_id: u64, response: SnapResponse) {
+        if let Some(tx) = self.inflight_requests.remove(&request_id) {
+            let _ = tx.send(Ok(response));
+        } else {
+            // unsolicited response
+            self.report_bad_message();
+        }
+    }
+
+    fn on_snap_message(&mut self, msg: SnapProtocolMessage) {
+        let peer_id = self.peer_id;
+        // requests that aren't served are answered as if the requested state is unavailable
+        let empty = empty_response(&msg);
+        match msg {
+            SnapProtocolMessage::GetAccountRange(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetAccountRange { peer_id, request, response },
+                SnapProtocolMessage::AccountRange,
+                empty,
+            ),
+            SnapProtocolMessage::GetStorageRanges(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetStorageRanges { peer_id, request, response },
+                SnapProtocolMessage::StorageRanges,
+                empty,
+            ),
+            SnapProtocolMessage::GetByteCodes(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetByteCodes { peer_id, request, response },
+                SnapProtocolMessage::ByteCodes,
+                empty,
+            ),
+            SnapProtocolMessage::GetTrieNodes(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetTrieNodes { peer_id, request, response },
+                SnapProtocolMessage::TrieNodes,
+                empty,
+            ),
+            SnapProtocolMessage::AccountRange(msg) => {
+                self.on_response(msg.request_id, SnapResponse::AccountRange(msg))
+            }
+            SnapProtocolMessage::StorageRanges(msg) => {
+                self.on_response(msg.request_id, SnapResponse::StorageRanges(msg))
+            }
+            SnapProtocolMessage::ByteCodes(msg) => {
+                self.on_response(msg.request_id, SnapResponse::ByteCodes(msg))
+            }
+            SnapProtocolMessage::TrieNodes(msg) => {
+                self.on_response(msg.request_id, SnapResponse::TrieNodes(msg))
+            }
+        }
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.terminated {
+            return Poll::Ready(None)
+        }
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(response) = response {
+                    return Poll::Ready(Some(BytesMut::from(response.encode().as_ref())))
+                }
+                continue
+            }
+
+            if let Poll::Ready(Some(request)) = this.outgoing_requests.poll_next_unpin(cx) {
+                let request = this.on_outgoing_request(request);
+                return Poll::Ready(Some(BytesMut::from(request.encode().as_ref())))
+            }
+
+            if let Poll::Ready(maybe_msg) = this.conn.poll_next_unpin(cx) {
+                let Some(next) = maybe_msg else { break };
+                let Some((&id, mut buf)) = next.split_first() else {
+                    this.report_bad_message();
+                    continue
+                };
+                match SnapProtocolMessage::decode(id, &mut buf) {
+                    Ok(msg) => {
+                        trace!(target: "net::snap", peer_id=%this.peer_id, id=?msg.message_id(), "received snap message");
+                        this.on_snap_message(msg);
+                    }
+                    Err(err) => {
+                        trace!(target: "net::snap", peer_id=%this.peer_id, %err, "failed to decode snap message");
+                        this.report_bad_message();
+                    }
+                }
+                continue
+            }
+
+            return Poll::Pending
+        }
+
+        // the connection was closed by the peer
+        this.terminated = true;
+        Poll::Ready(None)
+    }
+}
+
+impl Drop for SnapConnection {
+    fn drop(&mut self) {
+        self.snap_peers.remove(&self.peer_id, self.connection_id);
+        for (_, tx) in self.inflight_requests.drain() {
+            let _ = tx.send(Err(RequestError::ConnectionDropped));
+        }
+    }
+}
+
+/// Returns the empty response to the request, which signals that the state is unavailable.
+const fn empty_response(request: &SnapProtocolMessage) -> SnapProtocolMessage {
+    let request_id = request.request_id();
+    match request {
+        SnapProtocolMessage::GetStorageRanges(_) => {
+            SnapProtocolMessage::StorageRanges(StorageRangesMessage {
+                request_id,
+                slots: Vec::new(),
+                proof: Vec::new(),
+            })
+        }
+        SnapProtocolMessage::GetByteCodes(_) => {
+            SnapProtocolMessage::ByteCodes(ByteCodesMessage { request_id, codes: Vec::new() })
+        }
+        SnapProtocolMessage::GetTrieNodes(_) => {
+            SnapProtocolMessage::TrieNodes(TrieNodesMessage { request_id, nodes: Vec::new() })
+        }
+        _ => SnapProtocolMessage::AccountRange(AccountRangeMessage {
+            request_id,
+            accounts: Vec::new(),
+            proof: Vec::new(),
+        }),
+    }
+}
+
+type PendingSnapResponse = Pin<Box<dyn Future<Output = Option<SnapProtocolMessage>> + Send>>;
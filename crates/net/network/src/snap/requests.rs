@@ -0,0 +1,741 @@
+//! State snapshot serving for the `snap` protocol.
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, eth_requests::SOFT_RESPONSE_LIMIT,
+    metered_poll_nested_stream_with_budget, metrics::SnapRequestHandlerMetrics,
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Bytes, B256};
+use futures::StreamExt;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_eth_wire::snap::{
+    AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, SlimAccount, StorageData,
+    StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network_p2p::error::RequestResult;
+use reth_network_peers::PeerId;
+use reth_storage_api::{
+    errors::provider::ProviderResult, BlockNumReader, DBProvider, DatabaseProviderFactory,
+    HeaderProvider,
+};
+use reth_trie::{
+    proof::{Proof, ProofBlindedAccountProvider, ProofBlindedStorageProvider, StorageProof},
+    MultiProofTargets, Nibbles, StorageRoot,
+};
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseProof, DatabaseStorageRoot, DatabaseTrieCursorFactory,
+};
+use reth_trie_sparse::provider::TrieNodeProvider;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/snap/handler.go#L34-L57>
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// Requests are served from the latest persisted state: the hashed state tables, the
+/// intermediate trie tables and the bytecode table. Requests for any other state root than the one
+/// of the best block are answered with empty responses, as mandated by the protocol.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can access the database.
+    client: C,
+    /// Incoming requests from the `snap` sub-protocol connections.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            client,
+            incoming_requests: ReceiverStream::new(incoming),
+            metrics: Default::default(),
+        }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: DatabaseProviderFactory<Provider: BlockNumReader + HeaderProvider>,
+{
+    fn on_account_range_request(
+        &self,
+        peer_id: PeerId,
+        request: GetAccountRangeMessage,
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    ) {
+        self.metrics.snap_account_range_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let (accounts, proof) = self.get_account_range_response(request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, %err, "failed to serve account range");
+            Default::default()
+        });
+        let _ = response.send(Ok(AccountRangeMessage { request_id, accounts, proof }));
+    }
+
+    fn on_storage_ranges_request(
+        &self,
+        peer_id: PeerId,
+        request: GetStorageRangesMessage,
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    ) {
+        self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let (slots, proof) = self.get_storage_ranges_response(request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, %err, "failed to serve storage ranges");
+            Default::default()
+        });
+        let _ = response.send(Ok(StorageRangesMessage { request_id, slots, proof }));
+    }
+
+    fn on_byte_codes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetByteCodesMessage,
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    ) {
+        self.metrics.snap_byte_codes_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let codes = self.get_byte_codes_response(request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, %err, "failed to serve bytecodes");
+            Default::default()
+        });
+        let _ = response.send(Ok(ByteCodesMessage { request_id, codes }));
+    }
+
+    fn on_trie_nodes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetTrieNodesMessage,
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    ) {
+        self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let nodes = self.get_trie_nodes_response(request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", %peer_id, %err, "failed to serve trie nodes");
+            Default::default()
+        });
+        let _ = response.send(Ok(TrieNodesMessage { request_id, nodes }));
+    }
+
+    /// Returns the consecutive accounts starting at the requested origin and the boundary proofs
+    /// for the range.
+    fn get_account_range_response(
+        &self,
+        request: GetAccountRangeMessage,
+    ) -> ProviderResult<(Vec<AccountData>, Vec<Bytes>)> {
+        let GetAccountRangeMessage { root_hash, starting_hash, limit_hash, response_bytes, .. } =
+            request;
+
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        if !is_current_state_root(&provider, root_hash)? {
+            return Ok(Default::default())
+        }
+
+        let limit = response_limit(response_bytes);
+        let mut accounts = Vec::new();
+        let mut total_bytes = 0;
+
+        for entry in tx.cursor_read::<tables::HashedAccounts>()?.walk(Some(starting_hash))? {
+            let (hashed_address, account) = entry?;
+            let storage_root = StorageRoot::from_tx_hashed(tx, hashed_address)
+                .root()
+                .map_err(DatabaseError::from)?;
+            let body = Bytes::from(alloy_rlp::encode(SlimAccount::new(account, storage_root)));
+
+            total_bytes += B256::len_bytes() + body.len();
+            accounts.push(AccountData { hash: hashed_address, body });
+
+            if hashed_address >= limit_hash || total_bytes > limit {
+                break
+            }
+        }
+
+        // prove the origin and the last returned account, this also proves the absence of any
+        // accounts in between that weren't returned
+        let mut targets = MultiProofTargets::account(starting_hash);
+        if let Some(last) = accounts.last() {
+            targets.extend(MultiProofTargets::account(last.hash));
+        }
+        let proof = Proof::from_tx(tx).multiproof(targets)?;
+
+        Ok((accounts, proof_nodes(proof.account_subtree.into_nodes_sorted())))
+    }
+
+    /// Returns the storage slots of the requested accounts and the boundary proofs if the last
+    /// returned storage range is incomplete.
+    fn get_storage_ranges_response(
+        &self,
+        request: GetStorageRangesMessage,
+    ) -> ProviderResult<(Vec<Vec<StorageData>>, Vec<Bytes>)> {
+        let GetStorageRangesMessage {
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+            ..
+        } = request;
+
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        if !is_current_state_root(&provider, root_hash)? {
+            return Ok(Default::default())
+        }
+
+        let limit = response_limit(response_bytes);
+        let mut cursor = tx.cursor_dup_read::<tables::HashedStorages>()?;
+        let mut slots = Vec::new();
+        let mut proof = Vec::new();
+        let mut total_bytes = 0;
+
+        for (idx, hashed_address) in account_hashes.into_iter().enumerate() {
+            if total_bytes >= limit {
+                break
+            }
+
+            // only the first account may start at a custom origin
+            let origin = if idx == 0 { starting_hash } else { B256::ZERO };
+            // only the first account may end early, an unset limit is treated as unbounded
+            let range_limit = if idx == 0 && !limit_hash.is_zero() {
+                limit_hash
+            } else {
+                B256::repeat_byte(0xff)
+            };
+
+            let mut storage = Vec::new();
+            let mut aborted = false;
+            for entry in cursor.walk_dup(Some(hashed_address), Some(origin))? {
+                let (_, entry) = entry?;
+                if total_bytes >= limit {
+                    aborted = true;
+                    break
+                }
+
+                let data = Bytes::from(alloy_rlp::encode(entry.value));
+                total_bytes += B256::len_bytes() + data.len();
+                storage.push(StorageData { hash: entry.key, data });
+
+                if entry.key >= range_limit {
+                    break
+                }
+            }
+
+            let last = storage.last().map(|slot| slot.hash);
+            if !storage.is_empty() {
+                slots.push(storage);
+            }
+
+            // the storage range is incomplete, prove its boundaries and stop serving further
+            // accounts
+            if !origin.is_zero() || (aborted && last.is_some()) {
+                let targets = std::iter::once(origin).chain(last).collect();
+                let storage_proof = StorageProof::new_hashed(
+                    DatabaseTrieCursorFactory::new(tx),
+                    DatabaseHashedCursorFactory::new(tx),
+                    hashed_address,
+                )
+                .storage_multiproof(targets)?;
+                proof = proof_nodes(storage_proof.subtree.into_nodes_sorted());
+                break
+            }
+        }
+
+        Ok((slots, proof))
+    }
+
+    /// Returns the bytecodes for the requested code hashes.
+    fn get_byte_codes_response(&self, request: GetByteCodesMessage) -> ProviderResult<Vec<Bytes>> {
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+
+        let limit = response_limit(request.response_bytes);
+        let mut codes = Vec::new();
+        let mut total_bytes = 0;
+
+        for hash in request.hashes.into_iter().take(MAX_CODE_LOOKUPS) {
+            if let Some(code) = tx.get::<tables::Bytecodes>(hash)? {
+                let code = code.original_bytes();
+                total_bytes += code.len();
+                codes.push(code);
+
+                if total_bytes > limit {
+                    break
+                }
+            }
+        }
+
+        Ok(codes)
+    }
+
+    /// Returns the trie nodes for the requested paths.
+    ///
+    /// Nodes are not stored by path, they are re-derived from the intermediate trie tables in the
+    /// same way as for proofs.
+    fn get_trie_nodes_response(&self, request: GetTrieNodesMessage) -> ProviderResult<Vec<Bytes>> {
+        let GetTrieNodesMessage { root_hash, paths, response_bytes, .. } = request;
+
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        if !is_current_state_root(&provider, root_hash)? {
+            return Ok(Default::default())
+        }
+
+        let limit = response_limit(response_bytes);
+        let mut nodes = Vec::new();
+        let mut total_bytes = 0;
+
+        'paths: for path in paths {
+            if path.slot_paths.is_empty() {
+                let provider = ProofBlindedAccountProvider::new(
+                    DatabaseTrieCursorFactory::new(tx),
+                    DatabaseHashedCursorFactory::new(tx),
+                    Default::default(),
+                );
+                let node = provider
+                    .trie_node(&compact_to_nibbles(&path.account_path))
+                    .map_err(|err| DatabaseError::Other(err.to_string()))?;
+                let node = node.map(|node| node.node).unwrap_or_default();
+                total_bytes += node.len();
+                nodes.push(node);
+            } else {
+                let provider = ProofBlindedStorageProvider::new(
+                    DatabaseTrieCursorFactory::new(tx),
+                    DatabaseHashedCursorFactory::new(tx),
+                    Default::default(),
+                    B256::left_padding_from(&path.account_path),
+                );
+                for slot_path in path.slot_paths {
+                    let node = provider
+                        .trie_node(&compact_to_nibbles(&slot_path))
+                        .map_err(|err| DatabaseError::Other(err.to_string()))?;
+                    let node = node.map(|node| node.node).unwrap_or_default();
+                    total_bytes += node.len();
+                    nodes.push(node);
+
+                    if nodes.len() >= MAX_TRIE_NODE_LOOKUPS || total_bytes > limit {
+                        break 'paths
+                    }
+                }
+            }
+
+            if nodes.len() >= MAX_TRIE_NODE_LOOKUPS || total_bytes > limit {
+                break
+            }
+        }
+
+        Ok(nodes)
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: DatabaseProviderFactory<Provider: BlockNumReader + HeaderProvider> + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| {
+                match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_byte_codes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                }
+            },
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Returns the effective response byte limit for the requested soft limit.
+fn response_limit(response_bytes: u64) -> usize {
+    usize::try_from(response_bytes).unwrap_or(usize::MAX).min(SOFT_RESPONSE_LIMIT)
+}
+
+/// Returns `true` if the given root is the root of the state stored in the database.
+///
+/// The stored state is the state after the best block, so its root is the state root of the best
+/// block's header. The root is never computed from the state, which would be too expensive to do
+/// for every request.
+fn is_current_state_root<P>(provider: &P, root_hash: B256) -> ProviderResult<bool>
+where
+    P: BlockNumReader + HeaderProvider,
+{
+    let best_block = provider.best_block_number()?;
+    Ok(provider
+        .header_by_number(best_block)?
+        .is_some_and(|header| header.state_root() == root_hash))
+}
+
+/// Converts the retained proof nodes, sorted by path, into the list of RLP encoded trie nodes.
+fn proof_nodes(nodes: Vec<(Nibbles, Bytes)>) -> Vec<Bytes> {
+    nodes.into_iter().map(|(_, node)| node).collect()
+}
+
+/// Decodes a hex-prefix (compact) encoded trie path into nibbles.
+fn compact_to_nibbles(compact: &[u8]) -> Nibbles {
+    let nibbles = Nibbles::unpack(compact);
+    if nibbles.is_empty() {
+        return nibbles
+    }
+    // the first nibble carries the flags, an odd flag means the second nibble is part of the path
+    let start = if nibbles.get_unchecked(0) & 1 == 1 { 1 } else { 2 };
+    nibbles.slice(start.min(nibbles.len())..)
+}
+
+/// All `snap` requests delegated by the network.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the state trie.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The account range requested.
+        request: GetAccountRangeMessage,
+        /// The channel sender for the response containing the accounts and proofs.
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    },
+    /// Request storage slots of multiple accounts.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The storage ranges requested.
+        request: GetStorageRangesMessage,
+        /// The channel sender for the response containing the storage slots and proofs.
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    },
+    /// Request contract bytecodes by hash.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The bytecodes requested.
+        request: GetByteCodesMessage,
+        /// The channel sender for the response containing the bytecodes.
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    },
+    /// Request state trie nodes by path.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The trie nodes requested.
+        request: GetTrieNodesMessage,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{keccak256, U256};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_eth_wire::snap::TriePath;
+    use reth_primitives_traits::{Account, Bytecode, StorageEntry};
+    use reth_provider::{
+        providers::{ProviderFactory, StaticFileWriter},
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        StaticFileProviderFactory, StaticFileSegment, TrieWriter,
+    };
+    use reth_trie::StateRoot;
+    use reth_trie_db::DatabaseStateRoot;
+    use std::collections::BTreeMap;
+    use tokio::sync::mpsc;
+
+    /// Writes the hashed accounts and storages with their intermediate tries and, if
+    /// `with_header`, the header of the best block with the state root.
+    ///
+    /// Returns the state root.
+    fn seed_state(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        accounts: &BTreeMap<B256, Account>,
+        storages: &BTreeMap<B256, BTreeMap<B256, U256>>,
+        with_header: bool,
+    ) -> B256 {
+        let provider = factory.provider_rw().unwrap();
+        for (hashed_address, account) in accounts {
+            provider.tx_ref().put::<tables::HashedAccounts>(*hashed_address, *account).unwrap();
+        }
+        for (hashed_address, storage) in storages {
+            for (key, value) in storage {
+                let entry = StorageEntry { key: *key, value: *value };
+                provider.tx_ref().put::<tables::HashedStorages>(*hashed_address, entry).unwrap();
+            }
+        }
+        let (root, updates) = StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+        if with_header {
+            let header = Header { state_root: root, ..Default::default() };
+            let static_file_provider = provider.static_file_provider();
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+            writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+            writer.commit().unwrap();
+        }
+        provider.commit().unwrap();
+        root
+    }
+
+    fn accounts(num: u64) -> BTreeMap<B256, Account> {
+        (1..=num)
+            .map(|i| {
+                (keccak256(B256::from(U256::from(i))), Account { nonce: i, ..Default::default() })
+            })
+            .collect()
+    }
+
+    fn storage(num: u64) -> BTreeMap<B256, U256> {
+        (1..=num).map(|i| (keccak256(B256::from(U256::from(i))), U256::from(i))).collect()
+    }
+
+    fn handler(
+        factory: ProviderFactory<MockNodeTypesWithDB>,
+    ) -> SnapRequestHandler<ProviderFactory<MockNodeTypesWithDB>> {
+        let (_tx, rx) = mpsc::channel(1);
+        SnapRequestHandler::new(factory, rx)
+    }
+
+    #[test]
+    fn serves_account_range() {
+        let factory = create_test_provider_factory();
+        let accounts = accounts(100);
+        let root = seed_state(&factory, &accounts, &BTreeMap::new(), true);
+
+        let handler = handler(factory);
+        let request = |root_hash, response_bytes| GetAccountRangeMessage {
+            request_id: 1,
+            root_hash,
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes,
+        };
+
+        let (served, proof) = handler.get_account_range_response(request(root, u64::MAX)).unwrap();
+        assert!(served.iter().map(|account| account.hash).eq(accounts.keys().copied()));
+        assert_eq!(keccak256(&proof[0]), root);
+
+        // only a part of the range fits the budget
+        let (served, _) = handler.get_account_range_response(request(root, 1)).unwrap();
+        assert_eq!(served.len(), 1);
+
+        // unknown roots are not served
+        let (served, proof) =
+            handler.get_account_range_response(request(B256::ZERO, u64::MAX)).unwrap();
+        assert!(served.is_empty());
+        assert!(proof.is_empty());
+    }
+
+    #[test]
+    fn unavailable_without_best_header() {
+        let factory = create_test_provider_factory();
+        let root = seed_state(&factory, &accounts(100), &BTreeMap::new(), false);
+
+        let handler = handler(factory);
+        let request = GetAccountRangeMessage {
+            request_id: 1,
+            root_hash: root,
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: u64::MAX,
+        };
+        let (served, proof) = handler.get_account_range_response(request).unwrap();
+        assert!(served.is_empty());
+        assert!(proof.is_empty());
+    }
+
+    #[test]
+    fn serves_storage_ranges() {
+        let factory = create_test_provider_factory();
+        let accounts = accounts(2);
+        let mut addresses = accounts.keys().copied();
+        let (first, second) = (addresses.next().unwrap(), addresses.next().unwrap());
+        let storages = BTreeMap::from([(first, storage(50)), (second, storage(10))]);
+        let root = seed_state(&factory, &accounts, &storages, true);
+        let storage_root = StorageRoot::from_tx_hashed(factory.provider().unwrap().tx_ref(), first)
+            .root()
+            .unwrap();
+
+        let handler = handler(factory);
+        let request = |starting_hash, response_bytes| GetStorageRangesMessage {
+            request_id: 1,
+            root_hash: root,
+            account_hashes: vec![first, second],
+            starting_hash,
+            limit_hash: B256::ZERO,
+            response_bytes,
+        };
+
+        // complete ranges of both accounts don't need proofs
+        let (slots, proof) =
+            handler.get_storage_ranges_response(request(B256::ZERO, u64::MAX)).unwrap();
+        assert_eq!(slots.len(), 2);
+        assert!(slots[0].iter().map(|slot| slot.hash).eq(storages[&first].keys().copied()));
+        assert!(slots[1].iter().map(|slot| slot.hash).eq(storages[&second].keys().copied()));
+        assert!(proof.is_empty());
+
+        // an incomplete range of the first account is proven and ends the response
+        let (slots, proof) = handler.get_storage_ranges_response(request(B256::ZERO, 1)).unwrap();
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].len(), 1);
+        assert_eq!(keccak256(&proof[0]), storage_root);
+
+        // a custom origin is always proven
+        let origin = *storages[&first].keys().nth(10).unwrap();
+        let (slots, proof) =
+            handler.get_storage_ranges_response(request(origin, u64::MAX)).unwrap();
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].len(), 40);
+        assert_eq!(slots[0][0].hash, origin);
+        assert!(!proof.is_empty());
+    }
+
+    #[test]
+    fn serves_byte_codes() {
+        let factory = create_test_provider_factory();
+        let codes = (1..=3u8)
+            .map(|i| Bytecode::new_raw(Bytes::from(vec![0x60, i, 0x00])))
+            .collect::<Vec<_>>();
+        let provider = factory.provider_rw().unwrap();
+        for code in &codes {
+            provider.tx_ref().put::<tables::Bytecodes>(code.hash_slow(), code.clone()).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let handler = handler(factory);
+        let request =
+            |hashes, response_bytes| GetByteCodesMessage { request_id: 1, hashes, response_bytes };
+
+        // unknown codes are skipped
+        let hashes = vec![codes[0].hash_slow(), B256::ZERO, codes[2].hash_slow()];
+        let served = handler.get_byte_codes_response(request(hashes.clone(), u64::MAX)).unwrap();
+        assert_eq!(served, vec![codes[0].original_bytes(), codes[2].original_bytes()]);
+
+        // the code that exceeds the budget is the last one
+        let served = handler.get_byte_codes_response(request(hashes, 1)).unwrap();
+        assert_eq!(served, vec![codes[0].original_bytes()]);
+    }
+
+    #[test]
+    fn serves_trie_nodes() {
+        let factory = create_test_provider_factory();
+        let accounts = accounts(100);
+        let account = *accounts.keys().next().unwrap();
+        let storages = BTreeMap::from([(account, storage(50))]);
+        let root = seed_state(&factory, &accounts, &storages, true);
+        let storage_root =
+            StorageRoot::from_tx_hashed(factory.provider().unwrap().tx_ref(), account)
+                .root()
+                .unwrap();
+
+        let handler = handler(factory);
+        let request = |root_hash, paths| GetTrieNodesMessage {
+            request_id: 1,
+            root_hash,
+            paths,
+            response_bytes: u64::MAX,
+        };
+
+        // the root nodes of the account trie and the storage trie of the account
+        let paths = vec![
+            TriePath { account_path: Bytes::from_static(&[0x00]), slot_paths: vec![] },
+            TriePath {
+                account_path: Bytes::copy_from_slice(account.as_slice()),
+                slot_paths: vec![Bytes::from_static(&[0x00])],
+            },
+        ];
+        let nodes = handler.get_trie_nodes_response(request(root, paths.clone())).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(keccak256(&nodes[0]), root);
+        assert_eq!(keccak256(&nodes[1]), storage_root);
+
+        // unknown roots are not served
+        assert!(handler.get_trie_nodes_response(request(B256::ZERO, paths)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn decode_compact_paths() {
+        // even path
+        assert_eq!(compact_to_nibbles(&[0x00, 0x12]), Nibbles::from_nibbles([0x1, 0x2]));
+        // odd path
+        assert_eq!(compact_to_nibbles(&[0x11, 0x23]), Nibbles::from_nibbles([0x1, 0x2, 0x3]));
+        // root
+        assert_eq!(compact_to_nibbles(&[0x00]), Nibbles::default());
+        assert_eq!(compact_to_nibbles(&[]), Nibbles::default());
+    }
+
+    #[test]
+    fn caps_response_limit() {
+        assert_eq!(response_limit(1), 1);
+        assert_eq!(response_limit(u64::MAX), SOFT_RESPONSE_LIMIT);
+    }
+}
@@ -357,6 +357,14 @@ impl<N: NetworkPrimitives> NetworkState<N> {
                 self.ban_discovery(peer_id, ip_addr)
             }
             PeerAction::DiscoveryBanIp { ip_addr } => self.ban_ip_discovery(ip_addr),
+            PeerAction::DiscoveryBanTarget { target, until } => {
+                trace!(target: "net", %target, "Banning discovery");
+                self.discovery.ban_target(target, until)
+            }
+            PeerAction::DiscoveryUnbanTarget { target } => {
+                trace!(target: "net", %target, "Unbanning discovery");
+                self.discovery.unban_target(target)
+            }
             PeerAction::PeerAdded(peer_id) => {
                 self.queued_messages.push_back(StateAction::PeerAdded(peer_id))
             }
@@ -420,6 +428,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
             _ => None,
         };
 
+        // keep the peer's score in sync for eviction decisions
+        if let Some(score) = self.state_fetcher.peer_score(&peer) {
+            self.peers_manager.on_peer_score(&peer, score);
+        }
+
         if let Some(outcome) = outcome {
             self.on_block_response_outcome(outcome);
         }
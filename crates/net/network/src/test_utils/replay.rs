@@ -0,0 +1,185 @@
+//! Replays recorded traffic against a network, see [`Replay`].
+
+use crate::session::RecordedMessage;
+use alloy_eips::eip2124::ForkFilter;
+use futures::SinkExt;
+use reth_chainspec::{EthChainSpec, Hardforks, Head, MAINNET};
+use reth_ecies::{stream::ECIESStream, ECIESError};
+use reth_eth_wire::{
+    errors::{EthStreamError, P2PStreamError},
+    message::MessageError,
+    EthStream, EthVersion, HelloMessageWithProtocols, NetworkPrimitives, P2PStream,
+    UnauthedEthStream, UnauthedP2PStream, UnifiedStatus,
+};
+use reth_network_peers::{pk2id, PeerId};
+use secp256k1::{SecretKey, SECP256K1};
+use std::{io, net::SocketAddr};
+use tokio::net::TcpStream;
+
+/// The stream of the session a [`Replay`] sends its messages over.
+pub type ReplayStream<N> = EthStream<P2PStream<ECIESStream<TcpStream>>, N>;
+
+/// Replays the messages a node received from its peers against a network.
+///
+/// A recording of a [`TrafficRecorder`](crate::TrafficRecorder) contains the messages of all
+/// sessions of a node. The replay connects to the network under test as a new peer and sends the
+/// recorded _incoming_ messages in order over a single session, which turns a recording of a
+/// misbehaving peer into a reproducible test.
+///
+/// By default the replay authenticates with the genesis status of mainnet, which matches a
+/// [`Testnet`](crate::test_utils::Testnet) peer with the default provider.
+#[derive(Debug)]
+pub struct Replay {
+    /// The messages to send.
+    messages: Vec<RecordedMessage>,
+    /// The key of the replaying peer.
+    secret_key: SecretKey,
+    /// The status of the eth handshake.
+    status: UnifiedStatus,
+    /// The fork filter of the eth handshake.
+    fork_filter: ForkFilter,
+    /// Whether to wait between messages as long as recorded.
+    recorded_timing: bool,
+}
+
+// === impl Replay ===
+
+impl Replay {
+    /// Creates a replay of the incoming messages of the recording.
+    pub fn new(recording: impl IntoIterator<Item = RecordedMessage>) -> Self {
+        let head = Head {
+            hash: MAINNET.genesis_hash(),
+            number: 0,
+            timestamp: MAINNET.genesis().timestamp,
+            difficulty: MAINNET.genesis().difficulty,
+            total_difficulty: MAINNET.genesis().difficulty,
+        };
+        Self {
+            messages: recording.into_iter().filter(|msg| msg.direction.is_incoming()).collect(),
+            secret_key: SecretKey::new(&mut rand_08::thread_rng()),
+            status: UnifiedStatus::spec_builder(&*MAINNET, &head),
+            fork_filter: MAINNET.fork_filter(head),
+            recorded_timing: false,
+        }
+    }
+
+    /// Only replays the messages that were received from the given peer.
+    pub fn with_peer(mut self, peer_id: PeerId) -> Self {
+        self.messages.retain(|msg| msg.peer_id == peer_id);
+        self
+    }
+
+    /// Sets the key the replaying peer authenticates with.
+    pub const fn with_secret_key(mut self, secret_key: SecretKey) -> Self {
+        self.secret_key = secret_key;
+        self
+    }
+
+    /// Sets the status and fork filter of the eth handshake, e.g. for networks of another chain.
+    pub fn with_status(mut self, status: UnifiedStatus, fork_filter: ForkFilter) -> Self {
+        self.status = status;
+        self.fork_filter = fork_filter;
+        self
+    }
+
+    /// Waits between the messages as long as recorded, instead of sending them all at once.
+    pub const fn with_recorded_timing(mut self) -> Self {
+        self.recorded_timing = true;
+        self
+    }
+
+    /// Returns the messages that are replayed.
+    pub fn messages(&self) -> &[RecordedMessage] {
+        &self.messages
+    }
+
+    /// Returns the [`PeerId`] of the replaying peer.
+    pub fn peer_id(&self) -> PeerId {
+        pk2id(&self.secret_key.public_key(SECP256K1))
+    }
+
+    /// Connects to the node with the given address and id and sends all messages.
+    ///
+    /// The session uses the `eth` version of the first message, all messages must have been
+    /// recorded with that version. Returns the stream of the session, so the caller can await the
+    /// responses of the node.
+    pub async fn run<N: NetworkPrimitives>(
+        self,
+        addr: SocketAddr,
+        remote_peer_id: PeerId,
+    ) -> Result<ReplayStream<N>, ReplayError> {
+        let version = self.messages.first().map(|msg| msg.version).unwrap_or(EthVersion::LATEST);
+        if let Some((index, msg)) =
+            self.messages.iter().enumerate().find(|(_, msg)| msg.version != version)
+        {
+            return Err(ReplayError::VersionMismatch {
+                index,
+                version: msg.version,
+                expected: version,
+            })
+        }
+
+        let outgoing = TcpStream::connect(addr).await?;
+        let sink = ECIESStream::connect(outgoing, self.secret_key, remote_peer_id).await?;
+
+        let hello = HelloMessageWithProtocols::builder(self.peer_id()).protocol(version).build();
+        let (p2p_stream, _) = UnauthedP2PStream::new(sink).handshake(hello).await?;
+
+        let mut status = self.status;
+        status.set_eth_version(version);
+        let (mut stream, _) =
+            UnauthedEthStream::new(p2p_stream).handshake(status, self.fork_filter).await?;
+
+        let mut previous = None;
+        for (index, msg) in self.messages.iter().enumerate() {
+            let message =
+                msg.decode::<N>().map_err(|error| ReplayError::Decode { index, error })?;
+            if self.recorded_timing {
+                if let Some(elapsed) =
+                    previous.and_then(|previous| msg.timestamp.duration_since(previous).ok())
+                {
+                    tokio::time::sleep(elapsed).await;
+                }
+                previous = Some(msg.timestamp);
+            }
+            stream.send(message).await?;
+        }
+
+        Ok(stream)
+    }
+}
+
+/// Errors that can occur when replaying a recording.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// Failed to connect to the node.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to authenticate the connection.
+    #[error(transparent)]
+    Ecies(#[from] ECIESError),
+    /// The `p2p` handshake failed.
+    #[error(transparent)]
+    P2PStream(#[from] P2PStreamError),
+    /// The `eth` handshake failed or a message could not be sent.
+    #[error(transparent)]
+    EthStream(#[from] EthStreamError),
+    /// A message was recorded with another `eth` version than the session.
+    #[error("message {index} was recorded with {version}, but the session uses {expected}")]
+    VersionMismatch {
+        /// The index of the message.
+        index: usize,
+        /// The version of the message.
+        version: EthVersion,
+        /// The version of the session.
+        expected: EthVersion,
+    },
+    /// A recorded message could not be decoded.
+    #[error("failed to decode message {index}: {error}")]
+    Decode {
+        /// The index of the message.
+        index: usize,
+        /// The decoding error.
+        error: MessageError,
+    },
+}
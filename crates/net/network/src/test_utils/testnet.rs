@@ -10,7 +10,7 @@ use crate::{
         policy::NetworkPolicies,
         TransactionsHandle, TransactionsManager, TransactionsManagerConfig,
     },
-    NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
+    NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager, TrafficRecorderConfig,
 };
 use futures::{FutureExt, StreamExt};
 use pin_project::pin_project;
@@ -713,6 +713,13 @@ where
         Self { config, client, secret_key }
     }
 
+    /// Records the `eth` messages of the sessions of the peer, see
+    /// [`TrafficRecorder`](crate::TrafficRecorder).
+    pub fn with_traffic_recorder(mut self, config: TrafficRecorderConfig) -> Self {
+        self.config.traffic_recorder = Some(config);
+        self
+    }
+
     fn network_config_builder(secret_key: SecretKey) -> NetworkConfigBuilder {
         NetworkConfigBuilder::new(secret_key)
             .listener_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
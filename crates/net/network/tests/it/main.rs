@@ -3,8 +3,10 @@
 mod big_pooled_txs_req;
 mod connect;
 mod multiplex;
+mod replay;
 mod requests;
 mod session;
+mod snap;
 mod startup;
 mod transaction_hash_fetching;
 mod txgossip;
@@ -0,0 +1,74 @@
+//! Tests for recording and replaying network traffic
+
+use futures::StreamExt;
+use reth_eth_wire::{EthMessage, EthMessageID, EthNetworkPrimitives, HeadersDirection};
+use reth_network::{
+    read_recording,
+    test_utils::{NetworkEventStream, PeerConfig, Replay, Testnet},
+    BlockDownloaderProvider, NetworkEventListenerProvider, TrafficRecorderConfig,
+};
+use reth_network_api::{NetworkInfo, Peers};
+use reth_network_p2p::headers::client::{HeadersClient, HeadersRequest};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_recorded_requests() {
+    reth_tracing::init_test_tracing();
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut net = Testnet::create(1).await;
+    let config =
+        PeerConfig::default().with_traffic_recorder(TrafficRecorderConfig::new(dir.path()));
+    net.add_peer_with_config(config).await.unwrap();
+    net.for_each_mut(|peer| peer.install_request_handler());
+
+    let handle0 = net.peers()[0].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+    let handle1 = net.peers()[1].handle();
+    let _handle = net.spawn();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    let fetch0 = handle0.fetch_client().await.unwrap();
+    let request =
+        HeadersRequest { start: 0u64.into(), limit: 1, direction: HeadersDirection::Rising };
+    // the response is irrelevant, only the recorded request is replayed
+    let _ = fetch0.get_headers(request).await;
+
+    // the recorder writes in the background
+    let recording = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let recording = read_recording(dir.path())
+                .unwrap()
+                .into_iter()
+                .filter(|msg| msg.message_id() == Some(EthMessageID::GetBlockHeaders))
+                .collect::<Vec<_>>();
+            if !recording.is_empty() {
+                break recording
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("request should be recorded");
+    assert_eq!(recording[0].peer_id, *handle0.peer_id());
+    let EthMessage::GetBlockHeaders(recorded) =
+        recording[0].decode::<EthNetworkPrimitives>().unwrap()
+    else {
+        panic!("expected GetBlockHeaders")
+    };
+
+    // replaying the request as another peer is answered like the original request
+    let replay = Replay::new(recording).with_peer(*handle0.peer_id());
+    let mut stream =
+        replay.run::<EthNetworkPrimitives>(handle1.local_addr(), *handle1.peer_id()).await.unwrap();
+    loop {
+        // skip announcements of the node, e.g. block range updates
+        if let EthMessage::BlockHeaders(response) = stream.next().await.unwrap().unwrap() {
+            assert_eq!(response.request_id, recorded.request_id);
+            break
+        }
+    }
+}
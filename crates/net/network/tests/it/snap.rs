@@ -0,0 +1,155 @@
+//! Tests for serving the `snap` protocol
+
+use alloy_consensus::Header;
+use alloy_primitives::{keccak256, B256, U256};
+use futures::{stream::FuturesUnordered, StreamExt};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_eth_wire::snap::GetAccountRangeMessage;
+use reth_network::{
+    test_utils::NetworkEventStream, NetworkConfigBuilder, NetworkEventListenerProvider,
+    NetworkManager,
+};
+use reth_network_api::{NetworkInfo, Peers, SnapDownloaderProvider};
+use reth_network_p2p::{download::DownloadClient, snap::client::SnapClient};
+use reth_primitives_traits::Account;
+use reth_provider::{
+    noop::NoopProvider, providers::StaticFileWriter, test_utils::create_test_provider_factory,
+    StaticFileProviderFactory, StaticFileSegment, TrieWriter,
+};
+use reth_trie::StateRoot;
+use reth_trie_db::DatabaseStateRoot;
+use secp256k1::SecretKey;
+use std::{collections::BTreeMap, time::Duration};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_serve_account_range() {
+    reth_tracing::init_test_tracing();
+
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+    let accounts = (1..=10u64)
+        .map(|i| (keccak256(B256::from(U256::from(i))), Account { nonce: i, ..Default::default() }))
+        .collect::<BTreeMap<_, _>>();
+    for (hashed_address, account) in &accounts {
+        provider.tx_ref().put::<tables::HashedAccounts>(*hashed_address, *account).unwrap();
+    }
+    let (root, updates) = StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+    provider.write_trie_updates(&updates).unwrap();
+    // the state is served for the state root of the best block
+    let header = Header { state_root: root, ..Default::default() };
+    let static_file_provider = provider.static_file_provider();
+    let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+    writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+    writer.commit().unwrap();
+    provider.commit().unwrap();
+
+    // a node that serves its state
+    let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+    let config = NetworkConfigBuilder::eth(secret_key)
+        .listener_port(0)
+        .disable_discovery()
+        .build(NoopProvider::default());
+    let mut builder = NetworkManager::new(config).await.unwrap().into_builder();
+    let request_handler = builder.snap(Some(factory)).unwrap();
+    let (server, network, _, _) = builder.split_with_handle();
+    tokio::task::spawn(network);
+    tokio::task::spawn(request_handler);
+
+    // a node that only requests state
+    let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+    let config = NetworkConfigBuilder::eth(secret_key)
+        .listener_port(0)
+        .disable_discovery()
+        .build(NoopProvider::default());
+    let mut builder = NetworkManager::new(config).await.unwrap().into_builder();
+    assert!(builder.snap::<NoopProvider>(None).is_none());
+    let (client, network, _, _) = builder.split_with_handle();
+    tokio::task::spawn(network);
+
+    let mut events = NetworkEventStream::new(client.event_listener());
+    client.add_peer(*server.peer_id(), server.local_addr());
+    assert_eq!(events.next_session_established().await.unwrap(), *server.peer_id());
+
+    let snap_client = client.snap_client().unwrap();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while snap_client.num_connected_peers() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("snap connection was not established");
+
+    let request = GetAccountRangeMessage {
+        request_id: 0,
+        root_hash: root,
+        starting_hash: B256::ZERO,
+        limit_hash: B256::repeat_byte(0xff),
+        response_bytes: 512 * 1024,
+    };
+    let response = snap_client.get_account_range(request).await.unwrap();
+    assert_eq!(response.peer_id(), *server.peer_id());
+    let range = response.into_data().into_account_range().unwrap();
+    assert!(range.accounts.iter().map(|account| account.hash).eq(accounts.keys().copied()));
+    assert_eq!(keccak256(&range.proof[0]), root);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_empty_response_if_request_handler_is_busy() {
+    reth_tracing::init_test_tracing();
+
+    // a node that serves its state, but never handles the requests
+    let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+    let config = NetworkConfigBuilder::eth(secret_key)
+        .listener_port(0)
+        .disable_discovery()
+        .build(NoopProvider::default());
+    let mut builder = NetworkManager::new(config).await.unwrap().into_builder();
+    let _request_handler = builder.snap(Some(NoopProvider::default())).unwrap();
+    let (server, network, _, _) = builder.split_with_handle();
+    tokio::task::spawn(network);
+
+    let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+    let config = NetworkConfigBuilder::eth(secret_key)
+        .listener_port(0)
+        .disable_discovery()
+        .build(NoopProvider::default());
+    let mut builder = NetworkManager::new(config).await.unwrap().into_builder();
+    assert!(builder.snap::<NoopProvider>(None).is_none());
+    let (client, network, _, _) = builder.split_with_handle();
+    tokio::task::spawn(network);
+
+    let mut events = NetworkEventStream::new(client.event_listener());
+    client.add_peer(*server.peer_id(), server.local_addr());
+    assert_eq!(events.next_session_established().await.unwrap(), *server.peer_id());
+
+    let snap_client = client.snap_client().unwrap();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while snap_client.num_connected_peers() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("snap connection was not established");
+
+    // one request more than the channel to the request handler holds
+    let mut responses = (0..=256)
+        .map(|_| {
+            snap_client.get_account_range(GetAccountRangeMessage {
+                request_id: 0,
+                root_hash: B256::ZERO,
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 512 * 1024,
+            })
+        })
+        .collect::<FuturesUnordered<_>>();
+    let response = tokio::time::timeout(Duration::from_secs(10), responses.next())
+        .await
+        .expect("the request that didn't fit into the channel was not answered")
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.peer_id(), *server.peer_id());
+    let range = response.into_data().into_account_range().unwrap();
+    assert!(range.accounts.is_empty());
+    assert!(range.proof.is_empty());
+}
@@ -1,15 +1,67 @@
-use crate::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
+use crate::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    priority::Priority,
+};
 use futures::Future;
 use reth_eth_wire_types::snap::{
-    AccountRangeMessage, GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage,
-    GetTrieNodesMessage,
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
 };
+use reth_network_peers::PeerId;
+
+/// The response to a `snap` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapResponse {
+    /// Response to a [`GetAccountRangeMessage`].
+    AccountRange(AccountRangeMessage),
+    /// Response to a [`GetStorageRangesMessage`].
+    StorageRanges(StorageRangesMessage),
+    /// Response to a [`GetByteCodesMessage`].
+    ByteCodes(ByteCodesMessage),
+    /// Response to a [`GetTrieNodesMessage`].
+    TrieNodes(TrieNodesMessage),
+}
+
+impl SnapResponse {
+    /// Returns the account range response, if this is one.
+    pub fn into_account_range(self) -> Option<AccountRangeMessage> {
+        match self {
+            Self::AccountRange(msg) => Some(msg),
+            _ => None,
+        }
+    }
+
+    /// Returns the storage ranges response, if this is one.
+    pub fn into_storage_ranges(self) -> Option<StorageRangesMessage> {
+        match self {
+            Self::StorageRanges(msg) => Some(msg),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte codes response, if this is one.
+    pub fn into_byte_codes(self) -> Option<ByteCodesMessage> {
+        match self {
+            Self::ByteCodes(msg) => Some(msg),
+            _ => None,
+        }
+    }
+
+    /// Returns the trie nodes response, if this is one.
+    pub fn into_trie_nodes(self) -> Option<TrieNodesMessage> {
+        match self {
+            Self::TrieNodes(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 
 /// The snap sync downloader client
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait SnapClient: DownloadClient {
-    /// The output future type for account range requests
-    type Output: Future<Output = PeerRequestResult<AccountRangeMessage>> + Send + Sync + Unpin;
+    /// The output future type for snap requests
+    type Output: Future<Output = PeerRequestResult<SnapResponse>> + Send + Sync + Unpin;
 
     /// Sends the account range request to the p2p network and returns the account range
     /// response received from a peer.
@@ -27,7 +79,9 @@ pub trait SnapClient: DownloadClient {
 
     /// Sends the storage ranges request to the p2p network and returns the storage ranges
     /// response received from a peer.
-    fn get_storage_ranges(&self, request: GetStorageRangesMessage) -> Self::Output;
+    fn get_storage_ranges(&self, request: GetStorageRangesMessage) -> Self::Output {
+        self.get_storage_ranges_with_priority(request, Priority::Normal)
+    }
 
     /// Sends the storage ranges request to the p2p network with priority set and returns
     /// the storage ranges response received from a peer.
@@ -39,7 +93,9 @@ pub trait SnapClient: DownloadClient {
 
     /// Sends the byte codes request to the p2p network and returns the byte codes
     /// response received from a peer.
-    fn get_byte_codes(&self, request: GetByteCodesMessage) -> Self::Output;
+    fn get_byte_codes(&self, request: GetByteCodesMessage) -> Self::Output {
+        self.get_byte_codes_with_priority(request, Priority::Normal)
+    }
 
     /// Sends the byte codes request to the p2p network with priority set and returns
     /// the byte codes response received from a peer.
@@ -51,7 +107,9 @@ pub trait SnapClient: DownloadClient {
 
     /// Sends the trie nodes request to the p2p network and returns the trie nodes
     /// response received from a peer.
-    fn get_trie_nodes(&self, request: GetTrieNodesMessage) -> Self::Output;
+    fn get_trie_nodes(&self, request: GetTrieNodesMessage) -> Self::Output {
+        self.get_trie_nodes_with_priority(request, Priority::Normal)
+    }
 
     /// Sends the trie nodes request to the p2p network with priority set and returns
     /// the trie nodes response received from a peer.
@@ -61,3 +119,52 @@ pub trait SnapClient: DownloadClient {
         priority: Priority,
     ) -> Self::Output;
 }
+
+/// A [`SnapClient`] that has no peers and fails every request.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct NoopSnapClient;
+
+impl DownloadClient for NoopSnapClient {
+    fn report_bad_message(&self, _peer_id: PeerId) {}
+
+    fn num_connected_peers(&self) -> usize {
+        0
+    }
+}
+
+impl SnapClient for NoopSnapClient {
+    type Output = futures::future::Ready<PeerRequestResult<SnapResponse>>;
+
+    fn get_account_range_with_priority(
+        &self,
+        _request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        _request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        _request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        _request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::ready(Err(RequestError::UnsupportedCapability))
+    }
+}
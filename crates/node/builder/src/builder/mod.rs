@@ -821,15 +821,25 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         Node::Provider: BlockReaderFor<N>,
         Policy: TransactionPropagationPolicy + Debug,
     {
-        let (handle, network, txpool, eth) = builder
+        let mut builder = builder
             .transactions_with_policy(pool, tx_config, propagation_policy)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+            .request_handler(self.provider().clone());
+        let network_args = &self.config().network;
+        let snap = (network_args.serve_snap || network_args.snap_sync)
+            .then(|| builder.snap(network_args.serve_snap.then(|| self.provider().clone())))
+            .flatten();
+        let (handle, network, txpool, eth) = builder.split_with_handle();
 
         self.executor.spawn_critical("p2p txpool", Box::pin(txpool));
         self.executor.spawn_critical("p2p eth request handler", Box::pin(eth));
+        if let Some(snap) = snap {
+            self.executor.spawn_critical("p2p snap request handler", Box::pin(snap));
+        }
 
         let default_peers_path = self.config().datadir().known_peers();
+        let bans_file = self.config().network.persistent_bans_file(
+            self.config().network.peers_file.as_deref().unwrap_or(&default_peers_path),
+        );
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
@@ -847,6 +857,16 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
                             }
                         }
                     }
+                    if let Some(bans_file) = bans_file {
+                        match network.write_bans_to_file(bans_file.as_path()) {
+                            Ok(_) => {
+                                info!(target: "reth::cli", bans_file=?bans_file, "Wrote network bans to file");
+                            }
+                            Err(err) => {
+                                warn!(target: "reth::cli", %err, "Failed to write network bans to file");
+                            }
+                        }
+                    }
                 }))
             },
         );
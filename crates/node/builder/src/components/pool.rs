@@ -5,7 +5,7 @@ use reth_chain_state::CanonStateSubscriptions;
 use reth_node_api::TxTy;
 use reth_transaction_pool::{
     blobstore::DiskFileBlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit,
-    TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
+    TransactionOrdering, TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
 };
 use std::{collections::HashSet, future::Future};
 
@@ -145,15 +145,30 @@ where
             DiskFileBlobStore,
         >,
     > {
+        self.build_with_ordering_and_spawn_maintenance_task(
+            CoinbaseTipOrdering::default(),
+            blob_store,
+            pool_config,
+        )
+    }
+
+    /// Build the transaction pool with the given ordering and spawn its maintenance tasks.
+    pub fn build_with_ordering_and_spawn_maintenance_task<T>(
+        self,
+        ordering: T,
+        blob_store: DiskFileBlobStore,
+        pool_config: PoolConfig,
+    ) -> eyre::Result<
+        reth_transaction_pool::Pool<TransactionValidationTaskExecutor<V>, T, DiskFileBlobStore>,
+    >
+    where
+        T: TransactionOrdering<Transaction = V::Transaction>,
+    {
         // Destructure self to avoid partial move issues
         let TxPoolBuilder { ctx, validator, .. } = self;
 
-        let transaction_pool = reth_transaction_pool::Pool::new(
-            validator,
-            CoinbaseTipOrdering::default(),
-            blob_store,
-            pool_config.clone(),
-        );
+        let transaction_pool =
+            reth_transaction_pool::Pool::new(validator, ordering, blob_store, pool_config.clone());
 
         // Spawn maintenance tasks using standalone functions
         spawn_maintenance_tasks(ctx, transaction_pool.clone(), &pool_config)?;
@@ -195,6 +210,11 @@ where
     Node: FullNodeTypes,
     Pool: TransactionPool + Clone + 'static,
 {
+    // the snapshot of the pool includes the local transactions
+    if ctx.config().txpool.snapshot {
+        return Ok(())
+    }
+
     if !ctx.config().txpool.disable_transactions_backup {
         let data_dir = ctx.config().datadir();
         let transactions_path = ctx
@@ -223,6 +243,44 @@ where
     Ok(())
 }
 
+/// Spawn transaction pool snapshot task if enabled.
+fn spawn_pool_snapshot_task<Node, Pool>(
+    ctx: &BuilderContext<Node>,
+    pool: Pool,
+    pool_config: &PoolConfig,
+) -> eyre::Result<()>
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool + 'static,
+{
+    let txpool = &ctx.config().txpool;
+    if txpool.snapshot {
+        let snapshot_path = txpool
+            .snapshot_path
+            .clone()
+            .unwrap_or_else(|| ctx.config().datadir().txpool_snapshot());
+
+        let mut snapshot_config =
+            reth_transaction_pool::maintain::PoolSnapshotConfig::new(snapshot_path)
+                .with_max_age(pool_config.max_queued_lifetime);
+        if !txpool.snapshot_interval.is_zero() {
+            snapshot_config = snapshot_config.with_interval(txpool.snapshot_interval);
+        }
+
+        ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            "transaction pool snapshot task",
+            |shutdown| {
+                reth_transaction_pool::maintain::snapshot_pool_transactions_task(
+                    shutdown,
+                    pool,
+                    snapshot_config,
+                )
+            },
+        );
+    }
+    Ok(())
+}
+
 /// Spawn the main maintenance task for transaction pool.
 fn spawn_pool_maintenance_task<Node, Pool>(
     ctx: &BuilderContext<Node>,
@@ -255,7 +313,7 @@ where
     Ok(())
 }
 
-/// Spawn all maintenance tasks for a transaction pool (backup + main maintenance).
+/// Spawn all maintenance tasks for a transaction pool (backup + snapshot + main maintenance).
 fn spawn_maintenance_tasks<Node, Pool>(
     ctx: &BuilderContext<Node>,
     pool: Pool,
@@ -267,6 +325,7 @@ where
     Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
 {
     spawn_local_backup_task(ctx, pool.clone())?;
+    spawn_pool_snapshot_task(ctx, pool.clone(), pool_config)?;
     spawn_pool_maintenance_task(ctx, pool, pool_config)?;
     Ok(())
 }
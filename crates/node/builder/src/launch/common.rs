@@ -85,6 +85,7 @@ use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
+use reth_trie_db::RevertsCache;
 use std::{sync::Arc, thread::available_parallelism};
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedSender},
@@ -331,8 +332,9 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     /// This includes:
     /// - Making sure the ETL dir is set to the datadir
     /// - RPC settings are adjusted to the correct port
+    /// - RPC rate limits of the toml config are added to the ones of the CLI
     pub fn with_adjusted_configs(self) -> Self {
-        self.ensure_etl_datadir().with_adjusted_instance_ports()
+        self.ensure_etl_datadir().with_adjusted_instance_ports().with_merged_rpc_rate_limits()
     }
 
     /// Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
@@ -357,6 +359,13 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         self
     }
 
+    /// Adds the RPC rate limits of the toml config to the ones configured via CLI.
+    pub fn with_merged_rpc_rate_limits(mut self) -> Self {
+        let rate_limits = self.toml_config().rpc.rate_limit.clone();
+        self.node_config_mut().rpc.merge_rate_limit_config(rate_limits);
+        self
+    }
+
     /// Returns the container for all config types
     pub const fn configs(&self) -> &WithConfigs<ChainSpec> {
         self.attachment.left()
@@ -473,7 +482,15 @@ where
             StaticFileProvider::read_write(self.data_dir().static_files())?,
         )
         .with_prune_modes(self.prune_modes())
+        .with_log_index(self.toml_config().stages.index_logs.enabled)
+        .with_address_transactions_index(
+            self.toml_config().stages.index_address_transactions.enabled,
+        )
         .with_static_files_metrics();
+        let factory = match self.node_config().rpc.eth_proof_cache_size() {
+            Some(cache_size) => factory.with_reverts_cache(RevertsCache::new(cache_size)),
+            None => factory,
+        };
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
@@ -919,9 +936,19 @@ where
             .unwrap_or_default()
             .block_number;
 
+        // The index stages are optional and only part of the pipeline if enabled.
+        let stages_config = &self.toml_config().stages;
+        let optional_stages = [
+            stages_config.index_logs.enabled.then_some(StageId::IndexLogs),
+            stages_config
+                .index_address_transactions
+                .enabled
+                .then_some(StageId::IndexAddressTransactions),
+        ];
+
         // Skip the first stage as we've already retrieved it and comparing all other checkpoints
         // against it.
-        for stage_id in StageId::ALL.iter().skip(1) {
+        for stage_id in StageId::ALL.iter().skip(1).chain(optional_stages.iter().flatten()) {
             let stage_checkpoint = self
                 .blockchain_db()
                 .get_stage_checkpoint(*stage_id)?
@@ -1220,6 +1247,9 @@ mod tests {
                     bodies_distance: None,
                     receipts_log_filter: None,
                     bodies_before: None,
+                    address_transactions_full: false,
+                    address_transactions_distance: None,
+                    address_transactions_before: None,
                 },
                 ..NodeConfig::test()
             };
@@ -21,7 +21,7 @@ use reth_engine_tree::{
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
-use reth_network_api::BlockDownloaderProvider;
+use reth_network_api::{BlockDownloaderProvider, SnapDownloaderProvider};
 use reth_node_api::{
     BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
 };
@@ -148,6 +148,15 @@ where
 
         let consensus = Arc::new(ctx.components().consensus().clone());
 
+        let snap_client = if node_config.network.snap_sync {
+            if !ctx.prune_modes().has_receipts_pruning() {
+                eyre::bail!("snap sync requires receipts pruning")
+            }
+            network_handle.snap_client()
+        } else {
+            None
+        };
+
         let pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
@@ -161,6 +170,7 @@ where
             ctx.components().evm_config().clone(),
             maybe_exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty),
             ctx.era_import_source(),
+            snap_client,
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
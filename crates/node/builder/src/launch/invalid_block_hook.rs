@@ -56,6 +56,7 @@ where
 /// This function constructs the appropriate [`InvalidBlockHook`] based on the debug
 /// configuration in the node config. It supports:
 /// - Witness hooks for capturing block witness data
+/// - Prestate and opcode hooks for capturing prestate diffs and struct log traces
 /// - Healthy node verification via RPC
 ///
 /// # Arguments
@@ -83,7 +84,9 @@ where
     C: EthChainSpec + reth_chainspec::EthereumHardforks,
 {
     use reth_engine_primitives::{InvalidBlockHooks, NoopInvalidBlockHook};
-    use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+    use reth_invalid_block_hooks::{
+        InvalidBlockOpcodeHook, InvalidBlockPreStateHook, InvalidBlockWitnessHook,
+    };
 
     let Some(ref hook) = config.debug.invalid_block_hook else {
         return Ok(Box::new(NoopInvalidBlockHook::default()))
@@ -99,19 +102,30 @@ where
             let output_directory = output_directory.join(hook.to_string());
             std::fs::create_dir_all(&output_directory)?;
 
-            Ok(match hook {
+            let hook: Box<dyn InvalidBlockHook<_>> = match hook {
                 InvalidBlockHookType::Witness => Box::new(InvalidBlockWitnessHook::new(
                     provider.clone(),
                     evm_config.clone(),
                     output_directory,
                     healthy_node_rpc_client.clone(),
                 )),
-                InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
-                    eyre::bail!("invalid block hook {hook:?} is not implemented yet")
-                }
-            } as Box<dyn InvalidBlockHook<_>>)
+                InvalidBlockHookType::PreState => Box::new(InvalidBlockPreStateHook::new(
+                    provider.clone(),
+                    evm_config.clone(),
+                    output_directory,
+                    healthy_node_rpc_client.clone(),
+                )),
+                InvalidBlockHookType::Opcode => Box::new(InvalidBlockOpcodeHook::new(
+                    provider.clone(),
+                    evm_config.clone(),
+                    output_directory,
+                    healthy_node_rpc_client.clone(),
+                )),
+            };
+
+            Ok(hook)
         })
-        .collect::<Result<_, _>>()?;
+        .collect::<eyre::Result<_>>()?;
 
     Ok(Box::new(InvalidBlockHooks(hooks)))
 }
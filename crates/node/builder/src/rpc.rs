@@ -19,11 +19,11 @@ use reth_node_core::{
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
 use reth_rpc::eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_rpc_api::{eth::helpers::AddDevSigners, DebugApiServer, IntoEngineApiRpcModule};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
     TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
@@ -814,6 +814,18 @@ where
             registry.eth_api().with_dev_accounts();
         }
 
+        // `debug_setHead` rewinds the chain through the engine, so it's served by a debug API with
+        // the engine handle. Only this method is replaced, all other debug methods are kept as
+        // configured
+        let mut set_head =
+            registry.debug_api().with_engine_handle(beacon_engine_handle.clone()).into_rpc();
+        let other_methods =
+            set_head.method_names().filter(|name| *name != "debug_setHead").collect::<Vec<_>>();
+        for name in other_methods {
+            set_head.remove_method(name);
+        }
+        modules.replace_if_module_configured(RethRpcModule::Debug, set_head)?;
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -3,7 +3,7 @@
 use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use clap::Args;
@@ -32,13 +32,17 @@ use reth_network::{
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
     HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives, SessionsConfig,
+    TrafficRecorderConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
 use secp256k1::SecretKey;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::version::P2P_CLIENT_VERSION;
 
+/// The name of the file that stores the bans, next to the persistent peers file.
+pub const BANNED_PEERS_FILE_NAME: &str = "banned-peers.json";
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -88,7 +92,7 @@ pub struct NetworkArgs {
     #[arg(long, verbatim_doc_comment)]
     pub no_persist_peers: bool,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// NAT resolution method (any|none|upnp|natpmp|publicip|extip:\<IP\>)
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -161,6 +165,24 @@ pub struct NetworkArgs {
     /// The policy determines which peers transactions are gossiped to.
     #[arg(long = "tx-propagation-policy", default_value_t = TransactionPropagationKind::All)]
     pub tx_propagation_policy: TransactionPropagationKind,
+
+    /// Serve the `snap/1` protocol to peers.
+    ///
+    /// Requests are served from the latest persisted state.
+    #[arg(long = "serve-snap")]
+    pub serve_snap: bool,
+
+    /// Download the state of a recent block from `snap/1` peers instead of executing all blocks.
+    ///
+    /// Only applies to a node that has not executed any blocks yet. Requires receipts pruning.
+    #[arg(long = "snap-sync")]
+    pub snap_sync: bool,
+
+    /// Record the `eth` messages exchanged with peers to rotating files in the given directory.
+    ///
+    /// Recordings can be replayed against a test network to reproduce issues offline.
+    #[arg(long = "record-traffic", value_name = "DIR")]
+    pub record_traffic: Option<PathBuf>,
 }
 
 impl NetworkArgs {
@@ -237,6 +259,18 @@ impl NetworkArgs {
             .with_max_inbound_opt(self.max_inbound_peers)
             .with_max_outbound_opt(self.max_outbound_peers);
 
+        // Restore the bans that were persisted next to the peers file
+        let bans_file = self.persistent_bans_file(&peers_file);
+        let peers_config = match peers_config.clone().with_ban_list_from_file(bans_file.as_deref())
+        {
+            Ok(peers_config) => peers_config,
+            Err(err) => {
+                warn!(target: "reth::cli", %err, ?bans_file, "Failed to load saved bans");
+                peers_config
+            }
+        }
+        .with_persistent_bans_file(bans_file);
+
         // Configure basic network stack
         NetworkConfigBuilder::<N>::new(secret_key)
             .peer_config(config.peers_config_with_basic_nodes_from_file(
@@ -263,6 +297,10 @@ impl NetworkArgs {
                 let rlpx_socket = (addr, self.port).into();
                 self.discovery.apply_to_builder(builder, rlpx_socket, chain_bootnodes)
             })
+            .apply(|builder| match &self.record_traffic {
+                Some(dir) => builder.record_traffic(TrafficRecorderConfig::new(dir)),
+                None => builder,
+            })
             .listener_addr(SocketAddr::new(
                 addr, // set discovery port based on instance number
                 self.port,
@@ -279,6 +317,12 @@ impl NetworkArgs {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// If `no_persist_peers` is false then this returns the path to the persistent bans file,
+    /// which is stored next to the given peers file.
+    pub fn persistent_bans_file(&self, peers_file: &Path) -> Option<PathBuf> {
+        self.no_persist_peers.not().then(|| peers_file.with_file_name(BANNED_PEERS_FILE_NAME))
+    }
+
     /// Sets the p2p port to zero, to allow the OS to assign a random unused port when
     /// the network components bind to a socket.
     pub const fn with_unused_p2p_port(mut self) -> Self {
@@ -342,7 +386,10 @@ impl Default for NetworkArgs {
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
-            tx_propagation_policy: TransactionPropagationKind::default()
+            tx_propagation_policy: TransactionPropagationKind::default(),
+            serve_snap: false,
+            snap_sync: false,
+            record_traffic: None,
         }
     }
 }
@@ -103,6 +103,19 @@ pub struct PruningArgs {
     /// pruned.
     #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
     pub bodies_before: Option<BlockNumber>,
+
+    // Address Transactions
+    /// Prunes the whole address transactions index.
+    #[arg(long = "prune.addresstransactions.full", conflicts_with_all = &["address_transactions_distance", "address_transactions_before"])]
+    pub address_transactions_full: bool,
+    /// Prune the address transactions index before the `head-N` block number. In other words, keep
+    /// last N + 1 blocks.
+    #[arg(long = "prune.addresstransactions.distance", value_name = "BLOCKS", conflicts_with_all = &["address_transactions_full", "address_transactions_before"])]
+    pub address_transactions_distance: Option<u64>,
+    /// Prune the address transactions index before the specified block number. The specified
+    /// block number is not pruned.
+    #[arg(long = "prune.addresstransactions.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["address_transactions_full", "address_transactions_distance"])]
+    pub address_transactions_before: Option<BlockNumber>,
 }
 
 impl PruningArgs {
@@ -126,6 +139,7 @@ impl PruningArgs {
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     // TODO: set default to pre-merge block if available
                     bodies_history: None,
+                    address_transactions: None,
                     receipts_log_filter: Default::default(),
                 },
             }
@@ -153,6 +167,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.address_transactions_prune_mode() {
+            config.segments.address_transactions = Some(mode);
+        }
         if let Some(receipt_logs) =
             self.receipts_log_filter.as_ref().filter(|c| !c.is_empty()).cloned()
         {
@@ -246,6 +263,18 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn address_transactions_prune_mode(&self) -> Option<PruneMode> {
+        if self.address_transactions_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.address_transactions_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.address_transactions_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 /// Parses `,` separated pruning info into [`ReceiptsLogPruneConfig`].
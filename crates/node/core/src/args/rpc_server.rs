@@ -15,6 +15,7 @@ use clap::{
 };
 use rand::Rng;
 use reth_cli_util::parse_ether_value;
+use reth_config::config::{RpcRateLimitConfig, RpcRateLimitGroup, TokenBucketConfig};
 use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 
 use crate::args::{
@@ -215,6 +216,23 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// Serve historical proofs at any block of the available history, regardless of
+    /// `--rpc.eth-proof-window`.
+    ///
+    /// Proofs at blocks far from the tip are reconstructed from the changesets, which is slow and
+    /// memory intensive. See `--rpc.eth-proof-cache-size`.
+    #[arg(long = "rpc.eth-proof-archive", default_value_t = false)]
+    pub rpc_eth_proof_archive: bool,
+
+    /// Maximum size in megabytes of the historical state reverts that are cached to speed up
+    /// historical proofs. Only used if historical proofs are enabled, 0 disables the cache.
+    #[arg(
+        long = "rpc.eth-proof-cache-size",
+        value_name = "MB",
+        default_value_t = constants::DEFAULT_ETH_PROOF_CACHE_SIZE_MB
+    )]
+    pub rpc_eth_proof_cache_size: usize,
+
     /// Maximum number of concurrent getproof requests.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
@@ -224,6 +242,29 @@ pub struct RpcServerArgs {
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
     pub builder_disallow: Option<HashSet<Address>>,
 
+    /// Per client rate limit of a group of methods, in compute units per second and optionally
+    /// the largest burst, e.g. `trace_*,debug_*=200/400`.
+    ///
+    /// Can be repeated, a call only counts against the first group that matches its method. More
+    /// groups can be configured in the `[rpc.rate_limit]` section of the config file.
+    #[arg(long = "rpc.rate-limit", value_name = "METHODS=UNITS[/BURST]", value_parser = parse_rate_limit_group)]
+    pub rpc_rate_limits: Vec<RpcRateLimitGroup>,
+
+    /// Compute units charged for a call to a method, or to the methods of a prefix ending with
+    /// `*`, e.g. `eth_getLogs=50`.
+    #[arg(long = "rpc.compute-units", value_name = "METHOD=UNITS", value_parser = parse_compute_units)]
+    pub rpc_compute_units: Vec<(String, u64)>,
+
+    /// Compute units charged for a call to a method without configured compute units.
+    #[arg(long = "rpc.compute-units.default", value_name = "UNITS", default_value_t = 1)]
+    pub rpc_default_compute_units: u64,
+
+    /// Identify rate limited clients by the `X-Forwarded-For` or `X-Real-IP` header.
+    ///
+    /// Only enable this behind a reverse proxy that sets these headers.
+    #[arg(long = "rpc.rate-limit.trust-forwarded-for")]
+    pub rpc_rate_limit_trust_forwarded_for: bool,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -316,6 +357,61 @@ impl RpcServerArgs {
         self
     }
 
+    /// Returns the max number of blocks from the tip for historical proofs, which is unlimited if
+    /// `--rpc.eth-proof-archive` is set.
+    pub const fn eth_proof_window(&self) -> u64 {
+        if self.rpc_eth_proof_archive {
+            u64::MAX
+        } else {
+            self.rpc_eth_proof_window
+        }
+    }
+
+    /// Returns the max size in bytes of the cached historical state reverts, if historical proofs
+    /// are enabled and the cache is not disabled.
+    pub const fn eth_proof_cache_size(&self) -> Option<usize> {
+        if self.eth_proof_window() == 0 || self.rpc_eth_proof_cache_size == 0 {
+            None
+        } else {
+            Some(self.rpc_eth_proof_cache_size.saturating_mul(1024 * 1024))
+        }
+    }
+
+    /// Returns the rate limits of the RPC server.
+    pub fn rate_limit_config(&self) -> RpcRateLimitConfig {
+        RpcRateLimitConfig {
+            default_compute_units: self.rpc_default_compute_units,
+            trust_forwarded_for: self.rpc_rate_limit_trust_forwarded_for,
+            compute_units: self.rpc_compute_units.iter().cloned().collect(),
+            groups: self.rpc_rate_limits.clone(),
+        }
+    }
+
+    /// Merges the rate limits of the config file into the arguments.
+    ///
+    /// The arguments take precedence: groups of the config file are only matched after the groups
+    /// of the arguments, and compute units are only taken for methods that aren't set.
+    pub fn merge_rate_limit_config(&mut self, config: RpcRateLimitConfig) {
+        let RpcRateLimitConfig {
+            default_compute_units,
+            trust_forwarded_for,
+            compute_units,
+            groups,
+        } = config;
+
+        // only update if it's the default
+        if self.rpc_default_compute_units == 1 {
+            self.rpc_default_compute_units = default_compute_units;
+        }
+        self.rpc_rate_limit_trust_forwarded_for |= trust_forwarded_for;
+        for (method, units) in compute_units {
+            if !self.rpc_compute_units.iter().any(|(m, _)| *m == method) {
+                self.rpc_compute_units.push((method, units));
+            }
+        }
+        self.rpc_rate_limits.extend(groups);
+    }
+
     /// Configure all ports to be set to a random unused port when bound, and set the IPC path to a
     /// random path.
     pub fn with_unused_ports(mut self) -> Self {
@@ -363,10 +459,16 @@ impl Default for RpcServerArgs {
             rpc_tx_fee_cap: constants::DEFAULT_TX_FEE_CAP_WEI,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_eth_proof_archive: false,
+            rpc_eth_proof_cache_size: constants::DEFAULT_ETH_PROOF_CACHE_SIZE_MB,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
             builder_disallow: Default::default(),
+            rpc_rate_limits: Vec::new(),
+            rpc_compute_units: Vec::new(),
+            rpc_default_compute_units: 1,
+            rpc_rate_limit_trust_forwarded_for: false,
         }
     }
 }
@@ -403,6 +505,44 @@ impl TypedValueParser for RpcModuleSelectionValueParser {
     }
 }
 
+/// Parses a per client rate limit of a group of methods: `METHODS=UNITS[/BURST]`.
+///
+/// The burst defaults to one second worth of compute units.
+fn parse_rate_limit_group(value: &str) -> Result<RpcRateLimitGroup, String> {
+    let (methods, limit) =
+        value.split_once('=').ok_or_else(|| format!("expected METHODS=UNITS[/BURST]: {value}"))?;
+    let (refill, burst) = match limit.split_once('/') {
+        Some((refill, burst)) => (refill, Some(burst)),
+        None => (limit, None),
+    };
+    let refill_per_second =
+        refill.parse::<u64>().map_err(|err| format!("invalid compute units {refill}: {err}"))?;
+    let capacity = burst
+        .map(|burst| burst.parse::<u64>().map_err(|err| format!("invalid burst {burst}: {err}")))
+        .transpose()?
+        .unwrap_or(refill_per_second);
+    let methods = methods.split(',').map(|method| method.trim().to_string()).collect::<Vec<_>>();
+    if methods.iter().any(String::is_empty) {
+        return Err(format!("empty method in {value}"))
+    }
+
+    Ok(RpcRateLimitGroup {
+        name: methods.join(","),
+        methods,
+        global: None,
+        per_client: Some(TokenBucketConfig { refill_per_second, capacity }),
+    })
+}
+
+/// Parses the compute units of a method: `METHOD=UNITS`.
+fn parse_compute_units(value: &str) -> Result<(String, u64), String> {
+    let (method, units) =
+        value.split_once('=').ok_or_else(|| format!("expected METHOD=UNITS: {value}"))?;
+    let units =
+        units.parse::<u64>().map_err(|err| format!("invalid compute units {units}: {err}"))?;
+    Ok((method.trim().to_string(), units))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -455,6 +595,21 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_rpc_eth_proof_archive() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_proof_window(), 0);
+        assert_eq!(args.eth_proof_cache_size(), None);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.eth-proof-archive"]).args;
+        assert_eq!(args.eth_proof_window(), u64::MAX);
+        assert_eq!(
+            args.eth_proof_cache_size(),
+            Some(constants::DEFAULT_ETH_PROOF_CACHE_SIZE_MB * 1024 * 1024)
+        );
+    }
+
     #[test]
     fn test_rpc_tx_fee_cap_parse_integer() {
         let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.txfeecap", "2"]).args;
@@ -476,6 +631,65 @@ mod tests {
         assert_eq!(args.rpc_tx_fee_cap, 0); // 0 = no cap
     }
 
+    #[test]
+    fn test_rpc_rate_limit_args() {
+        let mut args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.rate-limit",
+            "trace_*,debug_*=200/400",
+            "--rpc.rate-limit",
+            "eth_getLogs=50",
+            "--rpc.compute-units",
+            "eth_getLogs=10",
+        ])
+        .args;
+
+        assert_eq!(
+            args.rpc_rate_limits[0],
+            RpcRateLimitGroup {
+                name: "trace_*,debug_*".to_string(),
+                methods: vec!["trace_*".to_string(), "debug_*".to_string()],
+                global: None,
+                per_client: Some(TokenBucketConfig { refill_per_second: 200, capacity: 400 }),
+            }
+        );
+        assert_eq!(
+            args.rpc_rate_limits[1].per_client,
+            Some(TokenBucketConfig { refill_per_second: 50, capacity: 50 })
+        );
+
+        args.merge_rate_limit_config(RpcRateLimitConfig {
+            default_compute_units: 5,
+            trust_forwarded_for: true,
+            compute_units: [("eth_getLogs".to_string(), 20), ("trace_*".to_string(), 100)].into(),
+            groups: vec![RpcRateLimitGroup { name: "eth".to_string(), ..Default::default() }],
+        });
+
+        let config = args.rate_limit_config();
+        assert_eq!(config.default_compute_units, 5);
+        assert!(config.trust_forwarded_for);
+        assert_eq!(config.compute_units.get("eth_getLogs"), Some(&10));
+        assert_eq!(config.compute_units.get("trace_*"), Some(&100));
+        assert_eq!(config.groups.len(), 3);
+        assert_eq!(config.groups[2].name, "eth");
+    }
+
+    #[test]
+    fn test_rpc_rate_limit_args_invalid() {
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.rate-limit",
+            "eth_call"
+        ])
+        .is_err());
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.rate-limit",
+            "eth_call=ten"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_rpc_tx_fee_cap_parse_none() {
         let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
@@ -50,4 +50,16 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The log index stage, if enabled.
+    ///
+    /// Indexes log addresses and topics for `eth_getLogs`.
+    IndexLogs,
+    /// The address transactions index stage, if enabled.
+    ///
+    /// Indexes the transactions of each address for `reth_getTransactionsByAddress`.
+    IndexAddressTransactions,
+    /// The S3 stage, if an `--s3-url` is given.
+    ///
+    /// Downloads the headers and bodies static files from an S3-compatible endpoint.
+    S3,
 }
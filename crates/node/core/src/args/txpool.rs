@@ -2,7 +2,7 @@
 
 use crate::cli::config::RethTransactionPoolConfig;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, MIN_PROTOCOL_BASE_FEE};
-use alloy_primitives::Address;
+use alloy_primitives::{Address, Selector};
 use clap::Args;
 use reth_cli_util::parse_duration_from_secs_or_ms;
 use reth_transaction_pool::{
@@ -10,13 +10,17 @@ use reth_transaction_pool::{
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    AdmissionRules, LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+    TransactionOrderingKind, DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+    MAX_EVICTED_TRANSACTIONS_HISTORY, MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
 use std::time::Duration;
 
+/// Default interval at which the transaction pool snapshot is written.
+const DEFAULT_TXPOOL_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(300);
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -132,6 +136,67 @@ pub struct TxPoolArgs {
         conflicts_with = "transactions_backup_path"
     )]
     pub disable_transactions_backup: bool,
+
+    /// Persists all transactions of the pool, including blob sidecars, to a snapshot that is
+    /// revalidated and restored on startup.
+    ///
+    /// This replaces the local transaction backup.
+    #[arg(long = "txpool.snapshot")]
+    pub snapshot: bool,
+
+    /// Path to store the transaction pool snapshot at.
+    #[arg(long = "txpool.snapshot-path", value_name = "PATH", requires = "snapshot")]
+    pub snapshot_path: Option<std::path::PathBuf>,
+
+    /// Interval at which the transaction pool snapshot is written, in addition to shutdown. A
+    /// value of 0 only writes the snapshot on shutdown.
+    #[arg(long = "txpool.snapshot-interval", value_parser = parse_duration_from_secs_or_ms, default_value = "300", value_name = "DURATION")]
+    pub snapshot_interval: Duration,
+
+    /// Max number of local transactions, usually received via RPC, in the pool.
+    #[arg(long = "txpool.max-local-txs")]
+    pub max_local_transactions: Option<usize>,
+
+    /// Max number of external transactions, usually received from peers, in the pool.
+    #[arg(long = "txpool.max-external-txs")]
+    pub max_external_transactions: Option<usize>,
+
+    /// Max number of private transactions in the pool.
+    #[arg(long = "txpool.max-private-txs")]
+    pub max_private_transactions: Option<usize>,
+
+    /// Max number of transactions per sender in the pool, including local transactions.
+    #[arg(long = "txpool.max-sender-txs")]
+    pub max_sender_transactions: Option<usize>,
+
+    /// Only admit transactions of these senders into the pool.
+    #[arg(long = "txpool.allowed-senders", value_delimiter = ',')]
+    pub allowed_senders: Vec<Address>,
+
+    /// Never admit transactions of these senders into the pool.
+    #[arg(long = "txpool.denied-senders", value_delimiter = ',')]
+    pub denied_senders: Vec<Address>,
+
+    /// Never admit transactions that call these contracts into the pool.
+    #[arg(long = "txpool.denied-call-targets", value_delimiter = ',')]
+    pub denied_call_targets: Vec<Address>,
+
+    /// Never admit transactions that call functions with these 4-byte selectors into the pool.
+    #[arg(long = "txpool.denied-selectors", value_delimiter = ',')]
+    pub denied_selectors: Vec<Selector>,
+
+    /// Max size of the calldata of transactions admitted into the pool, in bytes.
+    #[arg(long = "txpool.max-calldata-size")]
+    pub max_calldata_size: Option<usize>,
+
+    /// How to order the pending transactions of the pool: coinbase-tip, effective-gas-price
+    /// (including blob fees), sender-fair (by tip, round-robin across senders) or fifo.
+    #[arg(long = "txpool.ordering", default_value_t = TransactionOrderingKind::CoinbaseTip)]
+    pub ordering: TransactionOrderingKind,
+
+    /// Max number of recently evicted or replaced transactions that are kept for `txpool_evicted`.
+    #[arg(long = "txpool.max-evicted-history", default_value_t = MAX_EVICTED_TRANSACTIONS_HISTORY)]
+    pub max_evicted_history: usize,
 }
 
 impl Default for TxPoolArgs {
@@ -165,6 +230,20 @@ impl Default for TxPoolArgs {
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             transactions_backup_path: None,
             disable_transactions_backup: false,
+            snapshot: false,
+            snapshot_path: None,
+            snapshot_interval: DEFAULT_TXPOOL_SNAPSHOT_INTERVAL,
+            max_local_transactions: None,
+            max_external_transactions: None,
+            max_private_transactions: None,
+            max_sender_transactions: None,
+            allowed_senders: Default::default(),
+            denied_senders: Default::default(),
+            denied_call_targets: Default::default(),
+            denied_selectors: Default::default(),
+            max_calldata_size: None,
+            ordering: TransactionOrderingKind::default(),
+            max_evicted_history: MAX_EVICTED_TRANSACTIONS_HISTORY,
         }
     }
 }
@@ -207,6 +286,18 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            admission: AdmissionRules {
+                max_local_transactions: self.max_local_transactions,
+                max_external_transactions: self.max_external_transactions,
+                max_private_transactions: self.max_private_transactions,
+                max_sender_transactions: self.max_sender_transactions,
+                allowed_senders: self.allowed_senders.iter().copied().collect(),
+                denied_senders: self.denied_senders.iter().copied().collect(),
+                denied_call_targets: self.denied_call_targets.iter().copied().collect(),
+                denied_selectors: self.denied_selectors.iter().copied().collect(),
+                max_calldata_size: self.max_calldata_size,
+            },
+            max_evicted_transactions: self.max_evicted_history,
         }
     }
 }
@@ -260,4 +351,69 @@ mod tests {
 
         assert!(result.is_err(), "Expected an error for invalid duration");
     }
+
+    #[test]
+    fn txpool_parse_snapshot() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.snapshot",
+            "--txpool.snapshot-path",
+            "/tmp/txpool-snapshot.rlp",
+            "--txpool.snapshot-interval",
+            "60",
+        ])
+        .args;
+        assert!(args.snapshot);
+        assert_eq!(args.snapshot_path, Some("/tmp/txpool-snapshot.rlp".into()));
+        assert_eq!(args.snapshot_interval, Duration::from_secs(60));
+
+        // the path requires the snapshot to be enabled
+        let result = CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.snapshot-path",
+            "/tmp/txpool-snapshot.rlp",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn txpool_parse_admission_rules() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.max-external-txs",
+            "1000",
+            "--txpool.max-sender-txs",
+            "8",
+            "--txpool.denied-senders",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+            "--txpool.denied-selectors",
+            "0xa9059cbb",
+            "--txpool.max-calldata-size",
+            "4096",
+        ])
+        .args;
+
+        let admission = args.pool_config().admission;
+        assert_eq!(admission.max_local_transactions, None);
+        assert_eq!(admission.max_external_transactions, Some(1000));
+        assert_eq!(admission.max_sender_transactions, Some(8));
+        assert_eq!(
+            admission.denied_senders,
+            [Address::with_last_byte(1), Address::with_last_byte(2)].into()
+        );
+        assert_eq!(admission.denied_selectors, [Selector::from([0xa9, 0x05, 0x9c, 0xbb])].into());
+        assert_eq!(admission.max_calldata_size, Some(4096));
+    }
+
+    #[test]
+    fn txpool_parse_ordering() {
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.ordering", "sender-fair"])
+                .args;
+        assert_eq!(args.ordering, TransactionOrderingKind::SenderFair);
+
+        let result =
+            CommandParser::<TxPoolArgs>::try_parse_from(["reth", "--txpool.ordering", "invalid"]);
+        assert!(result.is_err());
+    }
 }
@@ -26,7 +26,7 @@ pub use error::{
 
 mod traits;
 pub use traits::{
-    BuildNextEnv, BuiltPayload, PayloadAttributes, PayloadAttributesBuilder,
+    BuildNextEnv, BuiltPayload, MinerConfig, PayloadAttributes, PayloadAttributesBuilder,
     PayloadBuilderAttributes,
 };
 
@@ -5,7 +5,7 @@ use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     eip7685::Requests,
 };
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_rpc_types_engine::{PayloadAttributes as EthPayloadAttributes, PayloadId};
 use core::fmt;
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
@@ -158,3 +158,15 @@ pub trait BuildNextEnv<Attributes, Header, Ctx>: Sized {
         ctx: &Ctx,
     ) -> Result<Self, PayloadBuilderError>;
 }
+
+/// Settings of a payload builder that can be changed while the node is running.
+///
+/// Used by the `miner_` api to update the extra data and the gas limit of the built blocks.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait MinerConfig: Send + Sync + fmt::Debug {
+    /// Sets the extra data to include in built blocks.
+    fn set_extra_data(&self, extra_data: Bytes);
+
+    /// Sets the gas limit to target.
+    fn set_gas_limit(&self, gas_limit: u64);
+}
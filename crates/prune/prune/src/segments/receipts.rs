@@ -15,9 +15,11 @@ use reth_provider::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment, SegmentOutput, SegmentOutputCheckpoint};
 use tracing::trace;
 
+/// Prunes the receipts of the input range, calling `on_pruned` with every deleted receipt.
 pub(crate) fn prune<Provider>(
     provider: &Provider,
     input: PruneInput,
+    mut on_pruned: impl FnMut(&<Provider::Primitives as NodePrimitives>::Receipt),
 ) -> Result<SegmentOutput, PrunerError>
 where
     Provider: DBProvider<Tx: DbTxMut>
@@ -43,7 +45,10 @@ where
         tx_range,
         &mut limiter,
         |_| false,
-        |row| last_pruned_transaction = row.0,
+        |(tx_num, receipt)| {
+            last_pruned_transaction = tx_num;
+            on_pruned(&receipt);
+        },
     )?;
     trace!(target: "pruner", %pruned, %done, "Pruned receipts");
 
@@ -81,23 +86,24 @@ pub(crate) fn save_checkpoint(
 
 #[cfg(test)]
 mod tests {
-    use crate::segments::{PruneInput, PruneLimiter, SegmentOutput};
-    use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use crate::segments::{PruneInput, PruneLimiter, Segment, SegmentOutput, UserReceipts};
+    use alloy_consensus::TxReceipt;
+    use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
     use assert_matches::assert_matches;
     use itertools::{
         FoldWhile::{Continue, Done},
         Itertools,
     };
-    use reth_db_api::tables;
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_db_api::{models::ShardedKey, tables, BlockNumberList};
+    use reth_provider::{DatabaseProviderFactory, LogIndexWriter, PruneCheckpointReader};
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::generators::{
-        self, random_block_range, random_receipt, BlockRangeParams,
+        self, random_block_range, random_log, random_receipt, BlockRangeParams,
     };
-    use std::ops::Sub;
+    use std::{collections::BTreeMap, ops::Sub};
 
     #[test]
     fn prune() {
@@ -167,7 +173,7 @@ mod tests {
                 .sub(1);
 
             let provider = db.factory.database_provider_rw().unwrap();
-            let result = super::prune(&provider, input).unwrap();
+            let result = super::prune(&provider, input, |_| {}).unwrap();
             limiter.increment_deleted_entries_count_by(result.pruned);
 
             assert_matches!(
@@ -223,4 +229,82 @@ mod tests {
         test_prune(6, (PruneProgress::Finished, 2));
         test_prune(10, (PruneProgress::Finished, 8));
     }
+
+    /// Merges the shards of each key of a log index table.
+    fn merge_shards<K: Ord>(
+        entries: Vec<(ShardedKey<K>, BlockNumberList)>,
+    ) -> BTreeMap<K, Vec<BlockNumber>> {
+        let mut index = BTreeMap::<_, Vec<_>>::new();
+        for (key, list) in entries {
+            index.entry(key.key).or_default().extend(list.iter());
+        }
+        index
+    }
+
+    #[test]
+    fn prune_log_index() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            1..=10,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // a handful of addresses, so that most of them are emitted below and above the pruned
+        // block
+        let mut receipts = Vec::new();
+        let mut block_receipts = Vec::new();
+        for block in &blocks {
+            let mut receipts_of_block = Vec::new();
+            for transaction in &block.body().transactions {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+                receipt.success = true;
+                for address in [receipts.len() % 4, (receipts.len() + 1) % 4] {
+                    let address = Address::with_last_byte(address as u8);
+                    receipt.logs.push(random_log(&mut rng, Some(address), Some(1)));
+                }
+                receipts.push((receipts.len() as u64, receipt.clone()));
+                receipts_of_block.push(receipt);
+            }
+            block_receipts.push((block.number, receipts_of_block));
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider
+            .insert_log_indices(block_receipts.iter().map(|(number, receipts)| {
+                (*number, receipts.iter().flat_map(|receipt| receipt.logs()))
+            }))
+            .unwrap();
+
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+        let result = UserReceipts::new(PruneMode::Before(6)).prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(5));
+        provider.commit().unwrap();
+
+        // the index only points to the blocks that still have receipts
+        let mut addresses = BTreeMap::<_, Vec<_>>::new();
+        let mut topics = BTreeMap::<_, Vec<_>>::new();
+        for (number, receipts) in block_receipts.iter().filter(|(number, _)| *number > 5) {
+            for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                let blocks = addresses.entry(log.address).or_default();
+                if blocks.last() != Some(number) {
+                    blocks.push(*number);
+                }
+                for topic in log.topics() {
+                    let blocks = topics.entry(*topic).or_default();
+                    if blocks.last() != Some(number) {
+                        blocks.push(*number);
+                    }
+                }
+            }
+        }
+        assert_eq!(merge_shards(db.table::<tables::LogAddressIndex>().unwrap()), addresses);
+        assert_eq!(merge_shards(db.table::<tables::LogTopicIndex>().unwrap()), topics);
+    }
 }
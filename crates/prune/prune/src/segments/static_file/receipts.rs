@@ -45,7 +45,8 @@ where
     }
 
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
-        crate::segments::receipts::prune(provider, input)
+        // The receipts are still available in static files, so the log index is left as is.
+        crate::segments::receipts::prune(provider, input, |_| {})
     }
 
     fn save_checkpoint(
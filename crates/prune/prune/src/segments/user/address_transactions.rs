@@ -0,0 +1,216 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use itertools::Itertools;
+use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
+use reth_provider::{transaction_addresses_by_tx_range, BlockReader, DBProvider};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use rustc_hash::FxHashMap;
+use tracing::{instrument, trace};
+
+#[derive(Debug)]
+pub struct AddressTransactions {
+    mode: PruneMode,
+}
+
+impl AddressTransactions {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for AddressTransactions
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockReader,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressTransactions
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let (start, end) = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address transactions to prune");
+                return Ok(SegmentOutput::done())
+            }
+        }
+        .into_inner();
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let tx_range = start..=
+            Some(end)
+                .min(limiter.deleted_entries_limit_left().map(|left| start + left as u64 - 1))
+                .unwrap();
+        let tx_range_end = *tx_range.end();
+
+        // The index doesn't store the addresses of a transaction, so they're derived from the
+        // transactions the same way the index was built.
+        let addresses = transaction_addresses_by_tx_range(provider, tx_range.clone())?;
+
+        // Number of transactions retrieved from the database should match the tx range count
+        let tx_count = tx_range.clone().count();
+        if addresses.len() != tx_count {
+            return Err(PrunerError::InconsistentData(
+                "Unexpected number of transactions retrieved by transaction number range",
+            ))
+        }
+
+        // Addresses with the highest transaction number to delete for each of them.
+        let mut highest_deleted_addresses = FxHashMap::default();
+        for (tx_number, addresses) in tx_range.zip(addresses) {
+            for address in addresses {
+                highest_deleted_addresses.insert(address, tx_number);
+            }
+        }
+
+        // Sort highest deleted transaction numbers by address and turn them into sharded keys.
+        let highest_sharded_keys = highest_deleted_addresses
+            .into_iter()
+            .sorted_unstable() // Unstable is fine because no equal keys exist in the map
+            .map(|(address, tx_number)| ShardedKey::new(address, tx_number));
+        let outcomes = prune_history_indices::<Provider, tables::AddressTransactions, _>(
+            provider,
+            highest_sharded_keys,
+            |a, b| a.key == b.key,
+        )?;
+
+        // Every transaction of the range has been removed from the index, which is what counts
+        // towards the limit, rather than the number of shards.
+        limiter.increment_deleted_entries_count_by(tx_count);
+
+        let done = tx_range_end == end;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned address transactions");
+
+        let last_pruned_block = provider
+            .transaction_block(tx_range_end)?
+            .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+            // If there's more transactions to prune, set the checkpoint block number to previous,
+            // so we could finish pruning its transactions on the next run.
+            .checked_sub(if done { 0 } else { 1 });
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: tx_count,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: Some(tx_range_end),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressTransactions, PruneInput, PruneLimiter, Segment, SegmentOutput};
+    use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use assert_matches::assert_matches;
+    use reth_db_api::tables;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_provider::{
+        transaction_addresses, AddressTransactionsWriter, DatabaseProviderFactory,
+        PruneCheckpointReader,
+    };
+    use reth_prune_types::{PruneInterruptReason, PruneMode, PruneProgress, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            1..=10,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let transactions = blocks
+            .iter()
+            .flat_map(|block| &block.body().transactions)
+            .enumerate()
+            .map(|(tx_number, transaction)| {
+                let sender = transaction.recover_signer().unwrap();
+                (tx_number as TxNumber, transaction_addresses(sender, transaction))
+            })
+            .collect::<Vec<_>>();
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.insert_address_transaction_indices(transactions.clone()).expect("insert index");
+        provider.commit().expect("commit");
+
+        let test_prune = |to_block: BlockNumber, expected_progress: PruneProgress| {
+            let prune_mode = PruneMode::Before(to_block);
+            let input = PruneInput {
+                previous_checkpoint: db
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::AddressTransactions)
+                    .unwrap(),
+                to_block,
+                limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+            };
+            let segment = AddressTransactions::new(prune_mode);
+
+            let provider = db.factory.database_provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            assert_matches!(
+                result,
+                SegmentOutput { progress, checkpoint: Some(_), .. } if progress == expected_progress
+            );
+
+            let checkpoint = result.checkpoint.unwrap();
+            segment.save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode)).unwrap();
+            provider.commit().expect("commit");
+
+            // Only the transactions after the last pruned one are left in the index
+            let last_pruned_transaction = checkpoint.tx_number.unwrap();
+            let mut expected = BTreeMap::<_, Vec<_>>::new();
+            for (tx_number, addresses) in &transactions {
+                if *tx_number > last_pruned_transaction {
+                    for address in addresses {
+                        let entry = expected.entry(*address).or_default();
+                        if entry.last() != Some(tx_number) {
+                            entry.push(*tx_number);
+                        }
+                    }
+                }
+            }
+
+            let mut actual = BTreeMap::<_, Vec<_>>::new();
+            for (key, list) in db.table::<tables::AddressTransactions>().unwrap() {
+                actual.entry(key.key).or_default().extend(list.iter());
+            }
+            assert_eq!(actual, expected);
+        };
+
+        // 12 transactions up to block 6, with a limit of 10 per run
+        test_prune(6, PruneProgress::HasMoreData(PruneInterruptReason::DeletedEntriesLimitReached));
+        test_prune(6, PruneProgress::Finished);
+        test_prune(10, PruneProgress::Finished);
+    }
+}
@@ -1,12 +1,16 @@
-use alloy_primitives::BlockNumber;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, Log, B256};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     models::ShardedKey,
     table::Table,
-    transaction::DbTxMut,
+    tables,
+    transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError, RawKey, RawTable, RawValue,
 };
 use reth_provider::DBProvider;
+use std::collections::BTreeSet;
+use tracing::trace;
 
 enum PruneShardOutcome {
     Deleted,
@@ -70,6 +74,66 @@ where
     Ok(outcomes)
 }
 
+/// Addresses and topics of the logs of pruned receipts.
+///
+/// If the node maintains a log index, these keys have to be pruned from
+/// [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`] together with the receipts.
+#[derive(Debug)]
+pub(crate) struct PrunedLogKeys {
+    /// Whether the node maintains a log index. Otherwise no keys are recorded.
+    enabled: bool,
+    addresses: BTreeSet<Address>,
+    topics: BTreeSet<B256>,
+}
+
+impl PrunedLogKeys {
+    /// Creates an empty set of keys, that only records them if the node maintains a log index.
+    pub(crate) fn new<Provider: DBProvider>(provider: &Provider) -> Result<Self, DatabaseError> {
+        Ok(Self {
+            enabled: provider.tx_ref().entries::<tables::LogAddressIndex>()? > 0,
+            addresses: BTreeSet::new(),
+            topics: BTreeSet::new(),
+        })
+    }
+
+    /// Records the keys of the logs of a pruned receipt.
+    pub(crate) fn insert(&mut self, receipt: &impl TxReceipt<Log = Log>) {
+        if self.enabled {
+            for log in receipt.logs() {
+                self.addresses.insert(log.address);
+                self.topics.extend(log.topics().iter().copied());
+            }
+        }
+    }
+
+    /// Prunes the recorded keys from the log index, up to and including the given block.
+    ///
+    /// Returns the number of deleted shards.
+    pub(crate) fn prune<Provider: DBProvider<Tx: DbTxMut>>(
+        self,
+        provider: &Provider,
+        to_block: BlockNumber,
+    ) -> Result<usize, DatabaseError> {
+        let outcomes = prune_history_indices::<Provider, tables::LogAddressIndex, _>(
+            provider,
+            self.addresses.into_iter().map(|address| ShardedKey::new(address, to_block)),
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, "Pruned log address index");
+        let mut deleted = outcomes.deleted;
+
+        let outcomes = prune_history_indices::<Provider, tables::LogTopicIndex, _>(
+            provider,
+            self.topics.into_iter().map(|topic| ShardedKey::new(topic, to_block)),
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, "Pruned log topic index");
+        deleted += outcomes.deleted;
+
+        Ok(deleted)
+    }
+}
+
 /// Prunes one shard of a history table.
 ///
 /// 1. If the shard has `highest_block_number` less than or equal to the target block number for
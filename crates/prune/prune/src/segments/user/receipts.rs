@@ -1,5 +1,5 @@
 use crate::{
-    segments::{PruneInput, Segment},
+    segments::{user::history::PrunedLogKeys, PruneInput, Segment},
     PrunerError,
 };
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -44,7 +44,16 @@ where
 
     #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
-        crate::segments::receipts::prune(provider, input)
+        // The log index can't point to blocks whose receipts are gone, so it's pruned with them.
+        let mut log_keys = PrunedLogKeys::new(provider)?;
+        let mut output =
+            crate::segments::receipts::prune(provider, input, |receipt| log_keys.insert(receipt))?;
+        if let Some(block_number) = output.checkpoint.and_then(|checkpoint| checkpoint.block_number)
+        {
+            output.pruned += log_keys.prune(provider, block_number)?;
+        }
+
+        Ok(output)
     }
 
     fn save_checkpoint(
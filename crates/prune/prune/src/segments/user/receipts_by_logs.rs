@@ -1,6 +1,6 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{PruneInput, Segment},
+    segments::{user::history::PrunedLogKeys, PruneInput, Segment},
     PrunerError,
 };
 use alloy_consensus::TxReceipt;
@@ -126,6 +126,8 @@ where
 
         let mut limiter = input.limiter;
 
+        let mut log_keys = PrunedLogKeys::new(provider)?;
+
         let mut done = true;
         let mut pruned = 0;
         let mut last_pruned_transaction = None;
@@ -165,7 +167,10 @@ where
                     }
                     skip
                 },
-                |row| last_pruned_transaction = Some(row.0),
+                |(tx_num, receipt)| {
+                    last_pruned_transaction = Some(tx_num);
+                    log_keys.insert(&receipt);
+                },
             )?;
 
             trace!(target: "pruner", %deleted, %done, ?block_range, "Pruned receipts");
@@ -210,15 +215,18 @@ where
             .lowest_block_with_distance(input.to_block, initial_last_pruned_block)?
             .unwrap_or(to_block);
 
+        let checkpoint_block = prune_mode_block.min(last_pruned_block.unwrap_or(u64::MAX));
         provider.save_prune_checkpoint(
             PruneSegment::ContractLogs,
             PruneCheckpoint {
-                block_number: Some(prune_mode_block.min(last_pruned_block.unwrap_or(u64::MAX))),
+                block_number: Some(checkpoint_block),
                 tx_number: last_pruned_transaction,
                 prune_mode: PruneMode::Before(prune_mode_block),
             },
         )?;
 
+        pruned += log_keys.prune(provider, checkpoint_block)?;
+
         let progress = limiter.progress(done);
 
         Ok(SegmentOutput { progress, pruned, checkpoint: None })
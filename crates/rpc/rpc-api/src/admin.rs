@@ -1,6 +1,20 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_net_banlist::{BanEntry, BanTarget};
 use reth_network_peers::{AnyNode, NodeRecord};
+use reth_network_types::PeerScore;
+use serde::{Deserialize, Serialize};
+
+/// The response of `admin_peers`: the info about a connected peer, extended by the score of its
+/// responses in the current session.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerInfoWithScore {
+    /// The info about the peer.
+    #[serde(flatten)]
+    pub info: PeerInfo,
+    /// The score of the peer's responses in the current session.
+    pub score: PeerScore,
+}
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -27,12 +41,33 @@ pub trait AdminApi {
     #[method(name = "removeTrustedPeer")]
     fn remove_trusted_peer(&self, record: AnyNode) -> RpcResult<bool>;
 
+    /// Bans a peer, an IP address or a CIDR range of IP addresses and disconnects the connected
+    /// peers that match the ban.
+    ///
+    /// The ban expires after `duration` seconds, or never if no duration is given. Returns false if
+    /// the target can't be banned, e.g. a local IP address.
+    #[method(name = "banPeer")]
+    async fn ban_peer(&self, target: BanTarget, duration: Option<u64>) -> RpcResult<bool>;
+
+    /// Lifts the ban of a peer, an IP address or a CIDR range.
+    ///
+    /// Returns true if the target was banned.
+    #[method(name = "unbanPeer")]
+    async fn unban_peer(&self, target: BanTarget) -> RpcResult<bool>;
+
+    /// Returns all active bans and when they expire.
+    #[method(name = "listBans")]
+    async fn list_bans(&self) -> RpcResult<Vec<BanEntry>>;
+
     /// The peers administrative property can be queried for all the information known about the
     /// connected remote nodes at the networking granularity. These include general information
     /// about the nodes themselves as participants of the devp2p P2P overlay protocol, as well as
-    /// specialized information added by each of the running application protocols
+    /// specialized information added by each of the running application protocols.
+    ///
+    /// Every peer is extended by the score of its responses in the current session, which is based
+    /// on the latency, throughput and validity of the responses.
     #[method(name = "peers")]
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+    async fn peers(&self) -> RpcResult<Vec<PeerInfoWithScore>>;
 
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
@@ -3,11 +3,12 @@ use alloy_genesis::ChainConfig;
 use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{Block, Bundle, StateContext};
+use alloy_rpc_types_eth::{Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::debug::{BadBlock, StateDump, StdTraceConfig, StorageRangeResult};
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
 
 /// Debug rpc interface.
@@ -38,7 +39,7 @@ pub trait DebugApi<TxReq: RpcObject> {
 
     /// Returns an array of recent bad blocks that the client has seen on the network.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>>;
 
     /// Returns the structured logs created during the execution of EVM between two blocks
     /// (excluding start) as a JSON object.
@@ -176,7 +177,7 @@ pub trait DebugApi<TxReq: RpcObject> {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StateDump>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -193,9 +194,12 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "chainConfig")]
     async fn debug_chain_config(&self) -> RpcResult<ChainConfig>;
 
-    /// Returns leveldb properties of the key-value database.
+    /// Returns MDBX statistics of the database.
+    ///
+    /// The `property` is either `mdbx.stats` for the statistics of the whole environment, or the
+    /// name of a table for the statistics of that table.
     #[method(name = "chaindbProperty")]
-    async fn debug_chaindb_property(&self, property: String) -> RpcResult<()>;
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String>;
 
     /// Returns the code associated with a given hash at the specified block ID.
     /// If no block ID is provided, it defaults to the latest block.
@@ -210,19 +214,31 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "cpuProfile")]
     async fn debug_cpu_profile(&self, file: String, seconds: u64) -> RpcResult<()>;
 
-    /// Retrieves an ancient binary blob from the freezer. The freezer is a collection of
-    /// append-only immutable files. The first argument `kind` specifies which table to look up data
-    /// from. The list of all table kinds are as follows:
+    /// Retrieves a raw binary blob from the static files, which are a collection of append-only
+    /// immutable files. The first argument `kind` specifies which data to look up:
+    ///  - `headers`: the header of the block `number`
+    ///  - `diffs`: the total difficulty of the block `number`
+    ///  - `hashes`: the hash of the block `number`
+    ///  - `transactions`: the RLP list of the transactions of the block `number`
+    ///  - `receipts`: the RLP list of the receipts of the block `number`
     #[method(name = "dbAncient")]
-    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<()>;
+    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<Option<Bytes>>;
 
-    /// Returns the number of ancient items in the ancient store.
+    /// Returns the number of blocks in the static files.
     #[method(name = "dbAncients")]
-    async fn debug_db_ancients(&self) -> RpcResult<()>;
+    async fn debug_db_ancients(&self) -> RpcResult<u64>;
 
-    /// Returns the raw value of a key stored in the database.
+    /// Returns the value of a key stored in the given database table, decoded the same way as by
+    /// `reth db get`.
+    ///
+    /// The key, and the subkey for dupsort tables, are JSON encoded.
     #[method(name = "dbGet")]
-    async fn debug_db_get(&self, key: String) -> RpcResult<()>;
+    async fn debug_db_get(
+        &self,
+        table: String,
+        key: String,
+        subkey: Option<String>,
+    ) -> RpcResult<Option<serde_json::Value>>;
 
     /// Retrieves the state that corresponds to the block number and returns a list of accounts
     /// (including storage and code).
@@ -260,17 +276,18 @@ pub trait DebugApi<TxReq: RpcObject> {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -283,7 +300,7 @@ pub trait DebugApi<TxReq: RpcObject> {
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -336,22 +353,24 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
+    /// about a block which has been rejected as invalid (for some reason).
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Replays the block and writes an EIP-3155 trace of every transaction to a local file.
+    ///
+    /// Returns the names of the files that were written.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>>;
 
     /// Turns on CPU profiling indefinitely, writing to the given file.
     #[method(name = "startCPUProfile")]
@@ -389,7 +408,7 @@ pub trait DebugApi<TxReq: RpcObject> {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -398,8 +417,8 @@ pub trait DebugApi<TxReq: RpcObject> {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
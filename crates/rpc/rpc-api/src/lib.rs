@@ -31,6 +31,8 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use admin::PeerInfoWithScore;
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -1,4 +1,6 @@
-use alloy_rpc_types_mev::{EthBundleHash, MevSendBundle, SimBundleOverrides, SimBundleResponse};
+use alloy_rpc_types_mev::{
+    EthBundleHash, EthCancelBundle, MevSendBundle, SimBundleOverrides, SimBundleResponse,
+};
 use jsonrpsee::proc_macros::rpc;
 
 /// Mev rpc interface.
@@ -21,12 +23,23 @@ pub trait MevSimApi {
 pub trait MevFullApi {
     /// Submitting bundles to the relay. It takes in a bundle and provides a bundle hash as a
     /// return value.
-    #[method(name = "sendBundle")]
+    ///
+    /// A bundle submitted with a replacement UUID replaces the bundle previously submitted with
+    /// the same UUID, and can be cancelled with `mev_cancelBundle`. Replacement UUIDs are scoped
+    /// to the signer of the request, so they require a request signed with the
+    /// `X-Flashbots-Signature` header.
+    #[method(name = "sendBundle", with_extensions)]
     async fn send_bundle(
         &self,
         request: MevSendBundle,
+        replacement_uuid: Option<String>,
     ) -> jsonrpsee::core::RpcResult<EthBundleHash>;
 
+    /// Cancels the bundle that the signer of the request submitted with the given replacement
+    /// UUID.
+    #[method(name = "cancelBundle", with_extensions)]
+    async fn cancel_bundle(&self, request: EthCancelBundle) -> jsonrpsee::core::RpcResult<()>;
+
     /// Similar to `mev_sendBundle` but instead of submitting a bundle to the relay, it returns
     /// a simulation result. Only fully matched bundles can be simulated.
     #[method(name = "simBundle")]
@@ -1,5 +1,5 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, TxHash, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use std::collections::HashMap;
 
@@ -17,6 +17,19 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns the hashes of all transactions sent by, sent to or creating the given address, in
+    /// ascending order.
+    ///
+    /// The range defaults to everything that's indexed up to the latest block. Requires the
+    /// address transactions index to be enabled.
+    #[method(name = "getTransactionsByAddress")]
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> RpcResult<Vec<TxHash>>;
+
     /// Subscribe to json `ChainNotifications`
     #[subscription(
         name = "subscribeChainNotifications",
@@ -1,7 +1,10 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::txpool::{
+    TxpoolEvictedTransaction, TxpoolSubPoolStats, TxpoolTransactionStatus,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +37,23 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns the sub-pool of the transaction with the given hash and the reasons it is not
+    /// pending, such as a nonce gap, a base fee that is too low or an insufficient balance.
+    ///
+    /// Returns `null` if the transaction is not in the pool.
+    #[method(name = "transactionStatus")]
+    async fn txpool_transaction_status(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TxpoolTransactionStatus>>;
+
+    /// Returns the transactions that were recently removed from the pool without being mined,
+    /// oldest first, together with the reason, e.g. that they were replaced or discarded.
+    #[method(name = "evicted")]
+    async fn txpool_evicted(&self) -> RpcResult<Vec<TxpoolEvictedTransaction>>;
+
+    /// Returns the number of transactions and their size in bytes of each sub-pool.
+    #[method(name = "subPoolStats")]
+    async fn txpool_sub_pool_stats(&self) -> RpcResult<TxpoolSubPoolStats>;
 }
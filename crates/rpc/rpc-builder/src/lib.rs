@@ -52,8 +52,8 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_storage_api::{
-    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, ProviderBlock,
-    StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReader, ChangeSetReader, FullRpcProvider,
+    ProviderBlock, StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
@@ -101,10 +101,19 @@ mod metrics;
 use crate::middleware::RethRpcMiddleware;
 pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
 use reth_chain_state::CanonStateSubscriptions;
+use reth_config::config::RpcRateLimitConfig;
 use reth_rpc::eth::sim_bundle::EthSimBundle;
 
 // Rpc rate limiter
 pub mod rate_limiter;
+use rate_limiter::{RpcClientIdLayer, RpcRateLimits};
+
+mod server;
+use server::RpcServer;
+
+// Rpc request signer
+pub mod request_signer;
+use request_signer::RpcRequestSignerLayer;
 
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
@@ -309,7 +318,8 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + AddressTransactionsReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -660,6 +670,7 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + AddressTransactionsReader
         + CanonStateSubscriptions,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiServer<
@@ -775,7 +786,8 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + AddressTransactionsReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -809,6 +821,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn debug_api(&self) -> DebugApi<EthApi> {
         DebugApi::new(self.eth_api().clone(), self.blocking_pool_guard.clone())
+            .with_max_modified_accounts_blocks(self.eth_config.max_blocks_per_filter)
     }
 
     /// Instantiates `NetApi`
@@ -837,7 +850,8 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + AddressTransactionsReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -927,6 +941,9 @@ where
                         }
                         RethRpcModule::Debug => {
                             DebugApi::new(eth_api.clone(), self.blocking_pool_guard.clone())
+                                .with_max_modified_accounts_blocks(
+                                    self.eth_config.max_blocks_per_filter,
+                                )
                                 .into_rpc()
                                 .into()
                         }
@@ -982,12 +999,14 @@ where
                         // implementation
                         // TODO: can we get rid of this here?
                         RethRpcModule::Flashbots => Default::default(),
-                        RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
-                        RethRpcModule::Mev => {
-                            EthSimBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
-                        }
+                        // the payload builder settings are only available for Ethereum and
+                        // configured in `EthereumAddOns`
+                        RethRpcModule::Miner => MinerApi::new(self.pool.clone()).into_rpc().into(),
+                        RethRpcModule::Mev => MevFullApiServer::into_rpc(EthSimBundle::new(
+                            eth_api.clone(),
+                            self.blocking_pool_guard.clone(),
+                        ))
+                        .into(),
                     })
                     .clone()
             })
@@ -1028,6 +1047,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Rate limits of the http and ws servers
+    rate_limits: Option<RpcRateLimits>,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
 }
@@ -1048,6 +1069,7 @@ impl Default for RpcServerConfig<Identity> {
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            rate_limits: None,
             rpc_middleware: Default::default(),
         }
     }
@@ -1112,6 +1134,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            rate_limits: self.rate_limits,
             rpc_middleware,
         }
     }
@@ -1191,6 +1214,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the rate limits of the http and ws servers.
+    ///
+    /// Rate limits are disabled if no group of the config has a limit.
+    pub fn with_rate_limits(mut self, config: RpcRateLimitConfig) -> Self {
+        self.rate_limits = config.is_enabled().then(|| RpcRateLimits::new(config));
+        self
+    }
+
     /// Configures a custom tokio runtime for the rpc server.
     pub fn with_tokio_runtime(mut self, tokio_runtime: tokio::runtime::Handle) -> Self {
         if let Some(http_server_config) = self.http_server_config {
@@ -1241,6 +1272,17 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`RpcClientIdLayer`] if rate limits are configured.
+    ///
+    /// Clients are identified by their JWT only if the server requires one.
+    fn maybe_client_id_layer(
+        rate_limits: Option<&RpcRateLimits>,
+        jwt_secret: Option<JwtSecret>,
+    ) -> Option<RpcClientIdLayer> {
+        rate_limits
+            .map(|rate_limits| RpcClientIdLayer::new(jwt_secret, rate_limits.trust_forwarded_for()))
+    }
+
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
     /// based on the client's `Accept-Encoding` header
     fn maybe_compression_layer(disable_compression: bool) -> Option<CompressionLayer> {
@@ -1314,6 +1356,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(Self::maybe_client_id_layer(
+                                self.rate_limits.as_ref(),
+                                self.jwt_secret,
+                            ))
+                            .layer(RpcRequestSignerLayer)
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
                             )),
@@ -1328,14 +1375,13 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
+                            .option_layer(self.rate_limits.clone())
                             .layer(self.rpc_middleware.clone()),
                     )
-                    .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
+                    .set_config(config.build());
+                let server = RpcServer::bind(server, http_socket_addr).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
                 let addr = server.local_addr().map_err(|err| {
                     RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                 })?;
@@ -1367,14 +1413,19 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_client_id_layer(
+                            self.rate_limits.as_ref(),
+                            self.jwt_secret,
+                        )),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .option_layer(self.rate_limits.clone())
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(ws_socket_addr)
+                );
+            let server = RpcServer::bind(server, ws_socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
@@ -1393,6 +1444,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_client_id_layer(
+                            self.rate_limits.as_ref(),
+                            self.jwt_secret,
+                        ))
+                        .layer(RpcRequestSignerLayer)
                         .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
                 )
                 .set_rpc_middleware(
@@ -1400,9 +1456,10 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         .layer(
                             modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
                         )
+                        .option_layer(self.rate_limits.clone())
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(http_socket_addr)
+                );
+            let server = RpcServer::bind(server, http_socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             let local_addr = server
@@ -1654,6 +1711,29 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Replace the given [`Methods`] in all configured transport modules if the given
+    /// [`RethRpcModule`] is configured for the transport.
+    ///
+    /// This can be used to swap out the implementation of a module after it was created.
+    pub fn replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.replace_ipc(other)?;
+        }
+
+        Ok(())
+    }
+
     /// Merge the given [Methods] in the configured http methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -2456,6 +2536,25 @@ mod tests {
         assert!(modules.ws.as_ref().unwrap().method("anything").is_some());
     }
 
+    #[test]
+    fn test_replace_if_module_configured() {
+        let mut modules = TransportRpcModules {
+            config: TransportRpcModuleConfig::set_http([RethRpcModule::Debug])
+                .with_ws([RethRpcModule::Eth]),
+            http: Some(create_test_module()),
+            ws: Some(create_test_module()),
+            ..Default::default()
+        };
+        let mut other_module = RpcModule::new(());
+        other_module.register_method("something", |_, _, _| "fails").unwrap();
+
+        modules.replace_if_module_configured(RethRpcModule::Debug, other_module).unwrap();
+
+        // only the transport that serves the module is updated
+        assert!(modules.http.as_ref().unwrap().method("something").is_some());
+        assert!(modules.ws.as_ref().unwrap().method("something").is_none());
+    }
+
     #[test]
     fn test_replace_configured() {
         let mut modules = TransportRpcModules {
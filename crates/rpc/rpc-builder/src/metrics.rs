@@ -5,7 +5,7 @@ use jsonrpsee::{
     MethodResponse, RpcModule,
 };
 use reth_metrics::{
-    metrics::{Counter, Histogram},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 use std::{
@@ -247,3 +247,19 @@ struct RpcServerCallMetrics {
     /// Response for a single call
     time_seconds: Histogram,
 }
+
+/// Metrics for the rate limited groups of RPC methods
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.rate_limit")]
+pub(crate) struct RpcRateLimitMetrics {
+    /// The number of calls that passed the rate limits
+    pub(crate) allowed_calls_total: Counter,
+    /// The number of calls rejected by the global rate limit
+    pub(crate) rejected_global_total: Counter,
+    /// The number of calls rejected by the rate limit of their client
+    pub(crate) rejected_per_client_total: Counter,
+    /// The number of compute units charged
+    pub(crate) compute_units_total: Counter,
+    /// The number of clients with a rate limit bucket
+    pub(crate) tracked_clients: Gauge,
+}
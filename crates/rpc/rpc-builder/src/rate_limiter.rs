@@ -1,15 +1,40 @@
-//! [`jsonrpsee`] helper layer for rate limiting certain methods.
+//! [`jsonrpsee`] helper layers for rate limiting certain methods.
 
-use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request};
+use crate::metrics::RpcRateLimitMetrics;
+use http::{header::AUTHORIZATION, HeaderMap};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification, ResponseFuture},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use parking_lot::Mutex;
+use reth_config::config::{RpcRateLimitConfig, RpcRateLimitGroup, TokenBucketConfig};
+use reth_rpc_layer::JwtSecret;
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
+    fmt,
     future::Future,
+    net::SocketAddr,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::PollSemaphore;
-use tower::Layer;
+use tower::{Layer, Service};
+
+/// Error code of calls that are rejected by [`RpcRateLimits`], which is the code for exceeded
+/// limits of [EIP-1474](https://eips.ethereum.org/EIPS/eip-1474).
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// Maximum number of clients tracked per group. Once reached, the bucket of the least recently
+/// seen client is dropped for a new client.
+const MAX_TRACKED_CLIENTS: u32 = 100_000;
 
 /// Rate limiter for the RPC server.
 ///
@@ -93,7 +118,7 @@ where
 
     fn notification<'a>(
         &self,
-        n: jsonrpsee::core::middleware::Notification<'a>,
+        n: Notification<'a>,
     ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
         self.inner.notification(n)
     }
@@ -130,3 +155,630 @@ impl<F: Future> Future for RateLimitingRequestFuture<F> {
         res
     }
 }
+
+/// Identifies the client of a call for per client rate limits.
+///
+/// This is set as a request extension by [`RpcClientIdLayer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RpcClientId(pub String);
+
+/// The remote address of the connection of a request.
+///
+/// This is set as a request extension by the http and ws servers of the
+/// [`RpcServerConfig`](crate::RpcServerConfig).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcRemoteAddr(pub SocketAddr);
+
+/// HTTP middleware that identifies the client of a request, see [`RpcClientId`].
+///
+/// Clients are identified by the `sub` claim of their JWT, by the `X-Forwarded-For` or
+/// `X-Real-IP` header set by a reverse proxy, or by the IP of the [`RpcRemoteAddr`] of the
+/// connection. Requests of clients that can't be identified don't get an [`RpcClientId`].
+#[derive(Debug, Clone, Copy)]
+pub struct RpcClientIdLayer {
+    /// The secret that the JWT of clients is validated with, if clients are identified by the
+    /// `sub` claim of their JWT.
+    jwt_secret: Option<JwtSecret>,
+    /// Whether clients are identified by the `X-Forwarded-For` or `X-Real-IP` header.
+    forwarded_for: bool,
+}
+
+impl RpcClientIdLayer {
+    /// Creates a new layer.
+    ///
+    /// If a `jwt_secret` is given, clients with a JWT that is valid for the secret are identified
+    /// by its `sub` claim.
+    pub const fn new(jwt_secret: Option<JwtSecret>, forwarded_for: bool) -> Self {
+        Self { jwt_secret, forwarded_for }
+    }
+
+    /// Returns the identity of the client that sent a request with the given headers over a
+    /// connection with the given remote address.
+    fn client_id(
+        &self,
+        headers: &HeaderMap,
+        remote_addr: Option<&RpcRemoteAddr>,
+    ) -> Option<RpcClientId> {
+        if let Some(secret) = &self.jwt_secret {
+            let subject = headers
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .and_then(|token| jwt_subject(secret, token));
+            if let Some(subject) = subject {
+                return Some(RpcClientId(format!("jwt:{subject}")))
+            }
+        }
+
+        if self.forwarded_for {
+            let ip = headers
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                // the first address is the one of the client, the others are the ones of proxies
+                .and_then(|value| value.split(',').next())
+                .or_else(|| headers.get("x-real-ip").and_then(|value| value.to_str().ok()))
+                .map(str::trim)
+                .filter(|ip| !ip.is_empty());
+            if let Some(ip) = ip {
+                return Some(RpcClientId(format!("ip:{ip}")))
+            }
+        }
+
+        remote_addr.map(|RpcRemoteAddr(addr)| RpcClientId(format!("ip:{}", addr.ip())))
+    }
+}
+
+impl<S> Layer<S> for RpcClientIdLayer {
+    type Service = RpcClientIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientIdService { inner, layer: *self }
+    }
+}
+
+/// HTTP service that sets the [`RpcClientId`] of requests.
+#[derive(Debug, Clone)]
+pub struct RpcClientIdService<S> {
+    inner: S,
+    layer: RpcClientIdLayer,
+}
+
+impl<S, B> Service<http::Request<B>> for RpcClientIdService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let client_id =
+            self.layer.client_id(req.headers(), req.extensions().get::<RpcRemoteAddr>());
+        if let Some(client_id) = client_id {
+            req.extensions_mut().insert(client_id);
+        }
+        self.inner.call(req)
+    }
+}
+
+/// Returns the `sub` claim of a JWT, if the JWT is valid for the secret.
+fn jwt_subject(secret: &JwtSecret, token: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct Claims {
+        sub: String,
+    }
+
+    // validates the signature and the `iat` claim the same way the auth layer does
+    secret.validate(token).ok()?;
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+    jsonwebtoken::decode::<Claims>(token, &DecodingKey::from_secret(secret.as_bytes()), &validation)
+        .ok()
+        .map(|data| data.claims.sub)
+}
+
+/// Token bucket rate limits of the RPC server, configured by [`RpcRateLimitConfig`].
+///
+/// Each call is charged the compute units of its method by the first group that matches the
+/// method. If the global bucket or the bucket of the client doesn't hold enough compute units, the
+/// call is rejected with a [`RATE_LIMIT_EXCEEDED_CODE`] error. Clients are identified by their
+/// [`RpcClientId`], all calls without one share a bucket.
+#[derive(Debug, Clone)]
+pub struct RpcRateLimits {
+    inner: Arc<RpcRateLimitsInner>,
+}
+
+impl RpcRateLimits {
+    /// Creates the rate limits of the given configuration.
+    pub fn new(config: RpcRateLimitConfig) -> Self {
+        let RpcRateLimitConfig {
+            default_compute_units,
+            trust_forwarded_for,
+            compute_units,
+            groups,
+        } = config;
+
+        let (prefixes, methods): (Vec<_>, Vec<_>) =
+            compute_units.into_iter().partition(|(method, _)| method.ends_with('*'));
+        let mut prefix_compute_units = prefixes
+            .into_iter()
+            .map(|(prefix, units)| (prefix.trim_end_matches('*').to_string(), units))
+            .collect::<Vec<_>>();
+        // longest prefix first
+        prefix_compute_units.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+        let now = Instant::now();
+        let groups = groups
+            .into_iter()
+            .map(|config| RateLimitGroup {
+                global: config.global.map(|global| Mutex::new(TokenBucket::new(global, now))),
+                clients: Mutex::new(LruMap::new(ByLength::new(MAX_TRACKED_CLIENTS))),
+                metrics: RpcRateLimitMetrics::new_with_labels(&[("group", config.name.clone())]),
+                config,
+            })
+            .collect();
+
+        Self {
+            inner: Arc::new(RpcRateLimitsInner {
+                default_compute_units,
+                trust_forwarded_for,
+                compute_units: methods.into_iter().collect(),
+                prefix_compute_units,
+                groups,
+            }),
+        }
+    }
+
+    /// Returns whether clients are identified by the `X-Forwarded-For` or `X-Real-IP` header.
+    pub fn trust_forwarded_for(&self) -> bool {
+        self.inner.trust_forwarded_for
+    }
+
+    /// Returns the compute units charged for a call to the method.
+    pub fn compute_units(&self, method: &str) -> u64 {
+        if let Some(units) = self.inner.compute_units.get(method) {
+            return *units
+        }
+        self.inner
+            .prefix_compute_units
+            .iter()
+            .find(|(prefix, _)| method.starts_with(prefix.as_str()))
+            .map(|(_, units)| *units)
+            .unwrap_or(self.inner.default_compute_units)
+    }
+
+    /// Charges a call to the method by the client, or returns the limit that rejects the call.
+    pub fn check(
+        &self,
+        method: &str,
+        client: Option<&RpcClientId>,
+    ) -> Result<(), RateLimitExceeded> {
+        self.check_at(method, client, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        method: &str,
+        client: Option<&RpcClientId>,
+        now: Instant,
+    ) -> Result<(), RateLimitExceeded> {
+        let Some(group) = self.inner.groups.iter().find(|group| group.config.matches(method))
+        else {
+            return Ok(())
+        };
+        let units = self.compute_units(method);
+        let exceeded = |limit, retry_after| RateLimitExceeded {
+            group: group.config.name.clone(),
+            limit,
+            compute_units: units,
+            retry_after,
+        };
+
+        // both buckets are checked before any of them is charged
+        let mut global = group.global.as_ref().map(|bucket| bucket.lock());
+        if let Some(bucket) = global.as_deref_mut() {
+            bucket.refill(now);
+            if let Err(retry_after) = bucket.check(units) {
+                group.metrics.rejected_global_total.increment(1);
+                return Err(exceeded(RateLimitKind::Global, retry_after))
+            }
+        }
+
+        let mut clients = group.clients.lock();
+        if let Some(config) = group.config.per_client {
+            let bucket = clients
+                .get_or_insert(client.cloned(), || TokenBucket::new(config, now))
+                .expect("the client limit is not zero");
+            bucket.refill(now);
+            if let Err(retry_after) = bucket.check(units) {
+                group.metrics.rejected_per_client_total.increment(1);
+                return Err(exceeded(RateLimitKind::PerClient, retry_after))
+            }
+            bucket.charge(units);
+        }
+        if let Some(bucket) = global.as_deref_mut() {
+            bucket.charge(units);
+        }
+
+        group.metrics.tracked_clients.set(clients.len() as f64);
+        group.metrics.allowed_calls_total.increment(1);
+        group.metrics.compute_units_total.increment(units);
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for RpcRateLimits {
+    type Service = RpcRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRateLimitService { inner, rate_limits: self.clone() }
+    }
+}
+
+#[derive(Debug)]
+struct RpcRateLimitsInner {
+    /// Compute units of methods without configured compute units.
+    default_compute_units: u64,
+    /// Whether clients are identified by the `X-Forwarded-For` or `X-Real-IP` header.
+    trust_forwarded_for: bool,
+    /// Compute units by method name.
+    compute_units: HashMap<String, u64>,
+    /// Compute units by method prefix, longest prefix first.
+    prefix_compute_units: Vec<(String, u64)>,
+    /// The rate limited groups.
+    groups: Vec<RateLimitGroup>,
+}
+
+/// The buckets of a rate limited group of methods.
+#[derive(Debug)]
+struct RateLimitGroup {
+    config: RpcRateLimitGroup,
+    /// Bucket shared by all clients.
+    global: Option<Mutex<TokenBucket>>,
+    /// Bucket of each client, up to [`MAX_TRACKED_CLIENTS`] recently seen clients.
+    clients: Mutex<LruMap<Option<RpcClientId>, TokenBucket, ByLength>>,
+    metrics: RpcRateLimitMetrics,
+}
+
+/// A bucket of compute units that is refilled at a constant rate.
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    config: TokenBucketConfig,
+    /// Compute units in the bucket.
+    units: f64,
+    /// Last time the bucket was refilled.
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    fn new(config: TokenBucketConfig, now: Instant) -> Self {
+        Self { config, units: config.capacity as f64, refilled_at: now }
+    }
+
+    /// Adds the compute units refilled since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.units = (self.units + elapsed * self.config.refill_per_second as f64)
+            .min(self.config.capacity as f64);
+        self.refilled_at = now;
+    }
+
+    /// Checks whether the bucket holds the compute units, or returns how long it takes until it
+    /// does. The wait time is `None` if the bucket can never hold them.
+    fn check(&self, units: u64) -> Result<(), Option<Duration>> {
+        let missing = units as f64 - self.units;
+        if missing <= 0.0 {
+            return Ok(())
+        }
+        if units > self.config.capacity || self.config.refill_per_second == 0 {
+            return Err(None)
+        }
+        Err(Some(Duration::from_secs_f64(missing / self.config.refill_per_second as f64)))
+    }
+
+    fn charge(&mut self, units: u64) {
+        self.units -= units as f64;
+    }
+}
+
+/// The limit of a group that rejected a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RateLimitKind {
+    /// The limit shared by all clients.
+    Global,
+    /// The limit of the client.
+    PerClient,
+}
+
+impl fmt::Display for RateLimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => f.write_str("global"),
+            Self::PerClient => f.write_str("per client"),
+        }
+    }
+}
+
+/// A call that was rejected by [`RpcRateLimits`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{limit} rate limit of {group} exceeded")]
+pub struct RateLimitExceeded {
+    /// Name of the group of the method.
+    pub group: String,
+    /// The limit that rejected the call.
+    pub limit: RateLimitKind,
+    /// Compute units of the call.
+    pub compute_units: u64,
+    /// How long it takes until the call is allowed, `None` if it never is.
+    pub retry_after: Option<Duration>,
+}
+
+impl From<RateLimitExceeded> for ErrorObject<'static> {
+    fn from(err: RateLimitExceeded) -> Self {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            group: String,
+            limit: RateLimitKind,
+            compute_units: u64,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            retry_after_ms: Option<u64>,
+        }
+
+        let message = err.to_string();
+        let RateLimitExceeded { group, limit, compute_units, retry_after } = err;
+        let data = Data {
+            group,
+            limit,
+            compute_units,
+            retry_after_ms: retry_after.map(|retry_after| retry_after.as_millis() as u64),
+        };
+        Self::owned(RATE_LIMIT_EXCEEDED_CODE, message, Some(data))
+    }
+}
+
+/// A [`RpcServiceT`] middleware that enforces [`RpcRateLimits`].
+#[derive(Debug, Clone)]
+pub struct RpcRateLimitService<S> {
+    inner: S,
+    rate_limits: RpcRateLimits,
+}
+
+impl<S> RpcServiceT for RpcRateLimitService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        match self.rate_limits.check(req.method_name(), req.extensions().get::<RpcClientId>()) {
+            Ok(()) => ResponseFuture::future(self.inner.call(req)),
+            Err(err) => ResponseFuture::ready(MethodResponse::error(req.id, err)),
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut batch: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // calls of a batch are charged individually, rejected calls are answered with an error
+        for entry in batch.iter_mut() {
+            let rejected = match entry {
+                Ok(BatchEntry::Call(req)) => self
+                    .rate_limits
+                    .check(req.method_name(), req.extensions().get::<RpcClientId>())
+                    .err()
+                    .map(|err| (req.id.clone(), err)),
+                _ => None,
+            };
+            if let Some((id, err)) = rejected {
+                *entry = Err(BatchEntryErr::new(id, err.into()));
+            }
+        }
+        self.inner.batch(batch)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn rate_limits() -> RpcRateLimits {
+        RpcRateLimits::new(RpcRateLimitConfig {
+            default_compute_units: 1,
+            trust_forwarded_for: false,
+            compute_units: [
+                ("trace_*".to_string(), 10),
+                ("trace_filter".to_string(), 50),
+                ("trace_block*".to_string(), 20),
+            ]
+            .into(),
+            groups: vec![
+                RpcRateLimitGroup {
+                    name: "trace".to_string(),
+                    methods: vec!["trace_*".to_string()],
+                    global: Some(TokenBucketConfig { refill_per_second: 100, capacity: 100 }),
+                    per_client: Some(TokenBucketConfig { refill_per_second: 20, capacity: 50 }),
+                },
+                RpcRateLimitGroup {
+                    name: "logs".to_string(),
+                    methods: vec!["eth_getLogs".to_string()],
+                    global: None,
+                    per_client: Some(TokenBucketConfig { refill_per_second: 1, capacity: 2 }),
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn compute_units() {
+        let rate_limits = rate_limits();
+        assert_eq!(rate_limits.compute_units("trace_filter"), 50);
+        assert_eq!(rate_limits.compute_units("trace_blockByNumber"), 20);
+        assert_eq!(rate_limits.compute_units("trace_call"), 10);
+        assert_eq!(rate_limits.compute_units("eth_call"), 1);
+    }
+
+    #[test]
+    fn per_client_limit() {
+        let rate_limits = rate_limits();
+        let now = Instant::now();
+        let alice = RpcClientId("alice".to_string());
+        let bob = RpcClientId("bob".to_string());
+
+        // methods without a group are never limited
+        for _ in 0..10 {
+            assert!(rate_limits.check_at("eth_call", Some(&alice), now).is_ok());
+        }
+
+        assert!(rate_limits.check_at("eth_getLogs", Some(&alice), now).is_ok());
+        assert!(rate_limits.check_at("eth_getLogs", Some(&alice), now).is_ok());
+        let err = rate_limits.check_at("eth_getLogs", Some(&alice), now).unwrap_err();
+        assert_eq!(
+            err,
+            RateLimitExceeded {
+                group: "logs".to_string(),
+                limit: RateLimitKind::PerClient,
+                compute_units: 1,
+                retry_after: Some(Duration::from_secs(1)),
+            }
+        );
+
+        // other clients and unidentified clients have their own buckets
+        assert!(rate_limits.check_at("eth_getLogs", Some(&bob), now).is_ok());
+        assert!(rate_limits.check_at("eth_getLogs", None, now).is_ok());
+
+        // the bucket is refilled over time
+        let later = now + Duration::from_secs(1);
+        assert!(rate_limits.check_at("eth_getLogs", Some(&alice), later).is_ok());
+        assert!(rate_limits.check_at("eth_getLogs", Some(&alice), later).is_err());
+    }
+
+    #[test]
+    fn global_limit() {
+        let rate_limits = rate_limits();
+        let now = Instant::now();
+        let clients = (0..3).map(|i| RpcClientId(i.to_string())).collect::<Vec<_>>();
+
+        // 2 * 50 compute units drain the global bucket
+        assert!(rate_limits.check_at("trace_filter", Some(&clients[0]), now).is_ok());
+        assert!(rate_limits.check_at("trace_filter", Some(&clients[1]), now).is_ok());
+        let err = rate_limits.check_at("trace_call", Some(&clients[2]), now).unwrap_err();
+        assert_eq!(err.limit, RateLimitKind::Global);
+        assert_eq!(err.retry_after, Some(Duration::from_millis(100)));
+
+        // a call rejected by the limit of its client doesn't charge the global bucket
+        let later = now + Duration::from_millis(500);
+        let err = rate_limits.check_at("trace_filter", Some(&clients[0]), later).unwrap_err();
+        assert_eq!(err.limit, RateLimitKind::PerClient);
+        assert!(rate_limits.check_at("trace_filter", Some(&clients[2]), later).is_ok());
+    }
+
+    #[test]
+    fn tracked_clients_limit() {
+        let rate_limits = rate_limits();
+        let now = Instant::now();
+
+        // every client charges its bucket, so none of the buckets is full
+        for i in 0..MAX_TRACKED_CLIENTS + 10 {
+            let client = RpcClientId(i.to_string());
+            assert!(rate_limits.check_at("eth_getLogs", Some(&client), now).is_ok());
+        }
+
+        // the buckets of the least recently seen clients are dropped
+        let clients = rate_limits.inner.groups[1].clients.lock();
+        assert_eq!(clients.len(), MAX_TRACKED_CLIENTS as usize);
+        assert!(clients.peek(&Some(RpcClientId(0.to_string()))).is_none());
+        assert!(clients.peek(&Some(RpcClientId(MAX_TRACKED_CLIENTS.to_string()))).is_some());
+    }
+
+    #[test]
+    fn call_above_capacity() {
+        let rate_limits = RpcRateLimits::new(RpcRateLimitConfig {
+            default_compute_units: 10,
+            groups: vec![RpcRateLimitGroup {
+                name: "all".to_string(),
+                methods: vec!["*".to_string()],
+                global: None,
+                per_client: Some(TokenBucketConfig { refill_per_second: 5, capacity: 5 }),
+            }],
+            ..Default::default()
+        });
+        let err = rate_limits.check_at("eth_call", None, Instant::now()).unwrap_err();
+        assert_eq!(err.retry_after, None);
+
+        let err = ErrorObject::from(err);
+        assert_eq!(err.code(), RATE_LIMIT_EXCEEDED_CODE);
+        assert_eq!(
+            err.data().unwrap().get(),
+            r#"{"group":"all","limit":"perClient","computeUnits":10}"#
+        );
+    }
+
+    #[test]
+    fn client_id() {
+        let remote_addr = RpcRemoteAddr(SocketAddr::from(([10, 0, 0, 2], 4321)));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.1".parse().unwrap());
+        assert_eq!(RpcClientIdLayer::new(None, false).client_id(&headers, None), None);
+        assert_eq!(
+            RpcClientIdLayer::new(None, false).client_id(&headers, Some(&remote_addr)),
+            Some(RpcClientId("ip:10.0.0.2".to_string()))
+        );
+        assert_eq!(
+            RpcClientIdLayer::new(None, true).client_id(&headers, Some(&remote_addr)),
+            Some(RpcClientId("ip:1.2.3.4".to_string()))
+        );
+
+        #[derive(Serialize)]
+        struct Claims {
+            sub: &'static str,
+            iat: u64,
+        }
+        let token = |secret: &JwtSecret| {
+            let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            jsonwebtoken::encode(
+                &Header::new(Algorithm::HS256),
+                &Claims { sub: "alice", iat },
+                &EncodingKey::from_secret(secret.as_bytes()),
+            )
+            .unwrap()
+        };
+        let secret = JwtSecret::random();
+        let layer = RpcClientIdLayer::new(Some(secret), true);
+
+        // the subject of a JWT signed with another secret is ignored
+        let other_token = token(&JwtSecret::random());
+        headers.insert(AUTHORIZATION, format!("Bearer {other_token}").parse().unwrap());
+        assert_eq!(
+            layer.client_id(&headers, Some(&remote_addr)),
+            Some(RpcClientId("ip:1.2.3.4".to_string()))
+        );
+
+        headers.insert(AUTHORIZATION, format!("Bearer {}", token(&secret)).parse().unwrap());
+        assert_eq!(
+            layer.client_id(&headers, Some(&remote_addr)),
+            Some(RpcClientId("jwt:alice".to_string()))
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! HTTP middleware that authenticates the signer of signed requests.
+
+use alloy_primitives::{hex, keccak256, Address, Signature};
+use http::{HeaderValue, Response, StatusCode};
+use http_body_util::{BodyExt, Full, Limited};
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use reth_rpc_server_types::{RpcRequestSigner, FLASHBOTS_SIGNATURE_HEADER};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// Maximum size of the body of a signed request, which is buffered to verify its signature.
+const MAX_SIGNED_REQUEST_SIZE: usize = 15 * 1024 * 1024;
+
+/// HTTP middleware that authenticates the signer of requests with a
+/// [`FLASHBOTS_SIGNATURE_HEADER`], see [`RpcRequestSigner`].
+///
+/// Requests with an invalid signature are rejected, requests without a signature are passed on
+/// without an [`RpcRequestSigner`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpcRequestSignerLayer;
+
+impl<S> Layer<S> for RpcRequestSignerLayer {
+    type Service = RpcRequestSignerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRequestSignerService { inner }
+    }
+}
+
+/// HTTP service that sets the [`RpcRequestSigner`] of signed requests.
+#[derive(Debug, Clone)]
+pub struct RpcRequestSignerService<S> {
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RpcRequestSignerService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        let Some(signature) = req.headers().get(FLASHBOTS_SIGNATURE_HEADER).cloned() else {
+            return Box::pin(self.inner.call(req))
+        };
+
+        // the body is read before the request is passed on, so the service that is ready is taken
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let (mut parts, body) = req.into_parts();
+            let Ok(body) = Limited::new(body, MAX_SIGNED_REQUEST_SIZE).collect().await else {
+                return Ok(err_response(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "invalid signed request body",
+                ))
+            };
+            let body = body.to_bytes();
+
+            let Some(signer) = recover_signer(&signature, &body) else {
+                return Ok(err_response(StatusCode::UNAUTHORIZED, "invalid request signature"))
+            };
+            parts.extensions.insert(RpcRequestSigner(signer));
+
+            inner.call(HttpRequest::from_parts(parts, HttpBody::new(Full::new(body)))).await
+        })
+    }
+}
+
+/// Returns the signer of the body, if the [`FLASHBOTS_SIGNATURE_HEADER`] is a valid signature of
+/// it by the address of the header.
+fn recover_signer(header: &HeaderValue, body: &[u8]) -> Option<Address> {
+    let (address, signature) = header.to_str().ok()?.split_once(':')?;
+    let address = address.parse::<Address>().ok()?;
+    let signature = signature.parse::<Signature>().ok()?;
+
+    // the signed message is the hex encoded hash, not the hash itself
+    let message = hex::encode_prefixed(keccak256(body));
+    let signer = signature.recover_address_from_msg(message).ok()?;
+    (signer == address).then_some(signer)
+}
+
+fn err_response(status: StatusCode, message: &'static str) -> HttpResponse {
+    Response::builder()
+        .status(status)
+        .body(HttpBody::from(message))
+        .expect("This should never happen")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn recover_request_signer() {
+        let signer = PrivateKeySigner::random();
+        let body = br#"{"jsonrpc":"2.0","id":1,"method":"mev_cancelBundle","params":[]}"#;
+        let signature =
+            signer.sign_message_sync(hex::encode_prefixed(keccak256(body)).as_bytes()).unwrap();
+        let header =
+            |address: Address| HeaderValue::from_str(&format!("{address}:{signature}")).unwrap();
+
+        assert_eq!(recover_signer(&header(signer.address()), body), Some(signer.address()));
+
+        // the signature must match both the address and the body
+        assert_eq!(recover_signer(&header(Address::ZERO), body), None);
+        assert_eq!(recover_signer(&header(signer.address()), b"{}"), None);
+        assert_eq!(recover_signer(&HeaderValue::from_static("invalid"), body), None);
+    }
+}
@@ -0,0 +1,100 @@
+//! Http and ws server that exposes the remote address of connections to the middleware.
+
+use crate::rate_limiter::RpcRemoteAddr;
+use hyper::body::{Body, Bytes, Incoming};
+use jsonrpsee::{
+    core::BoxError,
+    server::{
+        serve_with_graceful_shutdown, stop_channel, HttpBody, HttpRequest, HttpResponse,
+        ServerBuilder, ServerHandle, TowerService, TowerServiceBuilder,
+    },
+    Methods,
+};
+use std::{io, net::SocketAddr};
+use tokio::net::TcpListener;
+use tower::Service;
+use tracing::debug;
+
+/// A http or ws server that is bound to its address, but not started yet.
+///
+/// Unlike [`jsonrpsee::server::Server`], this sets the [`RpcRemoteAddr`] of the connection as a
+/// request extension, so the http middleware can identify clients by their address.
+#[derive(Debug)]
+pub(crate) struct RpcServer<RpcMiddleware, HttpMiddleware> {
+    listener: TcpListener,
+    builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+}
+
+impl<RpcMiddleware, HttpMiddleware> RpcServer<RpcMiddleware, HttpMiddleware> {
+    /// Binds the server configured by the builder to the address.
+    pub(crate) async fn bind(
+        builder: ServerBuilder<HttpMiddleware, RpcMiddleware>,
+        addr: SocketAddr,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self { listener, builder: builder.to_service_builder() })
+    }
+
+    /// Returns the address the server is bound to.
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Starts serving the methods on the connections accepted by the server.
+    ///
+    /// This runs on the tokio runtime until the server is stopped or the [`ServerHandle`] is
+    /// dropped.
+    pub(crate) fn start<B>(self, methods: impl Into<Methods>) -> ServerHandle
+    where
+        RpcMiddleware: Clone + Send + 'static,
+        HttpMiddleware: Clone + Send + 'static,
+        TowerService<RpcMiddleware, HttpMiddleware>:
+            Service<HttpRequest, Response = HttpResponse<B>, Error = BoxError> + Send,
+        <TowerService<RpcMiddleware, HttpMiddleware> as Service<HttpRequest>>::Future:
+            Send + 'static,
+        B: Body<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        let methods = methods.into();
+        let (stop_handle, server_handle) = stop_channel();
+        let Self { listener, builder } = self;
+
+        tokio::spawn(async move {
+            let stopped = stop_handle.clone().shutdown();
+            tokio::pin!(stopped);
+
+            loop {
+                let (socket, remote_addr) = tokio::select! {
+                    res = listener.accept() => match res {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            debug!(target: "rpc", %err, "failed to accept connection");
+                            continue
+                        }
+                    },
+                    _ = &mut stopped => break,
+                };
+                if let Err(err) = socket.set_nodelay(true) {
+                    debug!(target: "rpc", %err, "failed to set TCP_NODELAY");
+                }
+
+                let service = builder.clone().build(methods.clone(), stop_handle.clone());
+                let service = tower::service_fn(move |req: HttpRequest<Incoming>| {
+                    let mut req = req.map(HttpBody::new);
+                    req.extensions_mut().insert(RpcRemoteAddr(remote_addr));
+                    service.clone().call(req)
+                });
+
+                let shutdown = stop_handle.clone().shutdown();
+                tokio::spawn(async move {
+                    if let Err(err) = serve_with_graceful_shutdown(socket, service, shutdown).await
+                    {
+                        debug!(target: "rpc", %err, %remote_addr, "failed to serve connection");
+                    }
+                });
+            }
+        });
+
+        server_handle
+    }
+}
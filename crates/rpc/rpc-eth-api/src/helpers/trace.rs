@@ -1,24 +1,28 @@
 //! Loads a pending block from database. Helper trait for `eth_` call and trace RPC methods.
 
 use super::{Call, LoadBlock, LoadPendingBlock, LoadState, LoadTransaction};
-use crate::FromEvmError;
+use crate::{FromEthApiError, FromEvmError};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use alloy_rpc_types_eth::{BlockId, TransactionInfo};
 use futures::Future;
-use reth_chainspec::ChainSpecProvider;
+use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_errors::ProviderError;
 use reth_evm::{
     evm::EvmFactoryExt, system_calls::SystemCaller, tracing::TracingCtx, ConfigureEvm, Database,
     Evm, EvmEnvFor, EvmFor, HaltReasonFor, InspectorFor, TxEnvFor,
 };
 use reth_primitives_traits::{BlockBody, Recovered, RecoveredBlock, SignedTransaction};
-use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, CacheDB, State},
+};
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDb, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
     EthApiError,
 };
-use reth_storage_api::{ProviderBlock, ProviderTx};
+use reth_storage_api::{HashedPostStateProvider, ProviderBlock, ProviderTx, StateRootProvider};
+use reth_trie_common::TrieInput;
 use revm::{context_interface::result::ResultAndState, DatabaseCommit};
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
 use std::sync::Arc;
@@ -260,7 +264,7 @@ pub trait Trace: LoadState<Error: FromEvmError<Self::Evm>> {
         block_id: BlockId,
         block: Option<Arc<RecoveredBlock<ProviderBlock<Self::Provider>>>>,
         highest_index: Option<u64>,
-        mut inspector_setup: Setup,
+        inspector_setup: Setup,
         f: F,
     ) -> impl Future<Output = Result<Option<Vec<R>>, Self::Error>> + Send
     where
@@ -291,9 +295,55 @@ pub trait Trace: LoadState<Error: FromEvmError<Self::Evm>> {
 
             let Some(block) = block else { return Ok(None) };
 
+            self.trace_recovered_block_until_with_inspector(
+                block,
+                evm_env,
+                highest_index,
+                inspector_setup,
+                f,
+            )
+            .await
+            .map(Some)
+        }
+    }
+
+    /// Executes all transactions of the given block on top of the state of its parent block, using
+    /// the given EVM environment.
+    ///
+    /// Unlike [`Self::trace_block_until_with_inspector`], this does not look up the block or its
+    /// environment, so this can also replay blocks that are not part of the chain, e.g. blocks
+    /// that were rejected as invalid.
+    ///
+    /// See [`Self::trace_block_until_with_inspector`] for the `highest_index`, `inspector_setup`
+    /// and `f` arguments.
+    fn trace_recovered_block_until_with_inspector<Setup, Insp, F, R>(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Self::Provider>>>,
+        evm_env: EvmEnvFor<Self::Evm>,
+        highest_index: Option<u64>,
+        mut inspector_setup: Setup,
+        f: F,
+    ) -> impl Future<Output = Result<Vec<R>, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+        F: Fn(
+                TransactionInfo,
+                TracingCtx<
+                    '_,
+                    Recovered<&ProviderTx<Self::Provider>>,
+                    EvmFor<Self::Evm, StateCacheDbRefMutWrapper<'_, '_>, Insp>,
+                >,
+            ) -> Result<R, Self::Error>
+            + Send
+            + 'static,
+        Setup: FnMut() -> Insp + Send + 'static,
+        Insp: Clone + for<'a, 'b> InspectorFor<Self::Evm, StateCacheDbRefMutWrapper<'a, 'b>>,
+        R: Send + 'static,
+    {
+        async move {
             if block.body().transactions().is_empty() {
                 // nothing to trace
-                return Ok(Some(Vec::new()))
+                return Ok(Vec::new())
             }
 
             // replay all transactions of the block
@@ -343,12 +393,69 @@ pub trait Trace: LoadState<Error: FromEvmError<Self::Evm>> {
                     })
                     .collect::<Result<_, _>>()?;
 
-                Ok(Some(results))
+                Ok(results)
             })
             .await
         }
     }
 
+    /// Executes all transactions of the given block on top of the state of its parent block, using
+    /// the given EVM environment, and returns the state root after each transaction.
+    ///
+    /// Like [`Self::trace_recovered_block_until_with_inspector`], this can also replay blocks that
+    /// are not part of the chain.
+    fn intermediate_roots(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Self::Provider>>>,
+        evm_env: EvmEnvFor<Self::Evm>,
+    ) -> impl Future<Output = Result<Vec<B256>, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+    {
+        self.spawn_tracing(move |this| {
+            // we need to get the state of the parent block because we're replaying this block
+            // on top of its parent block's state
+            let state = this.state_at_block_id(block.parent_hash().into())?;
+
+            // track the changes of each transaction in a bundle, so only the trie paths touched by
+            // the transaction are recomputed for its state root
+            let mut db = State::builder()
+                .with_database(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)))
+                .with_bundle_update()
+                .build();
+            db.set_state_clear_flag(
+                this.provider().chain_spec().is_spurious_dragon_active_at_block(block.number()),
+            );
+
+            this.apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+            let mut evm = this.evm_config().evm_with_env(&mut db, evm_env);
+            let mut roots = Vec::with_capacity(block.body().transaction_count());
+            let mut input = TrieInput::default();
+            for tx in block.transactions_recovered() {
+                let ResultAndState { state: changes, .. } = evm
+                    .transact(this.evm_config().tx_env(tx))
+                    .map_err(Self::Error::from_evm_err)?;
+
+                let db = evm.db_mut();
+                db.commit(changes);
+                db.merge_transitions(BundleRetention::PlainState);
+                input.append(state.hashed_post_state(&db.take_bundle()));
+
+                // the trie nodes of the previous roots are reused for all paths the transaction
+                // did not touch
+                let (root, updates) = state
+                    .state_root_from_nodes_with_updates(input.clone())
+                    .map_err(Self::Error::from_eth_err)?;
+                input.nodes.extend(updates);
+                input.prefix_sets = Default::default();
+                roots.push(root);
+            }
+
+            Ok(roots)
+        })
+    }
+
     /// Executes all transactions of a block and returns a list of callback results invoked for each
     /// transaction in the block.
     ///
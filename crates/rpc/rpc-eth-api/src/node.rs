@@ -1,6 +1,6 @@
 //! Helper trait for interfacing with [`FullNodeComponents`].
 
-use reth_chain_state::CanonStateSubscriptions;
+use reth_chain_state::{BadBlockReader, CanonStateSubscriptions};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
@@ -8,7 +8,9 @@ use reth_node_api::{FullNodeComponents, NodePrimitives, PrimitivesTy};
 use reth_primitives_traits::{BlockTy, HeaderTy, ReceiptTy, TxTy};
 use reth_rpc_eth_types::EthStateCache;
 use reth_storage_api::{
-    BlockReader, BlockReaderIdExt, StageCheckpointReader, StateProviderFactory,
+    AddressTransactionsReader, BlockReader, BlockReaderIdExt, ChangeSetReader, DatabaseInspector,
+    HistoryIndexReader, LogIndexReader, PruneCheckpointReader, StageCheckpointReader,
+    StateProviderFactory,
 };
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
@@ -34,7 +36,14 @@ pub trait RpcNodeCore: Clone + Send + Sync + Unpin + 'static {
             ChainSpec: EthChainSpec<Header = HeaderTy<Self::Primitives>> + EthereumHardforks,
         > + StateProviderFactory
         + CanonStateSubscriptions<Primitives = Self::Primitives>
+        + BadBlockReader
         + StageCheckpointReader
+        + PruneCheckpointReader
+        + AddressTransactionsReader
+        + HistoryIndexReader
+        + LogIndexReader
+        + DatabaseInspector
+        + ChangeSetReader
         + Send
         + Sync
         + Clone
@@ -125,7 +134,14 @@ where
             ChainSpec: EthChainSpec<Header = HeaderTy<Evm::Primitives>> + EthereumHardforks,
         > + StateProviderFactory
         + CanonStateSubscriptions<Primitives = Evm::Primitives>
+        + BadBlockReader
         + StageCheckpointReader
+        + PruneCheckpointReader
+        + AddressTransactionsReader
+        + HistoryIndexReader
+        + LogIndexReader
+        + DatabaseInspector
+        + ChangeSetReader
         + Send
         + Sync
         + Unpin
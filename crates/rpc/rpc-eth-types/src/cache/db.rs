@@ -78,6 +78,14 @@ impl reth_storage_api::StorageRootProvider for StateProviderTraitObjWrapper<'_>
     ) -> ProviderResult<reth_trie::StorageMultiProof> {
         self.0.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn hashed_storage_root(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        self.0.hashed_storage_root(hashed_address, hashed_storage)
+    }
 }
 
 impl reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'_> {
@@ -169,6 +177,25 @@ impl StateProvider for StateProviderTraitObjWrapper<'_> {
     fn account_nonce(&self, addr: &Address) -> reth_errors::ProviderResult<Option<u64>> {
         self.0.account_nonce(addr)
     }
+
+    fn hashed_account_range(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, reth_primitives_traits::Account)>> {
+        self.0.hashed_account_range(hashed_state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        self.0.hashed_storage_range(hashed_address, hashed_storage, start, limit)
+    }
 }
 
 impl BytecodeReader for StateProviderTraitObjWrapper<'_> {
@@ -0,0 +1,348 @@
+//! Response types of the `debug` namespace that are not covered by alloy.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_trace::geth::GethDefaultTracingOptions;
+use revm_inspectors::tracing::types::{CallTraceNode, CallTraceStep, TraceMemberOrder};
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as, DisplayFromStr};
+use std::{collections::BTreeMap, io::Write};
+
+/// The maximum number of accounts returned by a single `debug_accountRange` call.
+pub const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
+
+/// The maximum number of storage slots returned by a single `debug_accountRange` call, summed over
+/// all accounts.
+pub const ACCOUNT_RANGE_MAX_STORAGE_SLOTS: usize = 10_000;
+
+/// The maximum number of storage slots returned by a single `debug_storageRangeAt` call.
+pub const STORAGE_RANGE_MAX_RESULTS: usize = 10_000;
+
+/// A page of the state trie, as returned by `debug_accountRange`.
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// The state root of the dumped state.
+    pub root: B256,
+    /// The dumped accounts, keyed by address or by `pre(<hashed address>)` if the address is not
+    /// known.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// The hashed address to continue the iteration from, if there are more accounts.
+    #[serde_as(as = "Option<Base64>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Bytes>,
+}
+
+/// An account of a [`StateDump`].
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// The balance of the account, as a decimal string.
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The storage root of the account.
+    pub root: B256,
+    /// The code hash of the account.
+    pub code_hash: B256,
+    /// The code of the account, unless omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The storage of the account by hashed slot, unless omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, String>>,
+    /// The address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The hashed address of the account, if the address is not known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<B256>,
+}
+
+/// A page of the storage of an account, as returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots, keyed by hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// The hashed slot of the next storage slot, if there are more slots.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot of a [`StorageRangeResult`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The slot, if the preimage of the hashed slot is known.
+    pub key: Option<B256>,
+    /// The value of the slot.
+    pub value: B256,
+}
+
+/// A block that was rejected as invalid, as returned by `debug_getBadBlocks`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The RPC representation of the block, including the full transactions.
+    pub block: serde_json::Value,
+    /// The RLP encoding of the block.
+    pub rlp: Bytes,
+}
+
+/// Options of `debug_standardTraceBlockToFile` and `debug_standardTraceBadBlockToFile`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StdTraceConfig {
+    /// The options of the struct logger.
+    #[serde(flatten)]
+    pub config: GethDefaultTracingOptions,
+    /// Only trace the transaction with this hash, if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
+}
+
+/// A single operation of an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StdTraceStep {
+    /// The program counter.
+    pub pc: u64,
+    /// The opcode.
+    pub op: u8,
+    /// The gas left before executing the operation.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    /// The gas cost of the operation.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_cost: u64,
+    /// The memory before executing the operation, if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Bytes>,
+    /// The size of the memory.
+    pub mem_size: u64,
+    /// The stack before executing the operation, empty if disabled.
+    pub stack: Vec<U256>,
+    /// The data returned by the last call, if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_data: Option<Bytes>,
+    /// The call depth.
+    pub depth: u64,
+    /// The refund counter.
+    pub refund: u64,
+    /// The name of the opcode.
+    pub op_name: String,
+    /// The error of the operation, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl StdTraceStep {
+    /// Creates the trace line of a step recorded by the
+    /// [`TracingInspector`](revm_inspectors::tracing::TracingInspector).
+    pub fn new(step: &CallTraceStep, opts: &GethDefaultTracingOptions) -> Self {
+        Self {
+            pc: step.pc as u64,
+            op: step.op.get(),
+            gas: step.gas_remaining,
+            gas_cost: step.gas_cost,
+            memory: step
+                .memory
+                .as_ref()
+                .filter(|_| opts.is_memory_enabled())
+                .map(|memory| memory.as_bytes().clone()),
+            mem_size: step.memory.as_ref().map_or(0, |memory| memory.len() as u64),
+            stack: step.stack.clone().filter(|_| opts.is_stack_enabled()).unwrap_or_default(),
+            return_data: opts.is_return_data_enabled().then(|| step.returndata.clone()),
+            depth: step.depth,
+            refund: step.gas_refund_counter,
+            op_name: step.op.as_str().to_string(),
+            error: step
+                .status
+                .filter(|status| status.is_error())
+                .map(|status| format!("{status:?}")),
+        }
+    }
+}
+
+/// The summary of an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace, written after the
+/// last operation of the transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StdTraceSummary {
+    /// The output of the transaction.
+    pub output: Bytes,
+    /// The gas used by the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Whether the transaction succeeded.
+    pub pass: bool,
+}
+
+/// Writes the [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace of a transaction as JSON
+/// lines: a [`StdTraceStep`] for every executed operation in execution order, followed by the
+/// [`StdTraceSummary`].
+///
+/// The given call trace nodes must be recorded with steps, see
+/// [`TracingInspectorConfig::from_geth_config`](revm_inspectors::tracing::TracingInspectorConfig::from_geth_config).
+pub fn write_std_trace<W: Write>(
+    mut writer: W,
+    nodes: &[CallTraceNode],
+    opts: &GethDefaultTracingOptions,
+    summary: &StdTraceSummary,
+) -> std::io::Result<()> {
+    let mut remaining = opts.limit.filter(|limit| *limit > 0).unwrap_or(u64::MAX);
+    if !nodes.is_empty() {
+        write_std_trace_steps(&mut writer, nodes, 0, opts, &mut remaining)?;
+    }
+    write_json_line(&mut writer, summary)?;
+    writer.flush()
+}
+
+/// Writes the steps of the given call and all of its child calls in the order they were executed.
+fn write_std_trace_steps<W: Write>(
+    writer: &mut W,
+    nodes: &[CallTraceNode],
+    idx: usize,
+    opts: &GethDefaultTracingOptions,
+    remaining: &mut u64,
+) -> std::io::Result<()> {
+    let node = &nodes[idx];
+    for member in &node.ordering {
+        if *remaining == 0 {
+            break
+        }
+        match *member {
+            TraceMemberOrder::Step(step) => {
+                write_json_line(writer, &StdTraceStep::new(&node.trace.steps[step], opts))?;
+                *remaining -= 1;
+            }
+            TraceMemberOrder::Call(child) => {
+                write_std_trace_steps(writer, nodes, node.children[child], opts, remaining)?
+            }
+            TraceMemberOrder::Log(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn write_json_line<W: Write>(writer: &mut W, value: &impl Serialize) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *writer, value)?;
+    writer.write_all(b"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        bytecode::opcode::{self, OpCode},
+        interpreter::InstructionResult,
+    };
+
+    #[test]
+    fn serde_state_dump() {
+        let hashed_address = B256::repeat_byte(0x11);
+        let dump = StateDump {
+            root: B256::ZERO,
+            accounts: BTreeMap::from([(
+                format!("pre({hashed_address})"),
+                DumpAccount {
+                    balance: U256::from(1_000_000_000u64),
+                    nonce: 1,
+                    key: Some(hashed_address),
+                    ..Default::default()
+                },
+            )]),
+            next: Some(Bytes::from_static(&[0xff; 4])),
+        };
+
+        let json = serde_json::to_value(&dump).unwrap();
+        assert_eq!(json["next"], "/////w==");
+        let account = &json["accounts"][format!("pre({hashed_address})")];
+        assert_eq!(account["balance"], "1000000000");
+        assert!(account.get("code").is_none());
+        assert!(account.get("address").is_none());
+
+        assert_eq!(serde_json::from_value::<StateDump>(json).unwrap(), dump);
+    }
+
+    fn trace_step(op: u8, pc: usize, depth: u64) -> CallTraceStep {
+        CallTraceStep {
+            depth,
+            pc,
+            op: OpCode::new(op).unwrap(),
+            contract: Address::ZERO,
+            stack: Some(vec![U256::from(pc)]),
+            push_stack: None,
+            memory: None,
+            returndata: Bytes::new(),
+            gas_remaining: 1_000,
+            gas_refund_counter: 0,
+            gas_used: 0,
+            gas_cost: 3,
+            storage_change: None,
+            status: None,
+            immediate_bytes: None,
+            decoded: None,
+        }
+    }
+
+    #[test]
+    fn write_std_trace_in_execution_order() {
+        let mut root = CallTraceNode {
+            children: vec![1],
+            ordering: vec![
+                TraceMemberOrder::Step(0),
+                TraceMemberOrder::Step(1),
+                TraceMemberOrder::Call(0),
+                TraceMemberOrder::Step(2),
+            ],
+            ..Default::default()
+        };
+        root.trace.steps = vec![
+            trace_step(opcode::PUSH1, 0, 1),
+            trace_step(opcode::CALL, 2, 1),
+            trace_step(opcode::STOP, 3, 1),
+        ];
+        let mut child = CallTraceNode {
+            parent: Some(0),
+            idx: 1,
+            ordering: vec![TraceMemberOrder::Step(0)],
+            ..Default::default()
+        };
+        let mut invalid = trace_step(opcode::INVALID, 0, 2);
+        invalid.status = Some(InstructionResult::InvalidFEOpcode);
+        child.trace.steps = vec![invalid];
+
+        let summary = StdTraceSummary { output: Bytes::new(), gas_used: 21_000, pass: false };
+        let opts = GethDefaultTracingOptions::default();
+        let mut out = Vec::new();
+        write_std_trace(&mut out, &[root.clone(), child.clone()], &opts, &summary).unwrap();
+
+        let lines = String::from_utf8(out).unwrap();
+        let lines = lines.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+
+        let steps = lines[..4]
+            .iter()
+            .map(|line| serde_json::from_str::<StdTraceStep>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps.iter().map(|step| (step.op_name.as_str(), step.depth)).collect::<Vec<_>>(),
+            vec![("PUSH1", 1), ("CALL", 1), ("INVALID", 2), ("STOP", 1)]
+        );
+        assert_eq!(steps[2].error.as_deref(), Some("InvalidFEOpcode"));
+        assert!(steps[0].memory.is_none());
+
+        let json = serde_json::from_str::<serde_json::Value>(lines[0]).unwrap();
+        assert_eq!(json["gas"], "0x3e8");
+        assert_eq!(json["gasCost"], "0x3");
+        assert_eq!(serde_json::from_str::<StdTraceSummary>(lines[4]).unwrap(), summary);
+
+        // the limit caps the number of operations
+        let mut out = Vec::new();
+        let opts = GethDefaultTracingOptions { limit: Some(2), ..Default::default() };
+        write_std_trace(&mut out, &[root, child], &opts, &summary).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 3);
+    }
+}
@@ -10,6 +10,7 @@
 
 pub mod builder;
 pub mod cache;
+pub mod debug;
 pub mod error;
 pub mod fee_history;
 pub mod gas_oracle;
@@ -19,6 +20,7 @@ pub mod pending_block;
 pub mod receipt;
 pub mod simulate;
 pub mod transaction;
+pub mod txpool;
 pub mod utils;
 
 pub use builder::config::{EthConfig, EthFilterConfig};
@@ -0,0 +1,215 @@
+//! Response types of the `txpool` namespace that are not covered by alloy.
+
+use alloy_primitives::{Address, TxHash};
+use reth_transaction_pool::{
+    EvictedTransaction, EvictionReason, ParkedReason, PoolSize, PoolTransactionStatus, SubPool,
+};
+use serde::{Deserialize, Serialize};
+use std::time::UNIX_EPOCH;
+
+/// The sub-pool of a transaction in the pool and why it is not pending, as returned by
+/// `txpool_transactionStatus`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolTransactionStatus {
+    /// The sub-pool that contains the transaction.
+    pub sub_pool: TxpoolSubPool,
+    /// The reasons why the transaction is not pending, empty if it is pending.
+    pub reasons: Vec<TxpoolParkedReason>,
+}
+
+impl From<PoolTransactionStatus> for TxpoolTransactionStatus {
+    fn from(status: PoolTransactionStatus) -> Self {
+        Self {
+            sub_pool: status.sub_pool.into(),
+            reasons: status.parked_reasons.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A sub-pool of the transaction pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSubPool {
+    /// Transactions that are ready to be included in the next block.
+    Pending,
+    /// Transactions that don't meet the base fee of the next block.
+    BaseFee,
+    /// Blob transactions that are not pending.
+    Blob,
+    /// Transactions with missing or queued ancestors, or whose sender lacks funds.
+    Queued,
+}
+
+impl From<SubPool> for TxpoolSubPool {
+    fn from(sub_pool: SubPool) -> Self {
+        match sub_pool {
+            SubPool::Pending => Self::Pending,
+            SubPool::BaseFee => Self::BaseFee,
+            SubPool::Blob => Self::Blob,
+            SubPool::Queued => Self::Queued,
+        }
+    }
+}
+
+/// The reason a transaction is not pending, see [`ParkedReason`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolParkedReason {
+    /// A transaction of the sender with a lower nonce is not pending.
+    ParkedAncestor,
+    /// A transaction of the sender with a lower nonce is missing.
+    NonceGap,
+    /// The balance of the sender can't cover the cost of this and all prior transactions.
+    InsufficientBalance,
+    /// The gas limit of the transaction exceeds the block gas limit.
+    ExceedsBlockGasLimit,
+    /// The max fee per gas of the transaction is below the base fee of the next block.
+    InsufficientBaseFee,
+    /// The max fee per blob gas of the transaction is below the blob fee of the next block.
+    InsufficientBlobFee,
+}
+
+impl From<ParkedReason> for TxpoolParkedReason {
+    fn from(reason: ParkedReason) -> Self {
+        match reason {
+            ParkedReason::ParkedAncestor => Self::ParkedAncestor,
+            ParkedReason::NonceGap => Self::NonceGap,
+            ParkedReason::InsufficientBalance => Self::InsufficientBalance,
+            ParkedReason::ExceedsBlockGasLimit => Self::ExceedsBlockGasLimit,
+            ParkedReason::InsufficientBaseFee => Self::InsufficientBaseFee,
+            ParkedReason::InsufficientBlobFee => Self::InsufficientBlobFee,
+        }
+    }
+}
+
+/// A transaction that was removed from the pool without being mined, as returned by
+/// `txpool_evicted`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolEvictedTransaction {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Why the transaction was removed.
+    pub reason: TxpoolEvictionReason,
+    /// The hash of the transaction that replaced it, if it was replaced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<TxHash>,
+    /// When the transaction was removed, in seconds since the unix epoch.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+}
+
+impl From<EvictedTransaction> for TxpoolEvictedTransaction {
+    fn from(tx: EvictedTransaction) -> Self {
+        let (reason, replaced_by) = match tx.reason {
+            EvictionReason::Replaced { replaced_by } => {
+                (TxpoolEvictionReason::Replaced, Some(replaced_by))
+            }
+            EvictionReason::PoolLimits => (TxpoolEvictionReason::PoolLimits, None),
+            EvictionReason::NonceTooLow => (TxpoolEvictionReason::NonceTooLow, None),
+            EvictionReason::Removed => (TxpoolEvictionReason::Removed, None),
+        };
+        Self {
+            hash: tx.hash,
+            sender: tx.sender,
+            nonce: tx.nonce,
+            reason,
+            replaced_by,
+            timestamp: tx.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        }
+    }
+}
+
+/// The reason a transaction was removed from the pool, see [`EvictionReason`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolEvictionReason {
+    /// The transaction was replaced by another transaction with the same sender and nonce.
+    Replaced,
+    /// The transaction was discarded to enforce the size limits of the pool.
+    PoolLimits,
+    /// A different transaction of the sender with the same nonce was mined.
+    NonceTooLow,
+    /// The transaction was removed explicitly, e.g. because it was stale.
+    Removed,
+}
+
+/// The number of transactions and their size in each sub-pool, as returned by
+/// `txpool_subPoolStats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolSubPoolStats {
+    /// The pending sub-pool.
+    pub pending: SubPoolStats,
+    /// The base fee sub-pool.
+    pub base_fee: SubPoolStats,
+    /// The blob sub-pool.
+    pub blob: SubPoolStats,
+    /// The queued sub-pool.
+    pub queued: SubPoolStats,
+}
+
+impl From<PoolSize> for TxpoolSubPoolStats {
+    fn from(size: PoolSize) -> Self {
+        let stats =
+            |count: usize, bytes: usize| SubPoolStats { count: count as u64, bytes: bytes as u64 };
+        Self {
+            pending: stats(size.pending, size.pending_size),
+            base_fee: stats(size.basefee, size.basefee_size),
+            blob: stats(size.blob, size.blob_size),
+            queued: stats(size.queued, size.queued_size),
+        }
+    }
+}
+
+/// The number of transactions in a sub-pool and their size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubPoolStats {
+    /// The number of transactions.
+    #[serde(with = "alloy_serde::quantity")]
+    pub count: u64,
+    /// The reported size of the transactions in bytes.
+    #[serde(with = "alloy_serde::quantity")]
+    pub bytes: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn serialize_evicted_transaction() {
+        let tx = TxpoolEvictedTransaction::from(EvictedTransaction {
+            hash: TxHash::with_last_byte(1),
+            sender: Address::with_last_byte(2),
+            nonce: 3,
+            reason: EvictionReason::Replaced { replaced_by: TxHash::with_last_byte(4) },
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(16),
+        });
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["nonce"], "0x3");
+        assert_eq!(json["reason"], "replaced");
+        assert_eq!(json["replacedBy"], serde_json::to_value(TxHash::with_last_byte(4)).unwrap());
+        assert_eq!(json["timestamp"], "0x10");
+        assert_eq!(serde_json::from_value::<TxpoolEvictedTransaction>(json).unwrap(), tx);
+    }
+
+    #[test]
+    fn serialize_transaction_status() {
+        let status = TxpoolTransactionStatus::from(PoolTransactionStatus {
+            sub_pool: SubPool::BaseFee,
+            parked_reasons: vec![ParkedReason::InsufficientBaseFee],
+        });
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            serde_json::json!({ "subPool": "baseFee", "reasons": ["insufficientBaseFee"] })
+        );
+    }
+}
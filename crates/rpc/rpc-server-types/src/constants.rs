@@ -61,6 +61,9 @@ pub const DEFAULT_TX_FEE_CAP_WEI: u128 = 1_000_000_000_000_000_000u128;
 /// second block time, and a month on a 2 second block time.
 pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 2;
 
+/// The default size of the state reverts that are cached for historical proofs: 512MB
+pub const DEFAULT_ETH_PROOF_CACHE_SIZE_MB: usize = 512;
+
 /// GPO specific constants
 pub mod gas_oracle {
     use alloy_primitives::U256;
@@ -15,4 +15,7 @@ pub mod result;
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
 
+mod signer;
+pub use signer::{RpcRequestSigner, FLASHBOTS_SIGNATURE_HEADER};
+
 pub use result::ToRpcResult;
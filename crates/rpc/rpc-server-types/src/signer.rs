@@ -0,0 +1,13 @@
+use alloy_primitives::Address;
+
+/// Header of HTTP requests that are signed by an address, in the format `<address>:<signature>`.
+///
+/// The signature is the EIP-191 signature of the hex encoded keccak256 hash of the request body.
+pub const FLASHBOTS_SIGNATURE_HEADER: &str = "x-flashbots-signature";
+
+/// The authenticated signer of an RPC request.
+///
+/// This is set as a request extension by the http server for requests with a valid
+/// [`FLASHBOTS_SIGNATURE_HEADER`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RpcRequestSigner(pub Address);
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -8,10 +8,10 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
+use reth_network_api::{BanEntry, BanTarget, NetworkInfo, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
+use reth_rpc_api::{AdminApiServer, PeerInfoWithScore};
 use reth_rpc_server_types::ToRpcResult;
 
 /// `admin` API implementation.
@@ -64,14 +64,29 @@ where
         Ok(true)
     }
 
+    /// Handler for `admin_banPeer`
+    async fn ban_peer(&self, target: BanTarget, duration: Option<u64>) -> RpcResult<bool> {
+        self.network.ban(target, duration.map(Duration::from_secs)).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_unbanPeer`
+    async fn unban_peer(&self, target: BanTarget) -> RpcResult<bool> {
+        self.network.unban(target).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_listBans`
+    async fn list_bans(&self) -> RpcResult<Vec<BanEntry>> {
+        self.network.bans().await.to_rpc_result()
+    }
+
     /// Handler for `admin_peers`
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+    async fn peers(&self) -> RpcResult<Vec<PeerInfoWithScore>> {
         let peers = self.network.get_all_peers().await.to_rpc_result()?;
         let mut infos = Vec::with_capacity(peers.len());
 
         for peer in peers {
             if let Ok(pk) = id2pk(peer.remote_id) {
-                infos.push(PeerInfo {
+                let info = PeerInfo {
                     id: pk.to_string(),
                     name: peer.client_version.to_string(),
                     enode: peer.enode,
@@ -96,7 +111,14 @@ where
                         snap: None,
                         other: Default::default(),
                     },
-                })
+                };
+                let score = self
+                    .network
+                    .score_by_id(peer.remote_id)
+                    .await
+                    .to_rpc_result()?
+                    .unwrap_or_default();
+                infos.push(PeerInfoWithScore { info, score })
             }
         }
 
@@ -1,11 +1,11 @@
 use alloy_consensus::{transaction::SignerRecoverable, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{uint, Address, Bytes, B256};
+use alloy_primitives::{hex, keccak256, map::HashMap, uint, Address, Bytes, B256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
-    state::EvmOverrides, Block as RpcBlock, BlockError, Bundle, StateContext, TransactionInfo,
+    state::EvmOverrides, BlockError, BlockTransactionsKind, Bundle, StateContext, TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
     call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
@@ -14,35 +14,52 @@ use alloy_rpc_types_trace::geth::{
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_chain_state::BadBlockReader;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::{BeaconSetHeadError, EngineSetHead};
+use reth_errors::RethError;
 use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{
     Block as _, BlockBody, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
 };
+use reth_prune_types::PruneSegment;
 use reth_revm::{
     database::StateProviderDatabase,
     db::{CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
 use reth_rpc_api::DebugApiServer;
-use reth_rpc_convert::RpcTxReq;
+use reth_rpc_convert::{RpcConvert, RpcTxReq};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_eth_types::{
+    debug::{
+        write_std_trace, BadBlock, DumpAccount, StateDump, StdTraceConfig, StdTraceSummary,
+        StorageRangeEntry, StorageRangeResult, ACCOUNT_RANGE_MAX_RESULTS,
+        ACCOUNT_RANGE_MAX_STORAGE_SLOTS, STORAGE_RANGE_MAX_RESULTS,
+    },
+    EthApiError, StateCacheDb,
+};
+use reth_rpc_server_types::{
+    constants::DEFAULT_MAX_BLOCKS_PER_FILTER, result::internal_rpc_err, ToRpcResult,
+};
+use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockBodyIndicesProvider, BlockIdReader, BlockNumReader, BlockReaderIdExt, ChangeSetReader,
+    DatabaseInspector, HeaderProvider, ProviderBlock, PruneCheckpointReader, ReceiptProviderIdExt,
+    StateProofProvider, StateProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
-use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
+use reth_trie_common::{updates::TrieUpdates, HashedPostState, HashedStorage};
+use revm::{
+    context_interface::Transaction, database::AccountState, state::EvmState, DatabaseCommit,
+};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use std::{io::BufWriter, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `debug` API implementation.
@@ -50,6 +67,10 @@ use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Eth> {
     inner: Arc<DebugApiInner<Eth>>,
+    /// Handle to the consensus engine, used to rewind the chain for `debug_setHead`.
+    engine_handle: Option<Arc<dyn EngineSetHead>>,
+    /// The maximum number of blocks whose modified accounts are returned by a single call.
+    max_modified_accounts_blocks: u64,
 }
 
 // === impl DebugApi ===
@@ -58,7 +79,24 @@ impl<Eth> DebugApi<Eth> {
     /// Create a new instance of the [`DebugApi`]
     pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
         let inner = Arc::new(DebugApiInner { eth_api, blocking_task_guard });
-        Self { inner }
+        Self {
+            inner,
+            engine_handle: None,
+            max_modified_accounts_blocks: DEFAULT_MAX_BLOCKS_PER_FILTER,
+        }
+    }
+
+    /// Configures the handle to the consensus engine that is required for `debug_setHead`.
+    pub fn with_engine_handle(mut self, engine_handle: impl EngineSetHead) -> Self {
+        self.engine_handle = Some(Arc::new(engine_handle));
+        self
+    }
+
+    /// Sets the maximum number of blocks whose modified accounts are returned by a single
+    /// `debug_getModifiedAccountsByNumber` or `debug_getModifiedAccountsByHash` call.
+    pub const fn with_max_modified_accounts_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_modified_accounts_blocks = max_blocks;
+        self
     }
 
     /// Access the underlying `Eth` API.
@@ -700,6 +738,628 @@ where
             .map(|b| b.original_bytes()))
     }
 
+    /// Returns up to `max_results` accounts of the state at the given block, ordered by hashed
+    /// address and starting at the `start` key.
+    ///
+    /// Reth doesn't store the preimages of hashed addresses, so all accounts are incomplete and are
+    /// keyed by their hashed address. Nothing is returned unless `incompletes` is set.
+    pub async fn debug_account_range(
+        &self,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<StateDump, Eth::Error> {
+        // the start key is a prefix of the hashed address
+        if start.len() > 32 {
+            return Err(
+                EthApiError::InvalidParams("start key is longer than 32 bytes".to_string()).into()
+            )
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+
+        let limit = match usize::try_from(max_results) {
+            Ok(limit) if limit > 0 => limit.min(ACCOUNT_RANGE_MAX_RESULTS),
+            _ => ACCOUNT_RANGE_MAX_RESULTS,
+        };
+
+        self.inner
+            .eth_api
+            .spawn_blocking_io(move |this| {
+                let header = this
+                    .provider()
+                    .sealed_header_by_number_or_tag(block_number)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                let mut dump = StateDump { root: header.state_root(), ..Default::default() };
+                if !incompletes {
+                    return Ok(dump)
+                }
+
+                let state = this
+                    .provider()
+                    .state_by_block_hash(header.hash())
+                    .map_err(Eth::Error::from_eth_err)?;
+                let mut accounts = state
+                    .hashed_account_range(HashedPostState::default(), start_key, limit + 1)
+                    .map_err(Eth::Error::from_eth_err)?;
+                if accounts.len() > limit {
+                    dump.next = accounts.pop().map(|(hashed_address, _)| {
+                        Bytes::copy_from_slice(hashed_address.as_slice())
+                    });
+                }
+
+                // the storage of all accounts is read at once, so it's capped for the whole dump
+                let mut storage_budget = ACCOUNT_RANGE_MAX_STORAGE_SLOTS;
+                for (hashed_address, account) in accounts {
+                    let root = state
+                        .hashed_storage_root(hashed_address, HashedStorage::default())
+                        .map_err(Eth::Error::from_eth_err)?;
+                    let code = match account.bytecode_hash.filter(|_| !nocode) {
+                        Some(code_hash) => state
+                            .bytecode_by_hash(&code_hash)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .map(|code| code.original_bytes())
+                            .filter(|code| !code.is_empty()),
+                        None => None,
+                    };
+                    let storage = if nostorage {
+                        None
+                    } else {
+                        let slots = state
+                            .hashed_storage_range(
+                                hashed_address,
+                                HashedStorage::default(),
+                                B256::ZERO,
+                                storage_budget + 1,
+                            )
+                            .map_err(Eth::Error::from_eth_err)?;
+                        if slots.len() > storage_budget {
+                            return Err(EthApiError::InvalidParams(format!(
+                                "storage exceeds {ACCOUNT_RANGE_MAX_STORAGE_SLOTS} slots; \
+                                 request fewer accounts or set nostorage"
+                            ))
+                            .into());
+                        }
+                        storage_budget -= slots.len();
+                        (!slots.is_empty()).then(|| {
+                            slots
+                                .into_iter()
+                                .map(|(slot, value)| {
+                                    (slot, hex::encode(value.to_be_bytes_trimmed_vec()))
+                                })
+                                .collect()
+                        })
+                    };
+
+                    dump.accounts.insert(
+                        format!("pre({hashed_address})"),
+                        DumpAccount {
+                            balance: account.balance,
+                            nonce: account.nonce,
+                            root,
+                            code_hash: account.get_bytecode_hash(),
+                            code,
+                            storage,
+                            address: None,
+                            key: Some(hashed_address),
+                        },
+                    );
+                }
+
+                Ok(dump)
+            })
+            .await
+    }
+
+    /// Returns up to `max_result` storage slots of the contract, ordered by hashed slot and
+    /// starting at `key_start`, after executing the first `tx_idx` transactions of the block.
+    ///
+    /// At most [`STORAGE_RANGE_MAX_RESULTS`] slots are returned per call.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .recovered_block(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let tx_count = block.body().transactions().len();
+        if tx_idx >= tx_count && !(tx_idx == 0 && tx_count == 0) {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            ))
+            .into())
+        }
+        let (evm_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+        let limit = usize::try_from(max_result)
+            .unwrap_or(STORAGE_RANGE_MAX_RESULTS)
+            .min(STORAGE_RANGE_MAX_RESULTS);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay all transactions prior to the targeted transaction
+                if let Some(target_tx) = block.body().transactions().get(tx_idx) {
+                    this.eth_api().replay_transactions_until(
+                        &mut db,
+                        evm_env,
+                        block.transactions_recovered(),
+                        *target_tx.tx_hash(),
+                    )?;
+                }
+
+                // the storage touched by the replayed transactions, with the known preimages
+                let mut hashed_storage = HashedStorage::default();
+                let mut preimages = HashMap::<B256, B256>::default();
+                if let Some(account) = db.cache.accounts.get(&contract_address) {
+                    hashed_storage.wiped = matches!(
+                        account.account_state,
+                        AccountState::NotExisting | AccountState::StorageCleared
+                    );
+                    for (slot, value) in &account.storage {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        hashed_storage.storage.insert(hashed_slot, *value);
+                        preimages.insert(hashed_slot, slot);
+                    }
+                }
+
+                let mut slots = db
+                    .db
+                    .hashed_storage_range(
+                        keccak256(contract_address),
+                        hashed_storage,
+                        key_start,
+                        limit.saturating_add(1),
+                    )
+                    .map_err(Eth::Error::from_eth_err)?;
+                let next_key =
+                    if slots.len() > limit { slots.pop().map(|(slot, _)| slot) } else { None };
+
+                let storage = slots
+                    .into_iter()
+                    .map(|(hashed_slot, value)| {
+                        let entry = StorageRangeEntry {
+                            key: preimages.get(&hashed_slot).copied(),
+                            value: value.into(),
+                        };
+                        (hashed_slot, entry)
+                    })
+                    .collect();
+
+                Ok(StorageRangeResult { storage, next_key })
+            })
+            .await
+    }
+
+    /// Returns the addresses of all accounts that were changed in the blocks after `start_number`
+    /// up to and including `end_number`.
+    ///
+    /// If `end_number` is not given, returns the accounts changed in the `start_number` block.
+    pub async fn debug_get_modified_accounts_by_number(
+        &self,
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let range = match end_number {
+            Some(end_number) if start_number >= end_number => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "start block height ({start_number}) must be less than end block height \
+                     ({end_number})"
+                ))
+                .into())
+            }
+            Some(end_number) => start_number + 1..=end_number,
+            None => start_number..=start_number,
+        };
+
+        let best_block = self.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if *range.end() > best_block {
+            return Err(EthApiError::HeaderNotFound((*range.end()).into()).into())
+        }
+
+        // ensure that the range is not too large, since we need to read the changesets of every
+        // block in the range
+        let max_blocks = self.max_modified_accounts_blocks;
+        if range.end() - range.start() >= max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large; currently limited to {max_blocks} blocks"
+            ))
+            .into())
+        }
+
+        // account changesets up to the prune checkpoint are gone
+        if self
+            .provider()
+            .get_prune_checkpoint(PruneSegment::AccountHistory)
+            .map_err(Eth::Error::from_eth_err)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .is_some_and(|pruned| pruned >= *range.start())
+        {
+            return Err(EthApiError::PrunedHistoryUnavailable.into())
+        }
+
+        self.inner
+            .eth_api
+            .spawn_blocking_io(move |this| {
+                let addresses = this
+                    .provider()
+                    .changed_accounts_in_range(range)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(addresses.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Returns the addresses of all accounts that were changed in the blocks after `start_hash` up
+    /// to and including `end_hash`.
+    ///
+    /// If `end_hash` is not given, returns the accounts changed in the `start_hash` block.
+    pub async fn debug_get_modified_accounts_by_hash(
+        &self,
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let block_number = |hash: B256| -> Result<u64, Eth::Error> {
+            self.provider()
+                .block_number(hash)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::HeaderNotFound(hash.into()).into())
+        };
+        let start_number = block_number(start_hash)?;
+        let end_number = end_hash.map(block_number).transpose()?;
+
+        self.debug_get_modified_accounts_by_number(start_number, end_number).await
+    }
+
+    /// Returns the block with the given hash from the blocks that were recently rejected as
+    /// invalid, if it is still tracked.
+    #[expect(clippy::type_complexity)]
+    fn recovered_bad_block(
+        &self,
+        block_hash: B256,
+    ) -> Result<Option<Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>>, Eth::Error> {
+        self.provider()
+            .bad_block_by_hash(block_hash)
+            .map(|block| {
+                block
+                    .try_recover()
+                    .map(Arc::new)
+                    .map_err(|_| EthApiError::InvalidTransactionSignature.into())
+            })
+            .transpose()
+    }
+
+    /// Returns the blocks that were recently rejected as invalid, oldest first.
+    pub fn debug_bad_blocks(&self) -> Result<Vec<BadBlock>, Eth::Error> {
+        let converter = self.eth_api().tx_resp_builder();
+        self.provider()
+            .bad_blocks()
+            .into_iter()
+            .map(|block| {
+                let hash = block.hash();
+                let rlp = alloy_rlp::encode(&block).into();
+                let block = block
+                    .try_recover()
+                    .map_err(|_| EthApiError::InvalidTransactionSignature)?
+                    .into_rpc_block(
+                        BlockTransactionsKind::Full,
+                        |tx, tx_info| converter.fill(tx, tx_info),
+                        |header, size| converter.convert_header(header, size),
+                    )?;
+                let block = serde_json::to_value(block)
+                    .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+
+                Ok(BadBlock { hash, block, rlp })
+            })
+            .collect()
+    }
+
+    /// Replays the block with the given hash from the blocks that were recently rejected as
+    /// invalid and returns the trace of each transaction.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let block = self
+            .recovered_bad_block(block_hash)?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        self.trace_block(block, evm_env, opts).await
+    }
+
+    /// Replays the block with the given hash and returns the state root after each transaction.
+    ///
+    /// If the block is not part of the chain, this falls back to the blocks that were recently
+    /// rejected as invalid.
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let block = match self.eth_api().recovered_block(block_hash.into()).await? {
+            Some(block) => block,
+            None => self
+                .recovered_bad_block(block_hash)?
+                .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?,
+        };
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        self.eth_api().intermediate_roots(block, evm_env).await
+    }
+
+    /// Replays the block with the given hash and writes an
+    /// [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace of each transaction to a file in
+    /// the temp directory.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: StdTraceConfig,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let block = self
+            .eth_api()
+            .recovered_block(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+
+        self.standard_trace_to_file(block, opts).await
+    }
+
+    /// Same as [`Self::debug_standard_trace_block_to_file`], but for a block that was recently
+    /// rejected as invalid.
+    pub async fn debug_standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: StdTraceConfig,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let block = self
+            .recovered_bad_block(block_hash)?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+
+        self.standard_trace_to_file(block, opts).await
+    }
+
+    /// Rewinds the canonical chain to the given block number.
+    ///
+    /// All blocks above the new head are removed from the engine tree and the database, and
+    /// forkchoice is re-applied from the new head.
+    pub async fn debug_set_head(&self, number: u64) -> Result<(), Eth::Error> {
+        let Some(engine_handle) = self.engine_handle.as_ref() else {
+            return Err(EthApiError::Unsupported("debug_setHead requires an engine handle").into())
+        };
+
+        engine_handle.set_head(number).await.map_err(|err| match err {
+            BeaconSetHeadError::NotCanonical(_) | BeaconSetHeadError::BackfillInProgress => {
+                EthApiError::InvalidParams(err.to_string())
+            }
+            err => EthApiError::Internal(RethError::other(err)),
+        })?;
+
+        Ok(())
+    }
+
+    /// Returns the value of the key in the given database table, decoded the same way as by
+    /// `reth db get`.
+    pub async fn debug_db_get(
+        &self,
+        table: String,
+        key: String,
+        subkey: Option<String>,
+    ) -> Result<Option<serde_json::Value>, Eth::Error> {
+        self.inner
+            .eth_api
+            .spawn_blocking_io(move |this| {
+                let Some(value) = this
+                    .provider()
+                    .table_value(&table, &key, subkey.as_deref())
+                    .map_err(Eth::Error::from_eth_err)?
+                else {
+                    return Ok(None)
+                };
+                serde_json::from_str(&value)
+                    .map(Some)
+                    .map_err(|err| EthApiError::Internal(RethError::other(err)).into())
+            })
+            .await
+    }
+
+    /// Returns the MDBX statistics of the database, one statistic per line.
+    ///
+    /// The `property` is either `mdbx.stats` for all statistics, or the name of a table for the
+    /// statistics of that table.
+    pub async fn debug_chaindb_property(&self, property: String) -> Result<String, Eth::Error> {
+        self.inner
+            .eth_api
+            .spawn_blocking_io(move |this| {
+                let stats = this.provider().database_stats().map_err(Eth::Error::from_eth_err)?;
+                let stats = if property == "mdbx.stats" {
+                    stats
+                } else {
+                    let stats = stats
+                        .into_iter()
+                        .filter(|stat| {
+                            stat.labels
+                                .iter()
+                                .any(|(label, value)| label == "table" && *value == property)
+                        })
+                        .collect::<Vec<_>>();
+                    if stats.is_empty() {
+                        return Err(EthApiError::InvalidParams(format!(
+                            "unknown property: {property}"
+                        ))
+                        .into())
+                    }
+                    stats
+                };
+
+                Ok(stats
+                    .iter()
+                    .map(|stat| {
+                        let labels = stat
+                            .labels
+                            .iter()
+                            .map(|(label, value)| format!("{label}=\"{value}\""))
+                            .collect::<Vec<_>>()
+                            .join(",");
+                        if labels.is_empty() {
+                            format!("{} {}", stat.name, stat.value)
+                        } else {
+                            format!("{}{{{labels}}} {}", stat.name, stat.value)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            })
+            .await
+    }
+
+    /// Returns the raw static file data of the given kind for the block `number`.
+    ///
+    /// Transactions and receipts are stored per transaction, so for these kinds the rows of all
+    /// transactions of the block are returned as an RLP list.
+    pub async fn debug_db_ancient(
+        &self,
+        kind: String,
+        number: u64,
+    ) -> Result<Option<Bytes>, Eth::Error> {
+        let (segment, column) = ancient_kind(&kind)
+            .ok_or_else(|| EthApiError::InvalidParams(format!("unknown ancient kind: {kind}")))?;
+        self.inner
+            .eth_api
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                let row = |key| -> Result<Option<Bytes>, Eth::Error> {
+                    Ok(provider
+                        .static_file_row(segment, key)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .and_then(|mut columns| {
+                            (column < columns.len()).then(|| columns.swap_remove(column))
+                        }))
+                };
+
+                if segment.is_headers() {
+                    return row(number)
+                }
+
+                let Some(indices) =
+                    provider.block_body_indices(number).map_err(Eth::Error::from_eth_err)?
+                else {
+                    return Ok(None)
+                };
+                let mut rows = Vec::with_capacity(indices.tx_count() as usize);
+                for tx_number in indices.tx_num_range() {
+                    let Some(row) = row(tx_number)? else { return Ok(None) };
+                    rows.push(row);
+                }
+                Ok(Some(alloy_rlp::encode(rows).into()))
+            })
+            .await
+    }
+
+    /// Returns the number of blocks that are stored in the static files.
+    pub fn debug_db_ancients(&self) -> u64 {
+        self.provider()
+            .highest_static_file_block(StaticFileSegment::Headers)
+            .map_or(0, |block| block + 1)
+    }
+
+    /// Replays the given block and writes the EIP-3155 trace of each transaction, or only of the
+    /// configured transaction, to a file in the temp directory.
+    async fn standard_trace_to_file(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+        opts: StdTraceConfig,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let StdTraceConfig { config, tx_hash } = opts;
+
+        // if a single transaction is requested, there's no need to replay the rest of the block
+        let highest_index = tx_hash
+            .map(|tx_hash| {
+                block
+                    .body()
+                    .transactions_iter()
+                    .position(|tx| *tx.tx_hash() == tx_hash)
+                    .map(|idx| idx as u64)
+                    .ok_or_else(|| {
+                        EthApiError::InvalidParams(format!(
+                            "transaction {tx_hash} not found in block"
+                        ))
+                    })
+            })
+            .transpose()?;
+
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+        let block_hash = block.hash();
+        // memory snapshots are always needed for the memory size of each step
+        let inspector_config =
+            TracingInspectorConfig::from_geth_config(&config).set_memory_snapshots(true);
+
+        let files = self
+            .eth_api()
+            .trace_recovered_block_until_with_inspector(
+                block,
+                evm_env,
+                highest_index,
+                move || TracingInspector::new(inspector_config),
+                move |tx_info, ctx| {
+                    let index = tx_info.index.expect("tx index is set");
+                    if highest_index.is_some_and(|highest| highest != index) {
+                        return Ok(None)
+                    }
+
+                    let tx_hash = tx_info.hash.expect("tx hash is set");
+                    let summary = StdTraceSummary {
+                        output: ctx.result.output().cloned().unwrap_or_default(),
+                        gas_used: ctx.result.gas_used(),
+                        pass: ctx.result.is_success(),
+                    };
+
+                    // the file is created exclusively under a random name, so an existing file or
+                    // link at a guessable path is never written to
+                    let path = tempfile::Builder::new()
+                        .prefix(&format!(
+                            "block_{}-{index}-{}-",
+                            hex::encode_prefixed(&block_hash[..4]),
+                            hex::encode_prefixed(&tx_hash[..4]),
+                        ))
+                        .suffix(".jsonl")
+                        .tempfile()
+                        .and_then(|file| {
+                            let (file, path) = file.keep().map_err(|err| err.error)?;
+                            write_std_trace(
+                                BufWriter::new(file),
+                                ctx.inspector.traces().nodes(),
+                                &config,
+                                &summary,
+                            )?;
+                            Ok(path)
+                        })
+                        .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+
+                    Ok(Some(path.display().to_string()))
+                },
+            )
+            .await?;
+
+        Ok(files.into_iter().flatten().collect())
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// It optionally takes fused inspector ([`TracingInspector::fused`]) to avoid re-creating the
@@ -969,8 +1629,8 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Ok(vec![])
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>> {
+        Self::debug_bad_blocks(self).map_err(Into::into)
     }
 
     /// Handler for `debug_traceChain`
@@ -1077,14 +1737,24 @@ where
 
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<StateDump> {
+        Self::debug_account_range(
+            self,
+            block_number,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1099,8 +1769,8 @@ where
         Ok(self.provider().chain_spec().genesis().config.clone())
     }
 
-    async fn debug_chaindb_property(&self, _property: String) -> RpcResult<()> {
-        Ok(())
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String> {
+        Self::debug_chaindb_property(self, property).await.map_err(Into::into)
     }
 
     async fn debug_code_by_hash(
@@ -1115,16 +1785,21 @@ where
         Ok(())
     }
 
-    async fn debug_db_ancient(&self, _kind: String, _number: u64) -> RpcResult<()> {
-        Ok(())
+    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<Option<Bytes>> {
+        Self::debug_db_ancient(self, kind, number).await.map_err(Into::into)
     }
 
-    async fn debug_db_ancients(&self) -> RpcResult<()> {
-        Ok(())
+    async fn debug_db_ancients(&self) -> RpcResult<u64> {
+        Ok(Self::debug_db_ancients(self))
     }
 
-    async fn debug_db_get(&self, _key: String) -> RpcResult<()> {
-        Ok(())
+    async fn debug_db_get(
+        &self,
+        table: String,
+        key: String,
+        subkey: Option<String>,
+    ) -> RpcResult<Option<serde_json::Value>> {
+        Self::debug_db_get(self, table, key, subkey).await.map_err(Into::into)
     }
 
     async fn debug_dump_block(&self, _number: BlockId) -> RpcResult<()> {
@@ -1153,30 +1828,43 @@ where
 
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_hash(self, start_hash, end_hash)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_number(self, start_number, end_number)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> RpcResult<Vec<B256>> {
+        // the roots are always those of the block as it was executed
+        if opts.is_some() {
+            return Err(EthApiError::InvalidParams(
+                "tracing options are not supported for intermediate roots".to_string(),
+            )
+            .into())
+        }
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1207,8 +1895,9 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_setHead`
+    async fn debug_set_head(&self, number: u64) -> RpcResult<()> {
+        Self::debug_set_head(self, number).await.map_err(Into::into)
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
@@ -1223,20 +1912,28 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_standard_trace_bad_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_standardTraceBlockToFile`
     async fn debug_standard_trace_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_standard_trace_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
@@ -1265,21 +1962,34 @@ where
 
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
-    }
-
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1303,6 +2013,22 @@ where
     }
 }
 
+/// Maps a `debug_dbAncient` kind to the static file segment and the column within it.
+///
+/// Supported kinds are `headers`, `diffs` (total difficulties) and `hashes` of the headers segment,
+/// as well as `transactions` and `receipts`.
+fn ancient_kind(kind: &str) -> Option<(StaticFileSegment, usize)> {
+    let kind = match kind {
+        "headers" => (StaticFileSegment::Headers, 0),
+        "diffs" => (StaticFileSegment::Headers, 1),
+        "hashes" => (StaticFileSegment::Headers, 2),
+        "transactions" => (StaticFileSegment::Transactions, 0),
+        "receipts" => (StaticFileSegment::Receipts, 0),
+        _ => return None,
+    };
+    Some(kind)
+}
+
 impl<Eth> std::fmt::Debug for DebugApi<Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1311,7 +2037,11 @@ impl<Eth> std::fmt::Debug for DebugApi<Eth> {
 
 impl<Eth> Clone for DebugApi<Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            engine_handle: self.engine_handle.clone(),
+            max_modified_accounts_blocks: self.max_modified_accounts_blocks,
+        }
     }
 }
 
@@ -1321,3 +2051,241 @@ struct DebugApiInner<Eth> {
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth::helpers::types::EthRpcConverter, EthApi, EthApiBuilder};
+    use alloy_consensus::{BlockBody, Header, TxLegacy};
+    use alloy_primitives::{TxKind, U256};
+    use reth_chainspec::{ChainSpec, ChainSpecProvider};
+    use reth_db_api::{models::AccountBeforeTx, tables, transaction::DbTxMut};
+    use reth_ethereum_primitives::{Block, Transaction};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives_traits::Account;
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockWriter, DatabaseProviderFactory, ExecutionOutcome, PruneCheckpointWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use reth_rpc_eth_api::node::RpcNodeCoreAdapter;
+    use reth_testing_utils::generators::{
+        self, generate_key, random_block_range, sign_tx_with_key_pair, BlockRangeParams,
+    };
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestDebugApi = DebugApi<
+        EthApi<
+            RpcNodeCoreAdapter<
+                BlockchainProvider<MockNodeTypesWithDB>,
+                TestPool,
+                NoopNetwork,
+                EthEvmConfig,
+            >,
+            EthRpcConverter<ChainSpec>,
+        >,
+    >;
+
+    /// Seeds blocks `0..=3`, where every block after genesis changes the zero address and the
+    /// address ending with its number, and prunes the account history of block 1.
+    ///
+    /// Returns the hashes of the blocks.
+    fn debug_api() -> (TestDebugApi, Vec<B256>) {
+        let factory = create_test_provider_factory();
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=3,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+
+        let provider = factory.database_provider_rw().unwrap();
+        provider
+            .append_blocks_with_state(
+                blocks.iter().map(|block| block.clone().try_recover().unwrap()).collect(),
+                &ExecutionOutcome {
+                    receipts: vec![Vec::new(); blocks.len()],
+                    ..Default::default()
+                },
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap();
+        for block in 1..=3 {
+            for address in [Address::ZERO, Address::with_last_byte(block as u8)] {
+                provider
+                    .tx_ref()
+                    .put::<tables::AccountChangeSets>(
+                        block,
+                        AccountBeforeTx { address, info: None },
+                    )
+                    .unwrap();
+            }
+        }
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let provider = BlockchainProvider::new(factory).unwrap();
+        let eth = EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        let api = DebugApi::new(eth, BlockingTaskGuard::new(1));
+        (api, blocks.iter().map(|block| block.hash()).collect())
+    }
+
+    #[tokio::test]
+    async fn modified_accounts_by_number() {
+        let (api, _) = debug_api();
+
+        // a single block
+        let accounts = api.debug_get_modified_accounts_by_number(2, None).await.unwrap();
+        assert_eq!(accounts, vec![Address::ZERO, Address::with_last_byte(2)]);
+
+        // the start block is excluded from a range
+        let accounts = api.debug_get_modified_accounts_by_number(1, Some(3)).await.unwrap();
+        assert_eq!(
+            accounts,
+            vec![Address::ZERO, Address::with_last_byte(2), Address::with_last_byte(3)]
+        );
+
+        assert!(api.debug_get_modified_accounts_by_number(3, Some(3)).await.is_err());
+        assert!(matches!(
+            api.debug_get_modified_accounts_by_number(2, Some(4)).await,
+            Err(EthApiError::HeaderNotFound(_))
+        ));
+        assert!(matches!(
+            api.debug_get_modified_accounts_by_number(1, None).await,
+            Err(EthApiError::PrunedHistoryUnavailable)
+        ));
+
+        let api = api.with_max_modified_accounts_blocks(1);
+        assert!(api.debug_get_modified_accounts_by_number(1, Some(2)).await.is_ok());
+        assert!(api.debug_get_modified_accounts_by_number(1, Some(3)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn modified_accounts_by_hash() {
+        let (api, hashes) = debug_api();
+
+        let accounts = api.debug_get_modified_accounts_by_hash(hashes[3], None).await.unwrap();
+        assert_eq!(accounts, vec![Address::ZERO, Address::with_last_byte(3)]);
+
+        let accounts =
+            api.debug_get_modified_accounts_by_hash(hashes[1], Some(hashes[3])).await.unwrap();
+        assert_eq!(
+            accounts,
+            vec![Address::ZERO, Address::with_last_byte(2), Address::with_last_byte(3)]
+        );
+
+        assert!(matches!(
+            api.debug_get_modified_accounts_by_hash(B256::random(), None).await,
+            Err(EthApiError::HeaderNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn bad_block() {
+        let (api, hashes) = debug_api();
+
+        // two transfers of a funded account on top of the chain
+        let key_pair = generate_key(&mut generators::rng());
+        let recipient = Address::random();
+        let transactions = (0..2)
+            .map(|nonce| {
+                let tx = TxLegacy {
+                    nonce,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(recipient),
+                    value: U256::from(1),
+                    ..Default::default()
+                };
+                sign_tx_with_key_pair(key_pair, Transaction::Legacy(tx))
+            })
+            .collect::<Vec<_>>();
+        let sender = transactions[0].recover_signer().unwrap();
+        let provider = api.provider().database_provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::PlainAccountState>(
+                sender,
+                Account { balance: U256::from(2), ..Default::default() },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let header = Header {
+            parent_hash: hashes[3],
+            number: 4,
+            gas_limit: 30_000_000,
+            ..Default::default()
+        };
+        let body = BlockBody { transactions: transactions.clone(), ..Default::default() };
+        let block = Block::new(header, body).seal_slow();
+        api.provider().canonical_in_memory_state().insert_bad_block(block.clone());
+
+        let bad_blocks = api.debug_bad_blocks().unwrap();
+        assert_eq!(bad_blocks.len(), 1);
+        assert_eq!(bad_blocks[0].hash, block.hash());
+        assert_eq!(bad_blocks[0].rlp, Bytes::from(alloy_rlp::encode(&block)));
+        assert_eq!(bad_blocks[0].block["hash"], serde_json::json!(block.hash()));
+        assert_eq!(bad_blocks[0].block["transactions"].as_array().unwrap().len(), 2);
+
+        let traces = api.debug_trace_bad_block(block.hash(), Default::default()).await.unwrap();
+        let traced = traces
+            .iter()
+            .map(|trace| match trace {
+                TraceResult::Success { tx_hash, .. } => tx_hash.unwrap(),
+                TraceResult::Error { error, .. } => panic!("{error}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(traced, transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>());
+        assert!(matches!(
+            api.debug_trace_bad_block(hashes[3], Default::default()).await,
+            Err(EthApiError::HeaderNotFound(_))
+        ));
+
+        // the incrementally computed roots match the roots of the full state after each transfer
+        let state = api.provider().latest().unwrap();
+        let expected = (1..=2)
+            .map(|transfers| {
+                let accounts = [
+                    (
+                        sender,
+                        Account {
+                            nonce: transfers,
+                            balance: U256::from(2 - transfers),
+                            ..Default::default()
+                        },
+                    ),
+                    (recipient, Account { balance: U256::from(transfers), ..Default::default() }),
+                    // the fee-less coinbase is touched before spurious dragon
+                    (Address::ZERO, Account::default()),
+                ];
+                state
+                    .state_root(
+                        HashedPostState::default().with_accounts(
+                            accounts
+                                .into_iter()
+                                .map(|(address, account)| (keccak256(address), Some(account))),
+                        ),
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(api.debug_intermediate_roots(block.hash()).await.unwrap(), expected);
+    }
+}
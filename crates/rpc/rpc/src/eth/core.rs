@@ -484,14 +484,15 @@ mod tests {
     use alloy_rpc_types::FeeHistory;
     use jsonrpsee_types::error::INVALID_PARAMS_CODE;
     use rand::Rng;
-    use reth_chain_state::CanonStateSubscriptions;
+    use reth_chain_state::{BadBlockReader, CanonStateSubscriptions};
     use reth_chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec};
     use reth_ethereum_primitives::TransactionSigned;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
     use reth_provider::{
         test_utils::{MockEthProvider, NoopProvider},
-        StageCheckpointReader,
+        AddressTransactionsReader, ChangeSetReader, DatabaseInspector, HistoryIndexReader,
+        LogIndexReader, PruneCheckpointReader, StageCheckpointReader,
     };
     use reth_rpc_eth_api::{node::RpcNodeCoreAdapter, EthApiServer};
     use reth_storage_api::{BlockReader, BlockReaderIdExt, StateProviderFactory};
@@ -513,7 +514,14 @@ mod tests {
             + ChainSpecProvider<ChainSpec = ChainSpec>
             + StateProviderFactory
             + CanonStateSubscriptions<Primitives = reth_ethereum_primitives::EthPrimitives>
+            + BadBlockReader
             + StageCheckpointReader
+            + PruneCheckpointReader
+            + AddressTransactionsReader
+            + HistoryIndexReader
+            + LogIndexReader
+            + DatabaseInspector
+            + ChangeSetReader
             + Unpin
             + Clone
             + 'static,
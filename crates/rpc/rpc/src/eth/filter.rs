@@ -22,13 +22,13 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, ReceiptProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, LogIndexReader,
+    ProviderBlock, ProviderReceipt, ReceiptProvider,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeSet, HashMap, VecDeque},
     fmt,
     future::Future,
     iter::{Peekable, StepBy},
@@ -564,31 +564,14 @@ where
         rx.await.map_err(|_| EthFilterError::InternalError)?
     }
 
-    /// Returns all logs in the given _inclusive_ range that match the filter
-    ///
-    /// Note: This function uses a mix of blocking db operations for fetching indices and header
-    /// ranges and utilizes the rpc cache for optimistically fetching receipts and blocks.
-    /// This function is considered blocking and should thus be spawned on a blocking task.
-    ///
-    /// Returns an error if:
-    ///  - underlying database error
-    async fn get_logs_in_block_range_inner(
-        self: Arc<Self>,
+    /// Collects the headers in the range whose logs bloom matches the filter.
+    fn collect_bloom_matching_headers(
+        &self,
         filter: &Filter,
-        from_block: u64,
-        to_block: u64,
-        limits: QueryLimits,
-    ) -> Result<Vec<Log>, EthFilterError> {
-        let mut all_logs = Vec::new();
-        let mut matching_headers = Vec::new();
-
-        // get current chain tip to determine processing mode
-        let chain_tip = self.provider().best_block_number()?;
-
-        // first collect all headers that match the bloom filter for cached mode decision
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
+        range: RangeInclusive<u64>,
+        matching_headers: &mut Vec<SealedHeader<<Eth::Provider as HeaderProvider>::Header>>,
+    ) -> Result<(), EthFilterError> {
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
             let headers = self.provider().headers_range(from..=to)?;
 
             let mut headers_iter = headers.into_iter().peekable();
@@ -615,6 +598,114 @@ where
             }
         }
 
+        Ok(())
+    }
+
+    /// Returns the blocks in the range that may contain logs matching the filter, according to
+    /// the log index.
+    ///
+    /// Returns `None` if the filter doesn't restrict addresses or topics, in which case the index
+    /// can't narrow down the range.
+    fn log_index_candidates(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<Option<BTreeSet<u64>>, EthFilterError> {
+        // blocks matching the addresses, and blocks matching each topic position
+        let mut candidates: Option<BTreeSet<u64>> = None;
+        let mut intersect = |blocks: BTreeSet<u64>| {
+            candidates = Some(match candidates.take() {
+                Some(candidates) => candidates.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        };
+
+        if !filter.address.is_empty() {
+            let mut blocks = BTreeSet::new();
+            for address in filter.address.iter() {
+                blocks.extend(self.provider().log_address_blocks(*address, range.clone())?);
+            }
+            intersect(blocks);
+        }
+
+        // topics are indexed regardless of their position, the logs of the candidate blocks are
+        // matched against the full filter afterwards
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for topic in topics.iter() {
+                blocks.extend(self.provider().log_topic_blocks(*topic, range.clone())?);
+            }
+            intersect(blocks);
+        }
+
+        Ok(candidates)
+    }
+
+    /// Returns all logs in the given _inclusive_ range that match the filter
+    ///
+    /// Note: This function uses a mix of blocking db operations for fetching indices and header
+    /// ranges and utilizes the rpc cache for optimistically fetching receipts and blocks.
+    /// This function is considered blocking and should thus be spawned on a blocking task.
+    ///
+    /// Returns an error if:
+    ///  - underlying database error
+    async fn get_logs_in_block_range_inner(
+        self: Arc<Self>,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+        limits: QueryLimits,
+    ) -> Result<Vec<Log>, EthFilterError> {
+        let mut all_logs = Vec::new();
+        let mut matching_headers = Vec::new();
+
+        // get current chain tip to determine processing mode
+        let chain_tip = self.provider().best_block_number()?;
+
+        // the part of the range covered by the log index doesn't need to be scanned
+        let mut indexed = None;
+        if let Some(index) = self.provider().log_index_range()? {
+            let range = (*index.start()).max(from_block)..=(*index.end()).min(to_block);
+            if !range.is_empty() {
+                if let Some(candidates) = self.log_index_candidates(filter, range.clone())? {
+                    indexed = Some((range, candidates));
+                }
+            }
+        }
+
+        // first collect all headers that match the bloom filter for cached mode decision
+        match indexed {
+            Some((indexed, candidates)) => {
+                if from_block < *indexed.start() {
+                    self.collect_bloom_matching_headers(
+                        filter,
+                        from_block..=*indexed.start() - 1,
+                        &mut matching_headers,
+                    )?;
+                }
+                for block_number in candidates {
+                    let Some(header) = self.provider().sealed_header(block_number)? else {
+                        continue
+                    };
+                    if filter.matches_bloom(header.logs_bloom()) {
+                        matching_headers.push(header);
+                    }
+                }
+                if *indexed.end() < to_block {
+                    self.collect_bloom_matching_headers(
+                        filter,
+                        *indexed.end() + 1..=to_block,
+                        &mut matching_headers,
+                    )?;
+                }
+            }
+            None => self.collect_bloom_matching_headers(
+                filter,
+                from_block..=to_block,
+                &mut matching_headers,
+            )?,
+        }
+
         // initialize the appropriate range mode based on collected headers
         let mut range_mode = RangeMode::new(
             self.clone(),
@@ -1737,4 +1828,98 @@ mod tests {
         assert_eq!(logs[0].block_hash, Some(expected_hashes[0])); // block 100
         assert_eq!(logs[1].block_hash, Some(expected_hashes[2])); // block 102
     }
+
+    #[tokio::test]
+    async fn test_log_index_candidates() {
+        use alloy_primitives::{Address, LogData, B256};
+        use reth_execution_types::ExecutionOutcome;
+        use reth_provider::{
+            providers::BlockchainProvider, test_utils::create_test_provider_factory, BlockWriter,
+            DatabaseProviderFactory, LogIndexWriter,
+        };
+        use reth_testing_utils::generators::{random_block_range, BlockRangeParams};
+
+        let (address_a, address_b) = (Address::with_last_byte(0xa), Address::with_last_byte(0xb));
+        let (topic_1, topic_2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let log = |address, topics| alloy_primitives::Log {
+            address,
+            data: LogData::new_unchecked(topics, Default::default()),
+        };
+
+        let factory = create_test_provider_factory();
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=3,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        let provider = factory.database_provider_rw().unwrap();
+        provider
+            .append_blocks_with_state(
+                blocks.iter().map(|block| block.clone().try_recover().unwrap()).collect(),
+                &ExecutionOutcome {
+                    receipts: vec![Vec::new(); blocks.len()],
+                    ..Default::default()
+                },
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap();
+        provider
+            .insert_log_indices(
+                [
+                    (1, vec![log(address_a, vec![topic_1])]),
+                    (2, vec![log(address_b, vec![topic_1, topic_2])]),
+                    (3, vec![log(address_a, vec![topic_2])]),
+                ]
+                .iter()
+                .map(|(number, logs)| (*number, logs)),
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let provider = BlockchainProvider::new(factory).unwrap();
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        let eth_filter = super::EthFilter::new(
+            eth_api,
+            EthFilterConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+        );
+        let candidates = |filter: Filter, range| {
+            eth_filter
+                .inner
+                .log_index_candidates(&filter, range)
+                .unwrap()
+                .map(|blocks| blocks.into_iter().collect::<Vec<_>>())
+        };
+
+        // the index can't narrow down a filter without addresses and topics
+        assert_eq!(candidates(Filter::new(), 0..=3), None);
+
+        // any of the addresses
+        assert_eq!(candidates(Filter::new().address(address_a), 0..=3), Some(vec![1, 3]));
+        assert_eq!(
+            candidates(Filter::new().address(vec![address_a, address_b]), 0..=3),
+            Some(vec![1, 2, 3])
+        );
+
+        // the addresses and every topic position have to match
+        assert_eq!(
+            candidates(Filter::new().address(address_a).event_signature(topic_2), 0..=3),
+            Some(vec![3])
+        );
+        assert_eq!(
+            candidates(Filter::new().event_signature(topic_1).topic1(topic_2), 0..=3),
+            Some(vec![2])
+        );
+
+        // only blocks within the range
+        assert_eq!(candidates(Filter::new().event_signature(topic_1), 2..=3), Some(vec![2]));
+        assert_eq!(candidates(Filter::new().address(address_b), 3..=3), Some(vec![]));
+    }
 }
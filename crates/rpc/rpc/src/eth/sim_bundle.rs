@@ -1,27 +1,31 @@
 //! `Eth` Sim bundle implementation and helpers.
 
 use alloy_consensus::BlockHeader;
-use alloy_eips::BlockNumberOrTag;
+use alloy_eips::{BlockNumberOrTag, Typed2718};
 use alloy_evm::overrides::apply_block_overrides;
-use alloy_primitives::U256;
+use alloy_primitives::{Address, U256};
 use alloy_rpc_types_eth::BlockId;
 use alloy_rpc_types_mev::{
-    BundleItem, Inclusion, MevSendBundle, Privacy, RefundConfig, SimBundleLogs, SimBundleOverrides,
-    SimBundleResponse, Validity,
+    BundleItem, EthBundleHash, EthCancelBundle, Inclusion, MevSendBundle, Privacy, RefundConfig,
+    SimBundleLogs, SimBundleOverrides, SimBundleResponse, Validity,
 };
 use jsonrpsee::core::RpcResult;
+use jsonrpsee_types::Extensions;
 use reth_evm::{ConfigureEvm, Evm};
 use reth_primitives_traits::{Recovered, SignedTransaction};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
-use reth_rpc_api::MevSimApiServer;
+use reth_rpc_api::{MevFullApiServer, MevSimApiServer};
 use reth_rpc_eth_api::{
     helpers::{block::LoadBlock, Call, EthTransactions},
     FromEthApiError, FromEvmError,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_rpc_server_types::RpcRequestSigner;
 use reth_storage_api::ProviderTx;
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    BundleTransaction, PoolBundle, PoolPooledTx, PoolTransaction, TransactionPool,
+};
 use revm::{context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef};
 use std::{sync::Arc, time::Duration};
 use tracing::trace;
@@ -61,6 +65,8 @@ pub struct FlattenedBundleItem<T> {
 }
 
 /// `Eth` sim bundle implementation.
+///
+/// Also serves `mev_sendBundle`, by adding the bundles to the bundle pool of the transaction pool.
 pub struct EthSimBundle<Eth> {
     /// All nested fields bundled together.
     inner: Arc<EthSimBundleInner<Eth>>,
@@ -406,6 +412,60 @@ where
 
         Ok(sim_response)
     }
+
+    /// Adds the bundle to the bundle pool of the transaction pool, so that it's tried at the top
+    /// of the blocks of its inclusion window.
+    fn send_bundle_inner(
+        &self,
+        request: MevSendBundle,
+        signer: Option<Address>,
+        replacement_uuid: Option<String>,
+    ) -> Result<EthBundleHash, EthApiError> {
+        let bundle_pool =
+            self.eth_api().pool().bundle_pool().ok_or(EthApiError::Unsupported(
+                "bundles are not supported by the transaction pool",
+            ))?;
+
+        let flattened_bundle = self.parse_and_flatten_bundle(&request)?;
+
+        // Nested bundles can only be included in the blocks all of their parents can be included
+        // in.
+        let mut block_number = request.inclusion.block_number();
+        let mut max_block_number = request.inclusion.max_block_number().unwrap_or(block_number);
+        let mut transactions = Vec::with_capacity(flattened_bundle.len());
+        for item in flattened_bundle {
+            // Refunds are paid out by the block builder, which the payload builder doesn't do.
+            if item.refund_percent.is_some() || item.refund_configs.is_some() {
+                return Err(EthApiError::InvalidParams(
+                    EthSimBundleError::UnsupportedRefund.to_string(),
+                ))
+            }
+            if item.tx.is_eip4844() {
+                return Err(EthApiError::InvalidParams(
+                    EthSimBundleError::BlobTransaction.to_string(),
+                ))
+            }
+
+            let item_block_number = item.inclusion.block_number();
+            block_number = block_number.max(item_block_number);
+            max_block_number = max_block_number
+                .min(item.inclusion.max_block_number().unwrap_or(item_block_number));
+            transactions
+                .push(BundleTransaction { transaction: item.tx, can_revert: item.can_revert });
+        }
+
+        let bundle_hash = bundle_pool
+            .add_bundle(PoolBundle {
+                transactions,
+                block_number,
+                max_block_number,
+                signer,
+                replacement_uuid,
+            })
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
 }
 
 #[async_trait::async_trait]
@@ -438,6 +498,51 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> MevFullApiServer for EthSimBundle<Eth>
+where
+    Eth: EthTransactions + LoadBlock + Call + 'static,
+{
+    async fn send_bundle(
+        &self,
+        ext: &Extensions,
+        request: MevSendBundle,
+        replacement_uuid: Option<String>,
+    ) -> RpcResult<EthBundleHash> {
+        trace!(
+            "mev_sendBundle called, request: {:?}, replacement_uuid: {:?}",
+            request,
+            replacement_uuid
+        );
+
+        let signer = ext.get::<RpcRequestSigner>().map(|signer| signer.0);
+        Ok(self.send_bundle_inner(request, signer, replacement_uuid)?)
+    }
+
+    async fn cancel_bundle(&self, ext: &Extensions, request: EthCancelBundle) -> RpcResult<()> {
+        trace!("mev_cancelBundle called, request: {:?}", request);
+
+        let bundle_pool =
+            self.eth_api().pool().bundle_pool().ok_or(EthApiError::Unsupported(
+                "bundles are not supported by the transaction pool",
+            ))?;
+        let RpcRequestSigner(signer) = ext.get::<RpcRequestSigner>().copied().ok_or_else(|| {
+            EthApiError::InvalidParams(EthSimBundleError::UnsignedRequest.to_string())
+        })?;
+        bundle_pool.cancel_bundle(signer, &request.replacement_uuid);
+
+        Ok(())
+    }
+
+    async fn sim_bundle(
+        &self,
+        request: MevSendBundle,
+        overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        MevSimApiServer::sim_bundle(self, request, overrides).await
+    }
+}
+
 /// Container type for `EthSimBundle` internals
 #[derive(Debug)]
 struct EthSimBundleInner<Eth> {
@@ -490,4 +595,13 @@ pub enum EthSimBundleError {
     /// Thrown when a bundle simulation returns negative profit
     #[error("bundle simulation returned negative profit")]
     NegativeProfit,
+    /// Thrown when a submitted bundle requests refunds
+    #[error("bundle refunds are not supported")]
+    UnsupportedRefund,
+    /// Thrown when a submitted bundle contains a blob transaction
+    #[error("blob transactions are not supported in bundles")]
+    BlobTransaction,
+    /// Thrown when a bundle is cancelled by a request without a signature
+    #[error("cancelling bundles requires a signed request")]
+    UnsignedRequest,
 }
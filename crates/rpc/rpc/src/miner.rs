@@ -1,25 +1,67 @@
+use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_primitives::{Bytes, U128};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_payload_primitives::MinerConfig;
 use reth_rpc_api::MinerApiServer;
+use reth_rpc_server_types::result::invalid_params_rpc_err;
+use reth_transaction_pool::TransactionPool;
+use std::sync::Arc;
 
 /// `miner` API implementation.
 ///
 /// This type provides the functionality for handling `miner` related requests.
-#[derive(Clone, Debug, Default)]
-pub struct MinerApi {}
+///
+/// The minimum tip is applied to the transaction pool. The extra data and the gas limit are only
+/// applied if the api is configured with the [`MinerConfig`] of the payload builder,
+/// otherwise these calls return `false`.
+#[derive(Clone, Debug)]
+pub struct MinerApi<Pool> {
+    /// The transaction pool.
+    pool: Pool,
+    /// Payload builder settings shared with the payload builder.
+    builder_config: Option<Arc<dyn MinerConfig>>,
+}
+
+impl<Pool> MinerApi<Pool> {
+    /// Creates a new instance of `MinerApi`.
+    pub const fn new(pool: Pool) -> Self {
+        Self { pool, builder_config: None }
+    }
+
+    /// Configures the payload builder settings that are updated by this api.
+    pub fn with_builder_config(mut self, builder_config: impl MinerConfig + 'static) -> Self {
+        self.builder_config = Some(Arc::new(builder_config));
+        self
+    }
+}
 
 #[async_trait]
-impl MinerApiServer for MinerApi {
-    fn set_extra(&self, _record: Bytes) -> RpcResult<bool> {
-        Ok(false)
+impl<Pool> MinerApiServer for MinerApi<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    fn set_extra(&self, record: Bytes) -> RpcResult<bool> {
+        if record.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "extra data exceeds the maximum of {MAXIMUM_EXTRA_DATA_SIZE} bytes"
+            )))
+        }
+        let Some(config) = &self.builder_config else { return Ok(false) };
+        config.set_extra_data(record);
+        Ok(true)
     }
 
-    fn set_gas_price(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_price(&self, gas_price: U128) -> RpcResult<bool> {
+        self.pool.set_minimum_priority_fee(Some(gas_price.to()));
+        Ok(true)
     }
 
-    fn set_gas_limit(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_limit(&self, gas_limit: U128) -> RpcResult<bool> {
+        let gas_limit = u64::try_from(gas_limit)
+            .map_err(|_| invalid_params_rpc_err("gas limit exceeds u64::MAX"))?;
+        let Some(config) = &self.builder_config else { return Ok(false) };
+        config.set_gas_limit(gas_limit);
+        Ok(true)
     }
 }
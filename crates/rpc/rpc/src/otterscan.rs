@@ -1,7 +1,7 @@
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
@@ -12,6 +12,7 @@ use alloy_rpc_types_trace::{
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_prune_types::PruneSegment;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
@@ -20,14 +21,27 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::{
+    transaction_addresses, AddressTransactionsReader, BlockBodyIndicesProvider, BlockIdReader,
+    BlockNumReader, BlockReader, HistoryIndexReader, PruneCheckpointReader, TransactionVariant,
+    TransactionsProvider,
+};
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
+use std::{collections::BTreeMap, ops::RangeInclusive};
 
 const API_LEVEL: u64 = 8;
 
+/// The number of blocks that are looked up at once when searching the transactions of an address.
+const SEARCH_WINDOW_SIZE: u64 = 100_000;
+
+/// The maximum number of blocks that are scanned directly by a single search, for blocks that
+/// aren't covered by the address transactions index.
+const MAX_SCANNED_BLOCKS: usize = 1_000;
+
 /// Otterscan API.
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
@@ -63,6 +77,246 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTxReq<Eth::NetworkTypes>,
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + TraceExt
+        + 'static,
+{
+    /// Resolves the block number of a search request.
+    fn resolve_block_number(
+        &self,
+        block_number: LenientBlockNumberOrTag,
+    ) -> RpcResult<BlockNumber> {
+        let block_number = block_number.into_inner();
+        self.eth
+            .provider()
+            .convert_block_number(block_number)
+            .map_err(EthApiError::from)?
+            .ok_or_else(|| EthApiError::HeaderNotFound(block_number.into()).into())
+    }
+
+    /// Returns the lowest block that is covered by the account and storage history.
+    fn lowest_history_block(&self) -> RpcResult<BlockNumber> {
+        let provider = self.eth.provider();
+        let mut lowest = 0;
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            // the checkpoint stores the highest pruned block number
+            if let Some(pruned) = provider
+                .get_prune_checkpoint(segment)
+                .map_err(EthApiError::from)?
+                .and_then(|checkpoint| checkpoint.block_number)
+            {
+                lowest = lowest.max(pruned + 1);
+            }
+        }
+        Ok(lowest)
+    }
+
+    /// Returns the blocks within the range that contain transactions of the address, in ascending
+    /// order, together with the indices of these transactions within the block.
+    ///
+    /// Blocks covered by the address transactions index are looked up in the index, newer blocks
+    /// that haven't been indexed yet are scanned directly. If the index is disabled, only the
+    /// blocks in which the account or its storage changed are scanned. Every scanned block is
+    /// taken from the scan budget, see [`Self::scan_transaction_blocks`].
+    fn address_transaction_blocks(
+        &self,
+        address: Address,
+        indexed: Option<&RangeInclusive<BlockNumber>>,
+        range: RangeInclusive<BlockNumber>,
+        scan_budget: &mut usize,
+    ) -> RpcResult<BTreeMap<BlockNumber, Vec<usize>>> {
+        let provider = self.eth.provider();
+        let Some(indexed) = indexed else {
+            let mut blocks = provider
+                .account_history_blocks(address, range.clone())
+                .map_err(EthApiError::from)?;
+            blocks.extend(
+                provider.storage_history_blocks(address, range).map_err(EthApiError::from)?,
+            );
+            blocks.sort_unstable();
+            blocks.dedup();
+            return self.scan_transaction_blocks(address, blocks, scan_budget)
+        };
+
+        let body_indices = |block: BlockNumber| {
+            provider
+                .block_body_indices(block)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::HeaderNotFound(block.into()))
+        };
+
+        let mut blocks = self.scan_transaction_blocks(
+            address,
+            (*range.start()).max(*indexed.end() + 1)..=*range.end(),
+            scan_budget,
+        )?;
+        let indexed_end = (*range.end()).min(*indexed.end());
+        if range.start() <= &indexed_end {
+            let first_tx = body_indices(*range.start())?.first_tx_num();
+            let next_tx = body_indices(indexed_end)?.next_tx_num();
+            if first_tx < next_tx {
+                let tx_numbers = provider
+                    .address_transactions(address, first_tx..=next_tx - 1)
+                    .map_err(EthApiError::from)?;
+                for tx_number in tx_numbers {
+                    let block = provider
+                        .transaction_block(tx_number)
+                        .map_err(EthApiError::from)?
+                        .ok_or(EthApiError::TransactionNotFound)?;
+                    let index = tx_number - body_indices(block)?.first_tx_num();
+                    blocks.entry(block).or_default().push(index as usize);
+                }
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Scans the given blocks for transactions of the address and returns the blocks that contain
+    /// any, together with the indices of these transactions within the block.
+    ///
+    /// Returns an error instead of scanning more blocks than are left in the scan budget.
+    fn scan_transaction_blocks(
+        &self,
+        address: Address,
+        blocks: impl IntoIterator<Item = BlockNumber>,
+        scan_budget: &mut usize,
+    ) -> RpcResult<BTreeMap<BlockNumber, Vec<usize>>> {
+        let provider = self.eth.provider();
+        let mut found = BTreeMap::new();
+        for block in blocks {
+            *scan_budget = scan_budget.checked_sub(1).ok_or_else(|| {
+                internal_rpc_err(format!(
+                    "address transactions index is not synced, the search would scan more than \
+                     {MAX_SCANNED_BLOCKS} blocks"
+                ))
+            })?;
+            let recovered = provider
+                .recovered_block(block.into(), TransactionVariant::NoHash)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::HeaderNotFound(block.into()))?;
+            let indices = recovered
+                .transactions_with_sender()
+                .enumerate()
+                .filter(|(_, (sender, tx))| transaction_addresses(**sender, *tx).contains(&address))
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+            if !indices.is_empty() {
+                found.insert(block, indices);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Returns the transactions of the block with the given indices, together with their
+    /// receipts.
+    async fn block_transactions(
+        &self,
+        block_number: BlockNumber,
+        indices: &[usize],
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>)> {
+        let block_id = block_number.into();
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+
+        let timestamp = Some(block.header.timestamp());
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+        if transactions.len() != receipts.len() {
+            return Err(internal_rpc_err(
+                "the number of transactions does not match the number of receipts",
+            ));
+        }
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .enumerate()
+            .filter(|(index, _)| indices.contains(index))
+            .map(|(_, (tx, receipt))| {
+                let receipt = ots_transaction_receipt(&receipt, tx.ty(), timestamp);
+                (tx, receipt)
+            })
+            .unzip())
+    }
+
+    /// Searches the transactions of the address, starting at the given block and moving towards
+    /// older blocks if `before` is set, or towards newer blocks otherwise.
+    ///
+    /// Blocks are always included as a whole, so this stops at the first block that completes
+    /// the page. The transactions are returned from newest to oldest if `before` is set, and from
+    /// oldest to newest otherwise. The returned flag indicates whether there are more blocks
+    /// to search.
+    ///
+    /// The transactions are found through the address transactions index, so this includes every
+    /// transaction that was sent by the address, sent to it, or created it, even if it didn't
+    /// change its state. Internal calls are not indexed. If the index is disabled, the account and
+    /// storage history are searched instead, which misses the transactions that didn't change the
+    /// state of the address. Blocks that aren't covered by the index are decoded one by one, so a
+    /// search fails if it would have to scan more than [`MAX_SCANNED_BLOCKS`] of them.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        start: BlockNumber,
+        before: bool,
+        page_size: usize,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>, bool)> {
+        let provider = self.eth.provider();
+        let indexed = provider.address_transactions_range().map_err(EthApiError::from)?;
+        let lowest = match &indexed {
+            Some(indexed) => *indexed.start(),
+            None => self.lowest_history_block()?,
+        };
+        let latest = provider.best_block_number().map_err(EthApiError::from)?;
+
+        let (mut txs, mut receipts) = (Vec::new(), Vec::new());
+        let mut scan_budget = MAX_SCANNED_BLOCKS;
+        let mut next = Some(if before { start.min(latest) } else { start });
+        while let Some(block) = next.filter(|block| (lowest..=latest).contains(block)) {
+            let window = if before {
+                block.saturating_sub(SEARCH_WINDOW_SIZE - 1).max(lowest)..=block
+            } else {
+                block..=block.saturating_add(SEARCH_WINDOW_SIZE - 1).min(latest)
+            };
+            next = if before { window.start().checked_sub(1) } else { window.end().checked_add(1) };
+
+            let mut blocks = self
+                .address_transaction_blocks(address, indexed.as_ref(), window, &mut scan_budget)?
+                .into_iter()
+                .collect::<Vec<_>>();
+            if before {
+                blocks.reverse();
+            }
+            for (block, indices) in blocks {
+                if txs.len() >= page_size {
+                    return Ok((txs, receipts, true))
+                }
+                let (mut block_txs, mut block_receipts) =
+                    self.block_transactions(block, &indices).await?;
+                if before {
+                    block_txs.reverse();
+                    block_receipts.reverse();
+                }
+                txs.extend(block_txs);
+                receipts.extend(block_receipts);
+            }
+        }
+        Ok((txs, receipts, false))
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -247,32 +501,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_transaction_receipt(&receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -284,21 +513,45 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let block_number = self.resolve_block_number(block_number)?;
+
+        // block 0 searches from the latest block, otherwise the given block is excluded
+        let first_page = block_number == 0;
+        let start = if first_page {
+            self.eth.provider().best_block_number().map_err(EthApiError::from)?
+        } else {
+            block_number - 1
+        };
+
+        let (txs, receipts, has_more) =
+            self.search_transactions(address, start, true, page_size).await?;
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page: !has_more })
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let block_number = self.resolve_block_number(block_number)?;
+
+        // block 0 searches from genesis, otherwise the given block is excluded
+        let last_page = block_number == 0;
+        let start = if last_page { 0 } else { block_number + 1 };
+
+        let (mut txs, mut receipts, has_more) =
+            self.search_transactions(address, start, false, page_size).await?;
+
+        // pages are always ordered from newest to oldest
+        txs.reverse();
+        receipts.reverse();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page: !has_more, last_page })
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -382,3 +635,176 @@ where
         Ok(found)
     }
 }
+
+/// Converts a receipt into an [`OtsTransactionReceipt`] of a transaction with the given type.
+fn ots_transaction_receipt(
+    receipt: &impl ReceiptResponse,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::helpers::types::EthRpcConverter, reth::tests::address_transactions_provider, EthApi,
+        EthApiBuilder,
+    };
+    use alloy_consensus::{Transaction as _, TxLegacy};
+    use alloy_eips::BlockNumberOrTag;
+    use reth_chainspec::{ChainSpec, ChainSpecProvider};
+    use reth_ethereum_primitives::{Transaction, TransactionSigned};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::MockNodeTypesWithDB, DatabaseProviderFactory,
+        HistoryWriter,
+    };
+    use reth_rpc_eth_api::node::RpcNodeCoreAdapter;
+    use reth_testing_utils::generators::{
+        self, generate_key, random_tx, sign_tx_with_key_pair, sign_tx_with_random_key_pair,
+    };
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestEthApi = EthApi<
+        RpcNodeCoreAdapter<
+            BlockchainProvider<MockNodeTypesWithDB>,
+            TestPool,
+            NoopNetwork,
+            EthEvmConfig,
+        >,
+        EthRpcConverter<ChainSpec>,
+    >;
+
+    /// Builds the API on top of the blocks of [`address_transactions_provider`].
+    fn otterscan_api(
+        transactions: [TransactionSigned; 3],
+        index: bool,
+    ) -> OtterscanApi<TestEthApi> {
+        let provider = address_transactions_provider(transactions, index);
+        let eth = EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        OtterscanApi::new(eth)
+    }
+
+    /// Returns the hashes of the transactions found by searching the address from the latest
+    /// block backwards and from genesis forwards, and whether the backward search is complete.
+    async fn search(
+        api: &OtterscanApi<TestEthApi>,
+        address: Address,
+        page_size: usize,
+    ) -> (Vec<TxHash>, Vec<TxHash>, bool) {
+        let start = LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(0));
+        let before = api.search_transactions_before(address, start, page_size).await.unwrap();
+        let after = api.search_transactions_after(address, start, page_size).await.unwrap();
+        let hashes = |page: TransactionsWithReceipts<_>| {
+            assert_eq!(page.txs.len(), page.receipts.len());
+            page.txs.iter().map(|tx: &alloy_rpc_types_eth::Transaction| tx.tx_hash()).collect()
+        };
+        let last_page = before.last_page;
+        (hashes(before), hashes(after), last_page)
+    }
+
+    #[tokio::test]
+    async fn search_transactions() {
+        let mut rng = generators::rng();
+        let key = generate_key(&mut rng);
+        let first = sign_tx_with_key_pair(key, random_tx(&mut rng));
+        let last = sign_tx_with_key_pair(key, random_tx(&mut rng));
+        // a call without value doesn't change the state of the callee
+        let Transaction::Legacy(call) = random_tx(&mut rng) else { unreachable!() };
+        let call = sign_tx_with_random_key_pair(
+            &mut rng,
+            Transaction::Legacy(TxLegacy { value: U256::ZERO, ..call }),
+        );
+
+        let sender = first.recover_signer().unwrap();
+        let recipient = first.to().unwrap();
+        let callee = call.to().unwrap();
+        let hashes = [*first.tx_hash(), *call.tx_hash(), *last.tx_hash()];
+        let api = otterscan_api([first, call, last], true);
+
+        // the sender is found in the index and in the unindexed last block
+        let (before, after, last_page) = search(&api, sender, 10).await;
+        assert_eq!(before, vec![hashes[2], hashes[0]]);
+        assert_eq!(after, before);
+        assert!(last_page);
+
+        let (before, after, _) = search(&api, recipient, 10).await;
+        assert_eq!(before, vec![hashes[0]]);
+        assert_eq!(after, before);
+
+        let (before, after, _) = search(&api, callee, 10).await;
+        assert_eq!(before, vec![hashes[1]]);
+        assert_eq!(after, before);
+
+        // pages end at the first block that fills them
+        let (before, _, last_page) = search(&api, sender, 1).await;
+        assert_eq!(before, vec![hashes[2]]);
+        assert!(!last_page);
+    }
+
+    #[tokio::test]
+    async fn search_transactions_without_index() {
+        let mut rng = generators::rng();
+        let key = generate_key(&mut rng);
+        let first = sign_tx_with_key_pair(key, random_tx(&mut rng));
+        let last = sign_tx_with_key_pair(key, random_tx(&mut rng));
+        let Transaction::Legacy(call) = random_tx(&mut rng) else { unreachable!() };
+        let call = sign_tx_with_random_key_pair(
+            &mut rng,
+            Transaction::Legacy(TxLegacy { value: U256::ZERO, ..call }),
+        );
+
+        let sender = first.recover_signer().unwrap();
+        let callee = call.to().unwrap();
+        let hashes = [*first.tx_hash(), *last.tx_hash()];
+        let api = otterscan_api([first, call, last], false);
+
+        // the sender changed in the blocks of its transactions, the callee never changed
+        let provider = api.eth.provider().database_provider_rw().unwrap();
+        provider.insert_account_history_index([(sender, [1, 3])]).unwrap();
+        provider.commit().unwrap();
+
+        let (before, after, last_page) = search(&api, sender, 10).await;
+        assert_eq!(before, vec![hashes[1], hashes[0]]);
+        assert_eq!(after, before);
+        assert!(last_page);
+
+        // transactions that didn't change the state of the address are not found
+        let (before, after, _) = search(&api, callee, 10).await;
+        assert!(before.is_empty());
+        assert!(after.is_empty());
+    }
+}
@@ -1,21 +1,31 @@
 use std::{collections::HashMap, future::Future, sync::Arc};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, BlockNumber, TxHash, U256};
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
 use reth_errors::RethResult;
-use reth_primitives_traits::NodePrimitives;
+use reth_primitives_traits::{NodePrimitives, SignedTransaction};
 use reth_rpc_api::RethApiServer;
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_storage_api::{
+    transaction_addresses, AddressTransactionsReader, BlockReaderIdExt, ChangeSetReader,
+    StateProviderFactory, TransactionVariant,
+};
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
 
+/// Maximum number of transactions returned by `reth_getTransactionsByAddress`.
+const MAX_ADDRESS_TRANSACTIONS: usize = 10_000;
+
+/// Maximum number of blocks past the end of the address transactions index that
+/// `reth_getTransactionsByAddress` scans.
+const MAX_UNINDEXED_BLOCKS: u64 = 1_024;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -40,7 +50,11 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + AddressTransactionsReader
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -88,6 +102,107 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the hashes of the transactions of an address in the given block range.
+    pub async fn transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> EthResult<Vec<TxHash>> {
+        self.on_blocking_task(|this| async move {
+            this.try_transactions_by_address(address, from_block, to_block)
+        })
+        .await
+    }
+
+    fn try_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> EthResult<Vec<TxHash>> {
+        let Some(indexed) = self.provider().address_transactions_range()? else {
+            return Err(EthApiError::Unsupported("address transactions index is not enabled"))
+        };
+
+        let block_number = |block: BlockNumberOrTag| -> EthResult<BlockNumber> {
+            self.provider()
+                .convert_block_number(block)?
+                .ok_or(EthApiError::HeaderNotFound(block.into()))
+        };
+        let from = from_block.map(block_number).transpose()?.unwrap_or_else(|| *indexed.start());
+        let to = block_number(to_block.unwrap_or_default())?;
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if from < *indexed.start() {
+            return Err(EthApiError::PrunedHistoryUnavailable)
+        }
+
+        let mut hashes = Vec::new();
+        let too_many_results = || {
+            EthApiError::InvalidParams(format!(
+                "query exceeds max results {MAX_ADDRESS_TRANSACTIONS}, narrow the block range"
+            ))
+        };
+
+        // Indexed part of the range
+        let indexed_end = to.min(*indexed.end());
+        if from <= indexed_end {
+            let first_tx = self
+                .provider()
+                .block_body_indices(from)?
+                .ok_or(EthApiError::HeaderNotFound(from.into()))?
+                .first_tx_num();
+            let next_tx = self
+                .provider()
+                .block_body_indices(indexed_end)?
+                .ok_or(EthApiError::HeaderNotFound(indexed_end.into()))?
+                .next_tx_num();
+            if first_tx < next_tx {
+                let tx_numbers =
+                    self.provider().address_transactions(address, first_tx..=next_tx - 1)?;
+                if tx_numbers.len() > MAX_ADDRESS_TRANSACTIONS {
+                    return Err(too_many_results())
+                }
+                for tx_number in tx_numbers {
+                    let transaction = self
+                        .provider()
+                        .transaction_by_id_unhashed(tx_number)?
+                        .ok_or(EthApiError::TransactionNotFound)?;
+                    hashes.push(*transaction.tx_hash());
+                }
+            }
+        }
+
+        // Blocks that haven't been indexed yet are scanned directly
+        let unindexed_start = from.max(*indexed.end() + 1);
+        if unindexed_start <= to {
+            if to - unindexed_start >= MAX_UNINDEXED_BLOCKS {
+                return Err(EthApiError::InvalidParams(format!(
+                    "address transactions index is behind by more than {MAX_UNINDEXED_BLOCKS} \
+                     blocks, narrow the block range"
+                )))
+            }
+            for number in unindexed_start..=to {
+                let block = self
+                    .provider()
+                    .recovered_block(number.into(), TransactionVariant::WithHash)?
+                    .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                for (sender, transaction) in block.transactions_with_sender() {
+                    if transaction_addresses(*sender, transaction).contains(&address) {
+                        hashes.push(*transaction.tx_hash());
+                    }
+                }
+                if hashes.len() > MAX_ADDRESS_TRANSACTIONS {
+                    return Err(too_many_results())
+                }
+            }
+        }
+
+        Ok(hashes)
+    }
 }
 
 #[async_trait]
@@ -96,6 +211,7 @@ where
     Provider: BlockReaderIdExt
         + ChangeSetReader
         + StateProviderFactory
+        + AddressTransactionsReader
         + CanonStateSubscriptions
         + 'static,
 {
@@ -107,6 +223,16 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getTransactionsByAddress`
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> RpcResult<Vec<TxHash>> {
+        Ok(Self::transactions_by_address(self, address, from_block, to_block).await?)
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
@@ -170,3 +296,148 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody, Transaction as _};
+    use alloy_primitives::B256;
+    use reth_ethereum_primitives::{Block, Receipt, TransactionSigned};
+    use reth_primitives_traits::{Block as _, SignerRecoverable};
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        AddressTransactionsWriter, BlockWriter, DatabaseProviderFactory, ExecutionOutcome,
+        StageCheckpointWriter,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators::{
+        self, generate_key, random_block_range, random_tx, sign_tx_with_key_pair, BlockRangeParams,
+    };
+
+    /// Seeds blocks `0..=3` with the given transactions in blocks `1..=3` and, if `index` is set,
+    /// indexes the address transactions of blocks `0..=2`, so the last block is only found by
+    /// scanning it.
+    pub(crate) fn address_transactions_provider(
+        transactions: [TransactionSigned; 3],
+        index: bool,
+    ) -> BlockchainProvider<MockNodeTypesWithDB> {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+
+        let mut blocks = random_block_range(
+            &mut rng,
+            0..=3,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        for (block, transaction) in blocks.iter_mut().skip(1).zip(transactions) {
+            let body = BlockBody { transactions: vec![transaction], ..Default::default() };
+            *block = Block::new(block.header().clone(), body).seal_slow();
+        }
+        let receipts = blocks
+            .iter()
+            .map(|block| {
+                block
+                    .body()
+                    .transactions
+                    .iter()
+                    .map(|tx| Receipt {
+                        tx_type: tx.tx_type(),
+                        success: true,
+                        ..Default::default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let provider = factory.database_provider_rw().unwrap();
+        provider
+            .append_blocks_with_state(
+                blocks.iter().map(|block| block.clone().try_recover().unwrap()).collect(),
+                &ExecutionOutcome { receipts, ..Default::default() },
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap();
+        if index {
+            provider
+                .insert_address_transaction_indices(
+                    blocks[..3]
+                        .iter()
+                        .flat_map(|block| block.body().transactions.iter())
+                        .enumerate()
+                        .map(|(tx_number, tx)| {
+                            let sender = tx.recover_signer().unwrap();
+                            (tx_number as u64, transaction_addresses(sender, tx))
+                        }),
+                )
+                .unwrap();
+            provider
+                .save_stage_checkpoint(StageId::IndexAddressTransactions, StageCheckpoint::new(2))
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        BlockchainProvider::new(factory).unwrap()
+    }
+
+    fn reth_api(
+        transactions: [TransactionSigned; 3],
+        index: bool,
+    ) -> RethApi<BlockchainProvider<MockNodeTypesWithDB>> {
+        let provider = address_transactions_provider(transactions, index);
+        RethApi::new(provider, Box::new(TokioTaskExecutor::default()))
+    }
+
+    #[tokio::test]
+    async fn transactions_by_address() {
+        let mut rng = generators::rng();
+        let key = generate_key(&mut rng);
+        let first = sign_tx_with_key_pair(key, random_tx(&mut rng));
+        let other = sign_tx_with_key_pair(generate_key(&mut rng), random_tx(&mut rng));
+        let last = sign_tx_with_key_pair(key, random_tx(&mut rng));
+
+        let sender = first.recover_signer().unwrap();
+        let recipient = other.to().unwrap();
+        let hashes = [*first.tx_hash(), *other.tx_hash(), *last.tx_hash()];
+        let api = reth_api([first, other, last], true);
+
+        // the sender is found in the index and in the unindexed last block
+        let found = api.transactions_by_address(sender, None, None).await.unwrap();
+        assert_eq!(found, vec![hashes[0], hashes[2]]);
+
+        let found = api.transactions_by_address(recipient, None, None).await.unwrap();
+        assert_eq!(found, vec![hashes[1]]);
+
+        // only the unindexed block
+        let found =
+            api.transactions_by_address(sender, Some(3.into()), Some(3.into())).await.unwrap();
+        assert_eq!(found, vec![hashes[2]]);
+
+        // only the indexed blocks
+        let found =
+            api.transactions_by_address(sender, Some(0.into()), Some(2.into())).await.unwrap();
+        assert_eq!(found, vec![hashes[0]]);
+
+        assert!(matches!(
+            api.transactions_by_address(sender, Some(3.into()), Some(2.into())).await,
+            Err(EthApiError::InvalidBlockRange)
+        ));
+    }
+
+    #[tokio::test]
+    async fn transactions_by_address_without_index() {
+        let mut rng = generators::rng();
+        let transactions = std::array::from_fn(|_| {
+            sign_tx_with_key_pair(generate_key(&mut rng), random_tx(&mut rng))
+        });
+        let sender = transactions[0].recover_signer().unwrap();
+        let api = reth_api(transactions, false);
+
+        assert!(matches!(
+            api.transactions_by_address(sender, None, None).await,
+            Err(EthApiError::Unsupported(_))
+        ));
+    }
+}
@@ -2,7 +2,7 @@ use core::fmt;
 use std::collections::BTreeMap;
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
@@ -12,6 +12,9 @@ use reth_primitives_traits::NodePrimitives;
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
+use reth_rpc_eth_types::txpool::{
+    TxpoolEvictedTransaction, TxpoolSubPoolStats, TxpoolTransactionStatus,
+};
 use reth_transaction_pool::{
     AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
 };
@@ -147,6 +150,33 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Returns the sub-pool of the transaction and the reasons it is not pending.
+    ///
+    /// Handler for `txpool_transactionStatus`
+    async fn txpool_transaction_status(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TxpoolTransactionStatus>> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_transactionStatus");
+        Ok(self.pool.transaction_status(&hash).map(Into::into))
+    }
+
+    /// Returns the transactions that were recently removed from the pool without being mined.
+    ///
+    /// Handler for `txpool_evicted`
+    async fn txpool_evicted(&self) -> RpcResult<Vec<TxpoolEvictedTransaction>> {
+        trace!(target: "rpc::eth", "Serving txpool_evicted");
+        Ok(self.pool.evicted_transactions().into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the number of transactions and their size in bytes of each sub-pool.
+    ///
+    /// Handler for `txpool_subPoolStats`
+    async fn txpool_sub_pool_stats(&self) -> RpcResult<TxpoolSubPoolStats> {
+        trace!(target: "rpc::eth", "Serving txpool_subPoolStats");
+        Ok(self.pool.pool_size().into())
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
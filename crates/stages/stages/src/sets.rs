@@ -39,9 +39,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
-        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        HeaderStage, IndexAccountHistoryStage, IndexAddressTransactionsStage, IndexLogsStage,
+        IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage, PruneStage,
+        SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -419,6 +419,9 @@ where
 }
 
 /// A set containing all stages that do additional indexing for historical state.
+///
+/// The [`IndexLogsStage`] and the [`IndexAddressTransactionsStage`] are only included if their
+/// index is enabled in the [`StageConfig`].
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct HistoryIndexingStages {
@@ -433,6 +436,8 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexLogsStage: Stage<Provider>,
+    IndexAddressTransactionsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -451,5 +456,15 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.account_history,
             ))
+            .add_stage_opt(self.stages_config.index_logs.enabled.then(|| {
+                IndexLogsStage::new(self.stages_config.index_logs, self.stages_config.etl.clone())
+            }))
+            .add_stage_opt(self.stages_config.index_address_transactions.enabled.then(|| {
+                IndexAddressTransactionsStage::new(
+                    self.stages_config.index_address_transactions,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.address_transactions,
+                )
+            }))
     }
 }
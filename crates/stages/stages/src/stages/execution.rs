@@ -1,27 +1,35 @@
 use crate::stages::MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD;
 use alloy_consensus::{BlockHeader, Header};
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{BlockNumber, B256};
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db::{static_file::HeaderMask, tables};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
+    transaction::DbTxMut,
+};
 use reth_evm::{execute::Executor, metrics::ExecutorMetrics, ConfigureEvm};
 use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
+use reth_primitives_traits::{
+    format_gas_throughput, Account, Block, BlockBody, NodePrimitives, StorageEntry,
+};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HeaderProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateCommitmentProvider,
-    StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation, TransactionVariant,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StageCheckpointReader,
+    StateCommitmentProvider, StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation,
+    TransactionVariant,
 };
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{database::StateProviderDatabase, db::CacheState};
 use reth_stages_api::{
     BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
     ExecutionCheckpoint, ExecutionStageThresholds, Stage, StageCheckpoint, StageError, StageId,
     UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
+use reth_trie_db::StateCommitment;
 use std::{
     cmp::Ordering,
     ops::RangeInclusive,
@@ -31,7 +39,7 @@ use std::{
 };
 use tracing::*;
 
-use super::missing_static_data_error;
+use super::{is_snap_synced, missing_static_data_error};
 
 /// The execution stage executes all transactions and
 /// update history indexes.
@@ -252,7 +260,7 @@ where
 impl<E, Provider> Stage<Provider> for ExecutionStage<E>
 where
     E: ConfigureEvm,
-    Provider: DBProvider
+    Provider: DBProvider<Tx: DbTxMut>
         + BlockReader<
             Block = <E::Primitives as NodePrimitives>::Block,
             Header = <E::Primitives as NodePrimitives>::BlockHeader,
@@ -260,7 +268,8 @@ where
         + StatsReader
         + BlockHashReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
-        + StateCommitmentProvider,
+        + StateCommitmentProvider
+        + StageCheckpointReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -289,6 +298,7 @@ where
 
         self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
 
+        let snap_synced = is_snap_synced(provider)?;
         let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
         let mut executor = self.evm_config.batch_executor(db);
 
@@ -391,11 +401,8 @@ where
 
         // prepare execution output for writing
         let time = Instant::now();
-        let mut state = ExecutionOutcome::from_blocks(
-            start_block,
-            executor.into_state().take_bundle(),
-            results,
-        );
+        let mut db = executor.into_state();
+        let mut state = ExecutionOutcome::from_blocks(start_block, db.take_bundle(), results);
         let write_preparation_duration = time.elapsed();
 
         // log the gas per second for the range we just executed
@@ -454,6 +461,16 @@ where
         // write output
         provider.write_state(&state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
 
+        // Plain state misses of snap synced nodes are resolved from the hashed state, so it has to
+        // be kept up to date before the next batch is executed. The entries that were only read
+        // from the hashed state are backfilled, so the plain state fills up as it is used.
+        if snap_synced {
+            let hashed_state = state
+                .hash_state_slow::<<Provider::StateCommitment as StateCommitment>::KeyHasher>();
+            provider.write_hashed_state(&hashed_state.into_sorted())?;
+            backfill_plain_state(provider, &db.cache)?;
+        }
+
         let db_write_duration = time.elapsed();
         debug!(
             target: "sync::stages::execution",
@@ -560,6 +577,33 @@ where
     }
 }
 
+/// Writes the accounts and storage slots of the cache that are missing from the plain state.
+///
+/// The plain state of a snap synced node only contains the entries that were read or changed
+/// after the pivot block, everything else is read from the hashed state by
+/// [`LatestStateProviderRef`]. The changed entries are already written with the execution outcome,
+/// so this only writes the entries that were read.
+fn backfill_plain_state<Provider>(provider: &Provider, cache: &CacheState) -> Result<(), StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    let mut accounts = provider.tx_ref().cursor_write::<tables::PlainAccountState>()?;
+    let mut storages = provider.tx_ref().cursor_dup_write::<tables::PlainStorageState>()?;
+    for (address, account) in &cache.accounts {
+        let Some(account) = &account.account else { continue };
+        if accounts.seek_exact(*address)?.is_none() {
+            accounts.upsert(*address, &Account::from(&account.info))?;
+        }
+        for (slot, value) in account.storage.iter().filter(|(_, value)| !value.is_zero()) {
+            let key = B256::from(*slot);
+            if storages.seek_by_key_subkey(*address, key)?.is_none_or(|entry| entry.key != key) {
+                storages.upsert(*address, &StorageEntry { key, value: *value })?;
+            }
+        }
+    }
+    Ok(())
+}
+
 fn execution_checkpoint<N: NodePrimitives>(
     provider: &StaticFileProvider<N>,
     start_block: BlockNumber,
@@ -661,10 +705,7 @@ mod tests {
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use reth_chainspec::ChainSpecBuilder;
-    use reth_db_api::{
-        models::AccountBeforeTx,
-        transaction::{DbTx, DbTxMut},
-    };
+    use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut};
     use reth_ethereum_consensus::EthBeaconConsensus;
     use reth_ethereum_primitives::Block;
     use reth_evm_ethereum::EthEvmConfig;
@@ -1231,4 +1272,58 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn backfill_plain_state() {
+        let factory = create_test_provider_factory();
+        let read = address!("0x1000000000000000000000000000000000000000");
+        let written = address!("0x2000000000000000000000000000000000000000");
+        let read_info = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let written_info = Account { nonce: 2, balance: U256::from(20), bytecode_hash: None };
+
+        let provider = factory.database_provider_rw().unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(written, written_info).unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::PlainStorageState>(
+                written,
+                StorageEntry { key: B256::with_last_byte(1), value: U256::from(3) },
+            )
+            .unwrap();
+
+        // the cache of an execution that read both accounts from the hashed state
+        let mut cache = CacheState::new(true);
+        cache.insert_account_with_storage(
+            read,
+            read_info.into(),
+            [(U256::from(1), U256::from(1)), (U256::from(2), U256::ZERO)].into_iter().collect(),
+        );
+        cache.insert_account_with_storage(
+            written,
+            Account { nonce: 3, ..written_info }.into(),
+            [(U256::from(1), U256::from(4)), (U256::from(2), U256::from(5))].into_iter().collect(),
+        );
+        super::backfill_plain_state(&provider, &cache).unwrap();
+
+        let tx = provider.tx_ref();
+        assert_eq!(tx.get::<tables::PlainAccountState>(read).unwrap(), Some(read_info));
+        assert_eq!(tx.get::<tables::PlainAccountState>(written).unwrap(), Some(written_info));
+        let storage = tx
+            .cursor_dup_read::<tables::PlainStorageState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.map(|(address, entry)| (address, entry.key, entry.value)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        // zero slots are not stored and existing entries are not overwritten
+        assert_eq!(
+            storage,
+            vec![
+                (read, B256::with_last_byte(1), U256::from(1)),
+                (written, B256::with_last_byte(1), U256::from(3)),
+                (written, B256::with_last_byte(2), U256::from(5)),
+            ]
+        );
+    }
 }
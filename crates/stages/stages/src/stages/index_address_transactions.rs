@@ -0,0 +1,309 @@
+use super::{flush_indices, load_history_indices, push_index, DEFAULT_CACHE_THRESHOLD};
+use alloy_primitives::{Address, TxNumber};
+use reth_config::config::{EtlConfig, IndexAddressTransactionsConfig};
+use reth_db_api::{
+    models::ShardedKey, table::Decode, tables, transaction::DbTxMut, BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{
+    transaction_addresses_by_tx_range, AddressTransactionsWriter, BlockReader, DBProvider,
+    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, TransactionsProviderExt,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, ops::RangeInclusive};
+use tracing::info;
+
+/// Number of transactions that are read at once.
+const TRANSACTIONS_BATCH_SIZE: u64 = 10_000;
+
+/// Stage is indexing the transactions of each address: the transactions it sent, the transactions
+/// sent to it, and the transaction that created it. For more information on index sharding take a
+/// look at [`tables::AddressTransactions`].
+///
+/// Not part of the default pipeline unless the index is enabled in the stage configuration.
+#[derive(Debug)]
+pub struct IndexAddressTransactionsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexAddressTransactionsStage {
+    /// Create new instance of [`IndexAddressTransactionsStage`].
+    pub const fn new(
+        config: IndexAddressTransactionsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexAddressTransactionsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexAddressTransactionsStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader
+        + TransactionsProviderExt
+        + AddressTransactionsWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressTransactions,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::AddressTransactions)?.is_none() {
+                    let target_prunable_tx_number = provider
+                        .block_body_indices(target_prunable_block)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(target_prunable_block))?
+                        .last_tx_num();
+
+                    provider.save_prune_checkpoint(
+                        PruneSegment::AddressTransactions,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: Some(target_prunable_tx_number),
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync there might be leftovers of a previously dropped index. We clear the table
+        // since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressTransactions>()?;
+        }
+
+        let tx_range = provider.transaction_range_by_block_range(range.clone())?;
+
+        info!(target: "sync::stages::index_address_transactions::exec", ?first_sync, ?tx_range, "Collecting indices");
+        let collector = collect_address_transactions(provider, tx_range, &self.etl_config)?;
+
+        info!(target: "sync::stages::index_address_transactions::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_address_transaction_indices_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Collects the transactions of each address into a [`Collector`], in the same format as
+/// [`collect_history_indices`](super::collect_history_indices) does for changesets, with
+/// transaction numbers in place of block numbers.
+fn collect_address_transactions<Provider: BlockReader>(
+    provider: &Provider,
+    tx_range: RangeInclusive<TxNumber>,
+    etl_config: &EtlConfig,
+) -> Result<Collector<ShardedKey<Address>, BlockNumberList>, StageError> {
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut cache = HashMap::<Address, Vec<TxNumber>>::default();
+
+    let mut flush_counter = 0;
+    let mut batch_start = *tx_range.start();
+    while batch_start <= *tx_range.end() {
+        let batch_end =
+            (*tx_range.end()).min(batch_start.saturating_add(TRANSACTIONS_BATCH_SIZE - 1));
+        let batch = batch_start..=batch_end;
+        for (tx_number, addresses) in
+            batch.clone().zip(transaction_addresses_by_tx_range(provider, batch)?)
+        {
+            for address in addresses {
+                push_index(cache.entry(address).or_default(), tx_number);
+            }
+        }
+
+        // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD transactions.
+        flush_counter += batch_end - batch_start + 1;
+        if flush_counter > DEFAULT_CACHE_THRESHOLD {
+            flush_indices(&mut cache, &mut collector)?;
+            flush_counter = 0;
+        }
+
+        batch_start = batch_end + 1;
+    }
+    flush_indices(&mut cache, &mut collector)?;
+
+    Ok(collector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, StorageKind,
+        TestRunnerError, TestStageDB, UnwindStageTestRunner,
+    };
+    use alloy_primitives::{BlockNumber, B256};
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_ethereum_primitives::Block;
+    use reth_primitives_traits::{SealedBlock, SignerRecoverable};
+    use reth_provider::{providers::StaticFileWriter, transaction_addresses};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::collections::BTreeMap;
+
+    stage_test_suite_ext!(IndexAddressTransactionsTestRunner, index_address_transactions);
+
+    #[derive(Default)]
+    struct IndexAddressTransactionsTestRunner {
+        db: TestStageDB,
+        blocks: Vec<SealedBlock<Block>>,
+    }
+
+    impl IndexAddressTransactionsTestRunner {
+        /// Builds the expected index from the seeded blocks of the range.
+        fn expected_index(
+            &self,
+            range: RangeInclusive<BlockNumber>,
+        ) -> BTreeMap<Address, Vec<TxNumber>> {
+            let mut index = BTreeMap::<_, Vec<_>>::new();
+            let mut tx_number = 0;
+            for block in &self.blocks {
+                for transaction in &block.body().transactions {
+                    if range.contains(&block.number) {
+                        let sender = transaction.recover_signer().unwrap();
+                        for address in transaction_addresses(sender, transaction) {
+                            push_index(index.entry(address).or_default(), tx_number);
+                        }
+                    }
+                    tx_number += 1;
+                }
+            }
+            index
+        }
+    }
+
+    impl StageTestRunner for IndexAddressTransactionsTestRunner {
+        type S = IndexAddressTransactionsStage;
+
+        fn db(&self) -> &TestStageDB {
+            &self.db
+        }
+
+        fn stage(&self) -> Self::S {
+            IndexAddressTransactionsStage::default()
+        }
+    }
+
+    impl ExecuteStageTestRunner for IndexAddressTransactionsTestRunner {
+        type Seed = ();
+
+        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
+            let mut rng = generators::rng();
+            self.blocks = random_block_range(
+                &mut rng,
+                0..=input.target(),
+                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..3, ..Default::default() },
+            );
+            self.db.insert_blocks(self.blocks.iter(), StorageKind::Static)?;
+
+            Ok(())
+        }
+
+        fn validate_execution(
+            &self,
+            input: ExecInput,
+            output: Option<ExecOutput>,
+        ) -> Result<(), TestRunnerError> {
+            if let Some(output) = output {
+                let start_block = input.next_block();
+                let end_block = output.checkpoint.block_number;
+                if start_block > end_block {
+                    return Ok(())
+                }
+
+                assert_eq!(
+                    output,
+                    ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true }
+                );
+
+                let expected = self.expected_index(start_block..=end_block);
+                let mut index = BTreeMap::<_, Vec<_>>::new();
+                for (key, list) in self.db.table::<tables::AddressTransactions>()? {
+                    index.entry(key.key).or_default().extend(list.iter());
+                }
+                assert_eq!(index, expected);
+
+                // the last shard of each address is keyed by `u64::MAX`
+                let provider = self.db.factory.provider()?;
+                let mut cursor = provider.tx_ref().cursor_read::<tables::AddressTransactions>()?;
+                for address in expected.keys() {
+                    assert!(cursor.seek_exact(ShardedKey::last(*address))?.is_some());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl UnwindStageTestRunner for IndexAddressTransactionsTestRunner {
+        fn validate_unwind(&self, _input: UnwindInput) -> Result<(), TestRunnerError> {
+            assert!(self.db.table::<tables::AddressTransactions>()?.is_empty());
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use super::{flush_indices, load_history_indices, push_index, DEFAULT_CACHE_THRESHOLD};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db_api::{
+    models::ShardedKey, table::Decode, tables, transaction::DbTxMut, BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{DBProvider, LogIndexWriter, PruneCheckpointReader, ReceiptProvider};
+use reth_prune_types::PruneSegment;
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, ops::RangeInclusive};
+use tracing::info;
+
+/// Number of blocks whose receipts are read at once.
+const RECEIPTS_BATCH_SIZE: u64 = 1_000;
+
+/// Stage is indexing the addresses and topics of the logs emitted in each block, so that
+/// `eth_getLogs` doesn't have to check the bloom filter of every header in the requested range.
+/// For more information on index sharding take a look at [`tables::LogAddressIndex`] and
+/// [`tables::LogTopicIndex`].
+///
+/// Not part of the default pipeline unless the log index is enabled in the stage configuration.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(config: IndexLogsConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + ReceiptProvider + LogIndexWriter + PruneCheckpointReader,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync there might be leftovers of a previously dropped index. We clear the
+        // tables since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+        }
+
+        // Receipts below the prune checkpoints are gone, so there's nothing to index there.
+        let mut start = *range.start();
+        for segment in [PruneSegment::ContractLogs, PruneSegment::Receipts] {
+            if let Some(pruned) = provider
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+            {
+                start = start.max(pruned + 1);
+            }
+        }
+
+        if start <= *range.end() {
+            info!(target: "sync::stages::index_logs::exec", ?first_sync, "Collecting indices");
+            let (addresses, topics) =
+                collect_log_indices(provider, start..=*range.end(), &self.etl_config)?;
+
+            info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+            load_history_indices::<_, tables::LogAddressIndex, _>(
+                provider,
+                addresses,
+                first_sync,
+                ShardedKey::new,
+                ShardedKey::<Address>::decode_owned,
+                |key| key.key,
+            )?;
+            load_history_indices::<_, tables::LogTopicIndex, _>(
+                provider,
+                topics,
+                first_sync,
+                ShardedKey::new,
+                ShardedKey::<B256>::decode_owned,
+                |key| key.key,
+            )?;
+        }
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_indices_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Collects the blocks that emitted logs for each log address and each log topic into two
+/// [`Collector`]s, in the same format as
+/// [`collect_history_indices`](super::collect_history_indices) does for changesets.
+#[expect(clippy::type_complexity)]
+fn collect_log_indices<Provider: ReceiptProvider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<
+    (Collector<ShardedKey<Address>, BlockNumberList>, Collector<ShardedKey<B256>, BlockNumberList>),
+    StageError,
+> {
+    let mut address_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut topic_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut addresses = HashMap::<Address, Vec<BlockNumber>>::default();
+    let mut topics = HashMap::<B256, Vec<BlockNumber>>::default();
+
+    let mut flush_counter = 0;
+    let mut batch_start = *range.start();
+    while batch_start <= *range.end() {
+        let batch_end = (*range.end()).min(batch_start.saturating_add(RECEIPTS_BATCH_SIZE - 1));
+        let batch = batch_start..=batch_end;
+        for (block_number, receipts) in batch.clone().zip(provider.receipts_by_block_range(batch)?)
+        {
+            for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                push_index(addresses.entry(log.address).or_default(), block_number);
+                for topic in log.topics() {
+                    push_index(topics.entry(*topic).or_default(), block_number);
+                }
+            }
+        }
+
+        // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
+        flush_counter += batch_end - batch_start + 1;
+        if flush_counter > DEFAULT_CACHE_THRESHOLD {
+            flush_indices(&mut addresses, &mut address_collector)?;
+            flush_indices(&mut topics, &mut topic_collector)?;
+            flush_counter = 0;
+        }
+
+        batch_start = batch_end + 1;
+    }
+    flush_indices(&mut addresses, &mut address_collector)?;
+    flush_indices(&mut topics, &mut topic_collector)?;
+
+    Ok((address_collector, topic_collector))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, StorageKind,
+        TestRunnerError, TestStageDB, UnwindStageTestRunner,
+    };
+    use rand::Rng;
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_ethereum_primitives::Receipt;
+    use reth_provider::providers::StaticFileWriter;
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_log, random_receipt, BlockRangeParams,
+    };
+    use std::collections::BTreeMap;
+
+    stage_test_suite_ext!(IndexLogsTestRunner, index_logs);
+
+    #[derive(Default)]
+    struct IndexLogsTestRunner {
+        db: TestStageDB,
+    }
+
+    impl IndexLogsTestRunner {
+        /// Builds the expected log index from the receipts of the range.
+        #[expect(clippy::type_complexity)]
+        fn expected_indices(
+            &self,
+            range: RangeInclusive<BlockNumber>,
+        ) -> Result<
+            (BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>),
+            TestRunnerError,
+        > {
+            let provider = self.db.factory.provider()?;
+            let mut addresses = BTreeMap::<_, Vec<_>>::new();
+            let mut topics = BTreeMap::<_, Vec<_>>::new();
+            for (block_number, receipts) in
+                range.clone().zip(provider.receipts_by_block_range(range)?)
+            {
+                for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                    push_index(addresses.entry(log.address).or_default(), block_number);
+                    for topic in log.topics() {
+                        push_index(topics.entry(*topic).or_default(), block_number);
+                    }
+                }
+            }
+            Ok((addresses, topics))
+        }
+    }
+
+    /// Merges the shards of each key of a log index table.
+    fn merge_shards<K: Ord>(
+        entries: Vec<(ShardedKey<K>, BlockNumberList)>,
+    ) -> BTreeMap<K, Vec<BlockNumber>> {
+        let mut index = BTreeMap::<_, Vec<_>>::new();
+        for (key, list) in entries {
+            index.entry(key.key).or_default().extend(list.iter());
+        }
+        index
+    }
+
+    impl StageTestRunner for IndexLogsTestRunner {
+        type S = IndexLogsStage;
+
+        fn db(&self) -> &TestStageDB {
+            &self.db
+        }
+
+        fn stage(&self) -> Self::S {
+            IndexLogsStage::default()
+        }
+    }
+
+    impl ExecuteStageTestRunner for IndexLogsTestRunner {
+        type Seed = ();
+
+        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
+            let mut rng = generators::rng();
+            let blocks = random_block_range(
+                &mut rng,
+                0..=input.target(),
+                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..3, ..Default::default() },
+            );
+            self.db.insert_blocks(blocks.iter(), StorageKind::Static)?;
+
+            let mut receipts = Vec::with_capacity(blocks.len());
+            let mut tx_num = 0u64;
+            for block in &blocks {
+                let mut block_receipts = Vec::with_capacity(block.transaction_count());
+                for transaction in &block.body().transactions {
+                    // a handful of addresses, so that they show up in many blocks
+                    let logs = (0..rng.random_range(0..3))
+                        .map(|_| {
+                            let address = Address::with_last_byte(rng.random_range(0..4));
+                            random_log(&mut rng, Some(address), Some(2))
+                        })
+                        .collect();
+                    let receipt = random_receipt(&mut rng, transaction, Some(0));
+                    block_receipts.push((tx_num, Receipt { logs, ..receipt }));
+                    tx_num += 1;
+                }
+                receipts.push((block.number, block_receipts));
+            }
+            self.db.insert_receipts_by_block(receipts, StorageKind::Static)?;
+
+            Ok(())
+        }
+
+        fn validate_execution(
+            &self,
+            input: ExecInput,
+            output: Option<ExecOutput>,
+        ) -> Result<(), TestRunnerError> {
+            if let Some(output) = output {
+                let start_block = input.next_block();
+                let end_block = output.checkpoint.block_number;
+                if start_block > end_block {
+                    return Ok(())
+                }
+
+                assert_eq!(
+                    output,
+                    ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true }
+                );
+
+                let (addresses, topics) = self.expected_indices(start_block..=end_block)?;
+                assert_eq!(merge_shards(self.db.table::<tables::LogAddressIndex>()?), addresses);
+                assert_eq!(merge_shards(self.db.table::<tables::LogTopicIndex>()?), topics);
+
+                // the last shard of each key is keyed by `u64::MAX`
+                let provider = self.db.factory.provider()?;
+                let mut cursor = provider.tx_ref().cursor_read::<tables::LogAddressIndex>()?;
+                for address in addresses.keys() {
+                    assert!(cursor.seek_exact(ShardedKey::last(*address))?.is_some());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl UnwindStageTestRunner for IndexLogsTestRunner {
+        fn validate_unwind(&self, _input: UnwindInput) -> Result<(), TestRunnerError> {
+            assert!(self.db.table::<tables::LogAddressIndex>()?.is_empty());
+            assert!(self.db.table::<tables::LogTopicIndex>()?.is_empty());
+            Ok(())
+        }
+    }
+}
@@ -12,6 +12,10 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index transactions by the addresses they involve
+mod index_address_transactions;
+/// Index log addresses and topics
+mod index_logs;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -21,6 +25,8 @@ mod prune;
 mod s3;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The snap sync stage.
+mod snap;
 /// The transaction lookup stage
 mod tx_lookup;
 
@@ -32,11 +38,14 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_transactions::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
 pub use s3::*;
 pub use sender_recovery::*;
+pub use snap::*;
 pub use tx_lookup::*;
 
 mod era;
@@ -1,5 +1,6 @@
 use alloy_primitives::B256;
 use reth_fs_util::FsPathError;
+use reth_stages_api::StageError;
 
 /// Possible downloader error variants.
 #[derive(Debug, thiserror::Error)]
@@ -16,6 +17,9 @@ pub enum DownloaderError {
     // Empty content length returned from the server.
     #[error("metadata got an empty content length from server")]
     EmptyContentLength,
+    /// The remote file list could not be parsed.
+    #[error("invalid file list: {0}")]
+    InvalidFileList(String),
     /// Reqwest error
     #[error(transparent)]
     FsPath(#[from] FsPathError),
@@ -29,3 +33,15 @@ pub enum DownloaderError {
     #[error(transparent)]
     Bincode(#[from] bincode::Error),
 }
+
+impl From<DownloaderError> for StageError {
+    fn from(err: DownloaderError) -> Self {
+        match err {
+            // Retrying won't help if the remote serves corrupted data.
+            DownloaderError::InvalidFileHash(..) | DownloaderError::InvalidFileList(_) => {
+                Self::Fatal(Box::new(err))
+            }
+            _ => Self::Recoverable(Box::new(err)),
+        }
+    }
+}
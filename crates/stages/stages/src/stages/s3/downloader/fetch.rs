@@ -30,7 +30,8 @@ use tracing::{debug, error, info};
 /// 4) `Orchestrator` manages workers, distributes chunk ranges, and ensures the download progresses
 ///    efficiently by dynamically assigning tasks to workers as they become available.
 /// 5) Once the file is downloaded:
-///     * If `file_hash` is `Some`, verifies its blake3 hash.
+///     * If `file_hash` is `Some`, verifies its blake3 hash. On mismatch, the downloaded file and
+///       its metadata are deleted, so that the next attempt starts over.
 ///     * Deletes the metadata file
 ///     * Moves downloaded file to target directory.
 pub async fn fetch(
@@ -46,15 +47,13 @@ pub async fn fetch(
 
     let data_file = download_dir.join(filename);
     let mut metadata = metadata(&data_file, url).await?;
-    if metadata.is_done() {
-        return Ok(())
-    }
 
-    // Ensure the file is preallocated so we can download it concurrently
+    // Ensure the file is preallocated so we can download it concurrently. It's not truncated, since
+    // it might hold chunks downloaded before an interruption.
     {
         let file = OpenOptions::new()
             .create(true)
-            .truncate(true)
+            .truncate(false)
             .read(true)
             .write(true)
             .open(&data_file)?;
@@ -72,7 +71,8 @@ pub async fn fetch(
         let missing_chunks = metadata.needed_ranges();
         concurrent = concurrent
             .min(std::thread::available_parallelism()?.get() as u64)
-            .min(missing_chunks.len() as u64);
+            .min(missing_chunks.len() as u64)
+            .max(1);
 
         let mut orchestrator_rx = spawn_workers(url, concurrent, &data_file);
 
@@ -114,7 +114,12 @@ pub async fn fetch(
 
     if let Some(file_hash) = file_hash {
         info!(target: "sync::stages::s3::downloader", ?filename, "Checking file integrity.");
-        check_file_hash(&data_file, &file_hash)?;
+        if let Err(err) = check_file_hash(&data_file, &file_hash) {
+            // Start over on the next attempt instead of resuming a corrupted download.
+            metadata.delete()?;
+            reth_fs_util::remove_file(&data_file)?;
+            return Err(err)
+        }
     }
 
     // No longer need the metadata file.
@@ -151,7 +156,7 @@ async fn metadata(data_file: &Path, url: &str) -> Result<Metadata, DownloaderErr
 }
 
 /// Ensures the file on path has the expected blake3 hash.
-fn check_file_hash(path: &Path, expected: &B256) -> Result<(), DownloaderError> {
+pub(crate) fn check_file_hash(path: &Path, expected: &B256) -> Result<(), DownloaderError> {
     let mut reader = BufReader::new(File::open(path)?);
     let mut hasher = blake3::Hasher::new();
     std::io::copy(&mut reader, &mut hasher)?;
@@ -7,25 +7,20 @@ mod meta;
 mod worker;
 
 pub(crate) use error::DownloaderError;
+pub(crate) use fetch::check_file_hash;
 pub use fetch::fetch;
 pub use meta::Metadata;
+use reth_static_file_types::SegmentRangeInclusive;
 
-/// Response sent by the fetch task to `S3Stage` once it has downloaded all files of a block
-/// range.
+/// Response sent by the fetch task to `S3Stage`.
+#[derive(Debug)]
 pub(crate) enum S3DownloaderResponse {
-    /// A new block range was downloaded.
-    AddedNewRange,
-    /// The last requested block range was downloaded.
+    /// All files of a new block range were downloaded and verified.
+    AddedNewRange(SegmentRangeInclusive),
+    /// There are no more block ranges to download.
     Done,
 }
 
-impl S3DownloaderResponse {
-    /// Whether the downloaded block range is the last requested one.
-    pub(crate) const fn is_done(&self) -> bool {
-        matches!(self, Self::Done)
-    }
-}
-
 /// Chunk nth remaining range to be downloaded.
 #[derive(Debug)]
 pub struct RemainingChunkRange {
@@ -1,21 +1,147 @@
+use super::downloader::DownloaderError;
 use alloy_primitives::B256;
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+use std::collections::{BTreeMap, HashMap};
 
-/// File list to be downloaded with their hashes.
-pub(crate) static DOWNLOAD_FILE_LIST: [[(&str, B256); 3]; 2] = [
-    [
-        ("static_file_transactions_0_499999", B256::ZERO),
-        ("static_file_transactions_0_499999.off", B256::ZERO),
-        ("static_file_transactions_0_499999.conf", B256::ZERO),
-        // ("static_file_blockmeta_0_499999", B256::ZERO),
-        // ("static_file_blockmeta_0_499999.off", B256::ZERO),
-        // ("static_file_blockmeta_0_499999.conf", B256::ZERO),
-    ],
-    [
-        ("static_file_transactions_500000_999999", B256::ZERO),
-        ("static_file_transactions_500000_999999.off", B256::ZERO),
-        ("static_file_transactions_500000_999999.conf", B256::ZERO),
-        // ("static_file_blockmeta_500000_999999", B256::ZERO),
-        // ("static_file_blockmeta_500000_999999.off", B256::ZERO),
-        // ("static_file_blockmeta_500000_999999.conf", B256::ZERO),
-    ],
-];
+/// Name of the file list served by the remote endpoint.
+///
+/// Each line holds the blake3 hash of a static file followed by its name, as produced by `b3sum`:
+///
+/// ```text
+/// 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08  static_file_headers_0_499999
+/// ```
+pub(crate) const FILE_LIST: &str = "static_files.b3sum";
+
+/// Segments that are downloaded for every block range.
+///
+/// [`StaticFileSegment::BlockMeta`] is only read from, since it holds the block body indices,
+/// ommers and withdrawals that are written to the database.
+pub(crate) const SEGMENTS: [StaticFileSegment; 3] =
+    [StaticFileSegment::Headers, StaticFileSegment::BlockMeta, StaticFileSegment::Transactions];
+
+/// Extensions of the files that make up a static file segment.
+const EXTENSIONS: [&str; 3] = ["", ".off", ".conf"];
+
+/// Static files of a block range with their expected hashes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BlockRangeFiles {
+    /// Block range of the static files.
+    pub(crate) block_range: SegmentRangeInclusive,
+    /// File names and their expected blake3 hashes.
+    pub(crate) files: Vec<(String, B256)>,
+}
+
+/// Parses the remote file list into the block ranges it covers, sorted by block number.
+///
+/// Only the files of [`SEGMENTS`] are kept. Every block range must have all of them, and ranges
+/// must be contiguous starting from genesis.
+pub(crate) fn parse_file_list(contents: &str) -> Result<Vec<BlockRangeFiles>, DownloaderError> {
+    let mut hashes = HashMap::new();
+    let mut ranges = BTreeMap::new();
+
+    for line in contents.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (hash, filename) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| DownloaderError::InvalidFileList(format!("malformed line: {line}")))?;
+        let filename = filename.trim_start().trim_start_matches('*');
+        let hash: B256 = hash.parse().map_err(|_| {
+            DownloaderError::InvalidFileList(format!("invalid hash for {filename}: {hash}"))
+        })?;
+
+        let jar_name = EXTENSIONS
+            .iter()
+            .skip(1)
+            .find_map(|extension| filename.strip_suffix(extension))
+            .unwrap_or(filename);
+        if let Some((segment, block_range)) = StaticFileSegment::parse_filename(jar_name) {
+            if SEGMENTS.contains(&segment) {
+                ranges.insert(block_range.start(), block_range);
+                hashes.insert(filename.to_string(), hash);
+            }
+        }
+    }
+
+    let mut expected_start = 0;
+    ranges
+        .into_values()
+        .map(|block_range| {
+            if block_range.start() != expected_start {
+                return Err(DownloaderError::InvalidFileList(format!(
+                    "expected block range starting at {expected_start}, got {block_range}"
+                )));
+            }
+            expected_start = block_range.end() + 1;
+
+            let files = SEGMENTS
+                .iter()
+                .flat_map(|segment| {
+                    let jar_name = segment.filename(&block_range);
+                    EXTENSIONS.iter().map(move |extension| format!("{jar_name}{extension}"))
+                })
+                .map(|filename| {
+                    let hash = hashes.get(&filename).copied().ok_or_else(|| {
+                        DownloaderError::InvalidFileList(format!("missing {filename}"))
+                    })?;
+                    Ok((filename, hash))
+                })
+                .collect::<Result<_, DownloaderError>>()?;
+
+            Ok(BlockRangeFiles { block_range, files })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_list(ranges: &[(u64, u64)]) -> String {
+        ranges
+            .iter()
+            .flat_map(|(start, end)| {
+                SEGMENTS.iter().flat_map(move |segment| {
+                    let jar_name = segment.filename(&SegmentRangeInclusive::new(*start, *end));
+                    EXTENSIONS
+                        .iter()
+                        .map(move |extension| format!("{}  {jar_name}{extension}\n", B256::ZERO))
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_contiguous_ranges() {
+        let mut contents = file_list(&[(500_000, 999_999), (0, 499_999)]);
+        // Other segments are ignored.
+        contents.push_str(&format!("{}  static_file_receipts_0_499999\n", B256::ZERO));
+
+        let ranges = parse_file_list(&contents).unwrap();
+        assert_eq!(
+            ranges.iter().map(|range| range.block_range).collect::<Vec<_>>(),
+            vec![
+                SegmentRangeInclusive::new(0, 499_999),
+                SegmentRangeInclusive::new(500_000, 999_999)
+            ]
+        );
+        assert!(ranges.iter().all(|range| range.files.len() == SEGMENTS.len() * EXTENSIONS.len()));
+    }
+
+    #[test]
+    fn reject_invalid_file_lists() {
+        // Gap between block ranges.
+        let contents = file_list(&[(0, 499_999), (1_000_000, 1_499_999)]);
+        assert!(matches!(parse_file_list(&contents), Err(DownloaderError::InvalidFileList(_))));
+
+        // Missing offsets file.
+        let contents = file_list(&[(0, 499_999)])
+            .lines()
+            .filter(|line| !line.ends_with("static_file_transactions_0_499999.off"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(matches!(parse_file_list(&contents), Err(DownloaderError::InvalidFileList(_))));
+
+        // Invalid hash.
+        let contents = "0xzz  static_file_headers_0_499999";
+        assert!(matches!(parse_file_list(contents), Err(DownloaderError::InvalidFileList(_))));
+    }
+}
@@ -1,47 +1,114 @@
 mod downloader;
+use downloader::{check_file_hash, DownloaderError, S3DownloaderResponse};
 pub use downloader::{fetch, Metadata};
-use downloader::{DownloaderError, S3DownloaderResponse};
 
 mod filelist;
-use filelist::DOWNLOAD_FILE_LIST;
-
-use reth_db_api::transaction::DbTxMut;
+use filelist::{parse_file_list, BlockRangeFiles, FILE_LIST};
+
+mod staged;
+use staged::StagedBlockRange;
+
+use super::bodies::ensure_consistency;
+use alloy_consensus::{BlockBody, BlockHeader, Sealable};
+use alloy_rlp::Decodable;
+use futures_util::FutureExt;
+use reqwest::Client;
+use reth_codecs::Compact;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    table::Value,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_era_utils::save_stage_checkpoints;
+use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    DBProvider, StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory,
+    providers::StaticFileWriter, BlockHashReader, BlockReader, BlockWriter, DBProvider,
+    ProviderError, StageCheckpointWriter, StaticFileProviderFactory, StorageLocation,
 };
 use reth_stages_api::{
     ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
-use reth_static_file_types::StaticFileSegment;
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     task::{ready, Context, Poll},
 };
-use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::{
+    sync::mpsc::{channel, Receiver, Sender},
+    task::JoinHandle,
+};
+use tracing::info;
+
+/// The [`StageId`] of the [`S3Stage`].
+pub const S3_STAGE_ID: StageId = StageId::Other("S3");
 
-/// S3 `StageId`
-const S3_STAGE_ID: StageId = StageId::Other("S3");
+/// Default maximum number of connections per download.
+const DEFAULT_MAX_CONCURRENT_REQUESTS: u64 = 10;
+
+/// Number of blocks that are read from the downloaded static files before being appended.
+const APPEND_BATCH_SIZE: u64 = 10_000;
 
 /// The S3 stage.
-#[derive(Default, Debug)]
+///
+/// Bootstraps the headers and bodies of the chain from static files hosted on an S3-compatible
+/// endpoint or a plain HTTP mirror. Both are expected to serve the static files and a
+/// [`FILE_LIST`] with their blake3 hashes under the same base URL.
+///
+/// The headers, block meta and transactions static files of each block range are downloaded into
+/// their own directory, verified and then appended through the
+/// [`StaticFileProvider`](reth_provider::providers::StaticFileProvider), one block range per
+/// [`Stage::execute`] call. Since this does the same work as the `HeaderStage` and `BodyStage`,
+/// their checkpoints are advanced as well.
+///
+/// Downloads resume from where they were interrupted, and block ranges that were already
+/// downloaded and verified are not downloaded again.
+#[derive(Debug)]
 #[non_exhaustive]
 pub struct S3Stage {
-    /// Static file directory.
-    static_file_directory: PathBuf,
     /// Remote server URL.
     url: String,
+    /// Directory the static files are downloaded to, before being appended.
+    download_directory: PathBuf,
     /// Maximum number of connections per download.
     max_concurrent_requests: u64,
     /// Channel to receive the downloaded ranges from the fetch task.
-    fetch_rx: Option<UnboundedReceiver<Result<S3DownloaderResponse, DownloaderError>>>,
+    fetch_rx: Option<Receiver<Result<S3DownloaderResponse, DownloaderError>>>,
+    /// Handle of the last spawned fetch task.
+    fetch_task: Option<JoinHandle<()>>,
+    /// Last response received from the fetch task, to be handled by [`Stage::execute`].
+    response: Option<S3DownloaderResponse>,
 }
 
-impl<Provider> Stage<Provider> for S3Stage
+impl S3Stage {
+    /// Creates a new [`S3Stage`] that downloads the static files from `url` into
+    /// `download_directory`.
+    pub fn new(url: impl Into<String>, download_directory: impl Into<PathBuf>) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            download_directory: download_directory.into(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            fetch_rx: None,
+            fetch_task: None,
+            response: None,
+        }
+    }
+
+    /// Sets the maximum number of connections per download.
+    pub const fn with_max_concurrent_requests(mut self, max_concurrent_requests: u64) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self
+    }
+}
+
+impl<Provider, N> Stage<Provider> for S3Stage
 where
     Provider: DBProvider<Tx: DbTxMut>
-        + StaticFileProviderFactory
-        + StageCheckpointReader
+        + StaticFileProviderFactory<Primitives = N>
+        + BlockWriter<Block = N::Block>
+        + BlockReader<Block = N::Block>
         + StageCheckpointWriter,
+    N: NodePrimitives<BlockHeader: Value + Compact, SignedTx: Value>,
 {
     fn id(&self) -> StageId {
         S3_STAGE_ID
@@ -52,244 +119,592 @@ where
         cx: &mut Context<'_>,
         input: ExecInput,
     ) -> Poll<Result<(), StageError>> {
-        loop {
-            // We are currently fetching and may have downloaded ranges that we can process.
-            if let Some(rx) = &mut self.fetch_rx {
-                // Whether we have downloaded all the required files.
-                let mut is_done = false;
-
-                let response = match ready!(rx.poll_recv(cx)) {
-                    Some(Ok(response)) => {
-                        is_done = response.is_done();
-                        Ok(())
-                    }
-                    Some(Err(_)) => todo!(), // TODO: DownloaderError -> StageError
-                    None => Err(StageError::ChannelClosed),
-                };
+        if input.target_reached() || self.response.is_some() {
+            return Poll::Ready(Ok(()))
+        }
 
-                if is_done {
-                    self.fetch_rx = None;
+        let fetch_rx = match self.fetch_rx.take() {
+            Some(fetch_rx) => fetch_rx,
+            None => {
+                // A previous fetch task might still be downloading into the same directories.
+                if let Some(mut fetch_task) = self.fetch_task.take() {
+                    if fetch_task.poll_unpin(cx).is_pending() {
+                        self.fetch_task = Some(fetch_task);
+                        return Poll::Pending
+                    }
                 }
 
-                return Poll::Ready(response)
+                // Spawns the fetch task, which downloads all block ranges above the checkpoint
+                let (fetch_rx, fetch_task) = self.spawn_fetch(input);
+                self.fetch_task = Some(fetch_task);
+                fetch_rx
             }
+        };
+        let rx = self.fetch_rx.insert(fetch_rx);
 
-            // Spawns the downloader task if there are any missing files
-            if let Some(fetch_rx) = self.maybe_spawn_fetch(input) {
-                self.fetch_rx = Some(fetch_rx);
-
-                // Polls fetch_rx & registers waker
-                continue
+        let result = match ready!(rx.poll_recv(cx)) {
+            Some(Ok(response)) => {
+                if matches!(response, S3DownloaderResponse::Done) {
+                    self.fetch_rx = None;
+                }
+                self.response = Some(response);
+                Ok(())
+            }
+            Some(Err(err)) => {
+                self.fetch_rx = None;
+                Err(err.into())
+            }
+            None => {
+                self.fetch_rx = None;
+                Err(StageError::ChannelClosed)
             }
+        };
 
-            // No files to be downloaded
-            return Poll::Ready(Ok(()))
-        }
+        Poll::Ready(result)
     }
 
-    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError>
-    where
-        Provider: DBProvider<Tx: DbTxMut>
-            + StaticFileProviderFactory
-            + StageCheckpointReader
-            + StageCheckpointWriter,
-    {
-        // Re-initializes the provider to detect the new additions
-        provider.static_file_provider().initialize_index()?;
-
-        // TODO logic for appending tx_block
-
-        // let (_, _to_block) = input.next_block_range().into_inner();
-        // let static_file_provider = provider.static_file_provider();
-        // let mut _tx_block_cursor =
-        // provider.tx_ref().cursor_write::<tables::TransactionBlocks>()?;
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        let Some(S3DownloaderResponse::AddedNewRange(block_range)) = self.response.take() else {
+            // Nothing left to download, the remaining blocks are synced by the following stages.
+            self.fetch_rx = None;
+            let highest_block = provider
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Headers)
+                .unwrap_or_default();
+            return Ok(ExecOutput::done(StageCheckpoint::new(
+                highest_block.max(input.checkpoint().block_number),
+            )))
+        };
+
+        // Static files might be ahead of the database if a previous run was interrupted.
+        ensure_consistency(provider, None)?;
+
+        let static_file_provider = provider.static_file_provider();
+        let last_block = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default();
+
+        // Bodies are appended right after the headers, so both need to be at the same height.
+        provider
+            .block_body_indices(last_block)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(last_block))?;
+        let mut last_hash = static_file_provider
+            .block_hash(last_block)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(last_block.into()))?;
+
+        let staged = StagedBlockRange::open(&self.range_directory(&block_range), &block_range)?;
+        let to_block = staged.highest_block().unwrap_or_default().min(input.target());
+
+        info!(
+            target: "sync::stages::s3",
+            from = last_block + 1,
+            to = to_block,
+            "Appending downloaded static files"
+        );
+
+        let mut headers_writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+        for batch_start in (last_block + 1..=to_block).step_by(APPEND_BATCH_SIZE as usize) {
+            let batch = batch_start..=(batch_start + APPEND_BATCH_SIZE - 1).min(to_block);
+
+            for (header, td, hash) in staged.headers::<N::BlockHeader>(batch.clone())? {
+                if header.parent_hash() != last_hash || header.hash_slow() != hash {
+                    return Err(StageError::Fatal(
+                        format!("downloaded header #{} does not extend the chain", header.number())
+                            .into(),
+                    ))
+                }
 
-        // tx_block_cursor.append(indice.last_tx_num(), &block_number)?;
+                headers_writer.append_header(&header, td, &hash)?;
+                provider.tx_ref().put::<tables::HeaderNumbers>(hash, header.number())?;
+                last_hash = hash;
+            }
 
-        // let checkpoint = StageCheckpoint { block_number: highest_block, stage_checkpoint: None };
-        // provider.save_stage_checkpoint(StageId::Bodies, checkpoint)?;
-        // provider.save_stage_checkpoint(S3_STAGE_ID, checkpoint)?;
+            let bodies = staged
+                .bodies::<N::SignedTx, N::BlockHeader>(batch)?
+                .into_iter()
+                .map(|(number, body)| Ok((number, body.map(into_block_body::<N>).transpose()?)))
+                .collect::<Result<Vec<_>, StageError>>()?;
+            provider.append_block_bodies(bodies, StorageLocation::StaticFiles)?;
+        }
 
-        // // TODO: verify input.target according to s3 stage specifications
-        // let done = highest_block == to_block;
+        let height = to_block.max(last_block);
+        save_stage_checkpoints(
+            provider,
+            input.checkpoint().block_number,
+            height,
+            height,
+            input.target(),
+        )?;
+
+        let done = height >= input.target();
+        if done {
+            // The fetch task was spawned for this target, a new one is needed for the next one.
+            self.fetch_rx = None;
+        }
 
-        Ok(ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true })
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(height), done })
     }
 
     fn unwind(
         &mut self,
-        _provider: &Provider,
+        provider: &Provider,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
-        // TODO
+        self.fetch_rx = None;
+        self.response = None;
+
+        // `BodyStage` and `HeaderStage` might have already unwound the data appended by this stage.
+        let last_body = provider.tx_ref().cursor_read::<tables::BlockBodyIndices>()?.last()?;
+        if last_body.is_some_and(|(number, _)| number > input.unwind_to) {
+            ensure_consistency(provider, Some(input.unwind_to))?;
+            provider.remove_bodies_above(input.unwind_to, StorageLocation::Both)?;
+        }
+
+        let static_file_provider = provider.static_file_provider();
+        let highest_block = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default();
+        if highest_block > input.unwind_to {
+            for block_number in (input.unwind_to + 1)..=highest_block {
+                if let Some(hash) = static_file_provider.block_hash(block_number)? {
+                    provider.tx_ref().delete::<tables::HeaderNumbers>(hash, None)?;
+                }
+            }
+
+            let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+            writer.prune_headers(highest_block - input.unwind_to)?;
+        }
+
         Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
     }
 }
 
 impl S3Stage {
-    /// It will only spawn a task to fetch files from the remote server, it there are any missing
-    /// static files.
+    /// Returns the directory the static files of the block range are downloaded to.
+    fn range_directory(&self, block_range: &SegmentRangeInclusive) -> PathBuf {
+        range_directory(&self.download_directory, block_range)
+    }
+
+    /// Spawns a task that fetches the remote file list and downloads the static files of every
+    /// block range above the checkpoint, up to the target.
     ///
     /// Every time a block range is ready with all the necessary files, it sends a
-    /// [`S3DownloaderResponse`] to `self.fetch_rx`. If it's the last requested block range, the
-    /// response will have `is_done` set to true.
-    fn maybe_spawn_fetch(
+    /// [`S3DownloaderResponse::AddedNewRange`]. Once there are no block ranges left, it sends a
+    /// [`S3DownloaderResponse::Done`].
+    fn spawn_fetch(
         &self,
         input: ExecInput,
-    ) -> Option<UnboundedReceiver<Result<S3DownloaderResponse, DownloaderError>>> {
-        let checkpoint = input.checkpoint();
-        // TODO: input target can only be certain numbers. eg. 499_999 , 999_999 etc.
+    ) -> (Receiver<Result<S3DownloaderResponse, DownloaderError>>, JoinHandle<()>) {
+        // Downloads at most one block range ahead of the one being appended.
+        let (fetch_tx, fetch_rx) = channel(1);
+        let fetch_task = tokio::spawn(fetch_block_ranges(
+            self.url.clone(),
+            self.download_directory.clone(),
+            self.max_concurrent_requests,
+            input,
+            fetch_tx,
+        ));
+
+        (fetch_rx, fetch_task)
+    }
+}
 
-        // Create a list of all the missing files per block range that need to be downloaded.
-        let mut requests = vec![];
-        for block_range_files in &DOWNLOAD_FILE_LIST {
-            let (_, block_range) =
-                StaticFileSegment::parse_filename(block_range_files[0].0).expect("qed");
+/// Converts a body read from the static files into the body of the node primitives, which shares
+/// its RLP encoding.
+fn into_block_body<N: NodePrimitives>(
+    body: BlockBody<N::SignedTx, N::BlockHeader>,
+) -> Result<N::BlockBody, StageError> {
+    N::BlockBody::decode(&mut alloy_rlp::encode(body).as_slice())
+        .map_err(|err| StageError::Fatal(Box::new(err)))
+}
 
-            if block_range.end() <= checkpoint.block_number {
-                continue
-            }
+/// Returns the directory the static files of the block range are downloaded to.
+fn range_directory(download_directory: &Path, block_range: &SegmentRangeInclusive) -> PathBuf {
+    download_directory.join(format!("{}_{}", block_range.start(), block_range.end()))
+}
 
-            let mut block_range_requests = vec![];
-            for (filename, file_hash) in block_range_files {
-                // If the file already exists, then we are resuming a previously interrupted stage
-                // run.
-                if self.static_file_directory.join(filename).exists() {
-                    // TODO: check hash if the file already exists
-                    continue
+/// Downloads the static files of every block range listed in the remote file list that's above the
+/// checkpoint and up to the target.
+async fn fetch_block_ranges(
+    url: String,
+    download_directory: PathBuf,
+    max_concurrent_requests: u64,
+    input: ExecInput,
+    fetch_tx: Sender<Result<S3DownloaderResponse, DownloaderError>>,
+) {
+    let checkpoint = input.checkpoint().block_number;
+    let result = async {
+        let file_list =
+            Client::new().get(format!("{url}/{FILE_LIST}")).send().await?.error_for_status()?;
+        let file_list = parse_file_list(&file_list.text().await?)?;
+
+        for BlockRangeFiles { block_range, files } in file_list {
+            let directory = range_directory(&download_directory, &block_range);
+
+            // The block range was appended and committed by a previous run.
+            if block_range.end() <= checkpoint {
+                if directory.exists() {
+                    reth_fs_util::remove_dir_all(&directory)?;
                 }
-
-                block_range_requests.push((filename, file_hash));
+                continue
             }
 
-            requests.push((block_range, block_range_requests));
-        }
-
-        // Return None, if we have downloaded all the files that are required.
-        if requests.is_empty() {
-            return None
-        }
-
-        let static_file_directory = self.static_file_directory.clone();
-        let url = self.url.clone();
-        let max_concurrent_requests = self.max_concurrent_requests;
+            if block_range.start() > input.target() {
+                break
+            }
 
-        let (fetch_tx, fetch_rx) = unbounded_channel();
-        tokio::spawn(async move {
-            let mut requests_iter = requests.into_iter().peekable();
-
-            while let Some((_, file_requests)) = requests_iter.next() {
-                for (filename, file_hash) in file_requests {
-                    if let Err(err) = fetch(
-                        filename,
-                        &static_file_directory,
-                        &format!("{url}/{filename}"),
-                        max_concurrent_requests,
-                        Some(*file_hash),
-                    )
-                    .await
-                    {
-                        let _ = fetch_tx.send(Err(err));
-                        return
+            for (filename, file_hash) in &files {
+                // Files are only moved into the directory once they're downloaded and verified,
+                // but they might have been changed since.
+                let path = directory.join(filename);
+                if path.exists() {
+                    if check_file_hash(&path, file_hash).is_ok() {
+                        continue
                     }
+                    reth_fs_util::remove_file(&path)?;
                 }
 
-                let response = if requests_iter.peek().is_none() {
-                    S3DownloaderResponse::Done
-                } else {
-                    S3DownloaderResponse::AddedNewRange
-                };
+                fetch(
+                    filename,
+                    &directory,
+                    &format!("{url}/{filename}"),
+                    max_concurrent_requests,
+                    Some(*file_hash),
+                )
+                .await?;
+            }
 
-                let _ = fetch_tx.send(Ok(response));
+            // The stage is no longer interested in the downloads.
+            if fetch_tx.send(Ok(S3DownloaderResponse::AddedNewRange(block_range))).await.is_err() {
+                return Ok(())
             }
-        });
+        }
 
-        Some(fetch_rx)
+        Ok(())
     }
+    .await;
+
+    let _ = fetch_tx.send(result.map(|()| S3DownloaderResponse::Done)).await;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{
-        ExecuteStageTestRunner, StageTestRunner, TestRunnerError, TestStageDB,
-        UnwindStageTestRunner,
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::{BlockNumber, B256, U256};
+    use reth_db::{test_utils::TempDatabase, Database, DatabaseEnv};
+    use reth_db_api::models::{
+        StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockOmmers,
     };
-    use reth_primitives_traits::SealedHeader;
-    use reth_testing_utils::{
-        generators,
-        generators::{random_header, random_header_range},
+    use reth_ethereum_primitives::{Block, EthPrimitives};
+    use reth_primitives_traits::SealedBlock;
+    use reth_provider::{
+        providers::StaticFileProvider, test_utils::MockNodeTypesWithDB,
+        writer::UnifiedStorageWriter, BlockNumReader, DatabaseProvider, DatabaseProviderFactory,
+        HeaderProvider, TransactionsProvider,
     };
+    use reth_stages_api::StageExt;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::iter;
+    use tempfile::TempDir;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    type TestProvider =
+        DatabaseProvider<<TempDatabase<DatabaseEnv> as Database>::TXMut, MockNodeTypesWithDB>;
 
-    // stage_test_suite_ext!(S3TestRunner, s3);
+    const BLOCKS_PER_FILE: u64 = 10;
+    const TIP: BlockNumber = 25;
 
-    #[derive(Default)]
-    #[allow(unused)]
-    struct S3TestRunner {
-        db: TestStageDB,
+    /// Remote static files served over HTTP.
+    struct Remote {
+        directory: TempDir,
+        url: String,
+        blocks: Vec<SealedBlock<Block>>,
     }
 
-    impl StageTestRunner for S3TestRunner {
-        type S = S3Stage;
+    impl Remote {
+        /// Writes random blocks up to [`TIP`] into static files of [`BLOCKS_PER_FILE`] blocks
+        /// each, along with their file list, and starts serving them.
+        async fn new() -> Self {
+            let mut rng = generators::rng();
+            let blocks = random_block_range(
+                &mut rng,
+                0..=TIP,
+                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..3, ..Default::default() },
+            );
+
+            let directory = tempfile::tempdir().unwrap();
+            let provider = StaticFileProvider::<EthPrimitives>::read_write(directory.path())
+                .unwrap()
+                .with_custom_blocks_per_file(BLOCKS_PER_FILE);
+            let mut headers = provider.latest_writer(StaticFileSegment::Headers).unwrap();
+            let mut block_meta = provider.latest_writer(StaticFileSegment::BlockMeta).unwrap();
+            let mut transactions = provider.latest_writer(StaticFileSegment::Transactions).unwrap();
+
+            let mut tx_num = 0;
+            for block in &blocks {
+                headers.append_header(block.header(), U256::ZERO, &block.hash()).unwrap();
+
+                let body = block.body();
+                let indices = StoredBlockBodyIndices {
+                    first_tx_num: tx_num,
+                    tx_count: body.transactions.len() as u64,
+                };
+                block_meta
+                    .append_block_meta(
+                        &indices,
+                        &StoredBlockOmmers { ommers: body.ommers.clone() },
+                        &StaticFileBlockWithdrawals { withdrawals: body.withdrawals.clone() },
+                        block.number,
+                    )
+                    .unwrap();
+
+                transactions.increment_block(block.number).unwrap();
+                for tx in &body.transactions {
+                    transactions.append_transaction(tx_num, tx).unwrap();
+                    tx_num += 1;
+                }
+            }
+            headers.commit().unwrap();
+            block_meta.commit().unwrap();
+            transactions.commit().unwrap();
+
+            let mut file_list = String::new();
+            for entry in reth_fs_util::read_dir(directory.path()).unwrap() {
+                let path = entry.unwrap().path();
+                let filename = path.file_name().unwrap().to_str().unwrap();
+                if filename.starts_with("static_file_") {
+                    let hash = blake3::hash(&reth_fs_util::read(&path).unwrap());
+                    file_list.push_str(&format!("{}  {filename}\n", hash.to_hex()));
+                }
+            }
+            reth_fs_util::write(directory.path().join(FILE_LIST), file_list).unwrap();
 
-        fn db(&self) -> &TestStageDB {
-            &self.db
+            let url = serve(directory.path().to_path_buf()).await;
+            Self { directory, url, blocks }
         }
 
-        fn stage(&self) -> Self::S {
-            S3Stage::default()
+        /// Replaces the hash of a file in the file list.
+        fn corrupt_file_list(&self, filename: &str) {
+            let path = self.directory.path().join(FILE_LIST);
+            let file_list = reth_fs_util::read_to_string(&path)
+                .unwrap()
+                .lines()
+                .map(|line| {
+                    if line.ends_with(&format!("  {filename}")) {
+                        format!("{}  {filename}\n", B256::ZERO)
+                    } else {
+                        format!("{line}\n")
+                    }
+                })
+                .collect::<String>();
+            reth_fs_util::write(path, file_list).unwrap();
         }
     }
 
-    impl ExecuteStageTestRunner for S3TestRunner {
-        type Seed = Vec<SealedHeader>;
+    /// Serves the files of a directory under `/bucket/`, like a path-style S3 endpoint.
+    async fn serve(directory: PathBuf) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/bucket/", listener.local_addr().unwrap());
 
-        fn seed_execution(&mut self, input: ExecInput) -> Result<Self::Seed, TestRunnerError> {
-            let start = input.checkpoint().block_number;
-            let mut rng = generators::rng();
-            let head = random_header(&mut rng, start, None);
-            self.db.insert_headers_with_td(std::iter::once(&head))?;
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let directory = directory.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0; 1024];
+                    while !request.ends_with(b"\r\n\r\n") {
+                        let read = stream.read(&mut buf).await.unwrap();
+                        if read == 0 {
+                            return
+                        }
+                        request.extend_from_slice(&buf[..read]);
+                    }
+
+                    let response = respond(&directory, &String::from_utf8(request).unwrap());
+                    stream.write_all(&response).await.unwrap();
+                    stream.shutdown().await.unwrap();
+                });
+            }
+        });
+
+        url
+    }
 
-            // use previous progress as seed size
-            let end = input.target.unwrap_or_default() + 1;
+    /// Builds the response to the `HEAD` and ranged `GET` requests of the downloader.
+    fn respond(directory: &Path, request: &str) -> Vec<u8> {
+        let mut lines = request.lines();
+        let mut request_line = lines.next().unwrap().split_whitespace();
+        let (method, path) = (request_line.next().unwrap(), request_line.next().unwrap());
+        let range = lines.find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            let range = name.eq_ignore_ascii_case("range").then_some(value.trim())?;
+            let (start, end) = range.strip_prefix("bytes=")?.split_once('-')?;
+            Some((start.parse::<usize>().ok()?, end.parse::<usize>().ok()?))
+        });
 
-            if start + 1 >= end {
-                return Ok(Vec::default())
+        let data = path
+            .strip_prefix("/bucket/")
+            .and_then(|filename| reth_fs_util::read(directory.join(filename)).ok());
+        let (status, mut body, content_range) = match (data, range) {
+            (None, _) => ("404 Not Found", Vec::new(), None),
+            (Some(data), Some((start, end))) => {
+                let end = end.min(data.len() - 1);
+                let content_range = format!("bytes {start}-{end}/{}", data.len());
+                ("206 Partial Content", data[start..=end].to_vec(), Some(content_range))
             }
+            (Some(data), None) => ("200 OK", data, None),
+        };
 
-            let mut headers = random_header_range(&mut rng, start + 1..end, head.hash());
-            self.db.insert_headers_with_td(headers.iter())?;
-            headers.insert(0, head);
-            Ok(headers)
+        let mut response = format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\n", body.len());
+        if let Some(content_range) = content_range {
+            response.push_str(&format!("Content-Range: {content_range}\r\n"));
         }
+        response.push_str("Connection: close\r\n\r\n");
 
-        fn validate_execution(
-            &self,
-            input: ExecInput,
-            output: Option<ExecOutput>,
-        ) -> Result<(), TestRunnerError> {
-            if let Some(output) = output {
-                assert!(output.done, "stage should always be done");
-                assert_eq!(
-                    output.checkpoint.block_number,
-                    input.target(),
-                    "stage progress should always match progress of previous stage"
-                );
-            }
-            Ok(())
+        if method == "HEAD" {
+            body.clear();
         }
+        let mut response = response.into_bytes();
+        response.append(&mut body);
+        response
     }
 
-    impl UnwindStageTestRunner for S3TestRunner {
-        fn validate_unwind(&self, _input: UnwindInput) -> Result<(), TestRunnerError> {
-            Ok(())
+    /// Creates a database that only holds the genesis block of the remote.
+    fn local_db(remote: &Remote) -> TestStageDB {
+        let db = TestStageDB::default();
+        db.insert_blocks(iter::once(&remote.blocks[0]), StorageKind::Static).unwrap();
+        db
+    }
+
+    /// Executes the stage from the checkpoint until it's done, committing after every execution
+    /// like the pipeline does.
+    async fn execute(
+        db: &TestStageDB,
+        stage: &mut S3Stage,
+        checkpoint: BlockNumber,
+        target: BlockNumber,
+    ) -> Result<BlockNumber, StageError> {
+        let mut checkpoint = StageCheckpoint::new(checkpoint);
+        loop {
+            let input = ExecInput { target: Some(target), checkpoint: Some(checkpoint) };
+            StageExt::<TestProvider>::execute_ready(stage, input).await?;
+
+            let provider = db.factory.database_provider_rw()?;
+            let output = stage.execute(&provider, input)?;
+            UnifiedStorageWriter::commit(provider)?;
+
+            checkpoint = output.checkpoint;
+            if output.done {
+                return Ok(checkpoint.block_number)
+            }
         }
     }
 
-    #[test]
-    fn parse_files() {
-        for block_range_files in &DOWNLOAD_FILE_LIST {
-            let (_, _) = StaticFileSegment::parse_filename(block_range_files[0].0).expect("qed");
+    /// Asserts that the local database holds exactly the remote blocks up to `tip`.
+    fn assert_blocks(db: &TestStageDB, remote: &Remote, tip: BlockNumber) {
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), tip);
+        assert_eq!(
+            db.factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(tip)
+        );
+
+        for block in &remote.blocks {
+            let expected = (block.number <= tip).then_some(block);
+            assert_eq!(
+                provider.sealed_header(block.number).unwrap(),
+                expected.map(|block| block.clone_sealed_header())
+            );
+            assert_eq!(
+                provider.block_number(block.hash()).unwrap(),
+                expected.map(|block| block.number)
+            );
+            assert_eq!(
+                provider.transactions_by_block(block.number.into()).unwrap(),
+                expected.map(|block| block.body().transactions.clone())
+            );
         }
     }
+
+    #[tokio::test]
+    async fn execute_and_unwind() {
+        let remote = Remote::new().await;
+        let db = local_db(&remote);
+        let download_directory = tempfile::tempdir().unwrap();
+        let mut stage = S3Stage::new(&remote.url, download_directory.path());
+
+        // Stops at the target, even if the block range holds more blocks.
+        assert_eq!(execute(&db, &mut stage, 0, 15).await.unwrap(), 15);
+        assert_blocks(&db, &remote, 15);
+
+        assert_eq!(execute(&db, &mut stage, 15, TIP).await.unwrap(), TIP);
+        assert_blocks(&db, &remote, TIP);
+
+        // The remote has nothing above its tip, so the stage is done at the highest appended block.
+        assert_eq!(execute(&db, &mut stage, TIP, TIP + 10).await.unwrap(), TIP);
+        assert_blocks(&db, &remote, TIP);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = Stage::<TestProvider>::unwind(
+            &mut stage,
+            &provider,
+            UnwindInput { checkpoint: StageCheckpoint::new(TIP), unwind_to: 12, bad_block: None },
+        )
+        .unwrap();
+        UnifiedStorageWriter::commit_unwind(provider).unwrap();
+        assert_eq!(output.checkpoint, StageCheckpoint::new(12));
+        assert_blocks(&db, &remote, 12);
+
+        // Appends the unwound blocks again, from the downloaded files.
+        assert_eq!(execute(&db, &mut stage, 12, TIP).await.unwrap(), TIP);
+        assert_blocks(&db, &remote, TIP);
+    }
+
+    #[tokio::test]
+    async fn resume_download() {
+        let remote = Remote::new().await;
+        let db = local_db(&remote);
+        let download_directory = tempfile::tempdir().unwrap();
+
+        // A file left behind by a previous run that was changed since it was verified.
+        let range_directory = download_directory.path().join("10_19");
+        reth_fs_util::create_dir_all(&range_directory).unwrap();
+        reth_fs_util::write(range_directory.join("static_file_headers_10_19"), b"corrupted")
+            .unwrap();
+
+        let mut stage = S3Stage::new(&remote.url, download_directory.path());
+        assert_eq!(execute(&db, &mut stage, 0, 9).await.unwrap(), 9);
+        assert_blocks(&db, &remote, 9);
+
+        // A new stage resumes from the checkpoint, and removes the block ranges it already
+        // appended.
+        let mut stage =
+            S3Stage::new(&remote.url, download_directory.path()).with_max_concurrent_requests(1);
+        assert_eq!(execute(&db, &mut stage, 9, TIP).await.unwrap(), TIP);
+        assert_blocks(&db, &remote, TIP);
+        assert!(!download_directory.path().join("0_9").exists());
+    }
+
+    #[tokio::test]
+    async fn invalid_file_hash() {
+        let remote = Remote::new().await;
+        remote.corrupt_file_list("static_file_transactions_10_19.off");
+        let db = local_db(&remote);
+        let download_directory = tempfile::tempdir().unwrap();
+        let mut stage = S3Stage::new(&remote.url, download_directory.path());
+
+        // The first block range is appended before the invalid file is downloaded.
+        assert!(matches!(execute(&db, &mut stage, 0, TIP).await, Err(StageError::Fatal(_))));
+        assert_blocks(&db, &remote, 9);
+        assert!(!download_directory
+            .path()
+            .join("10_19/static_file_transactions_10_19.off")
+            .exists());
+    }
 }
@@ -0,0 +1,121 @@
+use alloy_consensus::BlockBody;
+use alloy_primitives::{BlockHash, BlockNumber, U256};
+use reth_codecs::Compact;
+use reth_db::static_file::{
+    BodyIndicesMask, HeaderWithHashMask, OmmersMask, StaticFileCursor, TotalDifficultyMask,
+    TransactionMask, WithdrawalsMask,
+};
+use reth_db_api::table::Value;
+use reth_nippy_jar::{DataReader, NippyJar};
+use reth_provider::ProviderError;
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use reth_storage_errors::provider::ProviderResult;
+use std::{path::Path, sync::Arc};
+
+/// A downloaded static file that is read from directly, without being registered in a
+/// [`StaticFileProvider`](reth_provider::providers::StaticFileProvider).
+#[derive(Debug)]
+struct StagedJar {
+    jar: NippyJar<SegmentHeader>,
+    reader: Arc<DataReader>,
+}
+
+impl StagedJar {
+    fn open(
+        directory: &Path,
+        segment: StaticFileSegment,
+        block_range: &SegmentRangeInclusive,
+    ) -> ProviderResult<Self> {
+        let jar = NippyJar::<SegmentHeader>::load(&directory.join(segment.filename(block_range)))
+            .map_err(ProviderError::other)?;
+        let reader = Arc::new(jar.open_data_reader().map_err(ProviderError::other)?);
+        Ok(Self { jar, reader })
+    }
+
+    fn cursor(&self) -> ProviderResult<StaticFileCursor<'_>> {
+        StaticFileCursor::new(&self.jar, self.reader.clone())
+    }
+}
+
+/// The headers, block meta and transactions static files of a downloaded block range.
+#[derive(Debug)]
+pub(crate) struct StagedBlockRange {
+    headers: StagedJar,
+    block_meta: StagedJar,
+    transactions: StagedJar,
+}
+
+impl StagedBlockRange {
+    /// Opens the static files of the block range in the given directory.
+    pub(crate) fn open(
+        directory: &Path,
+        block_range: &SegmentRangeInclusive,
+    ) -> ProviderResult<Self> {
+        Ok(Self {
+            headers: StagedJar::open(directory, StaticFileSegment::Headers, block_range)?,
+            block_meta: StagedJar::open(directory, StaticFileSegment::BlockMeta, block_range)?,
+            transactions: StagedJar::open(directory, StaticFileSegment::Transactions, block_range)?,
+        })
+    }
+
+    /// Returns the highest block that both the headers and block meta static files hold.
+    pub(crate) fn highest_block(&self) -> Option<BlockNumber> {
+        let headers = self.headers.jar.user_header().block_end()?;
+        let block_meta = self.block_meta.jar.user_header().block_end()?;
+        Some(headers.min(block_meta))
+    }
+
+    /// Reads the header, total difficulty and hash of each block in the range, in ascending order.
+    pub(crate) fn headers<H: Value>(
+        &self,
+        range: impl Iterator<Item = BlockNumber>,
+    ) -> ProviderResult<Vec<(H, U256, BlockHash)>> {
+        let mut cursor = self.headers.cursor()?;
+        range
+            .map(|number| {
+                let (header, hash) = cursor
+                    .get_two::<HeaderWithHashMask<H>>(number.into())?
+                    .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+                let td = cursor
+                    .get_one::<TotalDifficultyMask>(number.into())?
+                    .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+                Ok((header, td.into(), hash))
+            })
+            .collect()
+    }
+
+    /// Reads the body of each block in the range, in ascending order.
+    #[expect(clippy::type_complexity)]
+    pub(crate) fn bodies<T: Value, H: Value + Compact>(
+        &self,
+        range: impl Iterator<Item = BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<BlockBody<T, H>>)>> {
+        let mut block_meta = self.block_meta.cursor()?;
+        let mut transactions = self.transactions.cursor()?;
+        range
+            .map(|number| {
+                let indices = block_meta
+                    .get_one::<BodyIndicesMask>(number.into())?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+                let ommers = block_meta
+                    .get_one::<OmmersMask<H>>(number.into())?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+                    .ommers;
+                let withdrawals = block_meta
+                    .get_one::<WithdrawalsMask>(number.into())?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+                    .withdrawals;
+                let transactions = indices
+                    .tx_num_range()
+                    .map(|tx_num| {
+                        transactions
+                            .get_one::<TransactionMask<T>>(tx_num.into())?
+                            .ok_or(ProviderError::TransactionNotFound(tx_num.into()))
+                    })
+                    .collect::<ProviderResult<_>>()?;
+
+                Ok((number, Some(BlockBody { transactions, ommers, withdrawals })))
+            })
+            .collect()
+    }
+}
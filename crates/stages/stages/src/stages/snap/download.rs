@@ -0,0 +1,359 @@
+//! Requests to `snap` peers and the verification of their responses.
+
+use alloy_primitives::{keccak256, map::B256Set, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use reth_eth_wire_types::snap::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, SlimAccount, StorageRangesMessage,
+    TrieNodesMessage, TriePath,
+};
+use reth_network_p2p::snap::client::{SnapClient, SnapResponse};
+use reth_trie::{proof::verify_range_proof, Nibbles, TrieAccount};
+use std::{future::Future, pin::Pin, time::Duration};
+use tracing::*;
+
+/// The soft limit for the size of a single response.
+const RESPONSE_BYTES: u64 = 512 * 1024;
+
+/// The number of attempts before the state of the pivot block is considered unavailable.
+const MAX_ATTEMPTS: usize = 8;
+
+/// The delay before the next attempt if there are no connected `snap` peers.
+const NO_PEERS_DELAY: Duration = Duration::from_secs(1);
+
+/// A future that resolves to the verified response of a [`SnapRequest`].
+pub(super) type DownloadFuture = Pin<Box<dyn Future<Output = Downloaded> + Send + Sync>>;
+
+/// A request for a part of the state at the pivot block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum SnapRequest {
+    /// The accounts starting at `origin`.
+    Accounts { root: B256, origin: B256 },
+    /// The storage slots of the given `(hashed address, storage root)` pairs.
+    ///
+    /// The first storage trie is requested starting at `origin`, all others from the beginning.
+    Storages { root: B256, accounts: Vec<(B256, B256)>, origin: B256 },
+    /// The bytecodes with the given hashes.
+    ByteCodes(Vec<B256>),
+    /// The account trie nodes at the given paths with their expected hashes.
+    TrieNodes { root: B256, paths: Vec<(Nibbles, B256)> },
+}
+
+impl SnapRequest {
+    /// Sends the request to the given client until a peer delivers a valid response.
+    pub(super) fn download<C>(self, client: C) -> DownloadFuture
+    where
+        C: SnapClient + 'static,
+    {
+        Box::pin(async move {
+            for _ in 0..MAX_ATTEMPTS {
+                while client.num_connected_peers() == 0 {
+                    tokio::time::sleep(NO_PEERS_DELAY).await;
+                }
+
+                let response = match &self {
+                    Self::Accounts { root, origin } => {
+                        client
+                            .get_account_range(GetAccountRangeMessage {
+                                request_id: 0,
+                                root_hash: *root,
+                                starting_hash: *origin,
+                                limit_hash: B256::repeat_byte(0xff),
+                                response_bytes: RESPONSE_BYTES,
+                            })
+                            .await
+                    }
+                    Self::Storages { root, accounts, origin } => {
+                        client
+                            .get_storage_ranges(GetStorageRangesMessage {
+                                request_id: 0,
+                                root_hash: *root,
+                                account_hashes: accounts
+                                    .iter()
+                                    .map(|(address, _)| *address)
+                                    .collect(),
+                                starting_hash: *origin,
+                                limit_hash: B256::repeat_byte(0xff),
+                                response_bytes: RESPONSE_BYTES,
+                            })
+                            .await
+                    }
+                    Self::ByteCodes(hashes) => {
+                        client
+                            .get_byte_codes(GetByteCodesMessage {
+                                request_id: 0,
+                                hashes: hashes.clone(),
+                                response_bytes: RESPONSE_BYTES,
+                            })
+                            .await
+                    }
+                    Self::TrieNodes { root, paths } => {
+                        client
+                            .get_trie_nodes(GetTrieNodesMessage {
+                                request_id: 0,
+                                root_hash: *root,
+                                paths: paths
+                                    .iter()
+                                    .map(|(path, _)| TriePath {
+                                        account_path: compact_path(path),
+                                        slot_paths: Vec::new(),
+                                    })
+                                    .collect(),
+                                response_bytes: RESPONSE_BYTES,
+                            })
+                            .await
+                    }
+                };
+
+                let (peer_id, response) = match response {
+                    Ok(response) => response.split(),
+                    Err(error) => {
+                        debug!(target: "sync::stages::snap", %error, "Snap request failed");
+                        continue
+                    }
+                };
+
+                match self.verify(response) {
+                    Ok(Some(downloaded)) => return downloaded,
+                    // the peer doesn't have the state of the pivot block
+                    Ok(None) => {
+                        debug!(target: "sync::stages::snap", %peer_id, "Peer does not serve the pivot state");
+                    }
+                    Err(error) => {
+                        debug!(target: "sync::stages::snap", %peer_id, %error, "Invalid snap response");
+                        client.report_bad_message(peer_id);
+                    }
+                }
+            }
+            Downloaded::Unavailable
+        })
+    }
+
+    /// Verifies the response against the request.
+    ///
+    /// Returns `None` if the response is empty, which means that the peer doesn't have the
+    /// requested state.
+    fn verify(&self, response: SnapResponse) -> Result<Option<Downloaded>, String> {
+        match (self, response) {
+            (Self::Accounts { root, origin }, SnapResponse::AccountRange(response)) => {
+                verify_accounts(*root, *origin, response)
+            }
+            (Self::Storages { accounts, origin, .. }, SnapResponse::StorageRanges(response)) => {
+                verify_storages(accounts, *origin, response)
+            }
+            (Self::ByteCodes(hashes), SnapResponse::ByteCodes(response)) => {
+                verify_byte_codes(hashes, response)
+            }
+            (Self::TrieNodes { paths, .. }, SnapResponse::TrieNodes(response)) => {
+                verify_trie_nodes(paths, response)
+            }
+            _ => Err("response does not match the request".to_string()),
+        }
+    }
+}
+
+/// A verified response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Downloaded {
+    /// A range of accounts, sorted by hashed address.
+    Accounts {
+        /// The accounts by hashed address.
+        accounts: Vec<(B256, TrieAccount)>,
+        /// The next account to download, `None` if this was the last range.
+        next: Option<B256>,
+    },
+    /// The storage slots of a prefix of the requested accounts.
+    Storages {
+        /// The storage slots by hashed address, sorted by hashed slot.
+        storages: Vec<(B256, Vec<(B256, U256)>)>,
+        /// The next slot of the last account if its storage was not delivered completely.
+        next: Option<B256>,
+    },
+    /// Bytecodes by their hash.
+    ByteCodes(Vec<(B256, Bytes)>),
+    /// A prefix of the requested trie nodes by path.
+    TrieNodes(Vec<(Nibbles, Bytes)>),
+    /// No peer delivered the requested state, because the pivot block is too old.
+    Unavailable,
+}
+
+fn verify_accounts(
+    root: B256,
+    origin: B256,
+    response: AccountRangeMessage,
+) -> Result<Option<Downloaded>, String> {
+    let AccountRangeMessage { accounts, proof, .. } = response;
+    if accounts.is_empty() && proof.is_empty() {
+        return Ok(None)
+    }
+
+    let mut keys = Vec::with_capacity(accounts.len());
+    let mut values = Vec::with_capacity(accounts.len());
+    let mut decoded = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let slim = SlimAccount::decode(&mut account.body.as_ref()).map_err(|e| e.to_string())?;
+        let trie_account = TrieAccount {
+            nonce: slim.nonce,
+            balance: slim.balance,
+            storage_root: slim.storage_root(),
+            code_hash: slim.code_hash(),
+        };
+        keys.push(account.hash);
+        values.push(alloy_rlp::encode(trie_account));
+        decoded.push((account.hash, trie_account));
+    }
+
+    let has_more =
+        verify_range_proof(root, origin, &keys, &values, &proof).map_err(|e| e.to_string())?;
+    let next = has_more.then(|| keys.last().and_then(|key| next_key(*key))).flatten();
+    Ok(Some(Downloaded::Accounts { accounts: decoded, next }))
+}
+
+fn verify_storages(
+    accounts: &[(B256, B256)],
+    origin: B256,
+    response: StorageRangesMessage,
+) -> Result<Option<Downloaded>, String> {
+    let StorageRangesMessage { slots, proof, .. } = response;
+    if slots.is_empty() {
+        // the requested storage tries are never empty
+        return if proof.is_empty() { Ok(None) } else { Err("empty storage ranges".to_string()) }
+    }
+    if slots.len() > accounts.len() {
+        return Err("more storage ranges than requested".to_string())
+    }
+
+    let last = slots.len().saturating_sub(1);
+    let mut storages = Vec::with_capacity(slots.len());
+    let mut next = None;
+    for (index, (slots, (hashed_address, storage_root))) in
+        slots.into_iter().zip(accounts).enumerate()
+    {
+        let keys = slots.iter().map(|slot| slot.hash).collect::<Vec<_>>();
+        let values = slots.iter().map(|slot| slot.data.as_ref()).collect::<Vec<_>>();
+        let origin = if index == 0 { origin } else { B256::ZERO };
+        // only the last range may be incomplete and carry a proof
+        let proof: &[Bytes] = if index == last { &proof } else { &[] };
+
+        let has_more = verify_range_proof(*storage_root, origin, &keys, &values, proof)
+            .map_err(|e| e.to_string())?;
+        if has_more {
+            next = keys.last().and_then(|key| next_key(*key));
+        }
+
+        let storage = slots
+            .into_iter()
+            .map(|slot| {
+                U256::decode(&mut slot.data.as_ref())
+                    .map(|value| (slot.hash, value))
+                    .map_err(|e| e.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        storages.push((*hashed_address, storage));
+    }
+
+    Ok(Some(Downloaded::Storages { storages, next }))
+}
+
+fn verify_byte_codes(
+    hashes: &[B256],
+    response: ByteCodesMessage,
+) -> Result<Option<Downloaded>, String> {
+    if response.codes.is_empty() {
+        return Ok(None)
+    }
+
+    let requested = hashes.iter().copied().collect::<B256Set>();
+    let codes = response
+        .codes
+        .into_iter()
+        .map(|code| {
+            let hash = keccak256(&code);
+            requested
+                .contains(&hash)
+                .then_some((hash, code))
+                .ok_or_else(|| format!("unrequested bytecode {hash}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(Downloaded::ByteCodes(codes)))
+}
+
+fn verify_trie_nodes(
+    paths: &[(Nibbles, B256)],
+    response: TrieNodesMessage,
+) -> Result<Option<Downloaded>, String> {
+    if response.nodes.is_empty() {
+        return Ok(None)
+    }
+    if response.nodes.len() > paths.len() {
+        return Err("more trie nodes than requested".to_string())
+    }
+
+    let nodes = response
+        .nodes
+        .into_iter()
+        .zip(paths)
+        .map(|(node, (path, hash))| {
+            if keccak256(&node) == *hash {
+                Ok((*path, node))
+            } else {
+                Err(format!("trie node at {path:?} does not match hash {hash}"))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(Downloaded::TrieNodes(nodes)))
+}
+
+/// Returns the key following the given key, or `None` if it is the last possible key.
+fn next_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+/// Returns the first hashed key under the given trie path.
+pub(super) fn padded_key(path: &Nibbles) -> B256 {
+    let mut key = path.pack();
+    key.resize(32, 0);
+    B256::from_slice(&key)
+}
+
+/// Encodes a trie path in the hex-prefix (compact) encoding used by the `snap` protocol.
+fn compact_path(path: &Nibbles) -> Bytes {
+    let nibbles = path.to_vec();
+    let mut compact = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        // an odd flag, the first nibble shares the byte with the flag
+        compact.push(0x10 | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        compact.push(0x00);
+        &nibbles[..]
+    };
+    compact.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    compact.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_compact_paths() {
+        assert_eq!(compact_path(&Nibbles::default()), Bytes::from_static(&[0x00]));
+        assert_eq!(compact_path(&Nibbles::from_nibbles([0x1])), Bytes::from_static(&[0x11]));
+        assert_eq!(
+            compact_path(&Nibbles::from_nibbles([0x1, 0x2, 0x3])),
+            Bytes::from_static(&[0x11, 0x23])
+        );
+        assert_eq!(
+            compact_path(&Nibbles::from_nibbles([0x1, 0x2])),
+            Bytes::from_static(&[0x00, 0x12])
+        );
+    }
+
+    #[test]
+    fn increment_keys() {
+        assert_eq!(next_key(B256::ZERO), Some(B256::with_last_byte(1)));
+        assert_eq!(next_key(B256::with_last_byte(0xff)), Some(B256::from(U256::from(0x100))));
+        assert_eq!(next_key(B256::repeat_byte(0xff)), None);
+    }
+}
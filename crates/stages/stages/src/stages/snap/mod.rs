@@ -0,0 +1,1052 @@
+mod download;
+use download::{padded_key, DownloadFuture, Downloaded, SnapRequest};
+
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
+use alloy_primitives::{
+    keccak256,
+    map::{B256Set, HashSet},
+    BlockNumber, B256,
+};
+use alloy_rlp::Decodable;
+use futures_util::FutureExt;
+use reth_codecs::Compact;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_network_p2p::snap::client::SnapClient;
+use reth_primitives_traits::{Account, Bytecode, StorageEntry};
+use reth_provider::{
+    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
+    TrieWriter,
+};
+use reth_stages_api::{
+    EntitiesCheckpoint, ExecInput, ExecOutput, SnapSyncCheckpoint, Stage, StageCheckpoint,
+    StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    proof::Proof,
+    MultiProofTargets, Nibbles, RlpNode, StateRoot, StateRootProgress, StorageRoot, TrieAccount,
+    TrieNode, EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot};
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    task::{ready, Context, Poll},
+};
+use thiserror::Error;
+use tracing::*;
+
+/// The distance between the pipeline target and the block whose state is downloaded.
+///
+/// Peers only keep the state of recent blocks, so the pivot has to stay close to the tip. The
+/// blocks after the pivot are executed by the following stages.
+pub const SNAP_SYNC_PIVOT_DISTANCE: u64 = 64;
+
+/// The maximum number of storage tries requested at once.
+const MAX_STORAGE_ACCOUNTS: usize = 128;
+
+/// The maximum number of bytecodes requested at once.
+const MAX_BYTECODES: usize = 64;
+
+/// The maximum number of trie nodes requested at once.
+const MAX_TRIE_NODES: usize = 256;
+
+/// The account download progress is measured in the first two bytes of the hashed address.
+const ACCOUNT_PREFIXES: u64 = 1 << 16;
+
+/// The snap sync stage downloads the state of a recent block from `snap` peers instead of
+/// executing all blocks from genesis.
+///
+/// The stage only runs on a node without any executed blocks. It picks the block
+/// [`SNAP_SYNC_PIVOT_DISTANCE`] blocks behind the target as the pivot and downloads its state:
+///
+/// 1. The account ranges, verified with range proofs against the state root of the pivot, and the
+///    storage slots and bytecodes of every account.
+/// 2. The state root of the downloaded state is computed. If the pivot moved during the download or
+///    a download was interrupted, the root doesn't match and the account trie is healed by
+///    downloading the trie nodes that differ from the local ones, starting from the root.
+///
+/// Once the state root matches, the checkpoints of the stages that build the state are moved to
+/// the pivot, so they only process the blocks after it.
+///
+/// # Tables
+///
+/// The downloaded state is written to [`HashedAccounts`][tables::HashedAccounts],
+/// [`HashedStorages`][tables::HashedStorages] and [`Bytecodes`][tables::Bytecodes], and the
+/// computed trie to [`AccountsTrie`][tables::AccountsTrie] and
+/// [`StoragesTrie`][tables::StoragesTrie].
+///
+/// Peers don't serve the preimages of the hashed keys, so the plain state tables only contain the
+/// accounts and storage slots that were read or changed after the pivot. Once the state is healed,
+/// the state providers resolve plain state misses from the hashed state, see
+/// [`LatestStateProviderRef`](reth_provider::LatestStateProviderRef).
+///
+/// # Requirements
+///
+/// Receipts before the pivot are never downloaded, so the node must be configured to prune
+/// receipts. The state before the pivot is not available and the stage can't be unwound below it.
+pub struct SnapSyncStage<C> {
+    /// The client used to download the state.
+    client: C,
+    /// The request that is downloaded before the stage is executed again.
+    request: Option<SnapRequest>,
+    /// The download of the request.
+    download: Option<DownloadFuture>,
+    /// The verified response to the request.
+    downloaded: Option<Downloaded>,
+    /// The progress that is not persisted in the checkpoint.
+    state: SnapSyncState,
+}
+
+impl<C> SnapSyncStage<C> {
+    /// Creates a new snap sync stage that downloads the state with the given client.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            request: None,
+            download: None,
+            downloaded: None,
+            state: SnapSyncState::default(),
+        }
+    }
+
+    /// Drops all in-memory progress that refers to the previous pivot.
+    fn move_pivot(&mut self, state_root: B256) {
+        self.request = None;
+        self.download = None;
+        self.downloaded = None;
+        self.state.move_pivot(state_root);
+    }
+
+    fn get_checkpoint(
+        &self,
+        provider: &impl StageCheckpointReader,
+    ) -> Result<Option<SnapSyncCheckpoint>, StageError> {
+        let buf = provider.get_stage_checkpoint_progress(StageId::SnapSync)?.unwrap_or_default();
+        if buf.is_empty() {
+            return Ok(None)
+        }
+        let (checkpoint, _) = SnapSyncCheckpoint::from_compact(&buf, buf.len());
+        Ok(Some(checkpoint))
+    }
+
+    fn save_checkpoint(
+        &self,
+        provider: &impl StageCheckpointWriter,
+        checkpoint: &SnapSyncCheckpoint,
+    ) -> Result<(), StageError> {
+        let mut buf = Vec::new();
+        checkpoint.to_compact(&mut buf);
+        Ok(provider.save_stage_checkpoint_progress(StageId::SnapSync, buf)?)
+    }
+}
+
+impl<C: Debug> Debug for SnapSyncStage<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapSyncStage")
+            .field("client", &self.client)
+            .field("request", &self.request)
+            .field("downloaded", &self.downloaded)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Provider, C> Stage<Provider> for SnapSyncStage<C>
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + TrieWriter,
+    C: SnapClient + Clone + 'static,
+{
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() || self.downloaded.is_some() {
+            return Poll::Ready(Ok(()))
+        }
+        // nothing to download until the stage is executed
+        let Some(request) = &self.request else { return Poll::Ready(Ok(())) };
+
+        let download =
+            self.download.get_or_insert_with(|| request.clone().download(self.client.clone()));
+        self.downloaded = Some(ready!(download.poll_unpin(cx)));
+        self.download = None;
+        Poll::Ready(Ok(()))
+    }
+
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+        let target = input.target();
+        let tx = provider.tx_ref();
+
+        let mut checkpoint = match self.get_checkpoint(provider)? {
+            Some(checkpoint) if checkpoint.healed => {
+                return Ok(ExecOutput::done(StageCheckpoint::new(target)))
+            }
+            Some(checkpoint) => checkpoint,
+            None => {
+                let pivot = target.saturating_sub(SNAP_SYNC_PIVOT_DISTANCE);
+                let executed = provider
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .unwrap_or_default()
+                    .block_number;
+                if pivot == 0 || executed > 0 {
+                    // the state is built by executing the blocks
+                    return Ok(ExecOutput::done(StageCheckpoint::new(target)))
+                }
+                if !provider.prune_modes_ref().has_receipts_pruning() {
+                    return Err(StageError::Fatal(Box::new(SnapSyncError::ReceiptsNotPruned)))
+                }
+
+                info!(target: "sync::stages::snap", pivot, "Starting snap sync");
+                clear_state(tx)?;
+                SnapSyncCheckpoint {
+                    pivot,
+                    state_root: pivot_state_root(provider, pivot)?
+                        .ok_or(ProviderError::HeaderNotFound(pivot.into()))?,
+                    next_account: Some(B256::ZERO),
+                    healed: false,
+                }
+            }
+        };
+
+        // Follow the target, because peers only serve the state of recent blocks. This also
+        // replaces a pivot that was unwound.
+        let pivot = target.saturating_sub(SNAP_SYNC_PIVOT_DISTANCE);
+        if pivot > checkpoint.pivot ||
+            pivot_state_root(provider, checkpoint.pivot)? != Some(checkpoint.state_root)
+        {
+            let state_root = pivot_state_root(provider, pivot)?
+                .ok_or(ProviderError::HeaderNotFound(pivot.into()))?;
+            info!(target: "sync::stages::snap", from = checkpoint.pivot, to = pivot, "Moving snap sync pivot");
+            checkpoint.pivot = pivot;
+            checkpoint.state_root = state_root;
+            self.move_pivot(state_root);
+        }
+
+        if let (Some(request), Some(downloaded)) = (self.request.take(), self.downloaded.take()) {
+            if matches!(downloaded, Downloaded::Unavailable) {
+                // Finish without progress, so the following stages have nothing to do and the
+                // next pipeline run continues with a newer pivot.
+                warn!(target: "sync::stages::snap", pivot = checkpoint.pivot, "Peers do not serve the state of the pivot block anymore");
+                self.save_checkpoint(provider, &checkpoint)?;
+                return Ok(ExecOutput::done(input.checkpoint()))
+            }
+            self.state.apply(tx, request, downloaded)?;
+        }
+
+        loop {
+            if let Some(request) = self.state.next_request(tx, &mut checkpoint)? {
+                self.request = Some(request);
+                break
+            }
+
+            let state_root = self.state.compute_state_root(provider)?;
+            if state_root == checkpoint.state_root {
+                info!(target: "sync::stages::snap", pivot = checkpoint.pivot, %state_root, "Snap sync finished");
+                checkpoint.healed = true;
+                self.save_checkpoint(provider, &checkpoint)?;
+                self.state = SnapSyncState::default();
+
+                // the state up to the pivot is complete
+                for stage_id in [
+                    StageId::SenderRecovery,
+                    StageId::Execution,
+                    StageId::AccountHashing,
+                    StageId::StorageHashing,
+                    StageId::MerkleExecute,
+                    StageId::IndexAccountHistory,
+                    StageId::IndexStorageHistory,
+                ] {
+                    provider
+                        .save_stage_checkpoint(stage_id, StageCheckpoint::new(checkpoint.pivot))?;
+                }
+                provider.save_stage_checkpoint_progress(StageId::MerkleExecute, Vec::new())?;
+
+                return Ok(ExecOutput::done(
+                    StageCheckpoint::new(target)
+                        .with_entities_stage_checkpoint(entities_checkpoint(&checkpoint)),
+                ));
+            }
+
+            debug!(target: "sync::stages::snap", got = %state_root, expected = %checkpoint.state_root, "Healing the state trie");
+            self.state.start_healing(checkpoint.state_root);
+        }
+
+        self.save_checkpoint(provider, &checkpoint)?;
+        Ok(ExecOutput {
+            checkpoint: input
+                .checkpoint()
+                .with_entities_stage_checkpoint(entities_checkpoint(&checkpoint)),
+            done: false,
+        })
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        if let Some(checkpoint) = self.get_checkpoint(provider)?.filter(|c| c.healed) {
+            if input.unwind_to < checkpoint.pivot {
+                return Err(StageError::Fatal(Box::new(SnapSyncError::UnwindBelowPivot {
+                    unwind_to: input.unwind_to,
+                    pivot: checkpoint.pivot,
+                })));
+            }
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// The download progress of the current pivot that is kept in memory.
+#[derive(Debug, Default)]
+struct SnapSyncState {
+    /// The next account range once the storages and bytecodes of the last range are downloaded.
+    next_account: Option<Option<B256>>,
+    /// The storage tries to download, as `(hashed address, storage root)`.
+    storages: VecDeque<(B256, B256)>,
+    /// The storage trie that was partially downloaded, with the next slot to download.
+    partial_storage: Option<(B256, B256, B256)>,
+    /// The bytecodes to download.
+    codes: Vec<B256>,
+    /// Whether the account trie was computed and is being healed.
+    healing: bool,
+    /// The account trie paths to compare against the pivot, with the node hashes of the pivot.
+    heal_paths: VecDeque<(Nibbles, B256)>,
+    /// The state changes since the account trie was computed.
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl SnapSyncState {
+    fn move_pivot(&mut self, state_root: B256) {
+        // bytecodes don't depend on the pivot
+        self.next_account = None;
+        self.storages.clear();
+        self.partial_storage = None;
+        if self.healing {
+            self.start_healing(state_root);
+        }
+    }
+
+    fn start_healing(&mut self, state_root: B256) {
+        self.healing = true;
+        self.heal_paths = VecDeque::from([(Nibbles::default(), state_root)]);
+    }
+
+    /// Returns the next request, or `None` if the state of the current phase is complete.
+    fn next_request<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        checkpoint: &mut SnapSyncCheckpoint,
+    ) -> Result<Option<SnapRequest>, StageError> {
+        let root = checkpoint.state_root;
+        if !self.codes.is_empty() {
+            return Ok(Some(SnapRequest::ByteCodes(
+                self.codes.iter().take(MAX_BYTECODES).copied().collect(),
+            )));
+        }
+        if let Some((hashed_address, storage_root, origin)) = self.partial_storage {
+            return Ok(Some(SnapRequest::Storages {
+                root,
+                accounts: vec![(hashed_address, storage_root)],
+                origin,
+            }));
+        }
+        if !self.storages.is_empty() {
+            return Ok(Some(SnapRequest::Storages {
+                root,
+                accounts: self.storages.iter().take(MAX_STORAGE_ACCOUNTS).copied().collect(),
+                origin: B256::ZERO,
+            }));
+        }
+
+        // the last account range is complete
+        if let Some(next_account) = self.next_account.take() {
+            checkpoint.next_account = next_account;
+        }
+        if let Some(origin) = checkpoint.next_account {
+            return Ok(Some(SnapRequest::Accounts { root, origin }))
+        }
+
+        if self.healing {
+            let paths = self.next_heal_paths(tx)?;
+            if !paths.is_empty() {
+                return Ok(Some(SnapRequest::TrieNodes { root, paths }))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the next trie paths whose local nodes differ from the pivot.
+    fn next_heal_paths<TX: DbTx>(&mut self, tx: &TX) -> Result<Vec<(Nibbles, B256)>, StageError> {
+        let mut paths = Vec::new();
+        while paths.len() < MAX_TRIE_NODES && !self.heal_paths.is_empty() {
+            let count = (MAX_TRIE_NODES - paths.len()).min(self.heal_paths.len());
+            let batch = self.heal_paths.drain(..count).collect::<Vec<_>>();
+
+            let targets = batch
+                .iter()
+                .map(|(path, _)| (padded_key(path), B256Set::default()))
+                .collect::<MultiProofTargets>();
+            let local = Proof::from_tx(tx)
+                .with_prefix_sets_mut(self.prefix_sets.clone())
+                .multiproof(targets)
+                .map_err(|error| StageError::Fatal(Box::new(error)))?
+                .account_subtree
+                .into_inner();
+
+            // identical nodes have identical subtries
+            paths.extend(batch.into_iter().filter(|(path, hash)| {
+                local.get(path).is_none_or(|node| keccak256(node) != *hash)
+            }));
+        }
+        Ok(paths)
+    }
+
+    /// Writes the downloaded response to the database.
+    fn apply<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        request: SnapRequest,
+        downloaded: Downloaded,
+    ) -> Result<(), StageError> {
+        match (request, downloaded) {
+            (SnapRequest::Accounts { .. }, Downloaded::Accounts { accounts, next }) => {
+                let mut cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+                for (hashed_address, account) in accounts {
+                    cursor.upsert(hashed_address, &into_account(&account))?;
+                    clear_storage(tx, hashed_address)?;
+                    if account.storage_root != EMPTY_ROOT_HASH {
+                        self.storages.push_back((hashed_address, account.storage_root));
+                    }
+                    self.queue_code(tx, account.code_hash)?;
+                }
+                self.next_account = Some(next);
+            }
+            (
+                SnapRequest::Storages { accounts, origin, .. },
+                Downloaded::Storages { storages, next },
+            ) => {
+                let continued = origin != B256::ZERO;
+                let delivered = storages.len();
+                for (index, (hashed_address, slots)) in storages.into_iter().enumerate() {
+                    if index > 0 || !continued {
+                        clear_storage(tx, hashed_address)?;
+                    }
+                    let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+                    for (key, value) in slots {
+                        cursor.append_dup(hashed_address, StorageEntry { key, value })?;
+                    }
+                    self.mark_storage(hashed_address);
+                }
+
+                if !continued {
+                    self.storages.drain(..delivered.min(self.storages.len()));
+                }
+                // only the last delivered storage trie can be incomplete
+                self.partial_storage = next.map(|next| {
+                    let (hashed_address, storage_root) = accounts[delivered - 1];
+                    (hashed_address, storage_root, next)
+                });
+            }
+            (SnapRequest::ByteCodes(_), Downloaded::ByteCodes(codes)) => {
+                let delivered = codes.iter().map(|(hash, _)| *hash).collect::<HashSet<_>>();
+                for (hash, code) in codes {
+                    tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+                }
+                self.codes.retain(|hash| !delivered.contains(hash));
+            }
+            (SnapRequest::TrieNodes { paths, .. }, Downloaded::TrieNodes(nodes)) => {
+                // undelivered nodes are requested again
+                for path in paths[nodes.len()..].iter().rev() {
+                    self.heal_paths.push_front(*path);
+                }
+                for (path, node) in nodes {
+                    let node = TrieNode::decode(&mut node.as_ref())
+                        .map_err(|error| StageError::Fatal(Box::new(error)))?;
+                    self.heal_node(tx, path, node)?;
+                }
+            }
+            (request, downloaded) => {
+                debug!(target: "sync::stages::snap", ?request, ?downloaded, "Response does not match the request");
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the local state under the path with the given node of the pivot.
+    fn heal_node<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        path: Nibbles,
+        node: TrieNode,
+    ) -> Result<(), StageError> {
+        match node {
+            TrieNode::EmptyRoot => self.delete_accounts(tx, path, |_| false)?,
+            TrieNode::Branch(branch) => {
+                for (nibble, child) in branch.as_ref().children() {
+                    let mut child_path = path;
+                    child_path.push(nibble);
+                    match child {
+                        Some(child) => self.heal_child(tx, child_path, child)?,
+                        None => self.delete_accounts(tx, child_path, |_| false)?,
+                    }
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let child_path = path.join(&extension.key);
+                self.delete_accounts(tx, path, |key| key.starts_with(&child_path))?;
+                self.heal_child(tx, child_path, &extension.child)?;
+            }
+            TrieNode::Leaf(leaf) => {
+                let key = path.join(&leaf.key);
+                self.delete_accounts(tx, path, |other| *other == key)?;
+
+                let account = TrieAccount::decode(&mut leaf.value.as_slice())
+                    .map_err(|error| StageError::Fatal(Box::new(error)))?;
+                self.heal_account(tx, padded_key(&key), account)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn heal_child<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        path: Nibbles,
+        child: &RlpNode,
+    ) -> Result<(), StageError> {
+        if let Some(hash) = child.as_hash() {
+            self.heal_paths.push_back((path, hash));
+            return Ok(())
+        }
+        // embedded nodes are not requested separately
+        let node = TrieNode::decode(&mut &child[..])
+            .map_err(|error| StageError::Fatal(Box::new(error)))?;
+        self.heal_node(tx, path, node)
+    }
+
+    fn heal_account<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        hashed_address: B256,
+        account: TrieAccount,
+    ) -> Result<(), StageError> {
+        tx.put::<tables::HashedAccounts>(hashed_address, into_account(&account))?;
+        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+
+        let storage_root = StorageRoot::from_tx_hashed(tx, hashed_address)
+            .root()
+            .map_err(|error| StageError::Fatal(Box::new(error)))?;
+        if storage_root != account.storage_root {
+            clear_storage(tx, hashed_address)?;
+            self.mark_storage(hashed_address);
+            if account.storage_root != EMPTY_ROOT_HASH &&
+                !self.storages.iter().any(|(queued, _)| *queued == hashed_address)
+            {
+                self.storages.push_back((hashed_address, account.storage_root));
+            }
+        }
+
+        self.queue_code(tx, account.code_hash)
+    }
+
+    /// Deletes the accounts under the prefix that are not kept.
+    fn delete_accounts<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        prefix: Nibbles,
+        keep: impl Fn(&Nibbles) -> bool,
+    ) -> Result<(), StageError> {
+        let mut deleted = Vec::new();
+        let mut cursor = tx.cursor_read::<tables::HashedAccounts>()?;
+        let mut entry = cursor.seek(padded_key(&prefix))?;
+        while let Some((hashed_address, _)) = entry {
+            let key = Nibbles::unpack(hashed_address);
+            if !key.starts_with(&prefix) {
+                break
+            }
+            if !keep(&key) {
+                deleted.push(hashed_address);
+            }
+            entry = cursor.next()?;
+        }
+
+        for hashed_address in deleted {
+            tx.delete::<tables::HashedAccounts>(hashed_address, None)?;
+            clear_storage(tx, hashed_address)?;
+            self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            self.prefix_sets.destroyed_accounts.insert(hashed_address);
+        }
+        Ok(())
+    }
+
+    /// Marks the storage trie of the account for recomputation, if the trie was computed.
+    fn mark_storage(&mut self, hashed_address: B256) {
+        if self.healing {
+            self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            self.prefix_sets.storage_prefix_sets.insert(hashed_address, PrefixSetMut::all());
+        }
+    }
+
+    fn queue_code<TX: DbTx>(&mut self, tx: &TX, code_hash: B256) -> Result<(), StageError> {
+        if code_hash != KECCAK_EMPTY &&
+            !self.codes.contains(&code_hash) &&
+            tx.get::<tables::Bytecodes>(code_hash)?.is_none()
+        {
+            self.codes.push(code_hash);
+        }
+        Ok(())
+    }
+
+    /// Computes the state root of the downloaded state and writes the trie.
+    ///
+    /// The trie is built from scratch the first time and updated incrementally while healing.
+    fn compute_state_root<Provider>(&mut self, provider: &Provider) -> Result<B256, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+    {
+        let tx = provider.tx_ref();
+        if self.healing {
+            let prefix_sets = std::mem::take(&mut self.prefix_sets).freeze();
+            let (state_root, updates) = StateRoot::from_tx(tx)
+                .with_prefix_sets(prefix_sets)
+                .root_with_updates()
+                .map_err(|error| StageError::Fatal(Box::new(error)))?;
+            provider.write_trie_updates(&updates)?;
+            return Ok(state_root)
+        }
+
+        info!(target: "sync::stages::snap", "Computing the state root of the downloaded state");
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+        let mut intermediate_state = None;
+        loop {
+            match StateRoot::from_tx(tx)
+                .with_intermediate_state(intermediate_state.take())
+                .root_with_progress()
+                .map_err(|error| StageError::Fatal(Box::new(error)))?
+            {
+                StateRootProgress::Progress(state, _, updates) => {
+                    provider.write_trie_updates(&updates)?;
+                    intermediate_state = Some(*state);
+                }
+                StateRootProgress::Complete(state_root, _, updates) => {
+                    provider.write_trie_updates(&updates)?;
+                    return Ok(state_root)
+                }
+            }
+        }
+    }
+}
+
+/// An error of the [`SnapSyncStage`].
+#[derive(Error, Debug)]
+pub enum SnapSyncError {
+    /// The node doesn't prune receipts.
+    #[error(
+        "snap sync requires receipts pruning, receipts before the pivot block are not downloaded"
+    )]
+    ReceiptsNotPruned,
+    /// The unwind target is below the pivot block.
+    #[error("cannot unwind to block {unwind_to}, the state before the snap sync pivot block {pivot} is not available")]
+    UnwindBelowPivot {
+        /// The unwind target.
+        unwind_to: BlockNumber,
+        /// The pivot block.
+        pivot: BlockNumber,
+    },
+}
+
+/// Returns the state root of the given block, if the header exists.
+fn pivot_state_root<Provider: HeaderProvider>(
+    provider: &Provider,
+    block: BlockNumber,
+) -> Result<Option<B256>, StageError> {
+    Ok(provider.header_by_number(block)?.map(|header| header.state_root()))
+}
+
+/// Clears the state that was created by the genesis block.
+fn clear_state<TX: DbTxMut>(tx: &TX) -> Result<(), StageError> {
+    tx.clear::<tables::PlainAccountState>()?;
+    tx.clear::<tables::PlainStorageState>()?;
+    tx.clear::<tables::HashedAccounts>()?;
+    tx.clear::<tables::HashedStorages>()?;
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+    Ok(())
+}
+
+/// Deletes the storage and storage trie of the account.
+fn clear_storage<TX: DbTxMut + DbTx>(tx: &TX, hashed_address: B256) -> Result<(), StageError> {
+    let mut storages = tx.cursor_dup_write::<tables::HashedStorages>()?;
+    if storages.seek_exact(hashed_address)?.is_some() {
+        storages.delete_current_duplicates()?;
+    }
+    let mut tries = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+    if tries.seek_exact(hashed_address)?.is_some() {
+        tries.delete_current_duplicates()?;
+    }
+    Ok(())
+}
+
+const fn into_account(account: &TrieAccount) -> Account {
+    Account {
+        nonce: account.nonce,
+        balance: account.balance,
+        bytecode_hash: if account.code_hash.const_eq(&KECCAK_EMPTY) {
+            None
+        } else {
+            Some(account.code_hash)
+        },
+    }
+}
+
+/// Measures the account download progress in the first two bytes of the next hashed address.
+fn entities_checkpoint(checkpoint: &SnapSyncCheckpoint) -> EntitiesCheckpoint {
+    let processed = checkpoint
+        .next_account
+        .map_or(ACCOUNT_PREFIXES, |next| u16::from_be_bytes([next[0], next[1]]) as u64);
+    EntitiesCheckpoint { processed, total: ACCOUNT_PREFIXES }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{Address, Bytes, U256};
+    use reth_db::{test_utils::TempDatabase, Database, DatabaseEnv};
+    use reth_eth_wire_types::snap::{
+        AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+        GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, SlimAccount,
+        StorageData, StorageRangesMessage, TrieNodesMessage,
+    };
+    use reth_network_p2p::{
+        download::DownloadClient, error::PeerRequestResult, priority::Priority,
+        snap::client::SnapResponse,
+    };
+    use reth_network_peers::{PeerId, WithPeerId};
+    use reth_primitives_traits::SealedHeader;
+    use reth_provider::{
+        test_utils::MockNodeTypesWithDB, DatabaseProvider, DatabaseProviderFactory,
+    };
+    use reth_prune_types::{PruneMode, PruneModes};
+    use reth_stages_api::StageExt;
+    use reth_testing_utils::generators::{self, random_header_range};
+    use reth_trie::test_utils::{state_root_prehashed, storage_root_prehashed};
+    use std::collections::BTreeMap;
+
+    type TestProvider =
+        DatabaseProvider<<TempDatabase<DatabaseEnv> as Database>::TXMut, MockNodeTypesWithDB>;
+
+    const TARGET: BlockNumber = 100;
+    const PIVOT: BlockNumber = TARGET - SNAP_SYNC_PIVOT_DISTANCE;
+
+    /// A `snap` peer that serves the entire state of a single state root in every response.
+    #[derive(Clone, Debug, Default)]
+    struct TestSnapClient {
+        accounts: BTreeMap<B256, Account>,
+        storages: BTreeMap<B256, BTreeMap<B256, U256>>,
+        codes: Vec<Bytes>,
+    }
+
+    impl TestSnapClient {
+        fn new() -> Self {
+            let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+            let contract = keccak256(Address::with_last_byte(2));
+            let mut client = Self { codes: vec![code.clone()], ..Default::default() };
+            client.accounts.insert(
+                keccak256(Address::with_last_byte(1)),
+                Account { nonce: 1, balance: U256::from(10), bytecode_hash: None },
+            );
+            client.accounts.insert(
+                contract,
+                Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(keccak256(&code)) },
+            );
+            client.storages.insert(
+                contract,
+                BTreeMap::from([
+                    (keccak256(B256::with_last_byte(1)), U256::from(1)),
+                    (keccak256(B256::with_last_byte(2)), U256::from(2)),
+                ]),
+            );
+            client
+        }
+
+        fn storage(&self, hashed_address: &B256) -> BTreeMap<B256, U256> {
+            self.storages.get(hashed_address).cloned().unwrap_or_default()
+        }
+
+        fn state_root(&self) -> B256 {
+            state_root_prehashed(self.accounts.iter().map(|(hashed_address, account)| {
+                (*hashed_address, (*account, self.storage(hashed_address)))
+            }))
+        }
+
+        fn respond(&self, response: SnapResponse) -> <Self as SnapClient>::Output {
+            futures_util::future::ready(Ok(WithPeerId::new(PeerId::random(), response)))
+        }
+    }
+
+    impl DownloadClient for TestSnapClient {
+        fn report_bad_message(&self, _peer_id: PeerId) {}
+
+        fn num_connected_peers(&self) -> usize {
+            1
+        }
+    }
+
+    impl SnapClient for TestSnapClient {
+        type Output = futures_util::future::Ready<PeerRequestResult<SnapResponse>>;
+
+        fn get_account_range_with_priority(
+            &self,
+            request: GetAccountRangeMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            // an empty response means that the peer doesn't serve the state of the root
+            let accounts = if request.root_hash == self.state_root() {
+                self.accounts
+                    .iter()
+                    .map(|(hashed_address, account)| {
+                        let storage_root = storage_root_prehashed(self.storage(hashed_address));
+                        AccountData {
+                            hash: *hashed_address,
+                            body: alloy_rlp::encode(SlimAccount::new(*account, storage_root))
+                                .into(),
+                        }
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            self.respond(SnapResponse::AccountRange(AccountRangeMessage {
+                request_id: request.request_id,
+                accounts,
+                proof: Vec::new(),
+            }))
+        }
+
+        fn get_storage_ranges_with_priority(
+            &self,
+            request: GetStorageRangesMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            let slots = if request.root_hash == self.state_root() {
+                request
+                    .account_hashes
+                    .iter()
+                    .map(|hashed_address| {
+                        self.storage(hashed_address)
+                            .into_iter()
+                            .map(|(hash, value)| StorageData {
+                                hash,
+                                data: alloy_rlp::encode(value).into(),
+                            })
+                            .collect()
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            self.respond(SnapResponse::StorageRanges(StorageRangesMessage {
+                request_id: request.request_id,
+                slots,
+                proof: Vec::new(),
+            }))
+        }
+
+        fn get_byte_codes_with_priority(
+            &self,
+            request: GetByteCodesMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            let codes = self
+                .codes
+                .iter()
+                .filter(|code| request.hashes.contains(&keccak256(code)))
+                .cloned()
+                .collect();
+            self.respond(SnapResponse::ByteCodes(ByteCodesMessage {
+                request_id: request.request_id,
+                codes,
+            }))
+        }
+
+        fn get_trie_nodes_with_priority(
+            &self,
+            request: GetTrieNodesMessage,
+            _priority: Priority,
+        ) -> Self::Output {
+            self.respond(SnapResponse::TrieNodes(TrieNodesMessage {
+                request_id: request.request_id,
+                nodes: Vec::new(),
+            }))
+        }
+    }
+
+    /// Creates a database with headers up to [`TARGET`], where the pivot has the given state root.
+    fn test_db(state_root: B256, prune_modes: PruneModes) -> TestStageDB {
+        let mut rng = generators::rng();
+        let mut headers = random_header_range(&mut rng, 0..TARGET + 1, B256::ZERO);
+        let mut pivot = headers[PIVOT as usize].clone_header();
+        pivot.state_root = state_root;
+        headers[PIVOT as usize] = SealedHeader::seal_slow(pivot);
+
+        let mut db = TestStageDB::default();
+        db.insert_headers(headers.iter()).unwrap();
+        db.factory = db.factory.clone().with_prune_modes(prune_modes);
+        db
+    }
+
+    fn receipts_pruned() -> PruneModes {
+        PruneModes { receipts: Some(PruneMode::Full), ..Default::default() }
+    }
+
+    /// Executes the stage until it's done, committing after every execution like the pipeline
+    /// does.
+    async fn execute(
+        db: &TestStageDB,
+        stage: &mut SnapSyncStage<TestSnapClient>,
+    ) -> Result<ExecOutput, StageError> {
+        let input = ExecInput { target: Some(TARGET), checkpoint: None };
+        loop {
+            StageExt::<TestProvider>::execute_ready(stage, input).await?;
+
+            let provider = db.factory.database_provider_rw()?;
+            let output = stage.execute(&provider, input)?;
+            provider.commit()?;
+
+            if output.done {
+                return Ok(output)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_downloads_state() {
+        let client = TestSnapClient::new();
+        let db = test_db(client.state_root(), receipts_pruned());
+        let mut stage = SnapSyncStage::new(client.clone());
+
+        let output = execute(&db, &mut stage).await.unwrap();
+        assert_eq!(output.checkpoint.block_number, TARGET);
+
+        let provider = db.factory.provider().unwrap();
+        let tx = provider.tx_ref();
+        let accounts = tx
+            .cursor_read::<tables::HashedAccounts>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .unwrap();
+        assert_eq!(accounts, client.accounts);
+        let storages = tx
+            .cursor_read::<tables::HashedStorages>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| {
+                entry.map(|(hashed_address, entry)| (hashed_address, entry.key, entry.value))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            storages,
+            client
+                .storages
+                .iter()
+                .flat_map(|(hashed_address, storage)| {
+                    storage.iter().map(|(key, value)| (*hashed_address, *key, *value))
+                })
+                .collect::<Vec<_>>()
+        );
+        for code in &client.codes {
+            assert_eq!(
+                tx.get::<tables::Bytecodes>(keccak256(code)).unwrap().map(|c| c.original_bytes()),
+                Some(code.clone())
+            );
+        }
+
+        for stage_id in [StageId::Execution, StageId::AccountHashing, StageId::MerkleExecute] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap().map(|c| c.block_number),
+                Some(PIVOT)
+            );
+        }
+        let checkpoint = stage.get_checkpoint(&provider).unwrap().unwrap();
+        assert_eq!(checkpoint.pivot, PIVOT);
+        assert!(checkpoint.healed);
+    }
+
+    #[tokio::test]
+    async fn unwind_below_pivot() {
+        let client = TestSnapClient::new();
+        let db = test_db(client.state_root(), receipts_pruned());
+        let mut stage = SnapSyncStage::new(client);
+        execute(&db, &mut stage).await.unwrap();
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let checkpoint = StageCheckpoint::new(TARGET);
+        let output = stage
+            .unwind(&provider, UnwindInput { checkpoint, unwind_to: PIVOT, bad_block: None })
+            .unwrap();
+        assert_eq!(output.checkpoint.block_number, PIVOT);
+        assert!(matches!(
+            stage.unwind(&provider, UnwindInput { checkpoint, unwind_to: PIVOT - 1, bad_block: None }),
+            Err(StageError::Fatal(error))
+                if matches!(
+                    error.downcast_ref::<SnapSyncError>(),
+                    Some(SnapSyncError::UnwindBelowPivot { unwind_to, pivot: PIVOT })
+                        if *unwind_to == PIVOT - 1
+                )
+        ));
+    }
+
+    #[tokio::test]
+    async fn execute_unavailable_state() {
+        let client = TestSnapClient::new();
+        // peers don't serve the state of the pivot
+        let db = test_db(B256::repeat_byte(1), receipts_pruned());
+        let mut stage = SnapSyncStage::new(client);
+
+        let output = execute(&db, &mut stage).await.unwrap();
+        assert_eq!(output.checkpoint, StageCheckpoint::default());
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::HashedAccounts>().unwrap(), 0);
+        assert_eq!(provider.get_stage_checkpoint(StageId::Execution).unwrap(), None);
+        assert!(!stage.get_checkpoint(&provider).unwrap().unwrap().healed);
+    }
+
+    #[tokio::test]
+    async fn execute_requires_receipts_pruning() {
+        let client = TestSnapClient::new();
+        let db = test_db(client.state_root(), PruneModes::default());
+        let mut stage = SnapSyncStage::new(client);
+
+        assert!(matches!(
+            execute(&db, &mut stage).await,
+            Err(StageError::Fatal(error))
+                if matches!(error.downcast_ref(), Some(SnapSyncError::ReceiptsNotPruned))
+        ));
+    }
+}
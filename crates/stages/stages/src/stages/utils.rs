@@ -1,25 +1,26 @@
 //! Utils for `stages`.
 use alloy_primitives::{BlockNumber, TxNumber};
+use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
-    models::sharded_key::NUM_OF_INDICES_IN_SHARD,
-    table::{Decompress, Table},
+    models::{sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey},
+    table::{Decompress, Key, Table},
     transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError,
 };
 use reth_etl::Collector;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError,
+    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError, StageCheckpointReader,
     StaticFileProviderFactory,
 };
-use reth_stages_api::StageError;
+use reth_stages_api::{SnapSyncCheckpoint, StageError, StageId};
 use reth_static_file_types::StaticFileSegment;
 use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
-const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
+pub(crate) const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
 /// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
 /// [`Collector`].
@@ -97,6 +98,30 @@ where
     Ok(collector)
 }
 
+/// Appends the number to the list of indices, unless it's already the last one.
+pub(crate) fn push_index(indices: &mut Vec<u64>, number: u64) {
+    if indices.last() != Some(&number) {
+        indices.push(number);
+    }
+}
+
+/// Moves the cached indices into the [`Collector`], keyed by their partial key and the highest
+/// index in their list, in the same format as [`collect_history_indices`].
+pub(crate) fn flush_indices<P>(
+    cache: &mut HashMap<P, Vec<u64>>,
+    collector: &mut Collector<ShardedKey<P>, BlockNumberList>,
+) -> Result<(), StageError>
+where
+    P: Eq + Hash,
+    ShardedKey<P>: Key,
+{
+    for (key, indices) in cache.drain() {
+        let last = *indices.last().expect("qed");
+        collector.insert(ShardedKey::new(key, last), BlockNumberList::new_pre_sorted(indices))?;
+    }
+    Ok(())
+}
+
 /// Given a [`Collector`] created by [`collect_history_indices`] it iterates all entries, loading
 /// the indices into the database in shards.
 ///
@@ -283,3 +308,22 @@ where
         segment,
     })
 }
+
+/// Returns `true` if the state was downloaded by the [`SnapSyncStage`](super::SnapSyncStage) and
+/// the download was healed, i.e. the downloaded state matches the state root of the pivot block.
+///
+/// The plain state of such a node only contains the accounts and storage slots that were read or
+/// changed after the pivot block, so the hashed state must never be rebuilt from it.
+pub(crate) fn is_snap_synced<Provider>(provider: &Provider) -> Result<bool, StageError>
+where
+    Provider: StageCheckpointReader,
+{
+    let Some(progress) = provider.get_stage_checkpoint_progress(StageId::SnapSync)? else {
+        return Ok(false)
+    };
+    if progress.is_empty() {
+        return Ok(false)
+    }
+    let (checkpoint, _) = SnapSyncCheckpoint::from_compact(&progress, progress.len());
+    Ok(checkpoint.healed)
+}
@@ -287,6 +287,23 @@ pub struct IndexHistoryCheckpoint {
     pub progress: EntitiesCheckpoint,
 }
 
+/// Saves the progress of `SnapSync` stage.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapSyncCheckpoint {
+    /// The block whose state is being downloaded.
+    pub pivot: BlockNumber,
+    /// The state root of the pivot block.
+    pub state_root: B256,
+    /// The next hashed account to download, `None` once all account ranges are downloaded.
+    pub next_account: Option<B256>,
+    /// Whether the downloaded state matches the state root of the pivot block.
+    pub healed: bool,
+}
+
 /// Saves the progress of abstract stage iterating over or downloading entities.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
@@ -12,6 +12,10 @@ pub enum StageId {
     Headers,
     Bodies,
     SenderRecovery,
+    /// Downloads the state of a recent block from `snap` peers.
+    ///
+    /// Not part of [`StageId::ALL`], because it only runs on nodes that opted into snap sync.
+    SnapSync,
     Execution,
     PruneSenderRecovery,
     MerkleUnwind,
@@ -21,6 +25,14 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Indexes log addresses and topics by block for `eth_getLogs`.
+    ///
+    /// Not part of [`StageId::ALL`], because it only runs on nodes that enabled the log index.
+    IndexLogs,
+    /// Indexes the transactions sent by, sent to or creating each address.
+    ///
+    /// Not part of [`StageId::ALL`], because it only runs on nodes that enabled the index.
+    IndexAddressTransactions,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -69,6 +81,7 @@ impl StageId {
             Self::Headers => "Headers",
             Self::Bodies => "Bodies",
             Self::SenderRecovery => "SenderRecovery",
+            Self::SnapSync => "SnapSync",
             Self::Execution => "Execution",
             Self::PruneSenderRecovery => "PruneSenderRecovery",
             Self::MerkleUnwind => "MerkleUnwind",
@@ -77,6 +90,8 @@ impl StageId {
             Self::MerkleExecute => "MerkleExecute",
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
+            Self::IndexLogs => "IndexLogs",
+            Self::IndexAddressTransactions => "IndexAddressTransactions",
             Self::IndexStorageHistory => "IndexStorageHistory",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
@@ -116,6 +131,7 @@ mod tests {
         assert_eq!(StageId::Headers.to_string(), "Headers");
         assert_eq!(StageId::Bodies.to_string(), "Bodies");
         assert_eq!(StageId::SenderRecovery.to_string(), "SenderRecovery");
+        assert_eq!(StageId::SnapSync.to_string(), "SnapSync");
         assert_eq!(StageId::Execution.to_string(), "Execution");
         assert_eq!(StageId::MerkleUnwind.to_string(), "MerkleUnwind");
         assert_eq!(StageId::AccountHashing.to_string(), "AccountHashing");
@@ -123,6 +139,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
+        assert_eq!(StageId::IndexAddressTransactions.to_string(), "IndexAddressTransactions");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
@@ -436,6 +436,35 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks that emitted at least one log from a given contract address.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], with the last shard of each address keyed by
+    /// `u64::MAX`. Only populated on nodes that enabled the log index.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks that emitted at least one log containing a given topic, at
+    /// any topic position.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], with the last shard of each topic keyed by
+    /// `u64::MAX`. Only populated on nodes that enabled the log index.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the transactions that were sent by, sent to or created a given address.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], except that the shards contain transaction
+    /// numbers and are keyed by the highest transaction number in them, with the last shard of
+    /// each address keyed by `u64::MAX`. Only populated on nodes that enabled the index.
+    table AddressTransactions {
+        type Key = ShardedKey<Address>;
+        type Value = TxNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -560,6 +589,9 @@ impl Decode for ChainStateKey {
 /// List with transaction numbers.
 pub type BlockNumberList = IntegerList;
 
+/// List with transaction numbers of an address.
+pub type TxNumberList = IntegerList;
+
 /// Encoded stage id.
 pub type StageId = String;
 
@@ -44,8 +44,8 @@ pub use revm_database::states::OriginalValuesKnown;
 pub mod writer;
 
 pub use reth_chain_state::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions,
+    BadBlockReader, CanonStateNotification, CanonStateNotificationSender,
+    CanonStateNotificationStream, CanonStateNotifications, CanonStateSubscriptions,
 };
 
 // reexport traits to avoid breaking changes
@@ -1,24 +1,27 @@
 #![allow(unused)]
 use crate::{
     providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
+    CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
+    DatabaseProvider, DatabaseProviderFactory, FullProvider, HashedPostStateProvider,
+    HeaderProvider, HistoryIndexReader, LogIndexReader, ProviderError, ProviderFactory,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
+    StateProviderBox, StateProviderFactory, StateReader, StaticFileProviderFactory,
+    TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, Header};
 use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
 };
-use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
+use alloy_primitives::{
+    Address, BlockHash, BlockNumber, Bytes, Sealable, TxHash, TxNumber, B256, U256,
+};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
-    BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
-    MemoryOverlayStateProvider,
+    BadBlockReader, BlockState, CanonicalInMemoryState, ForkChoiceNotifications,
+    ForkChoiceSubscriptions, MemoryOverlayStateProvider,
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db_api::{
@@ -35,15 +38,17 @@ use reth_primitives_traits::{
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
+use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StateCommitmentProvider,
-    StorageChangeSetReader,
+    BlockBodyIndicesProvider, DBProvider, DatabaseInspector, DatabaseStat, NodePrimitivesProvider,
+    StateCommitmentProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
 use reth_trie_db::StateCommitment;
 use revm_database::BundleState;
 use std::{
+    collections::BTreeSet,
     ops::{Add, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
     time::Instant,
@@ -682,6 +687,16 @@ impl<N: ProviderNodeTypes> CanonStateSubscriptions for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BadBlockReader for BlockchainProvider<N> {
+    fn bad_blocks(&self) -> Vec<SealedBlock<BlockTy<N>>> {
+        self.canonical_in_memory_state.bad_blocks()
+    }
+
+    fn bad_block_by_hash(&self, hash: B256) -> Option<SealedBlock<BlockTy<N>>> {
+        self.canonical_in_memory_state.bad_block_by_hash(hash)
+    }
+}
+
 impl<N: ProviderNodeTypes> ForkChoiceSubscriptions for BlockchainProvider<N> {
     type Header = HeaderTy<N>;
 
@@ -712,6 +727,94 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.consistent_provider()?.account_block_changeset(block_number)
     }
+
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.consistent_provider()?.changed_accounts_in_range(range)
+    }
+}
+
+impl<N: ProviderNodeTypes> HistoryIndexReader for BlockchainProvider<N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.account_history_blocks(address, range)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.storage_history_blocks(address, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for BlockchainProvider<N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.consistent_provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> AddressTransactionsReader for BlockchainProvider<N> {
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.consistent_provider()?.address_transactions_range()
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.consistent_provider()?.address_transactions(address, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> DatabaseInspector for BlockchainProvider<N> {
+    fn table_value(
+        &self,
+        table: &str,
+        key: &str,
+        subkey: Option<&str>,
+    ) -> ProviderResult<Option<String>> {
+        self.database.table_value(table, key, subkey)
+    }
+
+    fn database_stats(&self) -> ProviderResult<Vec<DatabaseStat>> {
+        self.database.database_stats()
+    }
+
+    fn static_file_row(
+        &self,
+        segment: StaticFileSegment,
+        key: u64,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.database.static_file_row(segment, key)
+    }
+
+    fn highest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.database.highest_static_file_block(segment)
+    }
 }
 
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
@@ -790,6 +893,7 @@ mod tests {
     };
     use revm_database::BundleState;
     use std::{
+        collections::BTreeSet,
         ops::{Bound, Deref, Range, RangeBounds},
         sync::Arc,
         time::Instant,
@@ -1737,6 +1841,17 @@ mod tests {
         };
         provider.canonical_in_memory_state.update_chain(chain);
 
+        assert_eq!(
+            provider
+                .changed_accounts_in_range(first_database_block..=first_in_memory_block)
+                .unwrap(),
+            database_changesets
+                .iter()
+                .flatten()
+                .chain(&in_memory_changesets)
+                .map(|(address, _, _)| *address)
+                .collect::<BTreeSet<_>>()
+        );
         assert_eq!(
             provider.account_block_changeset(last_database_block).unwrap(),
             database_changesets
@@ -1,10 +1,10 @@
 use super::{DatabaseProviderRO, ProviderFactory, ProviderNodeTypes};
 use crate::{
-    providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader, HeaderProvider,
-    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateReader, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider,
+    providers::StaticFileProvider, AccountReader, AddressTransactionsReader, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider,
+    ChangeSetReader, HeaderProvider, HistoryIndexReader, LogIndexReader, ProviderError,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
+    StateReader, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{
@@ -28,7 +28,7 @@ use reth_storage_api::{
     StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
-use revm_database::states::PlainStorageRevert;
+use revm_database::states::{PlainStateReverts, PlainStorageRevert};
 use std::{
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
@@ -611,6 +611,67 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         }
         into_history_at_block_hash(block_hash)
     }
+
+    /// Returns the blocks within the range that are found by `fetch_from_db` for persisted blocks
+    /// and that match `changed` for in-memory blocks, in ascending order.
+    ///
+    /// Returns an error if the history of the given segment was pruned for part of the range.
+    fn history_blocks<F, P>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        segment: PruneSegment,
+        fetch_from_db: F,
+        changed: P,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        F: FnOnce(
+            &DatabaseProviderRO<N::DB, N>,
+            RangeInclusive<BlockNumber>,
+        ) -> ProviderResult<Vec<BlockNumber>>,
+        P: Fn(&PlainStateReverts) -> bool,
+    {
+        // The checkpoint stores the highest pruned block number
+        if let Some(pruned) = self
+            .storage_provider
+            .get_prune_checkpoint(segment)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .filter(|pruned| range.start() <= pruned)
+        {
+            trace!(target: "providers::blockchain", ?range, pruned, ?segment, "History is pruned");
+            return Err(ProviderError::StateAtBlockPruned(*range.start()))
+        }
+
+        // in-memory blocks, from newest to oldest
+        let in_memory_chain = self.head_block.iter().flat_map(|b| b.chain()).collect::<Vec<_>>();
+        let db_end = in_memory_chain
+            .last()
+            .map_or(*range.end(), |block| (*range.end()).min(block.number().saturating_sub(1)));
+
+        let mut blocks = if *range.start() <= db_end {
+            fetch_from_db(&self.storage_provider, *range.start()..=db_end)?
+        } else {
+            Vec::new()
+        };
+
+        for block_state in in_memory_chain.into_iter().rev() {
+            let number = block_state.number();
+            if !range.contains(&number) {
+                continue
+            }
+            let reverts = block_state
+                .block_ref()
+                .execution_output
+                .bundle
+                .reverts
+                .clone()
+                .to_plain_state_reverts();
+            if changed(&reverts) {
+                blocks.push(number);
+            }
+        }
+
+        Ok(blocks)
+    }
 }
 
 impl<N: ProviderNodeTypes> ConsistentProvider<N> {
@@ -1416,6 +1477,78 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> HistoryIndexReader for ConsistentProvider<N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.history_blocks(
+            range,
+            PruneSegment::AccountHistory,
+            |provider, range| provider.account_history_blocks(address, range),
+            |reverts| reverts.accounts.iter().flatten().any(|(changed, _)| *changed == address),
+        )
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.history_blocks(
+            range,
+            PruneSegment::StorageHistory,
+            |provider, range| provider.storage_history_blocks(address, range),
+            |reverts| {
+                reverts
+                    .storage
+                    .iter()
+                    .flatten()
+                    .any(|revert| revert.address == address && !revert.storage_revert.is_empty())
+            },
+        )
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for ConsistentProvider<N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        // in-memory blocks are never indexed, the index only covers persisted blocks
+        self.storage_provider.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.storage_provider.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.storage_provider.log_topic_blocks(topic, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> AddressTransactionsReader for ConsistentProvider<N> {
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        // in-memory blocks are never indexed, the index only covers persisted blocks
+        self.storage_provider.address_transactions_range()
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.storage_provider.address_transactions(address, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -0,0 +1,140 @@
+use super::ProviderFactory;
+use crate::{providers::ProviderNodeTypes, StaticFileProviderFactory};
+use alloy_primitives::{BlockNumber, Bytes};
+use reth_db_api::{
+    cursor::DbDupCursorRO,
+    database::Database,
+    database_metrics::DatabaseMetrics,
+    table::{DupSort, Key, Table, Value},
+    transaction::DbTx,
+    DatabaseError, TableViewer, Tables,
+};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_api::{DatabaseInspector, DatabaseStat};
+use reth_storage_errors::provider::ProviderResult;
+
+impl<N: ProviderNodeTypes> DatabaseInspector for ProviderFactory<N> {
+    fn table_value(
+        &self,
+        table: &str,
+        key: &str,
+        subkey: Option<&str>,
+    ) -> ProviderResult<Option<String>> {
+        let table = table.parse::<Tables>().map_err(DatabaseError::Other)?;
+        let tx = self.db_ref().tx()?;
+        Ok(table.view(&TableValueViewer { tx: &tx, key, subkey })?)
+    }
+
+    fn database_stats(&self) -> ProviderResult<Vec<DatabaseStat>> {
+        Ok(self
+            .db_ref()
+            .gauge_metrics()
+            .into_iter()
+            .map(|(name, value, labels)| DatabaseStat {
+                name,
+                labels: labels
+                    .iter()
+                    .map(|label| (label.key().to_string(), label.value().to_string()))
+                    .collect(),
+                value,
+            })
+            .collect())
+    }
+
+    fn static_file_row(
+        &self,
+        segment: StaticFileSegment,
+        key: u64,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.static_file_provider().raw_row(segment, key)
+    }
+
+    fn highest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_file_provider().get_highest_static_file_block(segment)
+    }
+}
+
+/// Looks up a JSON encoded key in a table and returns the JSON encoded value, the same way as
+/// `reth db get` does.
+struct TableValueViewer<'a, TX> {
+    tx: &'a TX,
+    key: &'a str,
+    subkey: Option<&'a str>,
+}
+
+impl<TX: DbTx> TableViewer<Option<String>> for TableValueViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<Option<String>, Self::Error> {
+        let key = from_json::<T::Key>(self.key)?;
+        self.tx.get::<T>(key)?.as_ref().map(to_json).transpose()
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<Option<String>, Self::Error> {
+        let key = from_json::<T::Key>(self.key)?;
+        let subkey = from_json::<T::SubKey>(self.subkey.unwrap_or_default())?;
+        self.tx
+            .cursor_dup_read::<T>()?
+            .seek_by_key_subkey(key, subkey)?
+            .as_ref()
+            .map(to_json)
+            .transpose()
+    }
+}
+
+/// Decodes a table key from JSON.
+///
+/// Values that aren't valid JSON are treated as JSON strings, so that e.g. hashes don't need to be
+/// quoted.
+fn from_json<K: Key>(value: &str) -> Result<K, DatabaseError> {
+    serde_json::from_str(value)
+        .or_else(|_| serde_json::from_value(serde_json::Value::String(value.to_string())))
+        .map_err(|err| DatabaseError::Other(format!("invalid key {value:?}: {err}")))
+}
+
+/// Encodes a table value as JSON.
+fn to_json<V: Value>(value: &V) -> Result<String, DatabaseError> {
+    serde_json::to_string(value).map_err(|err| DatabaseError::Other(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use alloy_primitives::{address, B256, U256};
+    use reth_db_api::{
+        models::ShardedKey, transaction::DbTxMut, PlainAccountState, PlainStorageState,
+    };
+    use reth_primitives_traits::{Account, StorageEntry};
+
+    #[test]
+    fn table_value_decodes_like_db_get() {
+        let factory = create_test_provider_factory();
+        let address = address!("0x4448e1273fd5a8bfdb9ed111e96889c960eee145");
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        let slot = StorageEntry { key: B256::with_last_byte(1), value: U256::from(3) };
+
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<PlainAccountState>(address, account).unwrap();
+        provider.tx_ref().put::<PlainStorageState>(address, slot).unwrap();
+        provider.commit().unwrap();
+
+        // unquoted and quoted keys are both accepted
+        for key in [address.to_string(), serde_json::to_string(&address).unwrap()] {
+            let value = factory.table_value("PlainAccountState", &key, None).unwrap().unwrap();
+            assert_eq!(serde_json::from_str::<Account>(&value).unwrap(), account);
+        }
+
+        let value = factory
+            .table_value("PlainStorageState", &address.to_string(), Some(&slot.key.to_string()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(serde_json::from_str::<StorageEntry>(&value).unwrap(), slot);
+
+        let key = serde_json::to_string(&ShardedKey::new(address, u64::MAX)).unwrap();
+        assert_eq!(factory.table_value("AccountsHistory", &key, None).unwrap(), None);
+
+        assert!(factory.table_value("NotATable", "1", None).is_err());
+        assert!(factory.table_value("Headers", "not a number", None).is_err());
+    }
+}
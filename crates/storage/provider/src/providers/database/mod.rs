@@ -28,7 +28,7 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
-use reth_trie_db::StateCommitment;
+use reth_trie_db::{RevertsCache, StateCommitment};
 use revm_database::BundleState;
 use std::{
     ops::{RangeBounds, RangeInclusive},
@@ -46,6 +46,8 @@ use super::ProviderNodeTypes;
 mod builder;
 pub use builder::{ProviderFactoryBuilder, ReadOnlyConfig};
 
+mod inspector;
+
 mod metrics;
 
 mod chain;
@@ -65,6 +67,12 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     prune_modes: PruneModes,
     /// The node storage handler.
     storage: Arc<N::Storage>,
+    /// Cache of historical reverts shared by the read-only providers.
+    reverts_cache: Option<RevertsCache>,
+    /// Whether the log index is extended when blocks are written.
+    log_index: bool,
+    /// Whether the address transactions index is extended when blocks are written.
+    address_transactions_index: bool,
 }
 
 impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -87,6 +95,9 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            reverts_cache: None,
+            log_index: false,
+            address_transactions_index: false,
         }
     }
 
@@ -102,6 +113,27 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Sets the cache of historical reverts, which speeds up state roots and proofs at historical
+    /// blocks, see [`RevertsCache`].
+    pub fn with_reverts_cache(mut self, reverts_cache: RevertsCache) -> Self {
+        self.reverts_cache = Some(reverts_cache);
+        self
+    }
+
+    /// Sets whether the log index is extended when blocks are written. Only enable it if the
+    /// node runs the `IndexLogs` stage.
+    pub const fn with_log_index(mut self, enabled: bool) -> Self {
+        self.log_index = enabled;
+        self
+    }
+
+    /// Sets whether the address transactions index is extended when blocks are written. Only
+    /// enable it if the node runs the `IndexAddressTransactions` stage.
+    pub const fn with_address_transactions_index(mut self, enabled: bool) -> Self {
+        self.address_transactions_index = enabled;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -129,6 +161,9 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            reverts_cache: None,
+            log_index: false,
+            address_transactions_index: false,
         })
     }
 }
@@ -148,7 +183,8 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.static_file_provider.clone(),
             self.prune_modes.clone(),
             self.storage.clone(),
-        ))
+        )
+        .with_reverts_cache(self.reverts_cache.clone()))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -157,13 +193,17 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<N::DB, N>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-            self.storage.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+                self.storage.clone(),
+            )
+            .with_log_index(self.log_index)
+            .with_address_transactions_index(self.address_transactions_index),
+        ))
     }
 
     /// State provider for latest block
@@ -613,13 +653,25 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self {
+            db,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            reverts_cache,
+            log_index,
+            address_transactions_index,
+        } = self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
             .field("storage", &storage)
+            .field("reverts_cache", &reverts_cache)
+            .field("log_index", &log_index)
+            .field("address_transactions_index", &address_transactions_index)
             .finish()
     }
 }
@@ -632,6 +684,9 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
             storage: self.storage.clone(),
+            reverts_cache: self.reverts_cache.clone(),
+            log_index: self.log_index,
+            address_transactions_index: self.address_transactions_index,
         }
     }
 }
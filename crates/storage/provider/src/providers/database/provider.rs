@@ -7,7 +7,9 @@ use crate::{
     },
     to_range,
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, AddressTransactionsReader, AddressTransactionsWriter, BlockSource,
+        ChangeSetReader, HistoryIndexReader, LogIndexReader, LogIndexWriter, ReceiptProvider,
+        StageCheckpointWriter,
     },
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
@@ -26,11 +28,12 @@ use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_codecs::Compact;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     database::Database,
@@ -52,11 +55,11 @@ use reth_primitives_traits::{
 use reth_prune_types::{
     PruneCheckpoint, PruneMode, PruneModes, PruneSegment, MINIMUM_PRUNING_DISTANCE,
 };
-use reth_stages_types::{StageCheckpoint, StageId};
+use reth_stages_types::{SnapSyncCheckpoint, StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    transaction_addresses_by_tx_range, BlockBodyIndicesProvider, BlockBodyReader,
+    NodePrimitivesProvider, StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
@@ -64,7 +67,7 @@ use reth_trie::{
     updates::{StorageTrieUpdates, TrieUpdates},
     HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor, RevertsCache};
 use revm_database::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
 };
@@ -145,6 +148,12 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     prune_modes: PruneModes,
     /// Node storage handler.
     storage: Arc<N::Storage>,
+    /// Cache of historical reverts shared with other providers, see [`RevertsCache`].
+    reverts_cache: Option<RevertsCache>,
+    /// Whether the log index is extended when blocks are written.
+    log_index: bool,
+    /// Whether the address transactions index is extended when blocks are written.
+    address_transactions_index: bool,
 }
 
 impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -152,6 +161,29 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets the cache of historical reverts used by the historical state providers.
+    ///
+    /// This must only be set for read-only providers, because the cached reverts don't include
+    /// uncommitted changes.
+    pub fn with_reverts_cache(mut self, reverts_cache: Option<RevertsCache>) -> Self {
+        self.reverts_cache = reverts_cache;
+        self
+    }
+
+    /// Sets whether the log index is extended when blocks are written, see
+    /// [`LogIndexWriter::is_log_index_enabled`].
+    pub const fn with_log_index(mut self, enabled: bool) -> Self {
+        self.log_index = enabled;
+        self
+    }
+
+    /// Sets whether the address transactions index is extended when blocks are written, see
+    /// [`AddressTransactionsWriter::is_address_transactions_index_enabled`].
+    pub const fn with_address_transactions_index(mut self, enabled: bool) -> Self {
+        self.address_transactions_index = enabled;
+        self
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -182,7 +214,8 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
-        let mut state_provider = HistoricalStateProviderRef::new(self, block_number);
+        let mut state_provider = HistoricalStateProviderRef::new(self, block_number)
+            .with_reverts_cache(self.reverts_cache.as_ref());
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -200,10 +233,25 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
                 prune_checkpoint_block_number + 1,
             );
         }
+        if let Some(pivot) = self.snap_sync_pivot()? {
+            state_provider = state_provider.with_snap_sync_pivot(pivot);
+        }
 
         Ok(Box::new(state_provider))
     }
 
+    /// Returns the pivot block if the state was downloaded with snap sync and healed.
+    ///
+    /// The history before the pivot block was never stored.
+    fn snap_sync_pivot(&self) -> ProviderResult<Option<BlockNumber>> {
+        let progress = self.get_stage_checkpoint_progress(StageId::SnapSync)?.unwrap_or_default();
+        if progress.is_empty() {
+            return Ok(None)
+        }
+        let (checkpoint, _) = SnapSyncCheckpoint::from_compact(&progress, progress.len());
+        Ok(checkpoint.healed.then_some(checkpoint.pivot))
+    }
+
     #[cfg(feature = "test-utils")]
     /// Sets the prune modes for provider.
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
@@ -241,7 +289,16 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            reverts_cache: None,
+            log_index: false,
+            address_transactions_index: false,
+        }
     }
 }
 
@@ -252,6 +309,41 @@ impl<TX, N: NodeTypes> AsRef<Self> for DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Unwinds the log index above the given block, if the node maintains one.
+    ///
+    /// Must be called before the receipts above the block are removed.
+    fn unwind_log_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(())
+        };
+
+        if checkpoint.block_number > block {
+            self.unwind_log_indices_range(block + 1..=checkpoint.block_number)?;
+            self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(block))?;
+        }
+
+        Ok(())
+    }
+
+    /// Unwinds the address transactions index above the given block, if the node maintains one.
+    ///
+    /// Must be called before the transactions above the block are removed.
+    fn unwind_address_transactions_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexAddressTransactions)? else {
+            return Ok(())
+        };
+
+        if checkpoint.block_number > block {
+            self.unwind_address_transaction_indices_range(block + 1..=checkpoint.block_number)?;
+            self.save_stage_checkpoint(
+                StageId::IndexAddressTransactions,
+                StageCheckpoint::new(block),
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Unwinds trie state for the given range.
     ///
     /// This includes calculating the resulted state root and comparing it with the parent block
@@ -386,8 +478,11 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
             self.get_prune_checkpoint(PruneSegment::AccountHistory)?;
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
+        let snap_sync_pivot = self.snap_sync_pivot()?;
 
-        let mut state_provider = HistoricalStateProvider::new(self, block_number);
+        let reverts_cache = self.reverts_cache.clone();
+        let mut state_provider =
+            HistoricalStateProvider::new(self, block_number).with_reverts_cache(reverts_cache);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -405,6 +500,9 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
                 prune_checkpoint_block_number + 1,
             );
         }
+        if let Some(pivot) = snap_sync_pivot {
+            state_provider = state_provider.with_snap_sync_pivot(pivot);
+        }
 
         Ok(Box::new(state_provider))
     }
@@ -524,7 +622,16 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            reverts_cache: None,
+            log_index: false,
+            address_transactions_index: false,
+        }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -828,6 +935,39 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(Vec::new())
     }
 
+    /// Removes the blocks at or above the given block number from the shards of the keys in a
+    /// table indexed by [`ShardedKey`].
+    fn unwind_sharded_index<T, K>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for key in keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                block_number,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Insert history index to the database.
     ///
     /// For each updated partial key, this function removes the last shard from
@@ -948,6 +1088,150 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
             })
             .collect()
     }
+
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.changed_accounts_with_range(range)
+    }
+}
+
+impl<TX: DbTx, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the blocks within the range from the shards of the key in a table indexed by
+    /// [`ShardedKey`], in ascending order.
+    fn sharded_index_blocks<T, K>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut blocks = Vec::new();
+
+        // shards are keyed by their highest block number, so the first shard of the range is the
+        // first one with a key after the start
+        let mut entry = cursor.seek(ShardedKey::new(key, *range.start()))?;
+        while let Some((sharded_key, list)) =
+            entry.filter(|(sharded_key, _)| sharded_key.key == key)
+        {
+            blocks.extend(list.iter().filter(|block| range.contains(block)));
+            if sharded_key.highest_block_number >= *range.end() {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx, N: NodeTypes> HistoryIndexReader for DatabaseProvider<TX, N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks::<tables::AccountsHistory, _>(address, range)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut blocks = BTreeSet::new();
+
+        let mut entry = cursor.seek(StorageShardedKey::new(address, B256::ZERO, *range.start()))?;
+        while let Some((key, list)) = entry.filter(|(key, _)| key.address == address) {
+            let slot = key.sharded_key.key;
+            if key.sharded_key.highest_block_number < *range.start() {
+                // skip the shards of the slot before the range
+                entry = cursor.seek(StorageShardedKey::new(address, slot, *range.start()))?;
+                continue
+            }
+
+            blocks.extend(list.iter().filter(|block| range.contains(block)));
+            if key.sharded_key.highest_block_number < *range.end() {
+                entry = cursor.next()?;
+                continue
+            }
+
+            // skip the shards of the slot after the range
+            entry = match U256::from_be_bytes(slot.0).checked_add(U256::from(1)) {
+                Some(next) => {
+                    cursor.seek(StorageShardedKey::new(address, next.into(), *range.start()))?
+                }
+                None => None,
+            };
+        }
+
+        Ok(blocks.into_iter().collect())
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> LogIndexReader for DatabaseProvider<TX, N> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(None)
+        };
+
+        // the index is pruned together with the receipts, so it doesn't cover the blocks below
+        // the pruned ones
+        let mut start = 0;
+        for segment in [PruneSegment::ContractLogs, PruneSegment::Receipts] {
+            if let Some(pruned) =
+                self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+            {
+                start = start.max(pruned + 1);
+            }
+        }
+
+        Ok((start <= checkpoint.block_number).then_some(start..=checkpoint.block_number))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks::<tables::LogAddressIndex, _>(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.sharded_index_blocks::<tables::LogTopicIndex, _>(topic, range)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> AddressTransactionsReader for DatabaseProvider<TX, N> {
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexAddressTransactions)? else {
+            return Ok(None)
+        };
+
+        let start = self
+            .get_prune_checkpoint(PruneSegment::AddressTransactions)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |pruned| pruned + 1);
+
+        Ok((start <= checkpoint.block_number).then_some(start..=checkpoint.block_number))
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.sharded_index_blocks::<tables::AddressTransactions, _>(address, range)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderSyncGapProvider
@@ -2751,6 +3035,100 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> LogIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn is_log_index_enabled(&self) -> bool {
+        self.log_index
+    }
+
+    fn insert_log_indices<'a>(
+        &self,
+        blocks: impl IntoIterator<Item = (BlockNumber, impl IntoIterator<Item = &'a Log>)>,
+    ) -> ProviderResult<()> {
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+        for (block_number, logs) in blocks {
+            for log in logs {
+                let blocks = addresses.entry(log.address).or_default();
+                if blocks.last() != Some(&block_number) {
+                    blocks.push(block_number);
+                }
+
+                for topic in log.topics() {
+                    let blocks = topics.entry(*topic).or_default();
+                    if blocks.last() != Some(&block_number) {
+                        blocks.push(block_number);
+                    }
+                }
+            }
+        }
+
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+
+    fn unwind_log_indices_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
+        let first_block = *range.start();
+
+        let mut addresses = BTreeSet::new();
+        let mut topics = BTreeSet::new();
+        for receipts in self.receipts_by_block_range(range)? {
+            for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                addresses.insert(log.address);
+                topics.extend(log.topics().iter().copied());
+            }
+        }
+
+        self.unwind_sharded_index::<tables::LogAddressIndex, _>(addresses, first_block)?;
+        self.unwind_sharded_index::<tables::LogTopicIndex, _>(topics, first_block)
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> AddressTransactionsWriter
+    for DatabaseProvider<TX, N>
+{
+    fn is_address_transactions_index_enabled(&self) -> bool {
+        self.address_transactions_index
+    }
+
+    fn insert_address_transaction_indices(
+        &self,
+        transactions: impl IntoIterator<Item = (TxNumber, impl IntoIterator<Item = Address>)>,
+    ) -> ProviderResult<()> {
+        let mut addresses = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (tx_number, tx_addresses) in transactions {
+            for address in tx_addresses {
+                let transactions = addresses.entry(address).or_default();
+                if transactions.last() != Some(&tx_number) {
+                    transactions.push(tx_number);
+                }
+            }
+        }
+
+        self.append_history_index::<_, tables::AddressTransactions>(addresses, ShardedKey::new)
+    }
+
+    fn unwind_address_transaction_indices_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        if range.is_empty() {
+            return Ok(())
+        }
+
+        let tx_range = self.transaction_range_by_block_range(range)?;
+        let first_transaction = *tx_range.start();
+
+        let addresses = transaction_addresses_by_tx_range(self, tx_range)?
+            .into_iter()
+            .flatten()
+            .collect::<BTreeSet<_>>();
+
+        self.unwind_sharded_index::<tables::AddressTransactions, _>(addresses, first_transaction)
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecutionWriter
     for DatabaseProvider<TX, N>
 {
@@ -2762,6 +3140,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range.clone())?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_address_transactions_above(block)?;
 
         // get execution res
         let execution_state = self.take_state_above(block, remove_from)?;
@@ -2786,6 +3166,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range)?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_address_transactions_above(block)?;
 
         // remove execution res
         self.remove_state_above(block, remove_from)?;
@@ -3454,4 +3836,57 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn test_log_index_insert_and_unwind() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(
+                data.genesis.clone().try_recover().unwrap(),
+                crate::StorageLocation::Database,
+            )
+            .unwrap();
+        for i in 0..3 {
+            provider_rw
+                .insert_block(data.blocks[i].0.clone(), crate::StorageLocation::Database)
+                .unwrap();
+            provider_rw
+                .write_state(
+                    &data.blocks[i].1,
+                    crate::OriginalValuesKnown::No,
+                    crate::StorageLocation::Database,
+                )
+                .unwrap();
+        }
+
+        // the index is only available once it has a checkpoint
+        assert_eq!(provider_rw.log_index_range().unwrap(), None);
+
+        let receipts = provider_rw.receipts_by_block_range(1..=3).unwrap();
+        provider_rw
+            .insert_log_indices(
+                (1..=3).zip(receipts.iter().map(|receipts| receipts.iter().flat_map(|r| r.logs()))),
+            )
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(3)).unwrap();
+        assert_eq!(provider_rw.log_index_range().unwrap(), Some(0..=3));
+
+        let (address_1, address_2) = (Address::new([0x60; 20]), Address::new([0x61; 20]));
+        assert_eq!(provider_rw.log_address_blocks(address_1, 0..=3).unwrap(), vec![1]);
+        assert_eq!(provider_rw.log_address_blocks(address_2, 0..=3).unwrap(), vec![2, 3]);
+        assert_eq!(provider_rw.log_address_blocks(address_2, 3..=3).unwrap(), vec![3]);
+        assert_eq!(provider_rw.log_topic_blocks(B256::with_last_byte(2), 0..=3).unwrap(), vec![1]);
+        assert_eq!(
+            provider_rw.log_topic_blocks(B256::with_last_byte(4), 0..=3).unwrap(),
+            vec![2, 3]
+        );
+
+        provider_rw.unwind_log_indices_range(3..=3).unwrap();
+        assert_eq!(provider_rw.log_address_blocks(address_1, 0..=3).unwrap(), vec![1]);
+        assert_eq!(provider_rw.log_address_blocks(address_2, 0..=3).unwrap(), vec![2]);
+        assert_eq!(provider_rw.log_topic_blocks(B256::with_last_byte(4), 0..=3).unwrap(), vec![2]);
+    }
 }
@@ -1,9 +1,13 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    providers::state::{
+        hashed_account_range, hashed_storage_range, macros::delegate_provider_impls,
+        snap_synced_account, snap_synced_storage,
+    },
+    AccountReader, BlockHashReader, HashedPostStateProvider, ProviderError, StateProvider,
+    StateRootProvider,
 };
-use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_eips::{merge::EPOCH_SLOTS, BlockNumHash};
+use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
     models::{storage_sharded_key::StorageShardedKey, ShardedKey},
@@ -19,17 +23,22 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory,
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedPostStateSorted, HashedStorage, MultiProof,
+    MultiProofTargets, StateRoot, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
+    DatabaseHashedCursorFactory, DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof,
+    DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
+    RevertsCache, StateCommitment,
+};
+use std::{
+    fmt::Debug,
+    sync::{Arc, OnceLock},
 };
-use std::fmt::Debug;
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -50,6 +59,12 @@ pub struct HistoricalStateProviderRef<'b, Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Sorted revert state, cached by the owning [`HistoricalStateProvider`].
+    revert_state_cache: Option<&'b OnceLock<HashedPostStateSorted>>,
+    /// Cache of historical reverts shared with other providers.
+    reverts_cache: Option<&'b RevertsCache>,
+    /// Whether the state was downloaded with snap sync.
+    snap_synced: bool,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -65,7 +80,14 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            revert_state_cache: None,
+            reverts_cache: None,
+            snap_synced: false,
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -75,7 +97,41 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { provider, block_number, lowest_available_blocks }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks,
+            revert_state_cache: None,
+            reverts_cache: None,
+            snap_synced: false,
+        }
+    }
+
+    /// Sets the cache for the sorted revert state, so it's only computed once.
+    const fn with_revert_state_cache(mut self, cache: &'b OnceLock<HashedPostStateSorted>) -> Self {
+        self.revert_state_cache = Some(cache);
+        self
+    }
+
+    /// Sets the cache of historical reverts that is shared with other providers.
+    pub const fn with_reverts_cache(mut self, reverts_cache: Option<&'b RevertsCache>) -> Self {
+        self.reverts_cache = reverts_cache;
+        self
+    }
+
+    /// Marks the state as downloaded with snap sync at the given pivot block.
+    ///
+    /// The history before the pivot was never stored, and plain state misses are resolved from the
+    /// hashed state.
+    pub fn with_snap_sync_pivot(mut self, pivot: BlockNumber) -> Self {
+        self.lowest_available_blocks = self.lowest_available_blocks.with_snap_sync_pivot(pivot);
+        self.snap_synced = true;
+        self
+    }
+
+    const fn with_snap_synced(mut self, snap_synced: bool) -> Self {
+        self.snap_synced = snap_synced;
+        self
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -120,7 +176,9 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
     }
 
     /// Retrieve revert hashed state for this history provider.
-    fn revert_state(&self) -> ProviderResult<HashedPostState> {
+    ///
+    /// The revert state is shared with the reverts cache, if any.
+    fn revert_state(&self) -> ProviderResult<Arc<HashedPostState>> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -135,9 +193,54 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedPostState::from_reverts::<
-            <Provider::StateCommitment as StateCommitment>::KeyHasher,
-        >(self.tx(), self.block_number)?)
+        let Some(reverts_cache) = self.reverts_cache else {
+            return Ok(Arc::new(HashedPostState::from_reverts::<
+                <Provider::StateCommitment as StateCommitment>::KeyHasher,
+            >(self.tx(), self.block_number)?));
+        };
+
+        // the uncached reverts are read up to the end of the changesets, so the cached ones are
+        // relative to the last block with changesets as well
+        let tip = self.last_changeset_block()?;
+        let tip_hash = self
+            .provider
+            .block_hash(tip)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(tip.into()))?;
+        Ok(reverts_cache.reverts::<_, <Provider::StateCommitment as StateCommitment>::KeyHasher>(
+            self.tx(),
+            self.block_number,
+            BlockNumHash::new(tip, tip_hash),
+            |cached_tip| {
+                self.provider.block_hash(cached_tip.number).ok().flatten() == Some(cached_tip.hash)
+            },
+        )?)
+    }
+
+    /// Returns the last block that has account or storage changesets, or 0 if there are none.
+    fn last_changeset_block(&self) -> ProviderResult<BlockNumber> {
+        let account = self.tx().cursor_read::<tables::AccountChangeSets>()?.last()?;
+        let storage = self.tx().cursor_dup_read::<tables::StorageChangeSets>()?.last()?;
+        Ok(account
+            .map(|(block, _)| block)
+            .max(storage.map(|(key, _)| key.block_number()))
+            .unwrap_or_default())
+    }
+
+    /// Calls the closure with the sorted revert state for this history provider.
+    ///
+    /// The revert state is computed only once if this provider has a cache.
+    fn with_sorted_revert_state<R>(
+        &self,
+        f: impl FnOnce(&HashedPostStateSorted) -> ProviderResult<R>,
+    ) -> ProviderResult<R> {
+        let Some(cache) = self.revert_state_cache else {
+            return f(&Arc::unwrap_or_clone(self.revert_state()?).into_sorted());
+        };
+        if let Some(revert_state) = cache.get() {
+            return f(revert_state);
+        }
+        let revert_state = Arc::unwrap_or_clone(self.revert_state()?).into_sorted();
+        f(cache.get_or_init(|| revert_state))
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -263,7 +366,10 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> AccountRea
                 })?
                 .info),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)?)
+                match self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)? {
+                    None if self.snap_synced => snap_synced_account(self.tx(), address),
+                    account => Ok(account),
+                }
             }
         }
     }
@@ -290,14 +396,14 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
-        let mut revert_state = self.revert_state()?;
+        let mut revert_state = Arc::unwrap_or_clone(self.revert_state()?);
         revert_state.extend(hashed_state);
         StateRoot::overlay_root(self.tx(), revert_state)
             .map_err(|err| ProviderError::Database(err.into()))
     }
 
     fn state_root_from_nodes(&self, mut input: TrieInput) -> ProviderResult<B256> {
-        input.prepend(self.revert_state()?);
+        input.prepend(Arc::unwrap_or_clone(self.revert_state()?));
         StateRoot::overlay_root_from_nodes(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
     }
@@ -306,7 +412,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
         &self,
         hashed_state: HashedPostState,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        let mut revert_state = self.revert_state()?;
+        let mut revert_state = Arc::unwrap_or_clone(self.revert_state()?);
         revert_state.extend(hashed_state);
         StateRoot::overlay_root_with_updates(self.tx(), revert_state)
             .map_err(|err| ProviderError::Database(err.into()))
@@ -316,7 +422,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
         &self,
         mut input: TrieInput,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        input.prepend(self.revert_state()?);
+        input.prepend(Arc::unwrap_or_clone(self.revert_state()?));
         StateRoot::overlay_root_from_nodes_with_updates(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
     }
@@ -336,6 +442,27 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
             .map_err(|err| ProviderError::Database(err.into()))
     }
 
+    fn hashed_storage_root(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        let mut revert_storage = self.with_sorted_revert_state(|revert_state| {
+            let mut revert_storage = HashedStorage::default();
+            if let Some(sorted) = revert_state.storages.get(&hashed_address) {
+                revert_storage.wiped = sorted.wiped;
+                revert_storage.storage.extend(sorted.non_zero_valued_slots.iter().copied());
+                revert_storage
+                    .storage
+                    .extend(sorted.zero_valued_slots.iter().map(|slot| (*slot, U256::ZERO)));
+            }
+            Ok(revert_storage)
+        })?;
+        revert_storage.extend(&hashed_storage);
+        StorageRoot::overlay_root_hashed(self.tx(), hashed_address, revert_storage)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
     fn storage_proof(
         &self,
         address: Address,
@@ -371,7 +498,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        input.prepend(self.revert_state()?);
+        input.prepend(Arc::unwrap_or_clone(self.revert_state()?));
         Proof::overlay_account_proof(self.tx(), input, address, slots).map_err(ProviderError::from)
     }
 
@@ -380,12 +507,12 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         mut input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
-        input.prepend(self.revert_state()?);
+        input.prepend(Arc::unwrap_or_clone(self.revert_state()?));
         Proof::overlay_multiproof(self.tx(), input, targets).map_err(ProviderError::from)
     }
 
     fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
-        input.prepend(self.revert_state()?);
+        input.prepend(Arc::unwrap_or_clone(self.revert_state()?));
         TrieWitness::overlay_witness(self.tx(), input, target)
             .map_err(ProviderError::from)
             .map(|hm| hm.into_values().collect())
@@ -425,15 +552,63 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
                     })?
                     .value,
             )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                let value = self
+                    .tx()
+                    .cursor_dup_read::<tables::PlainStorageState>()?
+                    .seek_by_key_subkey(address, storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                    .map(|entry| entry.value);
+                match value {
+                    None if self.snap_synced => {
+                        Ok(snap_synced_storage(self.tx(), address, storage_key)?
+                            .or(Some(StorageValue::ZERO)))
+                    }
+                    value => Ok(value.or(Some(StorageValue::ZERO))),
+                }
+            }
         }
     }
+
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        let state_sorted = hashed_state.into_sorted();
+        self.with_sorted_revert_state(|revert_state| {
+            let factory = HashedPostStateCursorFactory::new(
+                HashedPostStateCursorFactory::new(
+                    DatabaseHashedCursorFactory::new(self.tx()),
+                    revert_state,
+                ),
+                &state_sorted,
+            );
+            Ok(hashed_account_range(factory, start, limit)?)
+        })
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let state_sorted =
+            HashedPostState::from_hashed_storage(hashed_address, hashed_storage).into_sorted();
+        self.with_sorted_revert_state(|revert_state| {
+            let factory = HashedPostStateCursorFactory::new(
+                HashedPostStateCursorFactory::new(
+                    DatabaseHashedCursorFactory::new(self.tx()),
+                    revert_state,
+                ),
+                &state_sorted,
+            );
+            Ok(hashed_storage_range(factory, hashed_address, start, limit)?)
+        })
+    }
 }
 
 impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> BytecodeReader
@@ -461,6 +636,12 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Sorted revert state, computed on first use.
+    revert_state_cache: OnceLock<HashedPostStateSorted>,
+    /// Cache of historical reverts shared with other providers.
+    reverts_cache: Option<RevertsCache>,
+    /// Whether the state was downloaded with snap sync.
+    snap_synced: bool,
 }
 
 impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
@@ -468,7 +649,29 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            revert_state_cache: OnceLock::new(),
+            reverts_cache: None,
+            snap_synced: false,
+        }
+    }
+
+    /// Sets the cache of historical reverts that is shared with other providers.
+    pub fn with_reverts_cache(mut self, reverts_cache: Option<RevertsCache>) -> Self {
+        self.reverts_cache = reverts_cache;
+        self
+    }
+
+    /// Marks the state as downloaded with snap sync at the given pivot block.
+    ///
+    /// See [`HistoricalStateProviderRef::with_snap_sync_pivot`].
+    pub fn with_snap_sync_pivot(mut self, pivot: BlockNumber) -> Self {
+        self.lowest_available_blocks = self.lowest_available_blocks.with_snap_sync_pivot(pivot);
+        self.snap_synced = true;
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -497,6 +700,9 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             self.block_number,
             self.lowest_available_blocks,
         )
+        .with_revert_state_cache(&self.revert_state_cache)
+        .with_reverts_cache(self.reverts_cache.as_ref())
+        .with_snap_synced(self.snap_synced)
     }
 }
 
@@ -535,6 +741,16 @@ impl LowestAvailableBlocks {
     pub fn is_storage_history_available(&self, at: BlockNumber) -> bool {
         self.storage_history_block_number.map(|block_number| block_number <= at).unwrap_or(true)
     }
+
+    /// Returns the lowest available blocks of a node whose state was downloaded with snap sync at
+    /// the given pivot block, the history is only available after the pivot.
+    fn with_snap_sync_pivot(self, pivot: BlockNumber) -> Self {
+        let lowest = Some(pivot + 1);
+        Self {
+            account_history_block_number: self.account_history_block_number.max(lowest),
+            storage_history_block_number: self.storage_history_block_number.max(lowest),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -544,7 +760,7 @@ mod tests {
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
     };
-    use alloy_primitives::{address, b256, Address, B256, U256};
+    use alloy_primitives::{address, b256, keccak256, Address, B256, U256};
     use reth_db_api::{
         models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
         tables,
@@ -557,6 +773,7 @@ mod tests {
         StateCommitmentProvider,
     };
     use reth_storage_errors::provider::ProviderError;
+    use reth_trie::HashedPostState;
 
     const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0x0000000000000000000000000000000000000005");
@@ -841,4 +1058,116 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         ));
     }
+
+    #[test]
+    fn history_provider_hashed_account_range() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let higher_acc_plain = Account { nonce: 4, balance: U256::ZERO, bytecode_hash: None };
+
+        // ADDRESS changed at block 3, HIGHER_ADDRESS was created at block 4
+        tx.put::<tables::AccountChangeSets>(
+            3,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            4,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+        )
+        .unwrap();
+        tx.put::<tables::HashedAccounts>(keccak256(ADDRESS), acc_plain).unwrap();
+        tx.put::<tables::HashedAccounts>(keccak256(HIGHER_ADDRESS), higher_acc_plain).unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        let mut at3 = vec![(keccak256(ADDRESS), acc_at3)];
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 3)
+                .hashed_account_range(HashedPostState::default(), B256::ZERO, 10)
+                .unwrap(),
+            at3.clone()
+        );
+
+        // the overlay is applied on top of the reverted state
+        let overlay = HashedPostState::default()
+            .with_accounts([(keccak256(HIGHER_ADDRESS), Some(higher_acc_plain))]);
+        at3.push((keccak256(HIGHER_ADDRESS), higher_acc_plain));
+        at3.sort_unstable_by_key(|(hashed_address, _)| *hashed_address);
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 3)
+                .hashed_account_range(overlay, B256::ZERO, 10)
+                .unwrap(),
+            at3
+        );
+
+        let mut at5 =
+            vec![(keccak256(ADDRESS), acc_plain), (keccak256(HIGHER_ADDRESS), higher_acc_plain)];
+        at5.sort_unstable_by_key(|(hashed_address, _)| *hashed_address);
+        let provider = HistoricalStateProviderRef::new(&db, 5);
+        assert_eq!(
+            provider.hashed_account_range(HashedPostState::default(), B256::ZERO, 10).unwrap(),
+            at5.clone()
+        );
+        assert_eq!(
+            provider.hashed_account_range(HashedPostState::default(), B256::ZERO, 1).unwrap(),
+            at5[..1].to_vec()
+        );
+        assert_eq!(
+            provider.hashed_account_range(HashedPostState::default(), at5[1].0, 10).unwrap(),
+            at5[1..].to_vec()
+        );
+    }
+
+    #[test]
+    fn history_provider_snap_synced() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let acc_hashed = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let higher_acc_plain = Account { nonce: 8, balance: U256::ZERO, bytecode_hash: None };
+        let higher_acc_at7 = Account { nonce: 7, balance: U256::ZERO, bytecode_hash: None };
+
+        // ADDRESS is only stored in the hashed state, HIGHER_ADDRESS changed at block 7
+        tx.put::<tables::HashedAccounts>(keccak256(ADDRESS), acc_hashed).unwrap();
+        tx.put::<tables::HashedStorages>(
+            keccak256(ADDRESS),
+            StorageEntry { key: keccak256(STORAGE), value: U256::from(1) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: HIGHER_ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            7,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: Some(higher_acc_at7) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(HIGHER_ADDRESS, higher_acc_plain).unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        // the state before the pivot was never stored
+        let provider = HistoricalStateProviderRef::new(&db, 5).with_snap_sync_pivot(5);
+        assert!(matches!(
+            provider.basic_account(&ADDRESS),
+            Err(ProviderError::StateAtBlockPruned(5))
+        ));
+
+        let provider = HistoricalStateProviderRef::new(&db, 6).with_snap_sync_pivot(5);
+        assert_eq!(provider.basic_account(&ADDRESS).unwrap(), Some(acc_hashed));
+        assert_eq!(provider.storage(ADDRESS, STORAGE).unwrap(), Some(U256::from(1)));
+        assert_eq!(provider.basic_account(&HIGHER_ADDRESS).unwrap(), Some(higher_acc_at7));
+
+        // plain state misses are only resolved from the hashed state after a snap sync
+        let provider = HistoricalStateProviderRef::new(&db, 6);
+        assert_eq!(provider.basic_account(&ADDRESS).unwrap(), None);
+    }
 }
@@ -1,8 +1,11 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, StateProvider, StateRootProvider,
+    providers::state::{
+        hashed_account_range, hashed_storage_range, macros::delegate_provider_impls,
+        snap_synced_account, snap_synced_storage, SnapSynced,
+    },
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateProvider, StateRootProvider,
 };
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256};
 use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
@@ -10,6 +13,7 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory,
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
@@ -17,31 +21,43 @@ use reth_trie::{
     StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
-    DatabaseTrieWitness, StateCommitment,
+    DatabaseHashedCursorFactory, DatabaseProof, DatabaseStateRoot, DatabaseStorageProof,
+    DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
+use std::sync::OnceLock;
 
 /// State provider over latest state that takes tx reference.
 ///
 /// Wraps a [`DBProvider`] to get access to database.
 #[derive(Debug)]
-pub struct LatestStateProviderRef<'b, Provider>(&'b Provider);
+pub struct LatestStateProviderRef<'b, Provider>(&'b Provider, SnapSynced<'b>);
 
 impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
     /// Create new state provider
     pub const fn new(provider: &'b Provider) -> Self {
-        Self(provider)
+        Self(provider, SnapSynced::new())
     }
 
     fn tx(&self) -> &Provider::Tx {
         self.0.tx_ref()
     }
+
+    /// Returns `true` if the state of the node was downloaded with snap sync.
+    fn is_snap_synced(&self) -> ProviderResult<bool> {
+        self.1.get(self.tx())
+    }
 }
 
 impl<Provider: DBProvider> AccountReader for LatestStateProviderRef<'_, Provider> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
-        self.tx().get_by_encoded_key::<tables::PlainAccountState>(address).map_err(Into::into)
+        if let Some(account) = self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)? {
+            return Ok(Some(account))
+        }
+        if self.is_snap_synced()? {
+            return snap_synced_account(self.tx(), address)
+        }
+        Ok(None)
     }
 }
 
@@ -102,6 +118,15 @@ impl<Provider: DBProvider + StateCommitmentProvider> StorageRootProvider
             .map_err(|err| ProviderError::Database(err.into()))
     }
 
+    fn hashed_storage_root(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        StorageRoot::overlay_root_hashed(self.tx(), hashed_address, hashed_storage)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
     fn storage_proof(
         &self,
         address: Address,
@@ -175,8 +200,41 @@ impl<Provider: DBProvider + BlockHashReader + StateCommitmentProvider> StateProv
                 return Ok(Some(entry.value))
             }
         }
+        if self.is_snap_synced()? {
+            return snap_synced_storage(self.tx(), account, storage_key)
+        }
         Ok(None)
     }
+
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        let state_sorted = hashed_state.into_sorted();
+        let factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(self.tx()),
+            &state_sorted,
+        );
+        Ok(hashed_account_range(factory, start, limit)?)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let state_sorted =
+            HashedPostState::from_hashed_storage(hashed_address, hashed_storage).into_sorted();
+        let factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(self.tx()),
+            &state_sorted,
+        );
+        Ok(hashed_storage_range(factory, hashed_address, start, limit)?)
+    }
 }
 
 impl<Provider: DBProvider + BlockHashReader + StateCommitmentProvider> BytecodeReader
@@ -196,18 +254,18 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 
 /// State provider for the latest state.
 #[derive(Debug)]
-pub struct LatestStateProvider<Provider>(Provider);
+pub struct LatestStateProvider<Provider>(Provider, OnceLock<bool>);
 
 impl<Provider: DBProvider + StateCommitmentProvider> LatestStateProvider<Provider> {
     /// Create new state provider
     pub const fn new(db: Provider) -> Self {
-        Self(db)
+        Self(db, OnceLock::new())
     }
 
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     const fn as_ref(&self) -> LatestStateProviderRef<'_, Provider> {
-        LatestStateProviderRef::new(&self.0)
+        LatestStateProviderRef(&self.0, SnapSynced::shared(&self.1))
     }
 }
 
@@ -221,6 +279,12 @@ delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvid
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use alloy_primitives::{address, keccak256};
+    use reth_codecs::Compact;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives_traits::StorageEntry;
+    use reth_stages_types::{SnapSyncCheckpoint, StageId};
 
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
@@ -229,4 +293,52 @@ mod tests {
     >() {
         assert_state_provider::<LatestStateProvider<T>>();
     }
+
+    #[test]
+    fn latest_provider_snap_synced() {
+        let factory = create_test_provider_factory();
+        let address = address!("0x0000000000000000000000000000000000000001");
+        let slot = B256::with_last_byte(1);
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::HashedAccounts>(keccak256(address), account).unwrap();
+        tx.put::<tables::HashedStorages>(
+            keccak256(address),
+            StorageEntry { key: keccak256(slot), value: U256::from(3) },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        // without snap sync, the plain state is authoritative
+        let db = factory.provider().unwrap();
+        let provider = LatestStateProviderRef::new(&db);
+        assert_eq!(provider.basic_account(&address).unwrap(), None);
+        assert_eq!(provider.storage(address, slot).unwrap(), None);
+        drop(db);
+
+        let write_checkpoint = |healed| {
+            let tx = factory.provider_rw().unwrap().into_tx();
+            let mut progress = Vec::new();
+            SnapSyncCheckpoint { pivot: 1, healed, ..Default::default() }.to_compact(&mut progress);
+            tx.put::<tables::StageCheckpointProgresses>(StageId::SnapSync.to_string(), progress)
+                .unwrap();
+            tx.commit().unwrap();
+        };
+
+        // the hashed state is incomplete until it is healed
+        write_checkpoint(false);
+        let db = factory.provider().unwrap();
+        let provider = LatestStateProviderRef::new(&db);
+        assert_eq!(provider.basic_account(&address).unwrap(), None);
+        drop(db);
+
+        // an account that was never touched after the pivot is only in the hashed state
+        write_checkpoint(true);
+        let db = factory.provider().unwrap();
+        let provider = LatestStateProviderRef::new(&db);
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(account));
+        assert_eq!(provider.storage(address, slot).unwrap(), Some(U256::from(3)));
+        assert_eq!(provider.storage(address, B256::ZERO).unwrap(), None);
+    }
 }
@@ -1,4 +1,113 @@
 //! [`StateProvider`](crate::StateProvider) implementations
+use alloy_primitives::{keccak256, Address, StorageKey, StorageValue, B256, U256};
+use reth_codecs::Compact;
+use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use reth_primitives_traits::Account;
+use reth_stages_types::{SnapSyncCheckpoint, StageId};
+use reth_storage_errors::{db::DatabaseError, provider::ProviderResult};
+use reth_trie::hashed_cursor::{HashedCursor, HashedCursorFactory};
+use std::sync::OnceLock;
+
 pub(crate) mod historical;
 pub(crate) mod latest;
 pub(crate) mod macros;
+
+/// Returns up to `limit` accounts of the hashed state, starting at the given hashed address.
+pub(crate) fn hashed_account_range(
+    factory: impl HashedCursorFactory,
+    start: B256,
+    limit: usize,
+) -> Result<Vec<(B256, Account)>, DatabaseError> {
+    let cursor = factory.hashed_account_cursor()?;
+    collect_range(cursor, start, limit)
+}
+
+/// Returns up to `limit` storage slots of the account with the given hashed address, starting at
+/// the given hashed slot.
+pub(crate) fn hashed_storage_range(
+    factory: impl HashedCursorFactory,
+    hashed_address: B256,
+    start: B256,
+    limit: usize,
+) -> Result<Vec<(B256, U256)>, DatabaseError> {
+    let cursor = factory.hashed_storage_cursor(hashed_address)?;
+    collect_range(cursor, start, limit)
+}
+
+/// Whether the state of the node was downloaded with snap sync.
+///
+/// Snap sync only fills the hashed state tables, so the plain state of a snap synced node only
+/// contains the entries that were written after the pivot block and a plain state miss has to be
+/// checked against the hashed state.
+///
+/// The flag is read from the database on the first plain state miss and cached, either in the
+/// provider itself or in the provider that owns the database provider.
+#[derive(Debug, Default)]
+pub(crate) struct SnapSynced<'a> {
+    /// The cache of the owning provider.
+    shared: Option<&'a OnceLock<bool>>,
+    /// The cache if there is no owning provider.
+    local: OnceLock<bool>,
+}
+
+impl<'a> SnapSynced<'a> {
+    /// Creates a flag that is cached by this instance.
+    pub(crate) const fn new() -> Self {
+        Self { shared: None, local: OnceLock::new() }
+    }
+
+    /// Creates a flag that is cached in the given cell of the owning provider.
+    pub(crate) const fn shared(cache: &'a OnceLock<bool>) -> Self {
+        Self { shared: Some(cache), local: OnceLock::new() }
+    }
+
+    /// Returns `true` if the state of the node was downloaded with snap sync and healed.
+    pub(crate) fn get<TX: DbTx>(&self, tx: &TX) -> ProviderResult<bool> {
+        let cache = self.shared.unwrap_or(&self.local);
+        if let Some(snap_synced) = cache.get() {
+            return Ok(*snap_synced)
+        }
+        let progress = tx
+            .get::<tables::StageCheckpointProgresses>(StageId::SnapSync.to_string())?
+            .unwrap_or_default();
+        let snap_synced = !progress.is_empty() &&
+            SnapSyncCheckpoint::from_compact(&progress, progress.len()).0.healed;
+        Ok(*cache.get_or_init(|| snap_synced))
+    }
+}
+
+/// Returns the account from the hashed state, for plain state misses of snap synced nodes.
+pub(crate) fn snap_synced_account<TX: DbTx>(
+    tx: &TX,
+    address: &Address,
+) -> ProviderResult<Option<Account>> {
+    Ok(tx.get::<tables::HashedAccounts>(keccak256(address))?)
+}
+
+/// Returns the storage slot from the hashed state, for plain state misses of snap synced nodes.
+pub(crate) fn snap_synced_storage<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    storage_key: StorageKey,
+) -> ProviderResult<Option<StorageValue>> {
+    let hashed_slot = keccak256(storage_key);
+    Ok(tx
+        .cursor_dup_read::<tables::HashedStorages>()?
+        .seek_by_key_subkey(keccak256(address), hashed_slot)?
+        .filter(|entry| entry.key == hashed_slot)
+        .map(|entry| entry.value))
+}
+
+fn collect_range<C: HashedCursor>(
+    mut cursor: C,
+    start: B256,
+    limit: usize,
+) -> Result<Vec<(B256, C::Value)>, DatabaseError> {
+    let mut entries = Vec::new();
+    let mut entry = cursor.seek(start)?;
+    while let Some(next) = entry.filter(|_| entries.len() < limit) {
+        entries.push(next);
+        entry = cursor.next()?;
+    }
+    Ok(entries)
+}
@@ -13,7 +13,7 @@ use alloy_consensus::{
 };
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{
-    b256, keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    b256, keccak256, Address, BlockHash, BlockNumber, Bytes, TxHash, TxNumber, B256, U256,
 };
 use dashmap::DashMap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
@@ -1097,6 +1097,36 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(None)
     }
 
+    /// Returns all raw, undecoded columns of the row with the given key in the static files of
+    /// the segment.
+    ///
+    /// The key is a block number for block based segments and a transaction number for
+    /// transaction based segments.
+    pub fn raw_row(
+        &self,
+        segment: StaticFileSegment,
+        key: u64,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        let provider = if segment.is_tx_based() {
+            self.get_segment_provider_from_transaction(segment, key, None)
+        } else {
+            self.get_segment_provider_from_block(segment, key, None)
+        };
+        let provider = match provider {
+            Ok(provider) => provider,
+            Err(
+                ProviderError::MissingStaticFileBlock(_, _) |
+                ProviderError::MissingStaticFileTx(_, _),
+            ) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let mask = (1 << segment.columns()) - 1;
+        let mut cursor = provider.cursor()?;
+        let row = cursor.get(key.into(), mask)?;
+        Ok(row.map(|columns| columns.into_iter().map(Bytes::copy_from_slice).collect()))
+    }
+
     /// Fetches data within a specified range across multiple static files.
     ///
     /// This function iteratively retrieves data using `get_fn` for each item in the given range.
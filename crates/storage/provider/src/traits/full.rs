@@ -1,17 +1,18 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider,
+    ChangeSetReader, DatabaseProviderFactory, HeaderProvider, HistoryIndexReader, LogIndexReader,
+    PruneCheckpointReader, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
 };
-use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
+use reth_chain_state::{BadBlockReader, CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
-use reth_storage_api::NodePrimitivesProvider;
+use reth_storage_api::{DatabaseInspector, NodePrimitivesProvider};
 use std::fmt::Debug;
 
 /// Helper trait to unify all provider traits for simplicity.
 pub trait FullProvider<N: NodeTypesWithDB>:
-    DatabaseProviderFactory<DB = N::DB>
+    DatabaseProviderFactory<DB = N::DB, Provider: BlockNumReader + HeaderProvider>
     + NodePrimitivesProvider<Primitives = N::Primitives>
     + StaticFileProviderFactory<Primitives = N::Primitives>
     + BlockReaderIdExt<
@@ -23,9 +24,15 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + StateProviderFactory
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
+    + HistoryIndexReader
+    + LogIndexReader
+    + AddressTransactionsReader
+    + DatabaseInspector
     + CanonStateSubscriptions
+    + BadBlockReader
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + StageCheckpointReader
+    + PruneCheckpointReader
     + Clone
     + Debug
     + Unpin
@@ -34,7 +41,7 @@ pub trait FullProvider<N: NodeTypesWithDB>:
 }
 
 impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
-    T: DatabaseProviderFactory<DB = N::DB>
+    T: DatabaseProviderFactory<DB = N::DB, Provider: BlockNumReader + HeaderProvider>
         + NodePrimitivesProvider<Primitives = N::Primitives>
         + StaticFileProviderFactory<Primitives = N::Primitives>
         + BlockReaderIdExt<
@@ -46,9 +53,15 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + StateProviderFactory
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
+        + HistoryIndexReader
+        + LogIndexReader
+        + AddressTransactionsReader
+        + DatabaseInspector
         + CanonStateSubscriptions
+        + BadBlockReader
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + StageCheckpointReader
+        + PruneCheckpointReader
         + Clone
         + Debug
         + Unpin
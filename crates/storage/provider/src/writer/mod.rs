@@ -1,15 +1,19 @@
 use crate::{
     providers::{StaticFileProvider, StaticFileWriter as SfWriter},
-    BlockExecutionWriter, BlockWriter, HistoryWriter, StateWriter, StaticFileProviderFactory,
-    StorageLocation, TrieWriter,
+    AddressTransactionsWriter, BlockExecutionWriter, BlockWriter, HistoryWriter, LogIndexWriter,
+    StateWriter, StaticFileProviderFactory, StorageLocation, TrieWriter,
 };
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, TxReceipt};
 use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
 use reth_db_api::transaction::{DbTx, DbTxMut};
 use reth_errors::{ProviderError, ProviderResult};
 use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{DBProvider, StageCheckpointWriter, TransactionsProviderExt};
+use reth_storage_api::{
+    transaction_addresses, DBProvider, StageCheckpointReader, StageCheckpointWriter,
+    TransactionsProviderExt,
+};
 use reth_storage_errors::writer::UnifiedStorageWriterError;
 use revm_database::OriginalValuesKnown;
 use std::sync::Arc;
@@ -126,6 +130,9 @@ where
         + TrieWriter
         + StateWriter
         + HistoryWriter
+        + LogIndexWriter
+        + AddressTransactionsWriter
+        + StageCheckpointReader
         + StageCheckpointWriter
         + BlockExecutionWriter
         + AsRef<ProviderDB>
@@ -151,6 +158,21 @@ where
 
         debug!(target: "provider::storage_writer", block_count = %blocks.len(), "Writing blocks and execution data to storage");
 
+        // The log index is only extended if the node is configured to maintain one and it reaches
+        // the parent of the first block, otherwise it's left for the `IndexLogs` stage to catch up.
+        let index_logs = self.database().is_log_index_enabled() &&
+            self.database()
+                .get_stage_checkpoint(StageId::IndexLogs)?
+                .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number);
+        let mut block_logs = Vec::new();
+
+        // Same for the address transactions index and the `IndexAddressTransactions` stage.
+        let index_address_transactions = self.database().is_address_transactions_index_enabled() &&
+            self.database()
+                .get_stage_checkpoint(StageId::IndexAddressTransactions)?
+                .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number);
+        let mut address_transactions = Vec::new();
+
         // TODO: Do performant / batched writes for each type of object
         // instead of a loop over all blocks,
         // meaning:
@@ -166,6 +188,17 @@ where
         } in blocks
         {
             let block_hash = recovered_block.hash();
+            if index_logs {
+                let logs = execution_output.receipts.iter().flatten().flat_map(|r| r.logs());
+                block_logs.push((recovered_block.number(), logs.cloned().collect::<Vec<_>>()));
+            }
+            if index_address_transactions {
+                address_transactions.extend(
+                    recovered_block
+                        .transactions_with_sender()
+                        .map(|(sender, tx)| transaction_addresses(*sender, tx)),
+                );
+            }
             self.database()
                 .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
 
@@ -188,6 +221,32 @@ where
         // update history indices
         self.database().update_history_indices(first_number..=last_block_number)?;
 
+        // update log index
+        if index_logs {
+            self.database()
+                .insert_log_indices(block_logs.iter().map(|(number, logs)| (*number, logs)))?;
+            self.database().save_stage_checkpoint(
+                StageId::IndexLogs,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
+
+        // update address transactions index
+        if index_address_transactions {
+            let first_transaction = self
+                .database()
+                .block_body_indices(first_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(first_number))?
+                .first_tx_num();
+            self.database().insert_address_transaction_indices(
+                (first_transaction..).zip(address_transactions),
+            )?;
+            self.database().save_stage_checkpoint(
+                StageId::IndexAddressTransactions,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
+
         // Update pipeline progress
         self.database().update_pipeline_stages(last_block_number, false)?;
 
@@ -43,14 +43,16 @@ use reth_errors::{ProviderError, ProviderResult};
 use reth_node_types::{
     Block, BlockBody, BlockTy, HeaderTy, NodeTypes, PrimitivesTy, ReceiptTy, TxTy,
 };
-use reth_primitives::{Account, Bytecode, RecoveredBlock, SealedHeader, TransactionMeta};
+use reth_primitives::{
+    Account, Bytecode, RecoveredBlock, SealedBlock, SealedHeader, TransactionMeta,
+};
 use reth_provider::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BytecodeReader,
-    CanonChainTracker, CanonStateNotification, CanonStateNotifications, CanonStateSubscriptions,
-    ChainStateBlockReader, ChainStateBlockWriter, ChangeSetReader, DatabaseProviderFactory,
-    HeaderProvider, PruneCheckpointReader, ReceiptProvider, StageCheckpointReader, StateProvider,
-    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, StorageReader,
-    TransactionVariant, TransactionsProvider,
+    AccountReader, BadBlockReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
+    BytecodeReader, CanonChainTracker, CanonStateNotification, CanonStateNotifications,
+    CanonStateSubscriptions, ChainStateBlockReader, ChainStateBlockWriter, ChangeSetReader,
+    DatabaseProviderFactory, HeaderProvider, PruneCheckpointReader, ReceiptProvider,
+    StageCheckpointReader, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, StorageReader, TransactionVariant, TransactionsProvider,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_rpc_convert::{TryFromBlockResponse, TryFromReceiptResponse, TryFromTransactionResponse};
@@ -883,6 +885,18 @@ where
     }
 }
 
+impl<P, Node, N> BadBlockReader for RpcBlockchainProvider<P, Node, N>
+where
+    P: Send + Sync,
+    N: Send + Sync,
+    Node: NodeTypes,
+{
+    fn bad_blocks(&self) -> Vec<SealedBlock<BlockTy<Node>>> {
+        // Bad blocks are only tracked by the engine of the remote node
+        Vec::new()
+    }
+}
+
 impl<P, Node, N> ChainSpecProvider for RpcBlockchainProvider<P, Node, N>
 where
     P: Send + Sync,
@@ -54,4 +54,38 @@ pub trait ChangeSetReader {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Iterate over account changesets of the block range and return all account addresses that
+    /// were changed.
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut addresses = BTreeSet::new();
+        for block_number in range {
+            addresses.extend(
+                self.account_block_changeset(block_number)?.into_iter().map(|acc| acc.address),
+            );
+        }
+        Ok(addresses)
+    }
+}
+
+/// Account and storage history index reader
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryIndexReader {
+    /// Returns the blocks within the range in which the account changed, in ascending order.
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the blocks within the range in which any storage slot of the account changed, in
+    /// ascending order.
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
 }
@@ -0,0 +1,47 @@
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{BlockNumber, Bytes};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+
+/// A single statistic of the database environment, e.g. the size of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseStat {
+    /// The name of the statistic, e.g. `db.table_size`.
+    pub name: &'static str,
+    /// The labels of the statistic as key-value pairs, e.g. the table it belongs to.
+    pub labels: Vec<(String, String)>,
+    /// The value of the statistic.
+    pub value: f64,
+}
+
+/// Read-only access to the raw contents of the database and the static files.
+///
+/// This allows inspecting a running node without stopping it for the `reth db` commands.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait DatabaseInspector: Send + Sync {
+    /// Returns the value stored for the given key in the table with the given name, encoded as
+    /// JSON.
+    ///
+    /// The key, and the subkey for dupsort tables, are JSON values that are decoded the same way
+    /// as by `reth db get`.
+    fn table_value(
+        &self,
+        table: &str,
+        key: &str,
+        subkey: Option<&str>,
+    ) -> ProviderResult<Option<String>>;
+
+    /// Returns the statistics of the database environment and its tables.
+    fn database_stats(&self) -> ProviderResult<Vec<DatabaseStat>>;
+
+    /// Returns the raw columns of the static file row with the given key, which is a block number
+    /// for block based segments and a transaction number for transaction based segments.
+    fn static_file_row(
+        &self,
+        segment: StaticFileSegment,
+        key: u64,
+    ) -> ProviderResult<Option<Vec<Bytes>>>;
+
+    /// Returns the highest block stored in the static files of the given segment.
+    fn highest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber>;
+}
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, Log, TxNumber, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
@@ -56,3 +56,54 @@ pub trait HistoryWriter: Send + Sync {
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
+
+/// Log index writer
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexWriter: Send + Sync {
+    /// Returns whether the node is configured to maintain the log index.
+    ///
+    /// If it isn't, blocks are written without extending the index, even if it was built before.
+    fn is_log_index_enabled(&self) -> bool;
+
+    /// Insert the addresses and topics of the logs emitted by each block into the log index.
+    ///
+    /// Blocks have to be in ascending order and above the highest block already in the index.
+    fn insert_log_indices<'a>(
+        &self,
+        blocks: impl IntoIterator<Item = (BlockNumber, impl IntoIterator<Item = &'a Log>)>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the log index in a given block range.
+    ///
+    /// The receipts of the range are used to find the affected keys, so they must not have been
+    /// removed yet.
+    fn unwind_log_indices_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+}
+
+/// Address transactions index writer
+#[auto_impl(&, Arc, Box)]
+pub trait AddressTransactionsWriter: Send + Sync {
+    /// Returns whether the node is configured to maintain the address transactions index.
+    ///
+    /// If it isn't, blocks are written without extending the index, even if it was built before.
+    fn is_address_transactions_index_enabled(&self) -> bool;
+
+    /// Insert the addresses each transaction is indexed under into the address transactions
+    /// index.
+    ///
+    /// Transactions have to be in ascending order and above the highest transaction already in
+    /// the index.
+    fn insert_address_transaction_indices(
+        &self,
+        transactions: impl IntoIterator<Item = (TxNumber, impl IntoIterator<Item = Address>)>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the address transactions index in a given block range.
+    ///
+    /// The transactions of the range are used to find the affected addresses, so they must not
+    /// have been removed yet.
+    fn unwind_address_transaction_indices_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()>;
+}
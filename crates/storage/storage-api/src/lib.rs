@@ -57,6 +57,9 @@ pub use trie::*;
 mod chain_info;
 pub use chain_info::*;
 
+mod database_inspector;
+pub use database_inspector::*;
+
 #[cfg(feature = "db-api")]
 mod database_provider;
 #[cfg(feature = "db-api")]
@@ -1,7 +1,7 @@
 use crate::BlockIdReader;
 use alloc::vec::Vec;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
-use alloy_primitives::{BlockNumber, TxHash, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxNumber, B256};
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_primitives_traits::Receipt;
 use reth_storage_errors::provider::ProviderResult;
@@ -91,3 +91,31 @@ pub trait ReceiptProviderIdExt: ReceiptProvider + BlockIdReader {
         self.receipts_by_block_id(number_or_tag.into())
     }
 }
+
+/// Reader for the optional log address and topic index.
+///
+/// The index maps each log address and each log topic (at any position) to the blocks that emitted
+/// a matching log. It is only available on nodes that enabled it, and only covers blocks that have
+/// receipts.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait LogIndexReader {
+    /// Returns the range of blocks covered by the log index, or `None` if the node doesn't
+    /// maintain one.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the blocks within the range that emitted at least one log from the address, in
+    /// ascending order.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the blocks within the range that emitted at least one log with the topic at any
+    /// position, in ascending order.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
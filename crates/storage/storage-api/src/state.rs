@@ -2,15 +2,15 @@ use super::{
     AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, StateRootProvider,
     StorageRootProvider,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber, StorageKey, StorageValue, B256, U256};
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives_traits::Bytecode;
-use reth_storage_errors::provider::ProviderResult;
-use reth_trie_common::HashedPostState;
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie_common::{HashedPostState, HashedStorage};
 use revm_database::BundleState;
 
 /// This just receives state, or [`ExecutionOutcome`], from the provider
@@ -90,6 +90,34 @@ pub trait StateProvider:
         // Returns None if acc doesn't exist
         self.basic_account(addr)?.map_or_else(|| Ok(None), |acc| Ok(Some(acc.nonce)))
     }
+
+    /// Returns up to `limit` accounts of the `HashedPostState` on top of the current state,
+    /// ordered by hashed address and starting at the given hashed address.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] if the provider can't iterate the state.
+    fn hashed_account_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    /// Returns up to `limit` storage slots of the `HashedStorage` on top of the current storage of
+    /// the account with the given hashed address, ordered by hashed slot and starting at the
+    /// given hashed slot.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] if the provider can't iterate the state.
+    fn hashed_storage_range(
+        &self,
+        _hashed_address: B256,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
 
 /// Minimal requirements to read a full account, for example, to validate its new transactions
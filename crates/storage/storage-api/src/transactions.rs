@@ -1,10 +1,10 @@
 use crate::{BlockNumReader, BlockReader};
 use alloc::vec::Vec;
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::{transaction::TransactionMeta, Transaction};
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{Address, BlockNumber, TxHash, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxKind, TxNumber};
 use core::ops::{Range, RangeBounds, RangeInclusive};
-use reth_primitives_traits::SignedTransaction;
+use reth_primitives_traits::{SignedTransaction, SignerRecoverable};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// Enum to control transaction hash inclusion.
@@ -112,3 +112,55 @@ pub trait TransactionsProviderExt: BlockReader {
         tx_range: Range<TxNumber>,
     ) -> ProviderResult<Vec<(TxHash, TxNumber)>>;
 }
+
+/// Client trait for reading the index of the transactions of each address.
+///
+/// The index maps each address to the numbers of the transactions that were sent by it, sent to
+/// it, or created it. It is only available on nodes that enabled it.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait AddressTransactionsReader {
+    /// Returns the range of blocks covered by the address transactions index, or `None` if the
+    /// node doesn't maintain one.
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the numbers of the transactions within the range that were sent by, sent to, or
+    /// created the address, in ascending order.
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
+
+/// Returns the addresses a transaction is indexed under in the address transactions index: its
+/// sender, and either its recipient or the address of the contract it created.
+pub fn transaction_addresses<T: Transaction>(sender: Address, transaction: &T) -> [Address; 2] {
+    let other = match transaction.kind() {
+        TxKind::Call(to) => to,
+        TxKind::Create => sender.create(transaction.nonce()),
+    };
+    [sender, other]
+}
+
+/// Returns the [`transaction_addresses`] of each transaction in the range.
+///
+/// Senders that have been pruned are recovered from the transaction signatures.
+pub fn transaction_addresses_by_tx_range<P: TransactionsProvider>(
+    provider: &P,
+    range: RangeInclusive<TxNumber>,
+) -> ProviderResult<Vec<[Address; 2]>> {
+    let transactions = provider.transactions_by_tx_range(range.clone())?;
+    let mut senders = provider.senders_by_tx_range(range)?;
+    if senders.len() != transactions.len() {
+        senders = transactions
+            .iter()
+            .map(|transaction| transaction.recover_signer_unchecked())
+            .collect::<Result<_, _>>()?;
+    }
+
+    Ok(senders
+        .into_iter()
+        .zip(&transactions)
+        .map(|(sender, transaction)| transaction_addresses(sender, transaction))
+        .collect())
+}
@@ -1,6 +1,6 @@
 use alloc::vec::Vec;
 use alloy_primitives::{map::B256Map, Address, Bytes, B256};
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie_common::{
     updates::{StorageTrieUpdates, TrieUpdates},
     AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
@@ -63,6 +63,19 @@ pub trait StorageRootProvider: Send + Sync {
         slots: &[B256],
         hashed_storage: HashedStorage,
     ) -> ProviderResult<StorageMultiProof>;
+
+    /// Returns the storage root of the `HashedStorage` for the account with the given hashed
+    /// address on top of the current state.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] if the provider only supports plain
+    /// addresses.
+    fn hashed_storage_root(
+        &self,
+        _hashed_address: B256,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
 
 /// A type that can generate state proof on top of a given post state.
@@ -0,0 +1,256 @@
+//! Admission policies for the transaction pool.
+//!
+//! Admission policies decide whether a transaction that passed validation is admitted into the
+//! pool. Unlike the [`TransactionValidator`](crate::TransactionValidator), which checks whether a
+//! transaction is valid, they enforce operator rules, such as quotas per origin and sender, or
+//! filters on senders and calls.
+
+use crate::{PoolTransaction, TransactionOrigin};
+use alloy_primitives::{Address, Selector};
+use std::{collections::HashSet, fmt};
+
+/// The state of the pool that an [`AdmissionPolicy`] checks a transaction against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdmissionContext {
+    /// The origin of the transaction.
+    pub origin: TransactionOrigin,
+    /// Whether the transaction replaces a transaction of the pool with the same sender and nonce.
+    pub is_replacement: bool,
+    /// Number of transactions in the pool with the same origin.
+    pub origin_transactions: usize,
+    /// Number of transactions in the pool from the same sender.
+    pub sender_transactions: usize,
+}
+
+/// A policy that decides whether a valid transaction is admitted into the pool.
+///
+/// Policies run after the [`TransactionValidator`](crate::TransactionValidator), right before the
+/// transaction is inserted into the pool. A rejected transaction results in a
+/// [`PoolErrorKind::AdmissionRejected`](crate::error::PoolErrorKind::AdmissionRejected) error.
+pub trait AdmissionPolicy<T: PoolTransaction>: fmt::Debug + Send + Sync {
+    /// Returns an error if the transaction must not be admitted into the pool.
+    fn check(&self, transaction: &T, ctx: &AdmissionContext) -> Result<(), AdmissionError>;
+}
+
+/// The reason a transaction was rejected by an [`AdmissionPolicy`].
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionError {
+    /// The pool already holds the maximum number of transactions of this origin.
+    #[error("pool quota of {max_transactions} transactions with origin {origin:?} reached")]
+    OriginQuotaExceeded {
+        /// The origin of the transaction.
+        origin: TransactionOrigin,
+        /// The maximum number of transactions with this origin.
+        max_transactions: usize,
+    },
+    /// The pool already holds the maximum number of transactions of this sender.
+    #[error("pool quota of {max_transactions} transactions of sender {sender} reached")]
+    SenderQuotaExceeded {
+        /// The sender of the transaction.
+        sender: Address,
+        /// The maximum number of transactions per sender.
+        max_transactions: usize,
+    },
+    /// Transactions of the sender are not admitted.
+    #[error("sender {0} is not allowed")]
+    SenderNotAllowed(Address),
+    /// Calls to the contract are not admitted.
+    #[error("calls to {0} are not allowed")]
+    CallTargetNotAllowed(Address),
+    /// Calls of the function are not admitted.
+    #[error("calls of function {0} are not allowed")]
+    SelectorNotAllowed(Selector),
+    /// The calldata of the transaction exceeds the size limit.
+    #[error("calldata size {size} exceeds limit {max_size}")]
+    CalldataTooLarge {
+        /// The size of the calldata.
+        size: usize,
+        /// The maximum size of the calldata.
+        max_size: usize,
+    },
+    /// Any other reason of a custom [`AdmissionPolicy`].
+    #[error(transparent)]
+    Other(Box<dyn core::error::Error + Send + Sync>),
+}
+
+/// Configurable admission rules of the pool, see [`PoolConfig`](crate::PoolConfig).
+///
+/// The rules apply to all transactions, including local ones. Replacements of transactions that
+/// are already in the pool don't count against the quotas.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdmissionRules {
+    /// Max number of [`TransactionOrigin::Local`] transactions in the pool, which are usually
+    /// received via RPC.
+    pub max_local_transactions: Option<usize>,
+    /// Max number of [`TransactionOrigin::External`] transactions in the pool, which are usually
+    /// received from peers.
+    pub max_external_transactions: Option<usize>,
+    /// Max number of [`TransactionOrigin::Private`] transactions in the pool.
+    pub max_private_transactions: Option<usize>,
+    /// Max number of transactions per sender in the pool, across all sub-pools.
+    pub max_sender_transactions: Option<usize>,
+    /// If not empty, only transactions of these senders are admitted.
+    pub allowed_senders: HashSet<Address>,
+    /// Transactions of these senders are never admitted.
+    pub denied_senders: HashSet<Address>,
+    /// Transactions that call these contracts are never admitted.
+    pub denied_call_targets: HashSet<Address>,
+    /// Transactions that call functions with these selectors are never admitted.
+    pub denied_selectors: HashSet<Selector>,
+    /// Max size of the calldata of a transaction in bytes.
+    pub max_calldata_size: Option<usize>,
+}
+
+impl AdmissionRules {
+    /// Returns the quota of transactions in the pool with the given origin.
+    pub const fn origin_quota(&self, origin: TransactionOrigin) -> Option<usize> {
+        match origin {
+            TransactionOrigin::Local => self.max_local_transactions,
+            TransactionOrigin::External => self.max_external_transactions,
+            TransactionOrigin::Private => self.max_private_transactions,
+        }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for AdmissionRules {
+    fn check(&self, transaction: &T, ctx: &AdmissionContext) -> Result<(), AdmissionError> {
+        let sender = transaction.sender();
+        if self.denied_senders.contains(&sender) ||
+            (!self.allowed_senders.is_empty() && !self.allowed_senders.contains(&sender))
+        {
+            return Err(AdmissionError::SenderNotAllowed(sender))
+        }
+
+        let input = transaction.input();
+        if let Some(max_size) = self.max_calldata_size {
+            if input.len() > max_size {
+                return Err(AdmissionError::CalldataTooLarge { size: input.len(), max_size })
+            }
+        }
+
+        if let Some(to) = transaction.to() {
+            if self.denied_call_targets.contains(&to) {
+                return Err(AdmissionError::CallTargetNotAllowed(to))
+            }
+            if let Some(selector) = input.get(..4).map(Selector::from_slice) {
+                if self.denied_selectors.contains(&selector) {
+                    return Err(AdmissionError::SelectorNotAllowed(selector))
+                }
+            }
+        }
+
+        if ctx.is_replacement {
+            return Ok(())
+        }
+
+        if let Some(max_transactions) = self.origin_quota(ctx.origin) {
+            if ctx.origin_transactions >= max_transactions {
+                return Err(AdmissionError::OriginQuotaExceeded {
+                    origin: ctx.origin,
+                    max_transactions,
+                });
+            }
+        }
+
+        if let Some(max_transactions) = self.max_sender_transactions {
+            if ctx.sender_transactions >= max_transactions {
+                return Err(AdmissionError::SenderQuotaExceeded { sender, max_transactions })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_consensus::Transaction;
+    use alloy_primitives::Bytes;
+
+    fn ctx(origin: TransactionOrigin) -> AdmissionContext {
+        AdmissionContext {
+            origin,
+            is_replacement: false,
+            origin_transactions: 0,
+            sender_transactions: 0,
+        }
+    }
+
+    #[test]
+    fn sender_lists() {
+        let tx = MockTransaction::eip1559();
+        let ctx = ctx(TransactionOrigin::External);
+
+        assert!(AdmissionRules::default().check(&tx, &ctx).is_ok());
+
+        let rules = AdmissionRules { denied_senders: [tx.sender()].into(), ..Default::default() };
+        assert!(matches!(rules.check(&tx, &ctx), Err(AdmissionError::SenderNotAllowed(_))));
+
+        let rules =
+            AdmissionRules { allowed_senders: [Address::ZERO].into(), ..Default::default() };
+        assert!(matches!(rules.check(&tx, &ctx), Err(AdmissionError::SenderNotAllowed(_))));
+
+        let rules = AdmissionRules { allowed_senders: [tx.sender()].into(), ..Default::default() };
+        assert!(rules.check(&tx, &ctx).is_ok());
+    }
+
+    #[test]
+    fn call_filters() {
+        let tx = MockTransaction::eip1559()
+            .with_input(Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]));
+        let target = tx.to().unwrap();
+        let ctx = ctx(TransactionOrigin::Local);
+
+        let rules = AdmissionRules { denied_call_targets: [target].into(), ..Default::default() };
+        assert!(matches!(rules.check(&tx, &ctx), Err(AdmissionError::CallTargetNotAllowed(_))));
+
+        let rules = AdmissionRules {
+            denied_selectors: [Selector::from([0xa9, 0x05, 0x9c, 0xbb])].into(),
+            ..Default::default()
+        };
+        assert!(matches!(rules.check(&tx, &ctx), Err(AdmissionError::SelectorNotAllowed(_))));
+
+        let rules = AdmissionRules { max_calldata_size: Some(4), ..Default::default() };
+        assert!(matches!(
+            rules.check(&tx, &ctx),
+            Err(AdmissionError::CalldataTooLarge { size: 5, max_size: 4 })
+        ));
+    }
+
+    #[test]
+    fn quotas() {
+        let tx = MockTransaction::eip1559();
+        let rules = AdmissionRules {
+            max_external_transactions: Some(2),
+            max_sender_transactions: Some(1),
+            ..Default::default()
+        };
+
+        let ctx = AdmissionContext { origin_transactions: 2, ..ctx(TransactionOrigin::External) };
+        assert!(matches!(
+            rules.check(&tx, &ctx),
+            Err(AdmissionError::OriginQuotaExceeded { max_transactions: 2, .. })
+        ));
+
+        // local transactions have their own quota
+        let ctx = AdmissionContext { origin_transactions: 2, ..ctx(TransactionOrigin::Local) };
+        assert!(rules.check(&tx, &ctx).is_ok());
+
+        let ctx = AdmissionContext { sender_transactions: 1, ..ctx(TransactionOrigin::Local) };
+        assert!(matches!(
+            rules.check(&tx, &ctx),
+            Err(AdmissionError::SenderQuotaExceeded { max_transactions: 1, .. })
+        ));
+
+        // replacements don't count against the quotas
+        let ctx = AdmissionContext {
+            is_replacement: true,
+            origin_transactions: 2,
+            sender_transactions: 1,
+            ..ctx(TransactionOrigin::External)
+        };
+        assert!(rules.check(&tx, &ctx).is_ok());
+    }
+}
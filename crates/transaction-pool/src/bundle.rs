@@ -0,0 +1,387 @@
+//! A pool of MEV bundles.
+//!
+//! Bundles are ordered lists of transactions that must be included together, at the top of a
+//! block within their validity window. Unlike transactions, bundles aren't validated against the
+//! state on submission, block builders simulate them and skip the ones that no longer apply.
+
+use alloy_primitives::{keccak256, Address, BlockNumber, B256};
+use parking_lot::RwLock;
+use reth_primitives_traits::{Recovered, SignedTransaction};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tracing::trace;
+
+/// The default maximum number of bundles kept in the [`BundlePool`].
+pub const DEFAULT_MAX_BUNDLES: usize = 1_000;
+
+/// A transaction of a [`PoolBundle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleTransaction<T> {
+    /// The signed transaction and its sender.
+    pub transaction: Recovered<T>,
+    /// Whether the transaction is allowed to revert without invalidating the bundle.
+    pub can_revert: bool,
+}
+
+/// A bundle of transactions that must be included together and in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolBundle<T> {
+    /// The transactions of the bundle, in execution order.
+    pub transactions: Vec<BundleTransaction<T>>,
+    /// The first block the bundle is valid for.
+    pub block_number: BlockNumber,
+    /// The last block the bundle is valid for.
+    pub max_block_number: BlockNumber,
+    /// The authenticated signer of the request that submitted the bundle, if it was signed.
+    pub signer: Option<Address>,
+    /// UUID that can be used to replace or cancel the bundle.
+    ///
+    /// The UUID is scoped to the [signer](PoolBundle::signer) of the bundle, so only requests of
+    /// the same signer can replace or cancel it. Bundles without a signer can't have one.
+    pub replacement_uuid: Option<String>,
+}
+
+impl<T: SignedTransaction> PoolBundle<T> {
+    /// Returns the hash of the bundle, which is the keccak256 hash of the concatenated
+    /// transaction hashes.
+    pub fn hash(&self) -> B256 {
+        let hashes =
+            self.transactions.iter().flat_map(|tx| tx.transaction.tx_hash().0).collect::<Vec<_>>();
+        keccak256(hashes)
+    }
+}
+
+impl<T> PoolBundle<T> {
+    /// Returns `true` if the bundle can be included in the given block.
+    pub const fn is_valid_at(&self, block_number: BlockNumber) -> bool {
+        self.block_number <= block_number && block_number <= self.max_block_number
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle doesn't contain any transactions.
+    #[error("bundle has no transactions")]
+    Empty,
+    /// The validity window of the bundle is empty.
+    #[error("invalid bundle validity window {block_number}..={max_block_number}")]
+    InvalidValidityWindow {
+        /// The first block the bundle is valid for.
+        block_number: BlockNumber,
+        /// The last block the bundle is valid for.
+        max_block_number: BlockNumber,
+    },
+    /// The validity window of the bundle ends before the next block.
+    #[error("bundle expired at block {max_block_number}, current block is {current_block}")]
+    Expired {
+        /// The last block the bundle is valid for.
+        max_block_number: BlockNumber,
+        /// The current block of the pool.
+        current_block: BlockNumber,
+    },
+    /// A bundle with the same hash is already in the pool.
+    #[error("bundle {0} already known")]
+    AlreadyKnown(B256),
+    /// The bundle has a replacement UUID, but no signer the UUID could be scoped to.
+    #[error("bundle replacement UUID requires a signed request")]
+    UnsignedReplacementUuid,
+    /// The pool has reached its bundle limit.
+    #[error("bundle pool is full, max {0} bundles")]
+    PoolFull(usize),
+}
+
+/// A pool of MEV bundles, shared between the RPC that accepts them and the payload builder that
+/// tries them at the top of each block.
+///
+/// Bundles are dropped once their validity window has passed, or once one of their transactions
+/// has been mined.
+#[derive(Debug)]
+pub struct BundlePool<T> {
+    inner: Arc<RwLock<BundlePoolInner<T>>>,
+}
+
+impl<T> Clone for BundlePool<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<T> Default for BundlePool<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+impl<T> BundlePool<T> {
+    /// Creates a new pool that keeps at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BundlePoolInner {
+                bundles: HashMap::default(),
+                replacement_uuids: HashMap::default(),
+                current_block: 0,
+                next_id: 0,
+                max_bundles,
+            })),
+        }
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns `true` if the pool contains no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+
+    /// Returns the bundles that can be included in the given block, in submission order.
+    pub fn bundles_at(&self, block_number: BlockNumber) -> Vec<Arc<PoolBundle<T>>> {
+        let inner = self.inner.read();
+        let mut bundles = inner
+            .bundles
+            .values()
+            .filter(|(_, bundle)| bundle.is_valid_at(block_number))
+            .collect::<Vec<_>>();
+        bundles.sort_unstable_by_key(|(id, _)| *id);
+        bundles.into_iter().map(|(_, bundle)| Arc::clone(bundle)).collect()
+    }
+
+    /// Removes the bundle the given signer submitted with the given replacement UUID.
+    ///
+    /// Returns the hash of the removed bundle, if any.
+    pub fn cancel_bundle(&self, signer: Address, replacement_uuid: &str) -> Option<B256> {
+        let mut inner = self.inner.write();
+        let hash = inner.replacement_uuids.remove(&(signer, replacement_uuid.to_string()))?;
+        inner.bundles.remove(&hash);
+        trace!(target: "txpool::bundles", %hash, %signer, %replacement_uuid, "cancelled bundle");
+        Some(hash)
+    }
+}
+
+impl<T: SignedTransaction> BundlePool<T> {
+    /// Adds a bundle to the pool and returns its hash.
+    ///
+    /// If the bundle has a replacement UUID, it replaces the bundle its signer previously submitted
+    /// with the same UUID.
+    pub fn add_bundle(&self, bundle: PoolBundle<T>) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::Empty)
+        }
+        if bundle.max_block_number < bundle.block_number {
+            return Err(BundlePoolError::InvalidValidityWindow {
+                block_number: bundle.block_number,
+                max_block_number: bundle.max_block_number,
+            })
+        }
+
+        let replacement_key = bundle
+            .replacement_uuid
+            .clone()
+            .map(|uuid| bundle.signer.map(|signer| (signer, uuid)))
+            .map(|key| key.ok_or(BundlePoolError::UnsignedReplacementUuid))
+            .transpose()?;
+
+        let hash = bundle.hash();
+        let mut inner = self.inner.write();
+        if bundle.max_block_number <= inner.current_block {
+            return Err(BundlePoolError::Expired {
+                max_block_number: bundle.max_block_number,
+                current_block: inner.current_block,
+            })
+        }
+        if inner.bundles.contains_key(&hash) {
+            return Err(BundlePoolError::AlreadyKnown(hash))
+        }
+
+        // remove the bundle this one replaces
+        let replaced =
+            replacement_key.as_ref().and_then(|key| inner.replacement_uuids.get(key).copied());
+        if let Some(replaced) = replaced {
+            inner.bundles.remove(&replaced);
+        }
+
+        if inner.bundles.len() >= inner.max_bundles {
+            return Err(BundlePoolError::PoolFull(inner.max_bundles))
+        }
+
+        if let Some(key) = replacement_key {
+            inner.replacement_uuids.insert(key, hash);
+        }
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.bundles.insert(hash, (id, Arc::new(bundle)));
+        trace!(target: "txpool::bundles", %hash, ?replaced, "added bundle");
+
+        Ok(hash)
+    }
+
+    /// Updates the pool on a new canonical block: drops the bundles that can't be included in the
+    /// next block anymore, and the bundles that contain one of the mined transactions.
+    pub fn on_canonical_block<'a>(
+        &self,
+        block_number: BlockNumber,
+        mined_transactions: impl IntoIterator<Item = &'a B256>,
+    ) {
+        let mut inner = self.inner.write();
+        inner.current_block = block_number;
+        if inner.bundles.is_empty() {
+            return
+        }
+
+        let mined = mined_transactions.into_iter().collect::<HashSet<_>>();
+        let before = inner.bundles.len();
+        inner.bundles.retain(|_, (_, bundle)| {
+            bundle.max_block_number > block_number &&
+                !bundle.transactions.iter().any(|tx| mined.contains(tx.transaction.tx_hash()))
+        });
+        let BundlePoolInner { bundles, replacement_uuids, .. } = &mut *inner;
+        replacement_uuids.retain(|_, hash| bundles.contains_key(hash));
+        trace!(target: "txpool::bundles", block_number, removed = before - bundles.len(), "updated bundles on canonical block");
+    }
+}
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    /// All bundles by hash, with the order they were submitted in.
+    bundles: HashMap<B256, (u64, Arc<PoolBundle<T>>)>,
+    /// The hash of the bundle of each signer and replacement UUID.
+    replacement_uuids: HashMap<(Address, String), B256>,
+    /// The number of the current canonical block.
+    current_block: BlockNumber,
+    /// The submission order of the next bundle.
+    next_id: u64,
+    /// The maximum number of bundles.
+    max_bundles: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{address, Signature};
+    use reth_ethereum_primitives::TransactionSigned;
+
+    const ALICE: Address = address!("0x000000000000000000000000000000000000a11c");
+    const BOB: Address = address!("0x0000000000000000000000000000000000000b0b");
+
+    fn bundle(
+        nonce: u64,
+        window: std::ops::RangeInclusive<BlockNumber>,
+        replacement_uuid: Option<&str>,
+    ) -> PoolBundle<TransactionSigned> {
+        signed_bundle(Some(ALICE), nonce, window, replacement_uuid)
+    }
+
+    fn signed_bundle(
+        signer: Option<Address>,
+        nonce: u64,
+        window: std::ops::RangeInclusive<BlockNumber>,
+        replacement_uuid: Option<&str>,
+    ) -> PoolBundle<TransactionSigned> {
+        let transaction = TransactionSigned::new_unhashed(
+            TxLegacy { nonce, ..Default::default() }.into(),
+            Signature::test_signature(),
+        );
+        PoolBundle {
+            transactions: vec![BundleTransaction {
+                transaction: Recovered::new_unchecked(transaction, Address::ZERO),
+                can_revert: false,
+            }],
+            block_number: *window.start(),
+            max_block_number: *window.end(),
+            signer,
+            replacement_uuid: replacement_uuid.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn bundles_in_validity_window() {
+        let pool = BundlePool::default();
+        let first = pool.add_bundle(bundle(0, 1..=2, None)).unwrap();
+        let second = pool.add_bundle(bundle(1, 2..=3, None)).unwrap();
+
+        let hashes = |block| pool.bundles_at(block).iter().map(|b| b.hash()).collect::<Vec<_>>();
+        assert_eq!(hashes(1), vec![first]);
+        assert_eq!(hashes(2), vec![first, second]);
+        assert_eq!(hashes(3), vec![second]);
+
+        assert_eq!(
+            pool.add_bundle(bundle(0, 1..=2, None)),
+            Err(BundlePoolError::AlreadyKnown(first))
+        );
+        assert_eq!(
+            pool.add_bundle(bundle(2, 3..=2, None)),
+            Err(BundlePoolError::InvalidValidityWindow { block_number: 3, max_block_number: 2 })
+        );
+
+        // the first bundle can't be included after block 2 anymore
+        pool.on_canonical_block(2, []);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(
+            pool.add_bundle(bundle(2, 1..=2, None)),
+            Err(BundlePoolError::Expired { max_block_number: 2, current_block: 2 })
+        );
+    }
+
+    #[test]
+    fn replace_and_cancel_bundles() {
+        let pool = BundlePool::default();
+        pool.add_bundle(bundle(0, 1..=5, Some("a"))).unwrap();
+        let replacement = pool.add_bundle(bundle(1, 1..=5, Some("a"))).unwrap();
+        let other = pool.add_bundle(bundle(2, 1..=5, Some("b"))).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.bundles_at(1)[0].hash(), replacement);
+
+        assert_eq!(pool.cancel_bundle(ALICE, "a"), Some(replacement));
+        assert_eq!(pool.cancel_bundle(ALICE, "a"), None);
+        assert_eq!(pool.bundles_at(1).iter().map(|b| b.hash()).collect::<Vec<_>>(), vec![other]);
+
+        // bundles with mined transactions are dropped
+        let mined = *pool.bundles_at(1)[0].transactions[0].transaction.tx_hash();
+        pool.on_canonical_block(1, [&mined]);
+        assert!(pool.is_empty());
+        assert_eq!(pool.cancel_bundle(ALICE, "b"), None);
+    }
+
+    #[test]
+    fn replacement_uuids_are_scoped_to_the_signer() {
+        let pool = BundlePool::default();
+        let alice = pool.add_bundle(signed_bundle(Some(ALICE), 0, 1..=5, Some("a"))).unwrap();
+
+        // another signer can neither replace nor cancel the bundle
+        let bob = pool.add_bundle(signed_bundle(Some(BOB), 1, 1..=5, Some("a"))).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.cancel_bundle(BOB, "a"), Some(bob));
+        assert_eq!(pool.cancel_bundle(BOB, "a"), None);
+        assert_eq!(pool.bundles_at(1).iter().map(|b| b.hash()).collect::<Vec<_>>(), vec![alice]);
+
+        assert_eq!(pool.cancel_bundle(ALICE, "a"), Some(alice));
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn unsigned_replacement_uuid() {
+        let pool = BundlePool::default();
+        assert_eq!(
+            pool.add_bundle(signed_bundle(None, 0, 1..=5, Some("a"))),
+            Err(BundlePoolError::UnsignedReplacementUuid)
+        );
+        assert!(pool.add_bundle(signed_bundle(None, 0, 1..=5, None)).is_ok());
+    }
+
+    #[test]
+    fn bundle_limit() {
+        let pool = BundlePool::new(1);
+        pool.add_bundle(bundle(0, 1..=1, Some("a"))).unwrap();
+        assert_eq!(pool.add_bundle(bundle(1, 1..=1, None)), Err(BundlePoolError::PoolFull(1)));
+
+        // replacing doesn't count towards the limit
+        pool.add_bundle(bundle(2, 1..=1, Some("a"))).unwrap();
+        assert_eq!(pool.len(), 1);
+    }
+}
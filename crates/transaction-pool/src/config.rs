@@ -1,4 +1,5 @@
 use crate::{
+    admission::AdmissionRules,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
@@ -31,6 +32,9 @@ pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 /// Default maximum new transactions for broadcasting.
 pub const MAX_NEW_PENDING_TXS_NOTIFICATIONS: usize = 200;
 
+/// Default maximum number of recently evicted transactions that are kept.
+pub const MAX_EVICTED_TRANSACTIONS_HISTORY: usize = 1024;
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -65,6 +69,11 @@ pub struct PoolConfig {
     pub max_new_pending_txs_notifications: usize,
     /// Maximum lifetime for transactions in the pool
     pub max_queued_lifetime: Duration,
+    /// Rules that valid transactions must satisfy to be admitted into the pool.
+    pub admission: AdmissionRules,
+    /// Max number of recently evicted or replaced transactions that are kept, see
+    /// [`TransactionPool::evicted_transactions`](crate::TransactionPool::evicted_transactions).
+    pub max_evicted_transactions: usize,
 }
 
 impl PoolConfig {
@@ -96,6 +105,8 @@ impl Default for PoolConfig {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            admission: Default::default(),
+            max_evicted_transactions: MAX_EVICTED_TRANSACTIONS_HISTORY,
         }
     }
 }
@@ -2,6 +2,7 @@
 
 use std::any::Any;
 
+use crate::admission::AdmissionError;
 use alloy_eips::eip4844::BlobTransactionValidationError;
 use alloy_primitives::{Address, TxHash, U256};
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
@@ -66,6 +67,10 @@ pub enum PoolErrorKind {
     /// Thrown if the mutual exclusivity constraint (blob vs normal transaction) is violated.
     #[error("transaction type {1} conflicts with existing transaction for {0}")]
     ExistingConflictingTransactionType(Address, u8),
+    /// Thrown if a valid transaction is rejected by an
+    /// [`AdmissionPolicy`](crate::admission::AdmissionPolicy) of the pool.
+    #[error(transparent)]
+    AdmissionRejected(#[from] AdmissionError),
     /// Any other error that occurred while inserting/validating a transaction. e.g. IO database
     /// error
     #[error(transparent)]
@@ -143,6 +148,10 @@ impl PoolError {
                 // exclusivity (blob vs normal tx) for all senders
                 false
             }
+            PoolErrorKind::AdmissionRejected(_) => {
+                // the transaction is valid but not admitted due to the local policy of the pool
+                false
+            }
         }
     }
 }
@@ -271,19 +271,27 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 pub use crate::{
+    admission::{AdmissionContext, AdmissionError, AdmissionPolicy, AdmissionRules},
     blobstore::{BlobStore, BlobStoreError},
+    bundle::{BundlePool, BundlePoolError, BundleTransaction, PoolBundle},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_EVICTED_TRANSACTIONS_HISTORY,
+        MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        CoinbaseTipOrdering, ConfiguredOrdering, EffectiveGasPriceOrdering, FifoOrdering, Priority,
+        RoundPriority, SenderFairOrdering, TransactionOrdering, TransactionOrderingKind,
+    },
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AddedTransactionOutcome,
-        AllTransactionsEvents, FullTransactionEvent, NewTransactionEvent, TransactionEvent,
-        TransactionEvents, TransactionListenerKind,
+        blob_tx_priority, fee_delta,
+        state::{ParkedReason, PoolTransactionStatus, SubPool},
+        AddedTransactionOutcome, AllTransactionsEvents, EvictedTransaction, EvictionReason,
+        FullTransactionEvent, NewTransactionEvent, TransactionEvent, TransactionEvents,
+        TransactionListenerKind,
     },
     traits::*,
     validate::{
@@ -307,6 +315,7 @@ use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
+pub mod admission;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
@@ -315,6 +324,7 @@ pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+pub mod bundle;
 mod config;
 pub mod identifier;
 mod ordering;
@@ -331,6 +341,14 @@ pub type EthTransactionPool<Client, S, T = EthPooledTransaction> = Pool<
     S,
 >;
 
+/// Type alias for an ethereum transaction pool whose ordering is selected at runtime, see
+/// [`TransactionOrderingKind`].
+pub type ConfiguredEthTransactionPool<Client, S, T = EthPooledTransaction> = Pool<
+    TransactionValidationTaskExecutor<EthTransactionValidator<Client, T>>,
+    ConfiguredOrdering<T>,
+    S,
+>;
+
 /// A shareable, generic, customizable `TransactionPool` implementation.
 #[derive(Debug)]
 pub struct Pool<V, T: TransactionOrdering, S> {
@@ -361,6 +379,15 @@ where
         self.inner().config()
     }
 
+    /// Sets a custom [`AdmissionPolicy`] that valid transactions must satisfy, in addition to the
+    /// configured [`PoolConfig::admission`] rules, to be added to the pool.
+    ///
+    /// This replaces a previously set policy and only applies to transactions that are added
+    /// afterwards.
+    pub fn set_admission_policy(&self, policy: Option<Arc<dyn AdmissionPolicy<V::Transaction>>>) {
+        self.inner().set_admission_policy(policy)
+    }
+
     /// Returns future that validates all transactions in the given iterator.
     ///
     /// This returns the validated transactions in the iterator's order.
@@ -421,6 +448,8 @@ where
     /// Returns a new [`Pool`] that uses the default [`TransactionValidationTaskExecutor`] when
     /// validating [`EthPooledTransaction`]s and ords via [`CoinbaseTipOrdering`]
     ///
+    /// See also [`ConfiguredEthTransactionPool::eth_pool_with_ordering`].
+    ///
     /// # Example
     ///
     /// ```
@@ -456,6 +485,26 @@ where
     }
 }
 
+impl<Client, S> ConfiguredEthTransactionPool<Client, S>
+where
+    Client:
+        ChainSpecProvider<ChainSpec: EthereumHardforks> + StateProviderFactory + Clone + 'static,
+    S: BlobStore,
+{
+    /// Returns a new [`Pool`] like [`EthTransactionPool::eth_pool`] that orders the transactions
+    /// by the given kind of ordering.
+    pub fn eth_pool_with_ordering(
+        validator: TransactionValidationTaskExecutor<
+            EthTransactionValidator<Client, EthPooledTransaction>,
+        >,
+        ordering: TransactionOrderingKind,
+        blob_store: S,
+        config: PoolConfig,
+    ) -> Self {
+        Self::new(validator, ConfiguredOrdering::new(ordering), blob_store, config)
+    }
+}
+
 /// implements the `TransactionPool` interface for various transaction pool API consumers.
 impl<V, T, S> TransactionPool for Pool<V, T, S>
 where
@@ -603,6 +652,20 @@ where
         self.pool.all_transactions()
     }
 
+    fn all_transactions_with_sub_pool(
+        &self,
+    ) -> Vec<(SubPool, Arc<ValidPoolTransaction<Self::Transaction>>)> {
+        self.pool.all_transactions_with_sub_pool()
+    }
+
+    fn transaction_status(&self, tx_hash: &TxHash) -> Option<PoolTransactionStatus> {
+        self.pool.transaction_status(tx_hash)
+    }
+
+    fn evicted_transactions(&self) -> Vec<EvictedTransaction> {
+        self.pool.evicted_transactions()
+    }
+
     fn remove_transactions(
         &self,
         hashes: Vec<TxHash>,
@@ -749,6 +812,14 @@ where
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
         self.pool.blob_store().get_by_versioned_hashes_v2(versioned_hashes)
     }
+
+    fn set_minimum_priority_fee(&self, minimum_priority_fee: Option<u128>) {
+        self.pool.set_minimum_priority_fee(minimum_priority_fee)
+    }
+
+    fn bundle_pool(&self) -> Option<&BundlePool<PoolConsensusTx<Self>>> {
+        Some(self.pool.bundle_pool())
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
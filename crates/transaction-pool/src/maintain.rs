@@ -5,12 +5,15 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction, PoolUpdateKind,
+    BlockInfo, PoolTransaction, PoolUpdateKind, SubPool, TransactionOrigin,
 };
 use alloy_consensus::{BlockHeader, Typed2718};
-use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Address, BlockHash, BlockNumber};
-use alloy_rlp::Encodable;
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    BlockNumberOrTag,
+};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -28,8 +31,10 @@ use std::{
     borrow::Borrow,
     collections::HashSet,
     hash::{Hash, Hasher},
+    io::Write,
     path::{Path, PathBuf},
     sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     sync::oneshot,
@@ -90,6 +95,39 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// Settings for the transaction pool snapshot task
+#[derive(Debug, Clone)]
+pub struct PoolSnapshotConfig {
+    /// Path to the snapshot file
+    pub snapshot_path: PathBuf,
+    /// Interval at which a snapshot is written while the node is running, in addition to the
+    /// snapshot that is written on shutdown.
+    pub interval: Option<Duration>,
+    /// Maximum age of the non-local transactions that are restored from the snapshot.
+    ///
+    /// Default: 3 hours
+    pub max_age: Duration,
+}
+
+impl PoolSnapshotConfig {
+    /// Creates a config that writes the snapshot to the given path on shutdown.
+    pub const fn new(snapshot_path: PathBuf) -> Self {
+        Self { snapshot_path, interval: None, max_age: MAX_QUEUED_TRANSACTION_LIFETIME }
+    }
+
+    /// Sets the interval at which a snapshot is written while the node is running.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets the maximum age of the non-local transactions that are restored from the snapshot.
+    pub const fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<N, Client, P, St, Tasks>(
     client: Client,
@@ -672,6 +710,208 @@ where
     }
 }
 
+/// Version of the [`PoolSnapshot`] format.
+const POOL_SNAPSHOT_VERSION: u8 = 1;
+
+/// Snapshot of all transactions in the pool, see [`snapshot_pool_transactions_task`].
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct PoolSnapshot {
+    /// Version of the snapshot format.
+    version: u8,
+    /// The transactions of the pool.
+    transactions: Vec<PoolSnapshotTransaction>,
+}
+
+/// A transaction of a [`PoolSnapshot`].
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct PoolSnapshotTransaction {
+    /// The [`SubPool`](crate::SubPool) the transaction was in.
+    sub_pool: u8,
+    /// The [`TransactionOrigin`] of the transaction.
+    origin: u8,
+    /// Unix timestamp in seconds of when the transaction was added to the pool.
+    timestamp: u64,
+    /// The EIP-2718 encoded pooled transaction, which includes the sidecar of blob transactions.
+    transaction: Bytes,
+}
+
+const fn encode_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+const fn decode_origin(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Writes a snapshot of all transactions in the pool to the file.
+fn save_pool_snapshot<P>(pool: &P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let now = Instant::now();
+    let unix_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let transactions = pool
+        .all_transactions_with_sub_pool()
+        .into_iter()
+        .filter_map(|(sub_pool, tx)| {
+            // blob transactions are only complete together with their sidecar
+            let pooled = pool.get_pooled_transaction_element(*tx.hash())?;
+            let age = now.saturating_duration_since(tx.timestamp);
+            Some(PoolSnapshotTransaction {
+                sub_pool: sub_pool as u8,
+                origin: encode_origin(tx.origin),
+                timestamp: unix_now.saturating_sub(age).as_secs(),
+                transaction: pooled.into_inner().encoded_2718().into(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let num_txs = transactions.len();
+    let snapshot = PoolSnapshot { version: POOL_SNAPSHOT_VERSION, transactions };
+    info!(target: "txpool", snapshot_file=?file_path, num_txs, "Saving transaction pool snapshot");
+    let result = file_path.parent().map(reth_fs_util::create_dir_all).transpose().and_then(|_| {
+        reth_fs_util::atomic_write_file(file_path, |file| {
+            file.write_all(&alloy_rlp::encode(&snapshot))
+        })
+    });
+
+    match result {
+        Ok(_) => {
+            info!(target: "txpool", snapshot_file=?file_path, "Wrote transaction pool snapshot to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file=?file_path, "Failed to write transaction pool snapshot to file");
+        }
+    }
+}
+
+/// Loads the snapshot of the pool from the file and inserts its transactions into the pool, which
+/// revalidates them.
+///
+/// Transactions that were pending are inserted first, and the transactions of each sender are
+/// inserted in nonce order. Non-local transactions that are older than
+/// [`PoolSnapshotConfig::max_age`] are dropped. The file is removed once it has been read.
+async fn load_and_revalidate_pool_snapshot<P>(
+    pool: &P,
+    config: &PoolSnapshotConfig,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    let file_path = &config.snapshot_path;
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Check persistent storage for a transaction pool snapshot");
+    let data = reth_fs_util::read(file_path)?;
+    reth_fs_util::remove_file(file_path)?;
+
+    if data.is_empty() {
+        return Ok(())
+    }
+
+    let snapshot = PoolSnapshot::decode(&mut data.as_slice())?;
+    if snapshot.version != POOL_SNAPSHOT_VERSION {
+        return Err(TransactionsBackupError::SnapshotVersion(snapshot.version))
+    }
+
+    let num_txs = snapshot.transactions.len();
+    let min_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .saturating_sub(config.max_age)
+        .as_secs();
+    let mut transactions = Vec::with_capacity(num_txs);
+    for tx in snapshot.transactions {
+        let Some(origin) = decode_origin(tx.origin) else { continue };
+        if !origin.is_local() && tx.timestamp < min_timestamp {
+            continue
+        }
+
+        let pending = tx.sub_pool == SubPool::Pending as u8;
+        let Some(tx) = <<P::Transaction as PoolTransaction>::Pooled as Decodable2718>::decode_2718(
+            &mut tx.transaction.as_ref(),
+        )
+        .ok()
+        .and_then(|tx| tx.try_into_recovered().ok()) else {
+            continue
+        };
+        transactions.push((pending, origin, <P::Transaction as PoolTransaction>::from_pooled(tx)));
+    }
+
+    // pending transactions come first, in case the pool is full before all are restored. The
+    // pending transactions of a sender have lower nonces than its other transactions, so each
+    // sender's transactions are still inserted in nonce order.
+    transactions.sort_by_key(|(pending, _, tx)| (!pending, tx.sender(), tx.nonce()));
+
+    // consecutive transactions of the same origin are inserted together
+    let mut restored = 0;
+    let mut transactions = transactions.into_iter().peekable();
+    while let Some((_, origin, tx)) = transactions.next() {
+        let mut batch = vec![tx];
+        while let Some((_, _, tx)) = transactions.next_if(|(_, next, _)| *next == origin) {
+            batch.push(tx);
+        }
+        let outcome = pool.add_transactions(origin, batch).await;
+        restored += outcome.iter().filter(|res| res.is_ok()).count();
+    }
+
+    info!(target: "txpool", snapshot_file =?file_path, num_txs, restored, "Restored transactions from transaction pool snapshot");
+    Ok(())
+}
+
+/// Task which writes a snapshot of all transactions in the pool, together with their sub-pool,
+/// origin and the time they were added, to a file on shutdown and periodically if configured.
+///
+/// On boot up, the transactions of the snapshot are revalidated and inserted into the pool.
+pub async fn snapshot_pool_transactions_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: PoolSnapshotConfig,
+) where
+    P: TransactionPool + 'static,
+{
+    if let Err(err) = load_and_revalidate_pool_snapshot(&pool, &config).await {
+        error!(target: "txpool", "{}", err)
+    }
+
+    let graceful_guard = if let Some(interval) = config.interval.filter(|i| !i.is_zero()) {
+        let mut interval = time::interval_at(time::Instant::now() + interval, interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        let mut shutdown = std::pin::pin!(shutdown);
+        loop {
+            tokio::select! {
+                guard = &mut shutdown => break guard,
+                _ = interval.tick() => {
+                    let pool = pool.clone();
+                    let snapshot_path = config.snapshot_path.clone();
+                    let _ = tokio::task::spawn_blocking(move || {
+                        save_pool_snapshot(&pool, &snapshot_path)
+                    })
+                    .await;
+                }
+            }
+        }
+    } else {
+        shutdown.await
+    };
+
+    // write transactions to disk
+    save_pool_snapshot(&pool, &config.snapshot_path);
+
+    drop(graceful_guard)
+}
+
 /// Errors possible during txs backup load and decode
 #[derive(thiserror::Error, Debug)]
 pub enum TransactionsBackupError {
@@ -684,6 +924,9 @@ pub enum TransactionsBackupError {
     /// Error adding transactions to the transaction pool
     #[error("failed to insert transactions to the transactions pool. Encountered pool error: {0}")]
     Pool(#[from] PoolError),
+    /// The transaction pool snapshot has an unknown format
+    #[error("failed to apply transaction pool snapshot. Unsupported snapshot version: {0}")]
+    SnapshotVersion(u8),
 }
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
@@ -786,6 +1029,52 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pool_snapshot_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = PoolSnapshotConfig::new(temp_dir.path().join("txpool-snapshot.rlp"));
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let provider = MockEthProvider::default();
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+
+        let new_pool = || {
+            let blob_store = InMemoryBlobStore::default();
+            let validator =
+                EthTransactionValidatorBuilder::new(provider.clone()).build(blob_store.clone());
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default())
+        };
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        save_pool_snapshot(&txpool, &config.snapshot_path);
+
+        // the transaction is revalidated and restored with its origin into a new pool
+        let txpool = new_pool();
+        load_and_revalidate_pool_snapshot(&txpool, &config).await.unwrap();
+        let restored = txpool.get(transaction.hash()).expect("transaction should be restored");
+        assert_eq!(restored.origin, TransactionOrigin::External);
+        assert!(!config.snapshot_path.exists());
+
+        // transactions older than the max age are dropped
+        save_pool_snapshot(&txpool, &config.snapshot_path);
+        let data = fs::read(&config.snapshot_path).unwrap();
+        let mut snapshot = PoolSnapshot::decode(&mut data.as_slice()).unwrap();
+        for tx in &mut snapshot.transactions {
+            tx.timestamp -= config.max_age.as_secs() + 1;
+        }
+        fs::write(&config.snapshot_path, alloy_rlp::encode(&snapshot)).unwrap();
+        let txpool = new_pool();
+        load_and_revalidate_pool_snapshot(&txpool, &config).await.unwrap();
+        assert!(txpool.is_empty());
+
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
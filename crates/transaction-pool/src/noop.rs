@@ -6,7 +6,10 @@
 use crate::{
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolError},
-    pool::TransactionListenerKind,
+    pool::{
+        state::{PoolTransactionStatus, SubPool},
+        EvictedTransaction, TransactionListenerKind,
+    },
     traits::{BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar},
     validate::ValidTransaction,
     AddedTransactionOutcome, AllPoolTransactions, AllTransactionsEvents, BestTransactions,
@@ -198,6 +201,20 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
         AllPoolTransactions::default()
     }
 
+    fn all_transactions_with_sub_pool(
+        &self,
+    ) -> Vec<(SubPool, Arc<ValidPoolTransaction<Self::Transaction>>)> {
+        vec![]
+    }
+
+    fn transaction_status(&self, _tx_hash: &TxHash) -> Option<PoolTransactionStatus> {
+        None
+    }
+
+    fn evicted_transactions(&self) -> Vec<EvictedTransaction> {
+        vec![]
+    }
+
     fn remove_transactions(
         &self,
         _hashes: Vec<TxHash>,
@@ -341,6 +358,8 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
         Ok(None)
     }
+
+    fn set_minimum_priority_fee(&self, _minimum_priority_fee: Option<u128>) {}
 }
 
 /// A [`TransactionValidator`] that does nothing.
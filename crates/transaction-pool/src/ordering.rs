@@ -1,6 +1,6 @@
 use crate::traits::PoolTransaction;
 use alloy_primitives::U256;
-use std::{cmp::Ordering, fmt::Debug, marker::PhantomData};
+use std::{cmp::Ordering, fmt, fmt::Debug, marker::PhantomData, str::FromStr};
 
 /// Priority of the transaction that can be missing.
 ///
@@ -57,6 +57,20 @@ pub trait TransactionOrdering: Debug + Send + Sync + 'static {
         transaction: &Self::Transaction,
         base_fee: u64,
     ) -> Priority<Self::PriorityValue>;
+
+    /// Returns the priority of a transaction that is unlocked when the transaction of the same
+    /// sender with the previous nonce, with the given `ancestor` priority, is yielded by
+    /// [`BestTransactions`](crate::traits::BestTransactions).
+    ///
+    /// By default, this is the priority of the unlocked transaction itself, so the transactions
+    /// of a sender with high priorities are yielded back to back.
+    fn unlocked_priority(
+        &self,
+        priority: Priority<Self::PriorityValue>,
+        _ancestor: &Priority<Self::PriorityValue>,
+    ) -> Priority<Self::PriorityValue> {
+        priority
+    }
 }
 
 /// Default ordering for the pool.
@@ -98,6 +112,287 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
     }
 }
 
+/// Orders the transactions by their effective gas price, including blob fees.
+///
+/// The effective gas price is the base fee plus the effective tip of the transaction. For blob
+/// transactions, the max blob fee of the transaction is added, spread over its gas limit.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct EffectiveGasPriceOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for EffectiveGasPriceOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        effective_gas_price(transaction, base_fee).into()
+    }
+}
+
+impl<T> Default for EffectiveGasPriceOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for EffectiveGasPriceOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Orders the transactions by their priority fee per gas, taking turns between the senders.
+///
+/// The transactions are yielded in rounds: the first round contains the first transaction of each
+/// sender, the second round the second transaction of each sender, and so on. Within a round, the
+/// transactions are ordered by their effective tip. This prevents a single sender with high tips
+/// from filling the block.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SenderFairOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for SenderFairOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = RoundPriority;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        transaction
+            .effective_tip_per_gas(base_fee)
+            .map(|tip| RoundPriority { round: 0, value: U256::from(tip) })
+            .into()
+    }
+
+    fn unlocked_priority(
+        &self,
+        priority: Priority<Self::PriorityValue>,
+        ancestor: &Priority<Self::PriorityValue>,
+    ) -> Priority<Self::PriorityValue> {
+        next_round(priority, ancestor)
+    }
+}
+
+impl<T> Default for SenderFairOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for SenderFairOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Orders the transactions by the time they became pending, first in first out.
+///
+/// All transactions have the same priority, so they're yielded in the order they were added to the
+/// pending sub-pool. This is intended for test networks where fees don't matter.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FifoOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for FifoOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = ();
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        _transaction: &Self::Transaction,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(())
+    }
+}
+
+impl<T> Default for FifoOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for FifoOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Priority value of a transaction that is yielded in a round, see [`SenderFairOrdering`].
+///
+/// Transactions of earlier rounds have a higher priority, transactions of the same round are
+/// ordered by their value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoundPriority {
+    /// The round of the transaction, starting at 0.
+    pub round: u64,
+    /// The priority of the transaction within its round.
+    pub value: U256,
+}
+
+impl PartialOrd for RoundPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RoundPriority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.round.cmp(&self.round).then_with(|| self.value.cmp(&other.value))
+    }
+}
+
+/// The built-in [`TransactionOrdering`] implementations, see [`ConfiguredOrdering`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionOrderingKind {
+    /// [`CoinbaseTipOrdering`]
+    #[default]
+    CoinbaseTip,
+    /// [`EffectiveGasPriceOrdering`]
+    EffectiveGasPrice,
+    /// [`SenderFairOrdering`]
+    SenderFair,
+    /// [`FifoOrdering`]
+    Fifo,
+}
+
+impl fmt::Display for TransactionOrderingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CoinbaseTip => f.write_str("coinbase-tip"),
+            Self::EffectiveGasPrice => f.write_str("effective-gas-price"),
+            Self::SenderFair => f.write_str("sender-fair"),
+            Self::Fifo => f.write_str("fifo"),
+        }
+    }
+}
+
+impl FromStr for TransactionOrderingKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coinbase-tip" => Ok(Self::CoinbaseTip),
+            "effective-gas-price" => Ok(Self::EffectiveGasPrice),
+            "sender-fair" => Ok(Self::SenderFair),
+            "fifo" => Ok(Self::Fifo),
+            _ => Err(format!("Invalid transaction ordering: {s}")),
+        }
+    }
+}
+
+/// A [`TransactionOrdering`] that is selected at runtime, e.g. from the node's configuration.
+///
+/// This behaves like the ordering of the configured [`TransactionOrderingKind`]. Orderings that
+/// don't yield transactions in rounds always use round 0.
+#[derive(Debug)]
+pub struct ConfiguredOrdering<T> {
+    kind: TransactionOrderingKind,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ConfiguredOrdering<T> {
+    /// Creates the ordering of the given kind.
+    pub const fn new(kind: TransactionOrderingKind) -> Self {
+        Self { kind, _marker: PhantomData }
+    }
+
+    /// Returns the kind of the ordering.
+    pub const fn kind(&self) -> TransactionOrderingKind {
+        self.kind
+    }
+}
+
+impl<T> TransactionOrdering for ConfiguredOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = RoundPriority;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        let value = match self.kind {
+            TransactionOrderingKind::CoinbaseTip | TransactionOrderingKind::SenderFair => {
+                transaction.effective_tip_per_gas(base_fee).map(U256::from)
+            }
+            TransactionOrderingKind::EffectiveGasPrice => {
+                effective_gas_price(transaction, base_fee)
+            }
+            TransactionOrderingKind::Fifo => Some(U256::ZERO),
+        };
+        value.map(|value| RoundPriority { round: 0, value }).into()
+    }
+
+    fn unlocked_priority(
+        &self,
+        priority: Priority<Self::PriorityValue>,
+        ancestor: &Priority<Self::PriorityValue>,
+    ) -> Priority<Self::PriorityValue> {
+        match self.kind {
+            TransactionOrderingKind::SenderFair => next_round(priority, ancestor),
+            _ => priority,
+        }
+    }
+}
+
+impl<T> Default for ConfiguredOrdering<T> {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<T> Clone for ConfiguredOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.kind)
+    }
+}
+
+/// Returns the effective gas price of the transaction including blob fees, or `None` if the
+/// transaction doesn't satisfy the base fee, see [`EffectiveGasPriceOrdering`].
+fn effective_gas_price<T: PoolTransaction>(transaction: &T, base_fee: u64) -> Option<U256> {
+    let tip = transaction.effective_tip_per_gas(base_fee)?;
+    let mut price = U256::from(base_fee) + U256::from(tip);
+    if let (Some(max_fee_per_blob_gas), Some(blob_gas)) =
+        (transaction.max_fee_per_blob_gas(), transaction.blob_gas_used())
+    {
+        price += U256::from(max_fee_per_blob_gas) * U256::from(blob_gas) /
+            U256::from(transaction.gas_limit().max(1));
+    }
+    Some(price)
+}
+
+/// Moves the priority of an unlocked transaction to the round after its ancestor.
+const fn next_round(
+    priority: Priority<RoundPriority>,
+    ancestor: &Priority<RoundPriority>,
+) -> Priority<RoundPriority> {
+    match (priority, ancestor) {
+        (Priority::Value(priority), Priority::Value(ancestor)) => {
+            Priority::Value(RoundPriority { round: ancestor.round + 1, ..priority })
+        }
+        (priority, _) => priority,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,4 +408,26 @@ mod tests {
         assert!(p2 > p3); // Value(1) > None
         assert_eq!(p3, Priority::None);
     }
+
+    #[test]
+    fn test_round_priority_ordering() {
+        let first = RoundPriority { round: 0, value: U256::from(1) };
+        let second = RoundPriority { round: 1, value: U256::from(100) };
+
+        assert!(first > second);
+        assert!(RoundPriority { round: 0, value: U256::from(2) } > first);
+    }
+
+    #[test]
+    fn test_ordering_kind_roundtrip() {
+        for kind in [
+            TransactionOrderingKind::CoinbaseTip,
+            TransactionOrderingKind::EffectiveGasPrice,
+            TransactionOrderingKind::SenderFair,
+            TransactionOrderingKind::Fifo,
+        ] {
+            assert_eq!(kind.to_string().parse::<TransactionOrderingKind>(), Ok(kind));
+        }
+        assert!("unknown".parse::<TransactionOrderingKind>().is_err());
+    }
 }
@@ -80,6 +80,8 @@ impl<T: TransactionOrdering> Iterator for BestTransactionsWithFees<T> {
 /// transaction with the current on chain nonce.
 #[derive(Debug)]
 pub struct BestTransactions<T: TransactionOrdering> {
+    /// The ordering of the pool, used to determine the priority of unlocked transactions.
+    pub(crate) ordering: Arc<T>,
     /// Contains a copy of _all_ transactions of the pending pool at the point in time this
     /// iterator was created.
     pub(crate) all: BTreeMap<TransactionId, PendingTransaction<T>>,
@@ -202,7 +204,10 @@ impl<T: TransactionOrdering> Iterator for BestTransactions<T> {
 
             // Insert transactions that just got unlocked.
             if let Some(unlocked) = self.all.get(&best.unlocks()) {
-                self.independent.insert(unlocked.clone());
+                let mut unlocked = unlocked.clone();
+                unlocked.priority =
+                    self.ordering.unlocked_priority(unlocked.priority, &best.priority);
+                self.independent.insert(unlocked);
             }
 
             if self.skip_blobs && best.transaction.transaction.is_eip4844() {
@@ -375,9 +380,10 @@ mod tests {
     use crate::{
         pool::pending::PendingPool,
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
-        BestTransactions, Priority,
+        BestTransactions, ConfiguredOrdering, EffectiveGasPriceOrdering, FifoOrdering, Priority,
+        SenderFairOrdering, TransactionOrderingKind,
     };
-    use alloy_primitives::U256;
+    use alloy_primitives::{B256, U256};
 
     #[test]
     fn test_best_iter() {
@@ -931,5 +937,112 @@ mod tests {
         assert!(best.new_transaction_receiver.is_none());
     }
 
+    /// Returns the senders of the transactions in the order they're yielded by the pool.
+    fn best_senders<T: TransactionOrdering<Transaction = MockTransaction>>(
+        pool: &PendingPool<T>,
+    ) -> Vec<Address> {
+        pool.best().map(|tx| tx.sender()).collect()
+    }
+
+    /// Adds the given number of transactions of a new sender with the given tip to the pool.
+    fn add_sender_transactions<T: TransactionOrdering<Transaction = MockTransaction>>(
+        pool: &mut PendingPool<T>,
+        f: &mut MockTransactionFactory,
+        num_tx: u64,
+        tip: u128,
+    ) -> Address {
+        let tx = MockTransaction::eip1559().with_priority_fee(tip).with_max_fee(tip);
+        for nonce in 0..num_tx {
+            let tx = tx.clone().rng_hash().with_nonce(nonce);
+            pool.add_transaction(Arc::new(f.validated(tx)), 0);
+        }
+        tx.sender()
+    }
+
+    #[test]
+    fn test_best_effective_gas_price_ordering() {
+        let mut pool = PendingPool::new(EffectiveGasPriceOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let tx = MockTransaction::eip1559().with_priority_fee(10).with_max_fee(10);
+        pool.add_transaction(Arc::new(f.validated(tx.clone())), 0);
+
+        // lower tip, but the blob fee makes up for it
+        let mut blob_tx = MockTransaction::eip4844()
+            .with_priority_fee(5)
+            .with_max_fee(5)
+            .with_blob_fee(100)
+            .with_gas_limit(21_000);
+        if let MockTransaction::Eip4844 { blob_versioned_hashes, .. } = &mut blob_tx {
+            blob_versioned_hashes.push(B256::ZERO);
+        }
+        pool.add_transaction(Arc::new(f.validated(blob_tx.clone())), 0);
+
+        assert_eq!(best_senders(&pool), vec![blob_tx.sender(), tx.sender()]);
+
+        // ordered by tip only, the other transaction comes first
+        let mut pool = PendingPool::new(MockOrdering::default());
+        pool.add_transaction(Arc::new(f.validated(tx.clone())), 0);
+        pool.add_transaction(Arc::new(f.validated(blob_tx.clone())), 0);
+        assert_eq!(best_senders(&pool), vec![tx.sender(), blob_tx.sender()]);
+    }
+
+    #[test]
+    fn test_best_sender_fair_ordering() {
+        let mut pool = PendingPool::new(SenderFairOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let a = add_sender_transactions(&mut pool, &mut f, 3, 100);
+        let b = add_sender_transactions(&mut pool, &mut f, 2, 10);
+        let c = add_sender_transactions(&mut pool, &mut f, 1, 50);
+
+        // each round yields one transaction per sender, ordered by tip
+        assert_eq!(best_senders(&pool), vec![a, c, b, a, b, a]);
+
+        // the nonces of each sender still increase
+        let mut best = pool.best();
+        let first = best.next().unwrap();
+        let second = best.by_ref().find(|tx| tx.sender() == a).unwrap();
+        assert_eq!(second.nonce(), first.nonce() + 1);
+    }
+
+    #[test]
+    fn test_best_fifo_ordering() {
+        let mut pool = PendingPool::new(FifoOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let a = MockTransaction::eip1559().with_priority_fee(1).with_max_fee(1);
+        let b = MockTransaction::eip1559().with_priority_fee(100).with_max_fee(100);
+        pool.add_transaction(Arc::new(f.validated(a.clone())), 0);
+        pool.add_transaction(Arc::new(f.validated(b.clone())), 0);
+        pool.add_transaction(Arc::new(f.validated(a.next())), 0);
+        pool.add_transaction(Arc::new(f.validated(b.next())), 0);
+
+        // yielded in the order the transactions were added, regardless of their tip
+        let best = pool.best().map(|tx| (tx.sender(), tx.nonce())).collect::<Vec<_>>();
+        assert_eq!(best, vec![(a.sender(), 0), (b.sender(), 0), (a.sender(), 1), (b.sender(), 1)]);
+    }
+
+    #[test]
+    fn test_best_configured_ordering() {
+        let mut f = MockTransactionFactory::default();
+
+        let mut pool = PendingPool::new(ConfiguredOrdering::default());
+        let a = add_sender_transactions(&mut pool, &mut f, 2, 100);
+        let b = add_sender_transactions(&mut pool, &mut f, 1, 10);
+        assert_eq!(best_senders(&pool), vec![a, a, b]);
+
+        let mut pool =
+            PendingPool::new(ConfiguredOrdering::new(TransactionOrderingKind::SenderFair));
+        let a = add_sender_transactions(&mut pool, &mut f, 2, 100);
+        let b = add_sender_transactions(&mut pool, &mut f, 1, 10);
+        assert_eq!(best_senders(&pool), vec![a, b, a]);
+
+        let mut pool = PendingPool::new(ConfiguredOrdering::new(TransactionOrderingKind::Fifo));
+        let b = add_sender_transactions(&mut pool, &mut f, 1, 10);
+        let a = add_sender_transactions(&mut pool, &mut f, 1, 100);
+        assert_eq!(best_senders(&pool), vec![b, a]);
+    }
+
     // TODO: Same nonce test
 }
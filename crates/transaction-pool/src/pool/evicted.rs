@@ -0,0 +1,118 @@
+//! History of transactions that left the pool without being mined.
+
+use crate::{PoolTransaction, ValidPoolTransaction};
+use alloy_primitives::{Address, TxHash};
+use std::{collections::VecDeque, sync::Arc, time::SystemTime};
+
+/// The reason a transaction was removed from the pool without being mined.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EvictionReason {
+    /// The transaction was replaced by another transaction with the same sender and nonce.
+    Replaced {
+        /// The hash of the transaction that replaced it.
+        replaced_by: TxHash,
+    },
+    /// The transaction was discarded to enforce the size limits of the pool.
+    PoolLimits,
+    /// The nonce of the transaction is lower than the nonce of the sender on chain, because a
+    /// different transaction with the same nonce was mined.
+    NonceTooLow,
+    /// The transaction was removed explicitly, e.g. because it was stale.
+    Removed,
+}
+
+/// A transaction that was removed from the pool without being mined.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EvictedTransaction {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// Why the transaction was removed.
+    pub reason: EvictionReason,
+    /// When the transaction was removed.
+    pub timestamp: SystemTime,
+}
+
+/// Keeps the most recently evicted transactions, up to a configured number.
+#[derive(Debug)]
+pub(crate) struct EvictedTransactions {
+    /// Max number of transactions to keep.
+    max_len: usize,
+    /// The evicted transactions, oldest first.
+    evicted: VecDeque<EvictedTransaction>,
+}
+
+impl EvictedTransactions {
+    /// Creates an empty history that keeps at most `max_len` transactions.
+    pub(crate) fn new(max_len: usize) -> Self {
+        Self { max_len, evicted: VecDeque::with_capacity(max_len.min(1024)) }
+    }
+
+    /// Records the eviction of the given transactions, dropping the oldest entries if the history
+    /// is full.
+    pub(crate) fn record<'a, T: PoolTransaction + 'a>(
+        &mut self,
+        transactions: impl IntoIterator<Item = &'a Arc<ValidPoolTransaction<T>>>,
+        reason: EvictionReason,
+    ) {
+        if self.max_len == 0 {
+            return
+        }
+        let timestamp = SystemTime::now();
+        for tx in transactions {
+            if self.evicted.len() == self.max_len {
+                self.evicted.pop_front();
+            }
+            self.evicted.push_back(EvictedTransaction {
+                hash: *tx.hash(),
+                sender: tx.sender(),
+                nonce: tx.nonce(),
+                reason,
+                timestamp,
+            });
+        }
+    }
+
+    /// Returns all recorded transactions, oldest first.
+    pub(crate) fn to_vec(&self) -> Vec<EvictedTransaction> {
+        self.evicted.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn keeps_most_recent() {
+        let mut f = MockTransactionFactory::default();
+        let txs = (0..3)
+            .map(|nonce| f.validated_arc(MockTransaction::eip1559().with_nonce(nonce)))
+            .collect::<Vec<_>>();
+
+        let mut evicted = EvictedTransactions::new(2);
+        evicted.record(&txs[..1], EvictionReason::PoolLimits);
+        evicted.record(&txs[1..], EvictionReason::NonceTooLow);
+
+        let evicted = evicted.to_vec();
+        assert_eq!(
+            evicted.iter().map(|tx| (tx.nonce, tx.reason)).collect::<Vec<_>>(),
+            vec![(1, EvictionReason::NonceTooLow), (2, EvictionReason::NonceTooLow)]
+        );
+        assert_eq!(evicted[0].hash, *txs[1].hash());
+    }
+
+    #[test]
+    fn disabled() {
+        let mut f = MockTransactionFactory::default();
+        let tx = f.validated_arc(MockTransaction::eip1559());
+
+        let mut evicted = EvictedTransactions::new(0);
+        evicted.record([&tx], EvictionReason::Removed);
+        assert!(evicted.to_vec().is_empty());
+    }
+}
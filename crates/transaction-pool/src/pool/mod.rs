@@ -66,16 +66,19 @@
 //!    category (2.) and become pending.
 
 use crate::{
+    admission::{AdmissionContext, AdmissionError, AdmissionPolicy},
     blobstore::BlobStore,
-    error::{PoolError, PoolErrorKind, PoolResult},
+    bundle::BundlePool,
+    error::{InvalidPoolTransactionError, PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
     pool::{
+        evicted::EvictedTransactions,
         listener::{
             BlobTransactionSidecarListener, PendingTransactionHashListener, PoolEventBroadcast,
             TransactionListener,
         },
-        state::SubPool,
+        state::{PoolTransactionStatus, SubPool},
         txpool::{SenderInfo, TxPool},
         update::UpdateOutcome,
     },
@@ -88,6 +91,7 @@ use crate::{
     TransactionValidator,
 };
 
+use alloy_consensus::Transaction;
 use alloy_primitives::{Address, TxHash, B256};
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -104,6 +108,7 @@ mod events;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
 pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
+pub use evicted::{EvictedTransaction, EvictionReason};
 pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
@@ -111,6 +116,7 @@ use reth_primitives_traits::Block;
 
 mod best;
 mod blob;
+mod evicted;
 mod listener;
 mod parked;
 pub(crate) mod pending;
@@ -151,6 +157,15 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Bundles submitted for inclusion at the top of upcoming blocks.
+    bundles: BundlePool<<T::Transaction as PoolTransaction>::Consensus>,
+    /// Minimum priority fee required for non-local transactions, initialized from
+    /// [`PoolConfig::minimum_priority_fee`] and adjustable at runtime.
+    minimum_priority_fee: RwLock<Option<u128>>,
+    /// Custom admission policy that is checked in addition to [`PoolConfig::admission`].
+    admission_policy: RwLock<Option<Arc<dyn AdmissionPolicy<T::Transaction>>>>,
+    /// Transactions that were recently removed from the pool without being mined.
+    evicted_transactions: Mutex<EvictedTransactions>,
 }
 
 // === impl PoolInner ===
@@ -168,12 +183,18 @@ where
             validator,
             event_listener: Default::default(),
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            minimum_priority_fee: RwLock::new(config.minimum_priority_fee),
+            admission_policy: Default::default(),
+            evicted_transactions: Mutex::new(EvictedTransactions::new(
+                config.max_evicted_transactions,
+            )),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            bundles: Default::default(),
         }
     }
 
@@ -182,6 +203,56 @@ where
         &self.blob_store
     }
 
+    /// Returns the minimum priority fee currently required for non-local transactions.
+    pub fn minimum_priority_fee(&self) -> Option<u128> {
+        *self.minimum_priority_fee.read()
+    }
+
+    /// Sets the minimum priority fee required for non-local transactions.
+    ///
+    /// This only applies to transactions that are added afterwards.
+    pub fn set_minimum_priority_fee(&self, minimum_priority_fee: Option<u128>) {
+        *self.minimum_priority_fee.write() = minimum_priority_fee;
+    }
+
+    /// Sets a custom admission policy that valid transactions must satisfy, in addition to the
+    /// configured [`PoolConfig::admission`] rules, to be added to the pool.
+    ///
+    /// This replaces a previously set policy and only applies to transactions that are added
+    /// afterwards.
+    pub fn set_admission_policy(&self, policy: Option<Arc<dyn AdmissionPolicy<T::Transaction>>>) {
+        *self.admission_policy.write() = policy;
+    }
+
+    /// Checks whether the transaction is admitted into the pool by the configured admission rules
+    /// and the custom admission policy.
+    fn check_admission(
+        &self,
+        pool: &TxPool<T>,
+        transaction: &T::Transaction,
+        transaction_id: TransactionId,
+        origin: TransactionOrigin,
+    ) -> Result<(), AdmissionError> {
+        let all = pool.all();
+        let ctx = AdmissionContext {
+            origin,
+            is_replacement: all.get(&transaction_id).is_some(),
+            origin_transactions: all.origin_tx_count(origin),
+            sender_transactions: all.tx_count(transaction_id.sender),
+        };
+
+        self.config.admission.check(transaction, &ctx)?;
+        if let Some(policy) = self.admission_policy.read().as_ref() {
+            policy.check(transaction, &ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the pool of bundles.
+    pub const fn bundle_pool(&self) -> &BundlePool<<T::Transaction as PoolTransaction>::Consensus> {
+        &self.bundles
+    }
+
     /// Returns stats about the size of the pool.
     pub fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -394,6 +465,9 @@ where
         } = update;
         self.validator.on_new_head_block(new_tip);
 
+        // bundles that are expired or already mined can't be included anymore
+        self.bundles.on_canonical_block(block_info.last_seen_block_number, &mined_transactions);
+
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
         // update the pool
@@ -433,6 +507,8 @@ where
             });
         }
 
+        self.record_evicted(&discarded, EvictionReason::NonceTooLow);
+
         {
             let mut listener = self.event_listener.write();
             if !listener.is_empty() {
@@ -484,6 +560,35 @@ where
                     }
                 };
 
+                // Drop non-local transactions with a fee lower than the configured fee for
+                // acceptance into the pool.
+                if let Some(minimum_priority_fee) = self.minimum_priority_fee() {
+                    let is_local = self
+                        .config
+                        .local_transactions_config
+                        .is_local(origin, transaction.sender_ref());
+                    if !is_local &&
+                        transaction.is_dynamic_fee() &&
+                        transaction.max_priority_fee_per_gas() < Some(minimum_priority_fee)
+                    {
+                        let hash = *transaction.hash();
+                        self.event_listener.write().invalid(&hash);
+                        return Err(PoolError::new(
+                            hash,
+                            InvalidPoolTransactionError::PriorityFeeBelowMinimum {
+                                minimum_priority_fee,
+                            },
+                        ))
+                    }
+                }
+
+                if let Err(err) = self.check_admission(pool, &transaction, transaction_id, origin) {
+                    let hash = *transaction.hash();
+                    trace!(target: "txpool", %err, ?hash, "transaction rejected by admission policy");
+                    self.event_listener.write().invalid(&hash);
+                    return Err(PoolError::new(hash, err))
+                }
+
                 let tx = ValidPoolTransaction {
                     transaction,
                     transaction_id,
@@ -519,6 +624,13 @@ where
                     self.on_new_pending_transaction(pending);
                 }
 
+                if let Some(replaced) = added.replaced() {
+                    self.record_evicted([replaced], EvictionReason::Replaced { replaced_by: hash });
+                }
+                if let Some(discarded) = added.discarded_transactions() {
+                    self.record_evicted(discarded, EvictionReason::NonceTooLow);
+                }
+
                 // Notify tx event listeners
                 self.notify_event_listeners(&added);
 
@@ -590,6 +702,7 @@ where
         if !discarded.is_empty() {
             // Delete any blobs associated with discarded blob transactions
             self.delete_discarded_blobs(discarded.iter());
+            self.record_evicted(&discarded, EvictionReason::PoolLimits);
             self.event_listener.write().discarded_many(&discarded);
 
             let discarded_hashes =
@@ -684,6 +797,8 @@ where
 
         let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
 
+        self.record_evicted(&discarded, EvictionReason::NonceTooLow);
+
         // broadcast specific transaction events
         let mut listener = self.event_listener.write();
 
@@ -700,6 +815,25 @@ where
         }
     }
 
+    /// Records transactions that were removed from the pool without being mined.
+    fn record_evicted<'a>(
+        &self,
+        evicted: impl IntoIterator<Item = &'a Arc<ValidPoolTransaction<T::Transaction>>>,
+        reason: EvictionReason,
+    ) {
+        self.evicted_transactions.lock().record(evicted, reason);
+    }
+
+    /// Returns the most recently evicted transactions, oldest first.
+    pub fn evicted_transactions(&self) -> Vec<EvictedTransaction> {
+        self.evicted_transactions.lock().to_vec()
+    }
+
+    /// Returns the sub-pool of the transaction with the given hash and why it is not pending.
+    pub fn transaction_status(&self, tx_hash: &TxHash) -> Option<PoolTransactionStatus> {
+        self.get_pool_data().transaction_status(tx_hash)
+    }
+
     /// Fire events for the newly added transaction if there are any.
     fn notify_event_listeners(&self, tx: &AddedTransaction<T::Transaction>) {
         let mut listener = self.event_listener.write();
@@ -771,6 +905,13 @@ where
         }
     }
 
+    /// Returns all transactions in the pool together with the sub-pool they are in.
+    pub fn all_transactions_with_sub_pool(
+        &self,
+    ) -> Vec<(SubPool, Arc<ValidPoolTransaction<T::Transaction>>)> {
+        self.get_pool_data().all_transactions_with_sub_pool()
+    }
+
     /// Removes and returns all matching transactions from the pool.
     ///
     /// This behaves as if the transactions got discarded (_not_ mined), effectively introducing a
@@ -784,6 +925,7 @@ where
         }
         let removed = self.pool.write().remove_transactions(hashes);
 
+        self.record_evicted(&removed, EvictionReason::Removed);
         self.event_listener.write().discarded_many(&removed);
 
         removed
@@ -800,6 +942,8 @@ where
         }
         let removed = self.pool.write().remove_transactions_and_descendants(hashes);
 
+        self.record_evicted(&removed, EvictionReason::Removed);
+
         let mut listener = self.event_listener.write();
 
         for tx in &removed {
@@ -817,6 +961,7 @@ where
         let sender_id = self.get_sender_id(sender);
         let removed = self.pool.write().remove_transactions_by_sender(sender_id);
 
+        self.record_evicted(&removed, EvictionReason::Removed);
         self.event_listener.write().discarded_many(&removed);
 
         removed
@@ -1245,15 +1390,18 @@ impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        admission::{AdmissionContext, AdmissionError, AdmissionPolicy, AdmissionRules},
         blobstore::{BlobStore, InMemoryBlobStore},
+        error::{InvalidPoolTransactionError, PoolErrorKind},
         identifier::SenderId,
+        pool::{state::ParkedReason, EvictionReason, SubPool},
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
         BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
     };
     use alloy_eips::{eip4844::BlobTransactionSidecar, eip7594::BlobTransactionSidecarVariant};
     use alloy_primitives::Address;
-    use std::{fs, path::PathBuf};
+    use std::{fs, path::PathBuf, sync::Arc};
 
     #[test]
     fn test_discard_blobs_on_blob_tx_eviction() {
@@ -1363,4 +1511,190 @@ mod tests {
         let identifiers = test_pool.identifiers.read();
         assert_eq!(identifiers.sender_id(&auth), Some(SenderId::from(1)));
     }
+    #[test]
+    fn test_minimum_priority_fee_updated_at_runtime() {
+        let test_pool = &TestPoolBuilder::default().with_config(Default::default()).pool;
+        let valid = |tx| TransactionValidationOutcome::Valid {
+            balance: U256::from(1_000),
+            state_nonce: 0,
+            bytecode_hash: None,
+            transaction: ValidTransaction::Valid(tx),
+            propagate: true,
+            authorities: None,
+        };
+
+        test_pool.set_minimum_priority_fee(Some(100));
+
+        // external transactions below the minimum are rejected
+        let tx = MockTransaction::eip1559().with_priority_fee(99).with_max_fee(1_000);
+        let results = test_pool.add_transactions(TransactionOrigin::External, [valid(tx)]);
+        assert!(matches!(
+            results[0].as_ref().unwrap_err().kind,
+            PoolErrorKind::InvalidTransaction(
+                InvalidPoolTransactionError::PriorityFeeBelowMinimum { minimum_priority_fee: 100 }
+            )
+        ));
+
+        // local transactions are exempt
+        let tx = MockTransaction::eip1559().with_priority_fee(99).with_max_fee(1_000);
+        let results = test_pool.add_transactions(TransactionOrigin::Local, [valid(tx)]);
+        assert!(results[0].is_ok());
+
+        // lowering the minimum applies to new transactions
+        test_pool.set_minimum_priority_fee(Some(50));
+        let tx = MockTransaction::eip1559().with_priority_fee(99).with_max_fee(1_000);
+        let results = test_pool.add_transactions(TransactionOrigin::External, [valid(tx)]);
+        assert!(results[0].is_ok());
+    }
+
+    #[test]
+    fn test_admission_quotas() {
+        let config = PoolConfig {
+            admission: AdmissionRules {
+                max_external_transactions: Some(2),
+                max_sender_transactions: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let test_pool = &TestPoolBuilder::default().with_config(config).pool;
+        let valid = |tx| TransactionValidationOutcome::Valid {
+            balance: U256::from(1_000_000),
+            state_nonce: 0,
+            bytecode_hash: None,
+            transaction: ValidTransaction::Valid(tx),
+            propagate: true,
+            authorities: None,
+        };
+
+        let tx = MockTransaction::eip1559();
+        let results = test_pool.add_transactions(
+            TransactionOrigin::External,
+            [valid(tx.clone()), valid(MockTransaction::eip1559())],
+        );
+        assert!(results.iter().all(Result::is_ok));
+
+        // the external quota is exhausted
+        let results = test_pool
+            .add_transactions(TransactionOrigin::External, [valid(MockTransaction::eip1559())]);
+        assert!(matches!(
+            results[0].as_ref().unwrap_err().kind,
+            PoolErrorKind::AdmissionRejected(AdmissionError::OriginQuotaExceeded {
+                origin: TransactionOrigin::External,
+                max_transactions: 2
+            })
+        ));
+
+        // local transactions have their own quota, but the sender quota applies to them as well
+        let results = test_pool.add_transactions(
+            TransactionOrigin::Local,
+            [valid(tx.next()), valid(MockTransaction::eip1559())],
+        );
+        assert!(matches!(
+            results[0].as_ref().unwrap_err().kind,
+            PoolErrorKind::AdmissionRejected(AdmissionError::SenderQuotaExceeded { .. })
+        ));
+        assert!(results[1].is_ok());
+
+        // replacements don't count against the quotas, and move the transaction to the new origin
+        let replacement = tx.inc_price_by(100).rng_hash();
+        let results = test_pool.add_transactions(TransactionOrigin::Local, [valid(replacement)]);
+        assert!(results[0].is_ok());
+        let results = test_pool
+            .add_transactions(TransactionOrigin::External, [valid(MockTransaction::eip1559())]);
+        assert!(results[0].is_ok());
+
+        let pool = test_pool.pool.read();
+        assert_eq!(pool.all().origin_tx_count(TransactionOrigin::Local), 2);
+        assert_eq!(pool.all().origin_tx_count(TransactionOrigin::External), 2);
+    }
+
+    #[test]
+    fn test_custom_admission_policy() {
+        /// Rejects all transactions with a nonce above a maximum.
+        #[derive(Debug)]
+        struct MaxNonce(u64);
+
+        impl AdmissionPolicy<MockTransaction> for MaxNonce {
+            fn check(
+                &self,
+                transaction: &MockTransaction,
+                _ctx: &AdmissionContext,
+            ) -> Result<(), AdmissionError> {
+                if *transaction.get_nonce() > self.0 {
+                    return Err(AdmissionError::Other("nonce too high".into()))
+                }
+                Ok(())
+            }
+        }
+
+        let test_pool = &TestPoolBuilder::default().with_config(Default::default()).pool;
+        let valid = |tx| TransactionValidationOutcome::Valid {
+            balance: U256::from(1_000),
+            state_nonce: 0,
+            bytecode_hash: None,
+            transaction: ValidTransaction::Valid(tx),
+            propagate: true,
+            authorities: None,
+        };
+
+        test_pool.set_admission_policy(Some(Arc::new(MaxNonce(0))));
+        let tx = MockTransaction::eip1559();
+        let results = test_pool
+            .add_transactions(TransactionOrigin::Local, [valid(tx.clone()), valid(tx.next())]);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1].as_ref().unwrap_err().kind,
+            PoolErrorKind::AdmissionRejected(AdmissionError::Other(_))
+        ));
+
+        // removing the policy admits the transaction
+        test_pool.set_admission_policy(None);
+        let results = test_pool.add_transactions(TransactionOrigin::Local, [valid(tx.next())]);
+        assert!(results[0].is_ok());
+    }
+
+    #[test]
+    fn test_transaction_status_and_evictions() {
+        let test_pool = &TestPoolBuilder::default().with_config(Default::default()).pool;
+        let valid = |tx| TransactionValidationOutcome::Valid {
+            balance: U256::from(1_000_000),
+            state_nonce: 0,
+            bytecode_hash: None,
+            transaction: ValidTransaction::Valid(tx),
+            propagate: true,
+            authorities: None,
+        };
+
+        let tx = MockTransaction::eip1559();
+        let gapped = tx.next().next();
+        let results = test_pool.add_transactions(
+            TransactionOrigin::External,
+            [valid(tx.clone()), valid(gapped.clone())],
+        );
+        assert!(results.iter().all(Result::is_ok));
+
+        let status = test_pool.transaction_status(gapped.get_hash()).unwrap();
+        assert_eq!(status.sub_pool, SubPool::Queued);
+        assert!(status.parked_reasons.contains(&ParkedReason::NonceGap));
+        assert!(test_pool.evicted_transactions().is_empty());
+
+        let replacement = tx.inc_price_by(100).rng_hash();
+        let results =
+            test_pool.add_transactions(TransactionOrigin::External, [valid(replacement.clone())]);
+        assert!(results[0].is_ok());
+        assert!(test_pool.transaction_status(tx.get_hash()).is_none());
+        assert!(test_pool.transaction_status(replacement.get_hash()).is_some());
+
+        test_pool.remove_transactions(vec![*gapped.get_hash()]);
+
+        let evicted = test_pool.evicted_transactions();
+        assert_eq!(
+            evicted.iter().map(|tx| (tx.hash, tx.reason)).collect::<Vec<_>>(),
+            vec![
+                (*tx.get_hash(), EvictionReason::Replaced { replaced_by: *replacement.get_hash() }),
+                (*gapped.get_hash(), EvictionReason::Removed),
+            ]
+        );
+    }
 }
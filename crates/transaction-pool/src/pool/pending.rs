@@ -28,7 +28,7 @@ use tokio::sync::broadcast;
 #[derive(Debug, Clone)]
 pub struct PendingPool<T: TransactionOrdering> {
     /// How to order transactions.
-    ordering: T,
+    ordering: Arc<T>,
     /// Keeps track of transactions inserted in the pool.
     ///
     /// This way we can determine when transactions were submitted to the pool.
@@ -62,7 +62,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
     pub fn with_buffer(ordering: T, buffer_capacity: usize) -> Self {
         let (new_transaction_notifier, _) = broadcast::channel(buffer_capacity);
         Self {
-            ordering,
+            ordering: Arc::new(ordering),
             submission_id: 0,
             by_id: Default::default(),
             independent_transactions: Default::default(),
@@ -105,6 +105,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
     /// Invalid transactions are skipped.
     pub fn best(&self) -> BestTransactions<T> {
         BestTransactions {
+            ordering: Arc::clone(&self.ordering),
             all: self.by_id.clone(),
             independent: self.independent_transactions.values().cloned().collect(),
             invalid: Default::default(),
@@ -68,6 +68,50 @@ impl TxState {
     pub(crate) const fn has_nonce_gap(&self) -> bool {
         !self.intersects(Self::NO_NONCE_GAPS)
     }
+
+    /// Returns the reasons why a transaction with this state is not pending.
+    ///
+    /// This is empty if the transaction is pending.
+    pub(crate) fn parked_reasons(&self) -> Vec<ParkedReason> {
+        [
+            (Self::NO_PARKED_ANCESTORS, ParkedReason::ParkedAncestor),
+            (Self::NO_NONCE_GAPS, ParkedReason::NonceGap),
+            (Self::ENOUGH_BALANCE, ParkedReason::InsufficientBalance),
+            (Self::NOT_TOO_MUCH_GAS, ParkedReason::ExceedsBlockGasLimit),
+            (Self::ENOUGH_FEE_CAP_BLOCK, ParkedReason::InsufficientBaseFee),
+            (Self::ENOUGH_BLOB_FEE_CAP_BLOCK, ParkedReason::InsufficientBlobFee),
+        ]
+        .into_iter()
+        .filter(|(bit, _)| !self.contains(*bit))
+        .map(|(_, reason)| reason)
+        .collect()
+    }
+}
+
+/// The reason a transaction is not in the pending sub-pool.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ParkedReason {
+    /// A transaction of the sender with a lower nonce is not pending.
+    ParkedAncestor,
+    /// A transaction of the sender with a lower nonce is missing.
+    NonceGap,
+    /// The balance of the sender can't cover the cost of this and all prior transactions.
+    InsufficientBalance,
+    /// The gas limit of the transaction exceeds the block gas limit.
+    ExceedsBlockGasLimit,
+    /// The max fee per gas of the transaction is below the base fee of the pending block.
+    InsufficientBaseFee,
+    /// The max fee per blob gas of the transaction is below the blob fee of the pending block.
+    InsufficientBlobFee,
+}
+
+/// The sub-pool a transaction is in, and why it is not pending.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PoolTransactionStatus {
+    /// The sub-pool that contains the transaction.
+    pub sub_pool: SubPool,
+    /// The reasons why the transaction is not pending, empty if it is pending.
+    pub parked_reasons: Vec<ParkedReason>,
 }
 
 /// Identifier for the transaction Sub-pool
@@ -213,6 +257,30 @@ mod tests {
         assert!(state.has_nonce_gap());
     }
 
+    #[test]
+    fn test_parked_reasons() {
+        assert!(TxState::PENDING_POOL_BITS.parked_reasons().is_empty());
+
+        let state = TxState::BASE_FEE_POOL_BITS | TxState::ENOUGH_BLOB_FEE_CAP_BLOCK;
+        assert_eq!(SubPool::from(state), SubPool::BaseFee);
+        assert_eq!(state.parked_reasons(), vec![ParkedReason::InsufficientBaseFee]);
+
+        let state = TxState::PENDING_POOL_BITS.difference(TxState::NO_NONCE_GAPS);
+        assert_eq!(SubPool::from(state), SubPool::Queued);
+        assert_eq!(state.parked_reasons(), vec![ParkedReason::NonceGap]);
+
+        let state = TxState::NO_PARKED_ANCESTORS | TxState::ENOUGH_FEE_CAP_BLOCK;
+        assert_eq!(
+            state.parked_reasons(),
+            vec![
+                ParkedReason::NonceGap,
+                ParkedReason::InsufficientBalance,
+                ParkedReason::ExceedsBlockGasLimit,
+                ParkedReason::InsufficientBlobFee,
+            ]
+        );
+    }
+
     #[test]
     fn test_tx_state_enough_balance() {
         let mut state = TxState::default();
@@ -13,11 +13,11 @@ use crate::{
         blob::BlobTransactions,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
-        state::{SubPool, TxState},
+        state::{PoolTransactionStatus, SubPool, TxState},
         update::{Destination, PoolUpdate, UpdateOutcome},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
+    traits::{BestTransactionsAttributes, BlockInfo, PoolSize, TransactionOrigin},
     PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
@@ -472,6 +472,17 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.basefee_pool.len() + self.queued_pool.len()
     }
 
+    /// Returns all transactions in the pool together with the sub-pool they are in.
+    pub(crate) fn all_transactions_with_sub_pool(
+        &self,
+    ) -> Vec<(SubPool, Arc<ValidPoolTransaction<T::Transaction>>)> {
+        self.all_transactions
+            .txs
+            .values()
+            .map(|tx| (tx.subpool, Arc::clone(&tx.transaction)))
+            .collect()
+    }
+
     /// Returns queued and pending transactions for the specified sender
     pub fn queued_and_pending_txs_by_sender(
         &self,
@@ -518,6 +529,15 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.by_hash.get(tx_hash).cloned()
     }
 
+    /// Returns the sub-pool of the transaction with the given hash and why it is not pending.
+    pub(crate) fn transaction_status(&self, tx_hash: &TxHash) -> Option<PoolTransactionStatus> {
+        let id = self.all_transactions.by_hash.get(tx_hash)?.id();
+        self.all_transactions.txs.get(id).map(|tx| PoolTransactionStatus {
+            sub_pool: tx.subpool,
+            parked_reasons: tx.state.parked_reasons(),
+        })
+    }
+
     /// Returns transactions for the multiple given hashes, if they exist.
     pub(crate) fn get_all(
         &self,
@@ -1229,6 +1249,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
     tx_counter: FxHashMap<SenderId, usize>,
+    /// Tracks the number of transactions by origin that are currently in the pool.
+    origin_counter: FxHashMap<TransactionOrigin, usize>,
     /// The current block number the pool keeps track of.
     last_seen_block_number: u64,
     /// The current block hash the pool keeps track of.
@@ -1281,16 +1303,28 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.txs.get(id)
     }
 
-    /// Increments the transaction counter for the sender
-    pub(crate) fn tx_inc(&mut self, sender: SenderId) {
-        let count = self.tx_counter.entry(sender).or_default();
+    /// Returns the number of transactions of the sender in the pool.
+    pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
+        self.tx_counter.get(&sender).copied().unwrap_or_default()
+    }
+
+    /// Returns the number of transactions with the given origin in the pool.
+    pub(crate) fn origin_tx_count(&self, origin: TransactionOrigin) -> usize {
+        self.origin_counter.get(&origin).copied().unwrap_or_default()
+    }
+
+    /// Increments the transaction counters for the sender and origin of the transaction
+    pub(crate) fn tx_inc(&mut self, tx: &ValidPoolTransaction<T>) {
+        self.origin_inc(tx.origin);
+        let count = self.tx_counter.entry(tx.sender_id()).or_default();
         *count += 1;
         self.metrics.all_transactions_by_all_senders.increment(1.0);
     }
 
-    /// Decrements the transaction counter for the sender
-    pub(crate) fn tx_decr(&mut self, sender: SenderId) {
-        if let hash_map::Entry::Occupied(mut entry) = self.tx_counter.entry(sender) {
+    /// Decrements the transaction counters for the sender and origin of the transaction
+    pub(crate) fn tx_decr(&mut self, tx: &ValidPoolTransaction<T>) {
+        self.origin_decr(tx.origin);
+        if let hash_map::Entry::Occupied(mut entry) = self.tx_counter.entry(tx.sender_id()) {
             let count = entry.get_mut();
             if *count == 1 {
                 entry.remove();
@@ -1302,6 +1336,21 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Increments the transaction counter for the origin
+    fn origin_inc(&mut self, origin: TransactionOrigin) {
+        *self.origin_counter.entry(origin).or_default() += 1;
+    }
+
+    /// Decrements the transaction counter for the origin
+    fn origin_decr(&mut self, origin: TransactionOrigin) {
+        if let hash_map::Entry::Occupied(mut entry) = self.origin_counter.entry(origin) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+
     /// Updates the block specific info
     fn set_block_info(&mut self, block_info: BlockInfo) {
         let BlockInfo {
@@ -1579,7 +1628,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(&tx.transaction_id)?;
         self.remove_auths(&internal);
         // decrement the counter for the sender.
-        self.tx_decr(tx.sender_id());
+        self.tx_decr(&tx);
         Some((tx, internal.subpool))
     }
 
@@ -1594,7 +1643,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let tx = self.by_hash.remove(internal.transaction.hash())?;
         self.remove_auths(&internal);
         // decrement the counter for the sender.
-        self.tx_decr(tx.sender_id());
+        self.tx_decr(&tx);
         Some((tx, internal.subpool))
     }
 
@@ -1638,7 +1687,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(id)?;
 
         // decrement the counter for the sender.
-        self.tx_decr(internal.transaction.sender_id());
+        self.tx_decr(&internal.transaction);
 
         let result =
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
@@ -1765,7 +1814,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                         cumulative_cost += tx.transaction.cost();
                         if tx.transaction.is_eip4844() && cumulative_cost > on_chain_balance {
                             // the transaction would shift
-                            return Err(InsertErr::Overdraft { transaction: Arc::new(new_blob_tx) })
+                            return Err(InsertErr::Overdraft { transaction: Arc::new(new_blob_tx) });
                         }
                     }
                 }
@@ -1906,6 +1955,12 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
                 self.remove_auths(&replaced);
 
+                // the replacement can have a different origin
+                if replaced.transaction.origin != transaction.origin {
+                    self.origin_decr(replaced.transaction.origin);
+                    self.origin_inc(transaction.origin);
+                }
+
                 // also remove the hash
                 replaced_tx = Some((replaced.transaction, replaced.subpool));
             }
@@ -1987,7 +2042,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
         // If this wasn't a replacement transaction we need to update the counter.
         if replaced_tx.is_none() {
-            self.tx_inc(inserted_tx_id.sender);
+            self.tx_inc(&transaction);
         }
 
         self.update_size_metrics();
@@ -2013,16 +2068,6 @@ impl<T: PoolTransaction> AllTransactions<T> {
     }
 }
 
-#[cfg(test)]
-impl<T: PoolTransaction> AllTransactions<T> {
-    /// This function retrieves the number of transactions stored in the pool for a specific sender.
-    ///
-    /// If there are no transactions for the given sender, it returns zero by default.
-    pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
-        self.tx_counter.get(&sender).copied().unwrap_or_default()
-    }
-}
-
 impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
@@ -2032,6 +2077,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             by_hash: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
+            origin_counter: Default::default(),
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
@@ -52,9 +52,11 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
     error::{InvalidPoolTransactionError, PoolError, PoolResult},
     pool::{
-        state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
+        state::{PoolTransactionStatus, SubPool},
+        BestTransactionFilter, EvictedTransaction, NewTransactionEvent, TransactionEvents,
         TransactionListenerKind,
     },
     validate::ValidPoolTransaction,
@@ -403,6 +405,32 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
+    /// Returns all transactions that are currently in the pool together with the sub-pool they
+    /// are in.
+    ///
+    /// Unlike [`Self::all_transactions`], this includes the blob transactions that are not
+    /// pending.
+    ///
+    /// Consumer: Pool snapshots
+    fn all_transactions_with_sub_pool(
+        &self,
+    ) -> Vec<(SubPool, Arc<ValidPoolTransaction<Self::Transaction>>)>;
+
+    /// Returns the sub-pool of the transaction with the given hash and the reasons it is not
+    /// pending, or `None` if the transaction is not in the pool.
+    ///
+    /// Consumer: RPC
+    fn transaction_status(&self, tx_hash: &TxHash) -> Option<PoolTransactionStatus>;
+
+    /// Returns the transactions that were recently removed from the pool without being mined,
+    /// e.g. because they were replaced or discarded, oldest first.
+    ///
+    /// The number of transactions is bounded by
+    /// [`PoolConfig::max_evicted_transactions`](crate::PoolConfig::max_evicted_transactions).
+    ///
+    /// Consumer: RPC
+    fn evicted_transactions(&self) -> Vec<EvictedTransaction>;
+
     /// Removes all transactions corresponding to the given hashes.
     ///
     /// Note: This removes the transactions as if they got discarded (_not_ mined).
@@ -598,6 +626,23 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError>;
+
+    /// Sets the minimum priority fee that non-local transactions must pay to be accepted into the
+    /// pool, or disables the check if `None`.
+    ///
+    /// This only applies to transactions that are added afterwards, transactions that are already
+    /// in the pool are kept.
+    ///
+    /// Consumer: RPC
+    fn set_minimum_priority_fee(&self, minimum_priority_fee: Option<u128>);
+
+    /// Returns the pool of bundles that are submitted for inclusion at the top of upcoming blocks,
+    /// if this pool supports bundles.
+    ///
+    /// Consumer: RPC, Block production
+    fn bundle_pool(&self) -> Option<&BundlePool<PoolConsensusTx<Self>>> {
+        None
+    }
 }
 
 /// Extension for [`TransactionPool`] trait that allows to set the current block info.
@@ -737,7 +782,7 @@ pub struct NewBlobSidecar {
 ///
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
 /// internally, e.g. limits for simultaneous transaction of one sender.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
 pub enum TransactionOrigin {
     /// Transaction is coming from a local source.
     #[default]
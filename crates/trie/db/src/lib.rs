@@ -6,6 +6,7 @@ mod commitment;
 mod hashed_cursor;
 mod prefix_set;
 mod proof;
+mod reverts_cache;
 mod state;
 mod storage;
 mod trie_cursor;
@@ -17,6 +18,7 @@ pub use hashed_cursor::{
 };
 pub use prefix_set::PrefixSetLoader;
 pub use proof::{DatabaseProof, DatabaseStorageProof};
+pub use reverts_cache::RevertsCache;
 pub use state::{DatabaseHashedPostState, DatabaseStateRoot};
 pub use storage::{DatabaseHashedStorage, DatabaseStorageRoot};
 pub use trie_cursor::{
@@ -0,0 +1,290 @@
+use crate::DatabaseHashedPostState;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{BlockNumber, B256, U256};
+use parking_lot::Mutex;
+use reth_db_api::{transaction::DbTx, DatabaseError};
+use reth_primitives_traits::Account;
+use reth_trie::{HashedPostState, HashedStorage, KeyHasher};
+use std::{collections::BTreeMap, mem::size_of, sync::Arc};
+
+/// Cache of the reverts of historical blocks that is shared across state providers.
+///
+/// The reverts of a block contain the state before the first change of every account and storage
+/// slot that changed between the block and the tip, see
+/// [`DatabaseHashedPostState::from_reverts`]. They are required to compute state roots and proofs
+/// at historical blocks, and computing them walks all changesets up to the tip, which gets slow
+/// for blocks far from the tip.
+///
+/// The cache keeps the reverts of the most recently requested blocks relative to a tip, up to a
+/// maximum size in bytes:
+/// - The reverts of an uncached block are derived from the closest cached block after it, so only
+///   the changesets between both blocks are read.
+/// - If the tip advances, the cached reverts are extended with the changesets of the new blocks.
+/// - If the cached tip is no longer canonical, e.g. after a reorg, the cache is cleared.
+///
+/// Changesets are never read while the cache is locked, so slow reads don't block lookups of
+/// cached reverts.
+///
+/// All reverts are hashed with the same [`KeyHasher`], so a cache must not be shared between
+/// providers of different state commitments.
+#[derive(Debug, Clone)]
+pub struct RevertsCache {
+    /// Max size of the cached reverts in bytes.
+    max_size: usize,
+    inner: Arc<Mutex<RevertsCacheInner>>,
+}
+
+#[derive(Debug, Default)]
+struct RevertsCacheInner {
+    /// The tip that the cached reverts are relative to.
+    tip: Option<BlockNumHash>,
+    /// The cached reverts by block number.
+    entries: BTreeMap<BlockNumber, CachedReverts>,
+    /// The size of the cached reverts in bytes.
+    size: usize,
+    /// Number of lookups, used to evict the least recently used entries.
+    lookups: u64,
+}
+
+#[derive(Debug)]
+struct CachedReverts {
+    /// The reverts of the block.
+    state: Arc<HashedPostState>,
+    /// The approximate size of the reverts in bytes.
+    size: usize,
+    /// The lookup that used the entry last.
+    last_used: u64,
+}
+
+impl RevertsCacheInner {
+    /// Evicts the least recently used reverts until the cache is no larger than `max_size`.
+    fn evict(&mut self, max_size: usize) {
+        while self.size > max_size {
+            let Some(least_used) =
+                self.entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(k, _)| *k)
+            else {
+                break
+            };
+            if let Some(entry) = self.entries.remove(&least_used) {
+                self.size -= entry.size;
+            }
+        }
+    }
+}
+
+impl RevertsCache {
+    /// Creates a cache that keeps reverts of at most `max_size` bytes.
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size, inner: Default::default() }
+    }
+
+    /// Returns the reverts of the blocks from `from` up to and including `tip`.
+    ///
+    /// `is_canonical` is used to check if the tip of the cached reverts is still canonical when
+    /// the tip advances.
+    pub fn reverts<TX: DbTx, KH: KeyHasher>(
+        &self,
+        tx: &TX,
+        from: BlockNumber,
+        tip: BlockNumHash,
+        is_canonical: impl Fn(BlockNumHash) -> bool,
+    ) -> Result<Arc<HashedPostState>, DatabaseError> {
+        let end = tip.number + 1;
+        if self.max_size == 0 || from >= end || !self.advance_to::<TX, KH>(tx, tip, is_canonical)? {
+            return HashedPostState::from_reverts_range::<KH>(tx, from..end).map(Arc::new)
+        }
+
+        let mut inner = self.inner.lock();
+        // Another lookup moved the cache to a different tip in the meantime.
+        if inner.tip != Some(tip) {
+            drop(inner);
+            return HashedPostState::from_reverts_range::<KH>(tx, from..end).map(Arc::new)
+        }
+
+        inner.lookups += 1;
+        let lookup = inner.lookups;
+        if let Some(entry) = inner.entries.get_mut(&from) {
+            entry.last_used = lookup;
+            return Ok(entry.state.clone())
+        }
+
+        // Derive the reverts from the closest cached block after the requested one.
+        let (later_reverts, range) = match inner.entries.range_mut(from..).next() {
+            Some((block, entry)) => {
+                entry.last_used = lookup;
+                (Some(entry.state.clone()), from..*block)
+            }
+            None => (None, from..end),
+        };
+        drop(inner);
+
+        let mut state = HashedPostState::from_reverts_range::<KH>(tx, range)?;
+        if let Some(later_reverts) = later_reverts {
+            extend_with_later_reverts(&mut state, &later_reverts);
+        }
+        let state = Arc::new(state);
+        let size = reverts_size(&state);
+
+        let mut inner = self.inner.lock();
+        // Only cache the reverts if the tip didn't change in the meantime.
+        if inner.tip == Some(tip) && size <= self.max_size {
+            let entry = CachedReverts { state: state.clone(), size, last_used: lookup };
+            if let Some(replaced) = inner.entries.insert(from, entry) {
+                inner.size -= replaced.size;
+            }
+            inner.size += size;
+            inner.evict(self.max_size);
+        }
+        drop(inner);
+
+        Ok(state)
+    }
+
+    /// Moves the cached reverts to the given tip.
+    ///
+    /// Returns `false` if the cached reverts are relative to a later tip and can't be used.
+    fn advance_to<TX: DbTx, KH: KeyHasher>(
+        &self,
+        tx: &TX,
+        tip: BlockNumHash,
+        is_canonical: impl Fn(BlockNumHash) -> bool,
+    ) -> Result<bool, DatabaseError> {
+        let cached_tip = self.inner.lock().tip;
+        let new_reverts = match cached_tip {
+            Some(cached_tip) if cached_tip == tip => return Ok(true),
+            // The transaction is older than the cached reverts, they can't be used.
+            Some(cached_tip) if cached_tip.number > tip.number => return Ok(false),
+            Some(cached_tip) if cached_tip.number < tip.number && is_canonical(cached_tip) => {
+                Some(HashedPostState::from_reverts_range::<KH>(
+                    tx,
+                    cached_tip.number + 1..tip.number + 1,
+                )?)
+            }
+            _ => None,
+        };
+
+        let mut inner = self.inner.lock();
+        // Another lookup moved the cache while the new reverts were read, so they may not apply
+        // to the cached reverts anymore.
+        if inner.tip != cached_tip {
+            return Ok(inner.tip == Some(tip))
+        }
+
+        match new_reverts {
+            Some(new_reverts) => {
+                let mut size = 0;
+                for entry in inner.entries.values_mut() {
+                    extend_with_later_reverts(Arc::make_mut(&mut entry.state), &new_reverts);
+                    entry.size = reverts_size(&entry.state);
+                    size += entry.size;
+                }
+                inner.size = size;
+                inner.evict(self.max_size);
+            }
+            None => {
+                inner.entries.clear();
+                inner.size = 0;
+            }
+        }
+        inner.tip = Some(tip);
+
+        Ok(true)
+    }
+}
+
+/// Extends the reverts with the reverts of later blocks. Accounts and storage slots that are
+/// already reverted keep their value, because it's the value before an earlier change.
+fn extend_with_later_reverts(reverts: &mut HashedPostState, later: &HashedPostState) {
+    for (hashed_address, account) in &later.accounts {
+        reverts.accounts.entry(*hashed_address).or_insert(*account);
+    }
+    for (hashed_address, later_storage) in &later.storages {
+        let storage = reverts.storages.entry(*hashed_address).or_default();
+        for (hashed_slot, value) in &later_storage.storage {
+            storage.storage.entry(*hashed_slot).or_insert(*value);
+        }
+    }
+}
+
+/// Returns the approximate size of the reverts in bytes.
+fn reverts_size(reverts: &HashedPostState) -> usize {
+    let accounts = reverts.accounts.len() * size_of::<(B256, Option<Account>)>();
+    let storages = reverts
+        .storages
+        .values()
+        .map(|storage| {
+            size_of::<(B256, HashedStorage)>() + storage.storage.len() * size_of::<(B256, U256)>()
+        })
+        .sum::<usize>();
+    accounts + storages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{
+        database::Database,
+        models::{AccountBeforeTx, BlockNumberAddress},
+        tables,
+        transaction::DbTxMut,
+    };
+    use reth_primitives_traits::{Account, StorageEntry};
+    use reth_trie::KeccakKeyHasher;
+
+    fn tip(number: BlockNumber) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(number as u8))
+    }
+
+    #[test]
+    fn derives_and_extends_cached_reverts() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        for block in 1..=4 {
+            let address = Address::with_last_byte(block as u8 % 2);
+            let info = Some(Account { nonce: block, ..Default::default() });
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address, info }).unwrap();
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((block, address)),
+                StorageEntry { key: B256::ZERO, value: U256::from(block) },
+            )
+            .unwrap();
+        }
+
+        let expected = |from, tip_number: BlockNumber| {
+            HashedPostState::from_reverts_range::<KeccakKeyHasher>(&tx, from..tip_number + 1)
+                .unwrap()
+        };
+        // the reverts of blocks 1 to 3 relative to block 4 have the same size
+        let cache = RevertsCache::new(2 * reverts_size(&expected(1, 4)));
+        let reverts = |from, tip_number| {
+            cache.reverts::<_, KeccakKeyHasher>(&tx, from, tip(tip_number), |_| true).unwrap()
+        };
+        let cached_blocks = || cache.inner.lock().entries.keys().copied().collect::<Vec<_>>();
+
+        assert_eq!(*reverts(3, 3), expected(3, 3));
+        // derived from the reverts of block 3
+        assert_eq!(*reverts(1, 3), expected(1, 3));
+        assert_eq!(cached_blocks(), vec![1, 3]);
+
+        // the cached reverts are extended with the new block
+        assert_eq!(*reverts(3, 4), expected(3, 4));
+        assert_eq!(*reverts(1, 4), expected(1, 4));
+        assert_eq!(cached_blocks(), vec![1, 3]);
+        // cached reverts are shared
+        assert!(Arc::ptr_eq(&reverts(1, 4), &reverts(1, 4)));
+
+        // the least recently used reverts are evicted
+        assert_eq!(*reverts(2, 4), expected(2, 4));
+        assert_eq!(cached_blocks(), vec![2, 3]);
+
+        // reverts relative to an older tip are not cached
+        assert_eq!(*reverts(2, 3), expected(2, 3));
+        assert_eq!(cache.inner.lock().tip, Some(tip(4)));
+
+        // the cache is cleared if the cached tip is not canonical anymore
+        cache.reverts::<_, KeccakKeyHasher>(&tx, 3, tip(5), |_| false).unwrap();
+        assert_eq!(cached_blocks(), vec![3]);
+    }
+}
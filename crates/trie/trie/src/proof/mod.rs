@@ -17,6 +17,9 @@ use reth_trie_common::{
     proof::ProofRetainer, AccountProof, MultiProof, MultiProofTargets, StorageMultiProof,
 };
 
+mod range;
+pub use range::verify_range_proof;
+
 mod trie_node;
 pub use trie_node::*;
 
@@ -0,0 +1,297 @@
+use crate::{HashBuilder, Nibbles, RlpNode, TrieNode};
+use alloy_primitives::{keccak256, map::B256Map, B256};
+use alloy_rlp::Decodable;
+use reth_execution_errors::trie::RangeProofError;
+
+/// Verifies a range proof as served by the `snap` protocol.
+///
+/// Checks that `keys` and `values` are the complete set of leaves of the trie with the given
+/// `root` between `origin` and the last key. The `proof` must contain the nodes on the paths to
+/// `origin` and to the last key. If the proof is empty, the leaves must make up the entire trie.
+///
+/// If `keys` is empty, the proof must show that there are no leaves at or after `origin`.
+///
+/// Returns `true` if the trie has more leaves after the last key.
+pub fn verify_range_proof<V: AsRef<[u8]>>(
+    root: B256,
+    origin: B256,
+    keys: &[B256],
+    values: &[V],
+    proof: &[impl AsRef<[u8]>],
+) -> Result<bool, RangeProofError> {
+    if keys.len() != values.len() {
+        return Err(RangeProofError::LengthMismatch { keys: keys.len(), values: values.len() })
+    }
+    if let Some(pair) = keys.windows(2).find(|pair| pair[0] >= pair[1]) {
+        return Err(RangeProofError::UnorderedKeys(pair[1]))
+    }
+    if let Some(key) = keys.first().filter(|key| **key < origin) {
+        return Err(RangeProofError::KeyBeforeOrigin { key: *key, origin })
+    }
+
+    let leaves = keys
+        .iter()
+        .zip(values)
+        .map(|(key, value)| (Nibbles::unpack(key), RangeItem::Leaf(value.as_ref().to_vec())));
+
+    // without a proof, the range must be the entire trie
+    if proof.is_empty() {
+        return verify_root(root, leaves.collect()).map(|_| false)
+    }
+
+    let mut walker = RangeWalker {
+        nodes: proof.iter().map(|node| (keccak256(node), node.as_ref())).collect(),
+        left: Nibbles::unpack(origin),
+        right: keys.last().map(Nibbles::unpack),
+        items: Vec::new(),
+        has_more: false,
+    };
+    walker.walk_hash(Nibbles::default(), root)?;
+
+    let RangeWalker { mut items, has_more, .. } = walker;
+    items.extend(leaves);
+    verify_root(root, items)?;
+
+    Ok(has_more)
+}
+
+/// Computes the root of the given trie items and compares it to the expected root.
+fn verify_root(
+    expected: B256,
+    mut items: Vec<(Nibbles, RangeItem)>,
+) -> Result<(), RangeProofError> {
+    items.sort_unstable_by_key(|(path, _)| *path);
+
+    let mut hash_builder = HashBuilder::default();
+    for (path, item) in items {
+        match item {
+            RangeItem::Hash(hash) => hash_builder.add_branch(path, hash, false),
+            RangeItem::Leaf(value) => hash_builder.add_leaf(path, &value),
+        }
+    }
+
+    let got = hash_builder.root();
+    if got != expected {
+        return Err(RangeProofError::RootMismatch { got, expected })
+    }
+    Ok(())
+}
+
+/// A node or leaf that is used to rebuild the trie of a range.
+#[derive(Debug)]
+enum RangeItem {
+    /// A subtree outside of the range, referenced by its hash.
+    Hash(B256),
+    /// A leaf with its value.
+    Leaf(Vec<u8>),
+}
+
+/// The position of a trie path relative to the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// All leaves under the path are before the range.
+    Left,
+    /// All leaves under the path are after the range.
+    Right,
+    /// All leaves under the path are inside the range.
+    Inside,
+    /// The path leads to either end of the range.
+    Boundary,
+}
+
+/// Walks the boundary paths of a range proof and collects everything outside of the range.
+#[derive(Debug)]
+struct RangeWalker<'a> {
+    /// The proof nodes by hash.
+    nodes: B256Map<&'a [u8]>,
+    /// The first key of the range.
+    left: Nibbles,
+    /// The last key of the range, unbounded if `None`.
+    right: Option<Nibbles>,
+    /// The subtrees and leaves outside of the range.
+    items: Vec<(Nibbles, RangeItem)>,
+    /// Whether there is anything after the range.
+    has_more: bool,
+}
+
+impl RangeWalker<'_> {
+    fn position(&self, path: &Nibbles) -> Position {
+        let left = self.left.slice(..path.len());
+        if *path < left {
+            return Position::Left
+        }
+        let right = self.right.map(|right| right.slice(..path.len()));
+        if right.is_some_and(|right| *path > right) {
+            return Position::Right
+        }
+        if *path == left || right == Some(*path) {
+            Position::Boundary
+        } else {
+            Position::Inside
+        }
+    }
+
+    /// Records an item outside of the range.
+    fn push_outside(&mut self, path: Nibbles, position: Position, item: RangeItem) {
+        if position == Position::Right {
+            self.has_more = true;
+        }
+        self.items.push((path, item));
+    }
+
+    fn walk_hash(&mut self, path: Nibbles, hash: B256) -> Result<(), RangeProofError> {
+        match self.position(&path) {
+            position @ (Position::Left | Position::Right) => {
+                self.push_outside(path, position, RangeItem::Hash(hash));
+                Ok(())
+            }
+            // rebuilt from the leaves of the range
+            Position::Inside => Ok(()),
+            Position::Boundary => {
+                let rlp =
+                    self.nodes.get(&hash).ok_or(RangeProofError::MissingNode { path, hash })?;
+                let node = TrieNode::decode(&mut &rlp[..])?;
+                self.walk_node(path, node)
+            }
+        }
+    }
+
+    fn walk_ref(&mut self, path: Nibbles, node: &RlpNode) -> Result<(), RangeProofError> {
+        if let Some(hash) = node.as_hash() {
+            self.walk_hash(path, hash)
+        } else {
+            // embedded nodes are expanded in place
+            let node = TrieNode::decode(&mut &node[..])?;
+            self.walk_node(path, node)
+        }
+    }
+
+    fn walk_node(&mut self, path: Nibbles, node: TrieNode) -> Result<(), RangeProofError> {
+        match node {
+            TrieNode::EmptyRoot => {}
+            TrieNode::Branch(branch) => {
+                for (nibble, child) in branch.as_ref().children() {
+                    if let Some(child) = child {
+                        let mut child_path = path;
+                        child_path.push(nibble);
+                        self.walk_ref(child_path, child)?;
+                    }
+                }
+            }
+            TrieNode::Extension(extension) => {
+                self.walk_ref(path.join(&extension.key), &extension.child)?;
+            }
+            TrieNode::Leaf(leaf) => {
+                let key = path.join(&leaf.key);
+                let position = self.position(&key);
+                if matches!(position, Position::Left | Position::Right) {
+                    self.push_outside(key, position, RangeItem::Leaf(leaf.value));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use reth_trie_common::proof::ProofRetainer;
+
+    fn trie(len: u64) -> Vec<(B256, Vec<u8>)> {
+        let mut leaves = (0..len)
+            .map(|i| (keccak256(B256::from(U256::from(i))), alloy_rlp::encode(U256::from(i + 1))))
+            .collect::<Vec<_>>();
+        leaves.sort_unstable_by_key(|(key, _)| *key);
+        leaves
+    }
+
+    fn range_proof(leaves: &[(B256, Vec<u8>)], origin: B256, last: B256) -> (B256, Vec<Vec<u8>>) {
+        let retainer = ProofRetainer::from_iter([Nibbles::unpack(origin), Nibbles::unpack(last)]);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        let proof = hash_builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node.to_vec())
+            .collect();
+        (root, proof)
+    }
+
+    #[test]
+    fn verify_middle_range() {
+        let leaves = trie(100);
+        let range = &leaves[10..20];
+        let (root, proof) = range_proof(&leaves, range[0].0, range[9].0);
+
+        let keys = range.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let values = range.iter().map(|(_, value)| value.clone()).collect::<Vec<_>>();
+        assert_eq!(verify_range_proof(root, range[0].0, &keys, &values, &proof), Ok(true));
+
+        // a missing leaf is detected
+        let (mut missing_keys, mut missing_values) = (keys.clone(), values.clone());
+        missing_keys.remove(4);
+        missing_values.remove(4);
+        let err = verify_range_proof(root, range[0].0, &missing_keys, &missing_values, &proof);
+        assert!(matches!(err, Err(RangeProofError::RootMismatch { .. })));
+
+        // a modified value is detected
+        let mut modified = values;
+        modified[5] = alloy_rlp::encode(U256::MAX);
+        let err = verify_range_proof(root, range[0].0, &keys, &modified, &proof);
+        assert!(matches!(err, Err(RangeProofError::RootMismatch { .. })));
+    }
+
+    #[test]
+    fn verify_last_range() {
+        let leaves = trie(50);
+        let range = &leaves[40..];
+        let (root, proof) = range_proof(&leaves, range[0].0, range[9].0);
+
+        let keys = range.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let values = range.iter().map(|(_, value)| value.clone()).collect::<Vec<_>>();
+        assert_eq!(verify_range_proof(root, range[0].0, &keys, &values, &proof), Ok(false));
+    }
+
+    #[test]
+    fn verify_entire_trie() {
+        let leaves = trie(20);
+        let (root, _) = range_proof(&leaves, B256::ZERO, B256::ZERO);
+
+        let keys = leaves.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let values = leaves.iter().map(|(_, value)| value.clone()).collect::<Vec<_>>();
+        let proof: &[Vec<u8>] = &[];
+        assert_eq!(verify_range_proof(root, B256::ZERO, &keys, &values, proof), Ok(false));
+        assert!(verify_range_proof(root, B256::ZERO, &keys[1..], &values[1..], proof).is_err());
+    }
+
+    #[test]
+    fn verify_empty_range() {
+        let leaves = trie(20);
+        let origin = B256::repeat_byte(0xff);
+        let (root, proof) = range_proof(&leaves, origin, origin);
+        let values: &[Vec<u8>] = &[];
+        assert_eq!(verify_range_proof(root, origin, &[], values, &proof), Ok(false));
+
+        // claiming there is nothing after the first leaf is detected
+        let (root, proof) = range_proof(&leaves, leaves[0].0, leaves[0].0);
+        let err = verify_range_proof(root, leaves[0].0, &[], values, &proof);
+        assert!(matches!(err, Err(RangeProofError::RootMismatch { .. })));
+    }
+
+    #[test]
+    fn reject_unordered_keys() {
+        let keys = [B256::repeat_byte(2), B256::repeat_byte(1)];
+        let values = [vec![1], vec![2]];
+        let proof: &[Vec<u8>] = &[];
+        assert_eq!(
+            verify_range_proof(B256::ZERO, B256::ZERO, &keys, &values, proof),
+            Err(RangeProofError::UnorderedKeys(B256::repeat_byte(1)))
+        );
+    }
+}